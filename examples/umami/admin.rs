@@ -39,60 +39,29 @@ pub async fn edit_article(user: &mut GooseUser) -> TransactionResult {
         goose,
         &goose_eggs::Validate::builder()
             .title(article.unwrap().title_en)
-            .text(&format!("en/node/{}/edit", article.unwrap().nid))
+            .text(format!("en/node/{}/edit", article.unwrap().nid))
             .build(),
     )
     .await?;
 
-    // Next, load the edit link for the chosen article.
-    let goose = user
-        .get(&format!("en/node/{}/edit", article.unwrap().nid))
-        .await?;
-
-    let edit_page = goose_eggs::validate_and_load_static_assets(
-        user,
-        goose,
-        &goose_eggs::Validate::builder()
-            .title("Edit Article")
-            .build(),
-    )
-    .await?;
-
-    let edit_form = goose_eggs::drupal::get_form(&edit_page, "node-article-edit-form");
-    let form_build_id = goose_eggs::drupal::get_form_value(&edit_form, "form_build_id");
-    let form_token = goose_eggs::drupal::get_form_value(&edit_form, "form_token");
-    let form_id = goose_eggs::drupal::get_form_value(&edit_form, "form_id");
-
-    // Build node form with random word from title.
-    let params = [
-        ("form_build_id", &form_build_id),
-        ("form_token", &form_token),
-        ("form_id", &form_id),
-        ("op", &"Save (this translation)".to_string()),
-    ];
-    let mut saved_article = user
-        .post_form(&format!("en/node/{}/edit", article.unwrap().nid), &params)
-        .await?;
-
-    // A successful node save is redirected.
-    if !saved_article.request.redirected {
-        return user.set_failure(
-            &format!("{}: saving article failed", saved_article.request.final_url),
-            &mut saved_article.request,
-            None,
-            None,
-        );
-    }
-
-    // Be sure we're viewing the same article after editing it.
-    goose_eggs::validate_and_load_static_assets(
-        user,
-        saved_article,
-        &goose_eggs::Validate::builder()
-            .title(article.unwrap().title_en)
-            .build(),
-    )
-    .await?;
+    // Next, edit the chosen article, saving it without changes, and be sure we're viewing
+    // the same article after editing it.
+    let url = format!("en/node/{}/edit", article.unwrap().nid);
+    let validate_edit_page = goose_eggs::Validate::builder()
+        .title("Edit Article")
+        .build();
+    let validate_saved_page = goose_eggs::Validate::builder()
+        .title(article.unwrap().title_en)
+        .build();
+    let edit_params = goose_eggs::drupal::EditParams::builder()
+        .nid(article.unwrap().nid as usize)
+        .url(&*url)
+        .form("node-article-edit-form")
+        .submit("Save (this translation)")
+        .edit_page_validation(&validate_edit_page)
+        .saved_page_validation(&validate_saved_page)
+        .build();
+    goose_eggs::drupal::edit_node(user, &edit_params).await?;
 
     Ok(())
 }
@@ -29,7 +29,7 @@ pub struct Term<'a> {
 }
 
 /// Returns a vector of all nodes of a specified content type.
-pub fn get_nodes(content_type: &ContentType) -> Vec<Node> {
+pub fn get_nodes(content_type: &ContentType) -> Vec<Node<'_>> {
     match content_type {
         ContentType::Article => {
             vec![
@@ -381,7 +381,7 @@ pub fn random_words(count: usize, english: bool) -> Vec<String> {
 
     for _ in 0..count {
         // Randomly select a content type, favoring articles and recipes.
-        let content_types = vec![
+        let content_types = [
             ContentType::Article,
             ContentType::Article,
             ContentType::Article,
@@ -414,84 +414,35 @@ pub fn random_words(count: usize, english: bool) -> Vec<String> {
 /// Anonymously load the contact form and POST feedback. The english boolean flag indicates
 /// whether to load the English form or the Spanish form.
 pub async fn anonymous_contact_form(user: &mut GooseUser, english: bool) -> TransactionResult {
-    let contact_form_url = if english { "en/contact" } else { "es/contact" };
-    let goose = user.get(contact_form_url).await?;
-    let contact_page = goose_eggs::validate_and_load_static_assets(
-        user,
-        goose,
-        &goose_eggs::Validate::builder()
-            .title(if english {
-                "Website feedback"
-            } else {
-                "Comentarios sobre el sitio web"
-            })
-            .build(),
-    )
-    .await?;
-
-    let form = goose_eggs::drupal::get_form(&contact_page, "contact-message-feedback-form");
-    let form_build_id = goose_eggs::drupal::get_form_value(&form, "form_build_id");
-    let form_id = goose_eggs::drupal::get_form_value(&form, "form_id");
+    let validate = goose_eggs::Validate::builder()
+        .title(if english {
+            "Website feedback"
+        } else {
+            "Comentarios sobre el sitio web"
+        })
+        .build();
 
     // Build contact form parameters.
     let name = random_words(2, english).join(" ");
     let email = format!("{}@example.com", random_words(1, english).pop().unwrap());
     let subject = random_words(8, english).join(" ");
     let message = random_words(12, english).join(" ");
-    let params = [
-        ("name", name.as_str()),
-        ("mail", email.as_str()),
-        ("subject[0][value]", subject.as_str()),
-        ("message[0][value]", message.as_str()),
-        ("form_build_id", &form_build_id),
-        ("form_id", &form_id),
-        ("op", "Send+message"),
-    ];
-    let mut goose = user.post_form(contact_form_url, &params).await?;
-
-    // Drupal 9 throttles how many times an IP address can submit the contact form, so we
-    // need special handling.
-    match goose.response {
-        Ok(response) => {
-            // Copy the headers so we have them for logging if there are errors.
-            let headers = &response.headers().clone();
-            match response.text().await {
-                Ok(html) => {
-                    // Drupal 9 will throttle how many times our IP address can actually
-                    // submit the contact form. We can detect this, but it happens a lot
-                    // so there's nothing useful to do.
-                    let error_text = if english {
-                        "You cannot send more than"
-                    } else {
-                        "No le está permitido enviar más"
-                    };
-                    if html.contains(error_text) {
-                        // The contact form was throttled, safely ignore this.
-                    }
-
-                    // Either way, a "real" user would still load all static elements on
-                    // the returned page.
-                    goose_eggs::load_static_elements(user, &html).await;
-                }
-                Err(e) => {
-                    return user.set_failure(
-                        &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
-                        &mut goose.request,
-                        Some(headers),
-                        None,
-                    );
-                }
-            }
-        }
-        Err(e) => {
-            return user.set_failure(
-                &format!("{}: no response from server: {}", goose.request.raw.url, e),
-                &mut goose.request,
-                None,
-                None,
-            );
-        }
-    }
+    let contact_params = goose_eggs::drupal::ContactParams::builder()
+        .url(if english { "en/contact" } else { "es/contact" })
+        .name(name.as_str())
+        .mail(email.as_str())
+        .subject(subject.as_str())
+        .message(message.as_str())
+        // Drupal 9 throttles how many times an IP address can submit the contact form,
+        // which happens a lot when load testing.
+        .flood_control(if english {
+            &["You cannot send more than"]
+        } else {
+            &["No le está permitido enviar más"]
+        })
+        .contact_page_validation(&validate)
+        .build();
+    goose_eggs::drupal::contact(user, &contact_params).await?;
 
     Ok(())
 }
@@ -0,0 +1,778 @@
+//! Configuration of how static assets are loaded.
+
+use goose::goose::GooseUser;
+use log::warn;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{user_key, UserKey, Validate};
+
+// Assets loaded by each Goose user of each load test, when deduplicating assets across
+// pages.
+static LOADED_ASSETS: Mutex<BTreeMap<UserKey, BTreeSet<String>>> = Mutex::new(BTreeMap::new());
+
+// The `If-None-Match` and `If-Modified-Since` headers used to revalidate the assets cached
+// by each Goose user of each load test, keyed by url.
+type AssetValidators = BTreeMap<String, Vec<(&'static str, String)>>;
+static CACHED_ASSETS: Mutex<BTreeMap<UserKey, AssetValidators>> = Mutex::new(BTreeMap::new());
+
+// Remember the `ETag` and `Last-Modified` headers of an asset loaded by the Goose user.
+pub(crate) fn remember_validators(user: &GooseUser, url: &str, headers: &HeaderMap) {
+    let mut validators = Vec::new();
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(etag) = header("etag") {
+        validators.push(("if-none-match", etag.to_string()));
+    }
+    if let Some(last_modified) = header("last-modified") {
+        validators.push(("if-modified-since", last_modified.to_string()));
+    }
+    if !validators.is_empty() {
+        let url = user.build_url(url).unwrap_or_else(|_| url.to_string());
+        CACHED_ASSETS
+            .lock()
+            .unwrap()
+            .entry(user_key(user))
+            .or_default()
+            .insert(url, validators);
+    }
+}
+
+// Returns the headers to revalidate an asset cached by the Goose user, if any.
+pub(crate) fn cached_validators(
+    user: &GooseUser,
+    url: &str,
+) -> Option<Vec<(&'static str, String)>> {
+    let url = user.build_url(url).unwrap_or_else(|_| url.to_string());
+    CACHED_ASSETS
+        .lock()
+        .unwrap()
+        .get(&user_key(user))?
+        .get(&url)
+        .cloned()
+}
+
+// Remember that the Goose user loaded the asset, returning false if it was already loaded.
+pub(crate) fn remember_loaded(user: &GooseUser, url: &str) -> bool {
+    let url = user.build_url(url).unwrap_or_else(|_| url.to_string());
+    LOADED_ASSETS
+        .lock()
+        .unwrap()
+        .entry(user_key(user))
+        .or_default()
+        .insert(url)
+}
+
+/// Forget which static assets the Goose user already loaded and cached, so they're loaded
+/// again by [`LoadAssets`] objects built with [`LoadAssetsBuilder::dedupe_across_pages`]
+/// or [`LoadAssetsBuilder::conditional_requests`].
+///
+/// Invoke this at the start of a transaction to only deduplicate assets within the
+/// transaction, or to simulate a first-time visitor with an empty browser cache.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::{forget_loaded_assets, load_assets, LoadAssets};
+///
+/// transaction!(browse_recipes).set_on_start();
+///
+/// async fn browse_recipes(user: &mut GooseUser) -> TransactionResult {
+///     // Each transaction starts with an empty browser cache.
+///     forget_loaded_assets(user);
+///     let assets = LoadAssets::builder().dedupe_across_pages().build();
+///
+///     for path in ["/recipes", "/recipes/quiche"] {
+///         let goose = user.get(path).await?;
+///         if let Ok(response) = goose.response {
+///             if let Ok(html) = response.text().await {
+///                 load_assets(user, &html, &assets).await;
+///             }
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// The assets are remembered for the life of the process, separately for each load test.
+/// Use [`reset_loaded_assets`] to forget the assets loaded by every Goose user.
+pub fn forget_loaded_assets(user: &GooseUser) {
+    LOADED_ASSETS.lock().unwrap().remove(&user_key(user));
+    CACHED_ASSETS.lock().unwrap().remove(&user_key(user));
+}
+
+/// Forget which static assets every Goose user already loaded and cached, for example before
+/// running another load test in the same process. The metrics returned by
+/// [`asset_metrics`](crate::asset_metrics) are kept until
+/// [`reset_asset_metrics`](crate::reset_asset_metrics).
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::reset_loaded_assets;
+///
+/// reset_loaded_assets();
+/// ```
+pub fn reset_loaded_assets() {
+    LOADED_ASSETS.lock().unwrap().clear();
+    CACHED_ASSETS.lock().unwrap().clear();
+    crate::ASSET_WORKERS.lock().unwrap().clear();
+}
+
+/// A static asset loaded by [`load_assets`](crate::load_assets).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedAsset {
+    /// The url of the asset.
+    pub url: String,
+    /// The type of the asset, used to name the request `static asset: <type>`.
+    pub resource_type: &'static str,
+    /// The status code of the response, or 0 if there was no response.
+    pub status_code: u16,
+    /// Whether Goose considered the request successful.
+    pub success: bool,
+    /// How many bytes of the response body were downloaded.
+    pub bytes: usize,
+    /// How long it took to request the asset and download the response body.
+    pub elapsed: Duration,
+}
+
+/// The static assets loaded from a page by [`load_assets`](crate::load_assets) and
+/// [`load_static_elements`](crate::load_static_elements), in the order they were loaded.
+/// Assets skipped because they were already loaded, or considered cached, are not included.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::load_static_elements;
+///
+/// transaction!(load_page_and_static_elements).set_on_start();
+///
+/// async fn load_page_and_static_elements(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("/").await?;
+///
+///     if let Ok(response) = goose.response {
+///         if let Ok(html) = response.text().await {
+///             let loaded = load_static_elements(user, &html).await;
+///             for asset in loaded.failures() {
+///                 println!("{} failed to load: {}", asset.url, asset.status_code);
+///             }
+///             println!("{} bytes of static assets", loaded.bytes());
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadedAssets {
+    /// The static assets that were loaded.
+    pub assets: Vec<LoadedAsset>,
+    /// How long it took to load all static assets of the page.
+    pub duration: Duration,
+}
+impl LoadedAssets {
+    /// The urls of the static assets that were loaded.
+    pub fn urls(&self) -> Vec<&str> {
+        self.assets.iter().map(|asset| asset.url.as_str()).collect()
+    }
+
+    /// The static assets that failed to load.
+    pub fn failures(&self) -> Vec<&LoadedAsset> {
+        self.assets.iter().filter(|asset| !asset.success).collect()
+    }
+
+    /// The total number of bytes downloaded.
+    pub fn bytes(&self) -> usize {
+        self.assets.iter().map(|asset| asset.bytes).sum()
+    }
+
+    /// The total time spent loading static assets. When loading assets in parallel this
+    /// is more than the time it took to load the page's assets, see
+    /// [`LoadedAssets::duration`].
+    pub fn elapsed(&self) -> Duration {
+        self.assets.iter().map(|asset| asset.elapsed).sum()
+    }
+}
+
+/// How video and audio assets are requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MediaRequest {
+    // Download the entire file.
+    Full,
+    // Only request the headers with a HEAD request.
+    Head,
+    // Only download the specified number of bytes with a Range request.
+    Range(u64),
+}
+
+/// How many of the static assets found on a page are loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AssetLimit {
+    // Load all assets.
+    All,
+    // Only load the specified number of assets, in the order they're found on the page.
+    First(usize),
+    // Only load the specified number of assets, randomly selected.
+    Random(usize),
+}
+
+/// Defines how static assets found on a page are loaded by [`load_assets`](crate::load_assets)
+/// and [`validate_and_load_static_assets`](crate::validate_and_load_static_assets).
+///
+/// By default all local static assets are loaded, like [`load_static_elements`](crate::load_static_elements).
+#[derive(Clone, Debug)]
+pub struct LoadAssets {
+    /// How video and audio assets are requested.
+    pub(crate) media: MediaRequest,
+    /// How many levels of css `@import` and `url()` references are followed.
+    pub(crate) css_depth: usize,
+    /// Whether images lazy loaded with `data-src=` and `data-srcset=` are loaded.
+    pub(crate) lazy_load: bool,
+    /// Whether documents hinted with `rel="prefetch"` and `rel="prerender"` are loaded.
+    pub(crate) prefetch: bool,
+    /// Whether assets already loaded by the Goose user on a previous page are skipped.
+    pub(crate) dedupe_across_pages: bool,
+    /// Whether cached assets are revalidated with conditional requests.
+    pub(crate) conditional: bool,
+    /// How many static assets are loaded at once.
+    pub(crate) parallel: usize,
+    /// Hosts other than the base url host that static assets are loaded from.
+    pub(crate) allowed_hosts: Vec<String>,
+    /// How many of the static assets found on a page are loaded.
+    pub(crate) limit: AssetLimit,
+    /// Custom types of static assets, keyed by lower case file extension.
+    pub(crate) asset_types: Vec<(String, &'static str)>,
+    /// The Drupal public files path to load the libraries declared by `ajaxPageState` from.
+    pub(crate) drupal_libraries: Option<String>,
+    /// The probability that a static asset is served from the browser cache, and not requested.
+    pub(crate) cache_hit_ratio: f32,
+    /// Types of static assets that are not loaded.
+    pub(crate) skip_types: Vec<&'static str>,
+    /// If not empty, only static assets of these types are loaded.
+    pub(crate) only_types: Vec<&'static str>,
+    /// Whether static assets within `<noscript>` blocks are ignored.
+    pub(crate) skip_noscript: bool,
+    /// Validations of the static assets with urls matching a regular expression.
+    pub(crate) validations: Vec<(Regex, Validate<'static>)>,
+}
+impl LoadAssets {
+    /// Convenience function to bring [`LoadAssetsBuilder`] into scope.
+    pub fn builder() -> LoadAssetsBuilder {
+        LoadAssetsBuilder::new()
+    }
+
+    // Returns true if static assets of the type are loaded.
+    pub(crate) fn loads_type(&self, resource_type: &str) -> bool {
+        !self.skip_types.contains(&resource_type)
+            && (self.only_types.is_empty() || self.only_types.contains(&resource_type))
+    }
+}
+impl Default for LoadAssets {
+    fn default() -> Self {
+        LoadAssets::builder().build()
+    }
+}
+
+/// Used to build a [`LoadAssets`] object, necessary to configure how static assets are
+/// loaded by [`load_assets`](crate::load_assets).
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::LoadAssets;
+///
+/// let _assets = LoadAssets::builder()
+///     .media_range(64 * 1024)
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct LoadAssetsBuilder {
+    /// How video and audio assets are requested.
+    media: MediaRequest,
+    /// How many levels of css `@import` and `url()` references are followed.
+    css_depth: usize,
+    /// Whether images lazy loaded with `data-src=` and `data-srcset=` are loaded.
+    lazy_load: bool,
+    /// Whether documents hinted with `rel="prefetch"` and `rel="prerender"` are loaded.
+    prefetch: bool,
+    /// Whether assets already loaded by the Goose user on a previous page are skipped.
+    dedupe_across_pages: bool,
+    /// Whether cached assets are revalidated with conditional requests.
+    conditional: bool,
+    /// How many static assets are loaded at once.
+    parallel: usize,
+    /// Hosts other than the base url host that static assets are loaded from.
+    allowed_hosts: Vec<String>,
+    /// How many of the static assets found on a page are loaded.
+    limit: AssetLimit,
+    /// Custom types of static assets, keyed by lower case file extension.
+    asset_types: Vec<(String, &'static str)>,
+    /// The Drupal public files path to load the libraries declared by `ajaxPageState` from.
+    drupal_libraries: Option<String>,
+    /// The probability that a static asset is served from the browser cache, and not requested.
+    cache_hit_ratio: f32,
+    /// Types of static assets that are not loaded.
+    skip_types: Vec<&'static str>,
+    /// If not empty, only static assets of these types are loaded.
+    only_types: Vec<&'static str>,
+    /// Whether static assets within `<noscript>` blocks are ignored.
+    skip_noscript: bool,
+    /// Validations of the static assets with urls matching a regular expression.
+    validations: Vec<(Regex, Validate<'static>)>,
+}
+impl LoadAssetsBuilder {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            media: MediaRequest::Full,
+            css_depth: 0,
+            lazy_load: false,
+            prefetch: false,
+            dedupe_across_pages: false,
+            conditional: false,
+            parallel: 1,
+            allowed_hosts: Vec::new(),
+            limit: AssetLimit::All,
+            asset_types: Vec::new(),
+            drupal_libraries: None,
+            cache_hit_ratio: 0.0,
+            skip_types: Vec::new(),
+            only_types: Vec::new(),
+            skip_noscript: false,
+            validations: Vec::new(),
+        }
+    }
+
+    /// Only request the headers of `<video>` and `<audio>` assets with a HEAD request,
+    /// instead of downloading large media files in their entirety.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .media_head()
+    ///     .build();
+    /// ```
+    pub fn media_head(mut self) -> Self {
+        self.media = MediaRequest::Head;
+        self
+    }
+
+    /// Only download the first `bytes` bytes of `<video>` and `<audio>` assets with a
+    /// Range request, like a browser that starts buffering a media file.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .media_range(1024 * 1024)
+    ///     .build();
+    /// ```
+    pub fn media_range(mut self, bytes: u64) -> Self {
+        self.media = MediaRequest::Range(bytes);
+        self
+    }
+
+    /// Parse downloaded css files for `@import` and `url()` references, such as fonts,
+    /// sprites and nested css, and load those too. References found in imported css files
+    /// are followed up to `depth` levels deep. By default css files are not parsed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .css_depth(2)
+    ///     .build();
+    /// ```
+    pub fn css_depth(mut self, depth: usize) -> Self {
+        self.css_depth = depth;
+        self
+    }
+
+    /// Load images that themes lazy load with javascript, defined with `data-src=` and
+    /// `data-srcset=` tags, to simulate the traffic of images below the fold. By default
+    /// lazy loaded images are not loaded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .lazy_load()
+    ///     .build();
+    /// ```
+    pub fn lazy_load(mut self) -> Self {
+        self.lazy_load = true;
+        self
+    }
+
+    /// Load the documents hinted with `<link rel="prefetch">` and `<link rel="prerender">`
+    /// tags and `Link` headers, which browsers may fetch for the next navigation. They're
+    /// requested as `static asset: prefetch`, separately from the assets of the page. By
+    /// default prefetched documents are not loaded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .prefetch()
+    ///     .build();
+    /// ```
+    pub fn prefetch(mut self) -> Self {
+        self.prefetch = true;
+        self
+    }
+
+    /// Skip static assets the Goose user already loaded on a previous page, until
+    /// [`forget_loaded_assets`] is invoked. Assets are always only loaded once per page.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .dedupe_across_pages()
+    ///     .build();
+    /// ```
+    pub fn dedupe_across_pages(mut self) -> Self {
+        self.dedupe_across_pages = true;
+        self
+    }
+
+    /// Simulate a warm browser cache: remember the `ETag` and `Last-Modified` headers of
+    /// each static asset the Goose user loads, and when the asset is loaded again request
+    /// it with `If-None-Match` and `If-Modified-Since` headers. Both a `304 Not Modified`
+    /// response and a `200 OK` response with the modified asset succeed, and the headers
+    /// of a modified asset are remembered to revalidate it next time. The cache is cleared
+    /// with [`forget_loaded_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .conditional_requests()
+    ///     .build();
+    /// ```
+    pub fn conditional_requests(mut self) -> Self {
+        self.conditional = true;
+        self
+    }
+
+    /// Load up to `connections` static assets at once instead of one at a time, closer to
+    /// how browsers load assets over several connections per host (typically 6).
+    ///
+    /// The Goose user loading the page loads assets over the first connection, and the
+    /// others are simulated by Goose users sharing its client, each taking the next asset
+    /// from a shared queue once done with the last. Cookies are shared, and requests are
+    /// included in the Goose metrics under the scenario and transaction of the page request.
+    /// When assets are loaded with [`load_assets`](crate::load_assets), which doesn't know the
+    /// page request, the first asset the Goose user loads itself is used instead. Requests
+    /// are sent over the connection pool of the client, so this limits how many
+    /// requests are in flight rather than how many connections are opened.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .parallel(6)
+    ///     .build();
+    /// ```
+    pub fn parallel(mut self, connections: usize) -> Self {
+        self.parallel = connections;
+        self
+    }
+
+    /// Also load static assets from `host`, such as a CDN the site offloads its static
+    /// assets to. By default only assets on the same host as the base url are loaded.
+    ///
+    /// Assets are requested with the client of the Goose user loading the page, and are
+    /// included in the Goose metrics. Can be invoked multiple times to allow several hosts.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .allow_host("cdn.example.com")
+    ///     .allow_host("fonts.example.net")
+    ///     .build();
+    /// ```
+    pub fn allow_host(mut self, host: &str) -> Self {
+        self.allowed_hosts.push(host.to_string());
+        self
+    }
+
+    /// Only load the first `max` static assets found on each page, so asset heavy pages
+    /// don't dominate the load test. Assets referenced by css files are not counted.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .max_assets(20)
+    ///     .build();
+    /// ```
+    pub fn max_assets(mut self, max: usize) -> Self {
+        self.limit = AssetLimit::First(max);
+        self
+    }
+
+    /// Only load `max` randomly selected static assets found on each page, so over many
+    /// page loads all assets are requested while asset heavy pages don't dominate the load
+    /// test. Assets referenced by css files are not counted.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .max_random_assets(20)
+    ///     .build();
+    /// ```
+    pub fn max_random_assets(mut self, max: usize) -> Self {
+        self.limit = AssetLimit::Random(max);
+        self
+    }
+
+    /// Load static assets with the file `extension` as `static asset: <resource_type>`,
+    /// replacing the type they'd otherwise be loaded as. Assets are otherwise classified by
+    /// their file extension, their MIME type if declared, and finally the tag referencing
+    /// them. Video and audio assets are only requested as configured with
+    /// [`LoadAssetsBuilder::media_head`] and [`LoadAssetsBuilder::media_range`] while
+    /// they're loaded as `media`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .asset_type("pdf", "document")
+    ///     .asset_type("webmanifest", "json")
+    ///     .build();
+    /// ```
+    pub fn asset_type(mut self, extension: &str, resource_type: &'static str) -> Self {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        self.asset_types.retain(|(e, _)| *e != extension);
+        self.asset_types.push((extension, resource_type));
+        self
+    }
+
+    /// Decode the `drupalSettings.ajaxPageState` libraries of Drupal pages, including those
+    /// attached by BigPipe placeholder replacements, and load the css and js aggregates of
+    /// those libraries that aren't already referenced by the page. Drupal 10.1 and later
+    /// generates these aggregates on demand from the public `files_path`, typically
+    /// `/sites/default/files`.
+    ///
+    /// See [`get_ajax_page_states`](crate::drupal::get_ajax_page_states) to inspect the
+    /// declared libraries.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .drupal_libraries("/sites/default/files")
+    ///     .build();
+    /// ```
+    pub fn drupal_libraries(mut self, files_path: &str) -> Self {
+        self.drupal_libraries = Some(files_path.to_string());
+        self
+    }
+
+    /// Simulate returning visitors with a warm browser cache: each static asset is
+    /// considered cached with a probability of `ratio`, between `0.0` and `1.0`, and is then
+    /// not requested. For example with a ratio of `0.8` each Goose user only requests each
+    /// static asset 20% of the time. By default every static asset is requested.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .cache_hit_ratio(0.8)
+    ///     .build();
+    /// ```
+    pub fn cache_hit_ratio(mut self, ratio: f32) -> Self {
+        self.cache_hit_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Don't load static assets of the type, such as `img`, `js`, `css`, `font` or `media`,
+    /// the type requests are named with, `static asset: <type>`. Can be invoked multiple
+    /// times to skip several types.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .skip_type("font")
+    ///     .skip_type("media")
+    ///     .build();
+    /// ```
+    pub fn skip_type(mut self, resource_type: &'static str) -> Self {
+        self.skip_types.push(resource_type);
+        self
+    }
+
+    /// Only load static assets of the type, such as `img`, `js`, `css`, `font` or `media`,
+    /// the type requests are named with, `static asset: <type>`. Can be invoked multiple
+    /// times to load several types.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .only_type("css")
+    ///     .only_type("font")
+    ///     .css_depth(1)
+    ///     .build();
+    /// ```
+    pub fn only_type(mut self, resource_type: &'static str) -> Self {
+        self.only_types.push(resource_type);
+        self
+    }
+
+    /// Don't load images, a shortcut for `skip_type("img")`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .skip_images()
+    ///     .build();
+    /// ```
+    pub fn skip_images(self) -> Self {
+        self.skip_type("img")
+    }
+
+    /// Don't load javascript, a shortcut for `skip_type("js")`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .skip_js()
+    ///     .build();
+    /// ```
+    pub fn skip_js(self) -> Self {
+        self.skip_type("js")
+    }
+
+    /// Only load css files, a shortcut for `only_type("css")`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .only_css()
+    ///     .build();
+    /// ```
+    pub fn only_css(self) -> Self {
+        self.only_type("css")
+    }
+
+    /// Ignore static assets within `<noscript>` blocks, such as tracking pixels and fallback
+    /// images, which browsers with javascript enabled never load. By default they're loaded,
+    /// simulating a browser with javascript disabled.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .skip_noscript()
+    ///     .build();
+    /// ```
+    pub fn skip_noscript(mut self) -> Self {
+        self.skip_noscript = true;
+        self
+    }
+
+    /// Validate the responses of static assets with urls matching the regular expression,
+    /// for example to confirm static files are served from the CDN cache. Can be invoked
+    /// multiple times, and each matching validation is checked.
+    ///
+    /// Failed validations are reported like page validations, and mark the static asset as
+    /// failed in [`LoadedAssets`]. Invalid regular expressions are logged and ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{LoadAssets, Validate};
+    ///
+    /// let _assets = LoadAssets::builder()
+    ///     .validate(
+    ///         "/sites/default/files/",
+    ///         Validate::builder().header_value("x-cache", "HIT").build(),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn validate(mut self, regex: &str, validate: Validate<'static>) -> Self {
+        match Regex::new(regex) {
+            Ok(regex) => self.validations.push((regex, validate)),
+            Err(e) => warn!("invalid static asset validation regex {}: {}", regex, e),
+        }
+        self
+    }
+
+    /// Build the [`LoadAssets`] object which is then passed to the
+    /// [`load_assets`](crate::load_assets) function, or to
+    /// [`ValidateBuilder::assets`](crate::ValidateBuilder::assets).
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::LoadAssets;
+    ///
+    /// let _assets = LoadAssets::builder().build();
+    /// ```
+    pub fn build(self) -> LoadAssets {
+        let LoadAssetsBuilder {
+            media,
+            css_depth,
+            lazy_load,
+            prefetch,
+            dedupe_across_pages,
+            conditional,
+            parallel,
+            allowed_hosts,
+            limit,
+            asset_types,
+            drupal_libraries,
+            cache_hit_ratio,
+            skip_types,
+            only_types,
+            skip_noscript,
+            validations,
+        } = self;
+        LoadAssets {
+            media,
+            css_depth,
+            lazy_load,
+            prefetch,
+            dedupe_across_pages,
+            conditional,
+            parallel,
+            allowed_hosts,
+            limit,
+            asset_types,
+            drupal_libraries,
+            cache_hit_ratio,
+            skip_types,
+            only_types,
+            skip_noscript,
+            validations,
+        }
+    }
+}
@@ -1,10 +1,206 @@
 //! Functionality that's specific to Drupal.
 
+use goose::goose::GooseResponse;
+use goose::metrics::GooseRequestMetric;
 use goose::prelude::*;
-use log::warn;
+use log::{info, warn};
 use regex::Regex;
-use std::collections::HashMap;
+use reqwest::cookie::{CookieStore, Jar};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{user_key, UserKey};
+
+// The CSRF token of the session of each Goose user of each load test.
+static SESSION_TOKENS: Mutex<BTreeMap<UserKey, String>> = Mutex::new(BTreeMap::new());
+
+// The Goose users of each load test whose session expired.
+static EXPIRED_SESSIONS: Mutex<BTreeSet<UserKey>> = Mutex::new(BTreeSet::new());
+
+pub mod contextual;
+pub mod jsonapi;
+pub mod quickedit;
+pub mod rest;
+
+/// The paths, form ids, form values and button labels that differ between Drupal versions,
+/// consulted by the helpers of this module for their defaults.
+///
+/// Built-in profiles are provided for Drupal 7, 9 and 10, and custom profiles can be
+/// defined for websites with different markup. The profile used by default is set with
+/// [`set_version_profile`], per Goose user with [`set_user_version_profile`], or per helper
+/// with [`LoginBuilder::profile`], [`SearchParamsBuilder::profile`] and
+/// [`EditParamsBuilder::profile`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::DrupalVersionProfile;
+///
+/// // A Drupal 10 website with a custom log in path.
+/// let _profile = DrupalVersionProfile {
+///     login_url: "account/login",
+///     ..DrupalVersionProfile::DRUPAL_10
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrupalVersionProfile {
+    /// The path of the log in form.
+    pub login_url: &'static str,
+    /// The `data-drupal-selector` or id of the log in form.
+    pub login_form: &'static str,
+    /// The values extracted from the log in form and posted with it.
+    pub login_form_values: &'static [&'static str],
+    /// The label of the log in button.
+    pub login_submit: &'static str,
+    /// The path that logs the user out.
+    pub logout_url: &'static str,
+    /// The `data-drupal-selector` or id of the form confirming the log out, if any.
+    pub logout_form: Option<&'static str>,
+    /// The label of the button confirming the log out.
+    pub logout_submit: &'static str,
+    /// The path of the search form.
+    pub search_url: &'static str,
+    /// The values extracted from the search form and posted with it.
+    pub search_form_values: &'static [&'static str],
+    /// The label of the search button.
+    pub search_submit: &'static str,
+    /// The label of the button that saves a node.
+    pub save_submit: &'static str,
+    /// The classes of the elements the themes wrap the text of status, warning and error
+    /// messages in, in order of preference. The whole message is used if none is found.
+    pub message_content: &'static [&'static str],
+}
+
+impl DrupalVersionProfile {
+    /// Drupal 7, whose log in form has an id of `user-login` and whose search form also
+    /// requires its `form_token`.
+    pub const DRUPAL_7: Self = Self {
+        login_url: "user/login",
+        login_form: "user-login",
+        login_form_values: &["form_build_id", "form_id"],
+        login_submit: "Log in",
+        logout_url: "user/logout",
+        logout_form: None,
+        logout_submit: "Log out",
+        search_url: "search/node",
+        search_form_values: &["form_token", "form_build_id", "form_id"],
+        search_submit: "Search",
+        save_submit: "Save",
+        message_content: &[],
+    };
+
+    /// Drupal 8 and 9.
+    pub const DRUPAL_9: Self = Self {
+        login_url: "user/login",
+        login_form: "user-login-form",
+        login_form_values: &["form_build_id", "form_id"],
+        login_submit: "Log in",
+        logout_url: "user/logout",
+        logout_form: None,
+        logout_submit: "Log out",
+        search_url: "search",
+        search_form_values: &["form_build_id", "form_id"],
+        search_submit: "Search",
+        save_submit: "Save",
+        message_content: &[],
+    };
+
+    /// Drupal 10.1 and later, the default. Logging out is confirmed with the
+    /// `user-logout-confirm` form at `user/logout/confirm`, and the Olivero and Claro themes
+    /// wrap the text of messages in `messages__content` and `messages__list` elements.
+    pub const DRUPAL_10: Self = Self {
+        login_url: "user/login",
+        login_form: "user-login-form",
+        login_form_values: &["form_build_id", "form_id"],
+        login_submit: "Log in",
+        logout_url: "user/logout/confirm",
+        logout_form: Some("user-logout-confirm"),
+        logout_submit: "Log out",
+        search_url: "search",
+        search_form_values: &["form_build_id", "form_id"],
+        search_submit: "Search",
+        save_submit: "Save",
+        message_content: &["messages__content", "messages__list"],
+    };
+}
+
+impl Default for DrupalVersionProfile {
+    fn default() -> Self {
+        Self::DRUPAL_10
+    }
+}
+
+// The profile the helpers of this module default to.
+static VERSION_PROFILE: Mutex<DrupalVersionProfile> = Mutex::new(DrupalVersionProfile::DRUPAL_10);
+
+// The profiles set for Goose users of each load test, overriding the default profile.
+static USER_VERSION_PROFILES: Mutex<BTreeMap<UserKey, DrupalVersionProfile>> =
+    Mutex::new(BTreeMap::new());
+
+/// Set the [`DrupalVersionProfile`] the helpers of this module default to, typically once
+/// before the load test starts. Any profiles set per Goose user with
+/// [`set_user_version_profile`] are forgotten.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::{set_version_profile, version_profile, DrupalVersionProfile};
+///
+/// set_version_profile(DrupalVersionProfile::DRUPAL_7);
+/// assert_eq!(version_profile().login_form, "user-login");
+/// # set_version_profile(DrupalVersionProfile::DRUPAL_10);
+/// ```
+pub fn set_version_profile(profile: DrupalVersionProfile) {
+    *VERSION_PROFILE.lock().unwrap() = profile;
+    USER_VERSION_PROFILES.lock().unwrap().clear();
+}
+
+/// The [`DrupalVersionProfile`] the helpers of this module default to, set with
+/// [`set_version_profile`]. Defaults to [`DrupalVersionProfile::DRUPAL_10`].
+pub fn version_profile() -> DrupalVersionProfile {
+    *VERSION_PROFILE.lock().unwrap()
+}
+
+/// Set the [`DrupalVersionProfile`] the helpers of this module default to for the Goose
+/// user, for example when scenarios of the same load test target websites running
+/// different versions of Drupal. Typically invoked in a transaction that runs on start.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{log_in, set_user_version_profile, DrupalVersionProfile, Login};
+///
+/// transaction!(log_in_legacy_site).set_on_start();
+///
+/// async fn log_in_legacy_site(user: &mut GooseUser) -> TransactionResult {
+///     set_user_version_profile(user, DrupalVersionProfile::DRUPAL_7);
+///     let _html = log_in(user, &Login::builder().build()).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn set_user_version_profile(user: &GooseUser, profile: DrupalVersionProfile) {
+    USER_VERSION_PROFILES
+        .lock()
+        .unwrap()
+        .insert(user_key(user), profile);
+}
+
+/// The [`DrupalVersionProfile`] the helpers of this module default to for the Goose user,
+/// set with [`set_user_version_profile`], or otherwise [`version_profile`].
+pub fn user_version_profile(user: &GooseUser) -> DrupalVersionProfile {
+    USER_VERSION_PROFILES
+        .lock()
+        .unwrap()
+        .get(&user_key(user))
+        .copied()
+        .unwrap_or_else(version_profile)
+}
 
 /// Use a regular expression to get the specific form identified by data-drupal-selector.
 ///
@@ -39,13 +235,12 @@ use std::env;
 pub fn get_form(html: &str, name: &str) -> String {
     let re = Regex::new(&format!(
         // Lazy match to avoid matching multiple forms.
-        r#"<form.*?(data-drupal-selector|id)="{name}".*?>(.*?)</form>"#,
+        r#"(?s)<form.*?(data-drupal-selector|id)="{name}".*?>(.*?)</form>"#,
     ))
     .unwrap();
-    // Strip carriage returns to simplify regex.
-    let line = html.replace('\n', "");
-    // Return the entire form, a subset of the received html.
-    match re.captures(&line) {
+    // Return the entire form, a subset of the received html, keeping the line breaks of
+    // its textareas.
+    match re.captures(html) {
         Some(capture) => capture[2].to_string(),
         None => {
             warn!("form {} not found", name);
@@ -56,6 +251,10 @@ pub fn get_form(html: &str, name: &str) -> String {
 
 /// Use regular expression to get the value of a named form element.
 ///
+/// The value of a `<textarea>` is its decoded contents, and the value of a `<select>` is
+/// the value of its selected `<option>`, or of its first option if none is selected. Use
+/// [`get_form_fields`] to get every value of a `<select multiple>`.
+///
 /// # Example
 /// ```rust
 /// use goose_eggs::drupal::{get_form, get_form_value};
@@ -83,6 +282,16 @@ pub fn get_form(html: &str, name: &str) -> String {
 /// let form = get_form(html, "user-login-form");
 /// let form_build_id = get_form_value(&form, "form_build_id");
 /// assert_eq!(&form_build_id, "form-bHZME2HeTuevNWQR5Y4pyP8jcAu2dfbHERwoscwnajM");
+///
+/// let form = r#"
+///   <textarea name="body[0][value]" rows="9">Fish &amp; chips</textarea>
+///   <select name="moderation_state[0][state]">
+///     <option value="draft">Draft</option>
+///     <option value="published" selected="selected">Published</option>
+///   </select>
+/// "#;
+/// assert_eq!(get_form_value(form, "body[0][value]"), "Fish & chips");
+/// assert_eq!(get_form_value(form, "moderation_state[0][state]"), "published");
 /// ```
 pub fn get_form_value(form_html: &str, name: &str) -> String {
     let re = Regex::new(&format!(
@@ -94,12 +303,99 @@ pub fn get_form_value(form_html: &str, name: &str) -> String {
     match re.captures(form_html) {
         Some(v) => v[1].to_string(),
         None => {
+            // Textareas and selects have no value attribute, so get the value a browser
+            // would submit.
+            let element = Regex::new(&format!(
+                r#"<(?:textarea|select)[^>]*\sname="{}""#,
+                regex::escape(name)
+            ))
+            .unwrap();
+            if element.is_match(form_html) {
+                return get_form_fields(form_html)
+                    .into_iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, value)| value)
+                    .unwrap_or_default();
+            }
             warn!("form element {} not found", name);
             "none".to_string()
         }
     }
 }
 
+/// Use a regular expression to get the form that's submitted to a specific action, for forms
+/// without a `data-drupal-selector` or id, such as forms added by contributed modules or
+/// embedded snippets.
+///
+/// The action matches with or without its query string, so `/user/login` matches a form
+/// with the action `/user/login?destination=/node/1`.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_form_by_action;
+///
+/// let html = r#"
+///   <form action="/newsletter/subscribe?destination=/node/1" method="post">
+///     <input type="email" name="email" value="" />
+///   </form>
+/// "#;
+///
+/// let form = get_form_by_action(html, "/newsletter/subscribe");
+/// assert!(form.contains(r#"name="email""#));
+/// ```
+pub fn get_form_by_action(html: &str, action: &str) -> String {
+    let form = get_forms(html).into_iter().find(|(tag, _)| {
+        get_attribute(tag, "action")
+            .map(|a| a == action || a.split('?').next() == Some(action))
+            .unwrap_or(false)
+    });
+    match form {
+        Some((_, form)) => form.to_string(),
+        None => {
+            warn!("form with action {} not found", action);
+            "".to_string()
+        }
+    }
+}
+
+/// Use a regular expression to get the nth form of a page, counting from 0, for forms
+/// without a `data-drupal-selector`, id or distinct action.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_nth_form;
+///
+/// let html = r#"
+///   <form action="/search" method="get"><input type="search" name="keys" value="" /></form>
+///   <form action="/subscribe" method="post"><input type="email" name="email" value="" /></form>
+/// "#;
+///
+/// assert!(get_nth_form(html, 1).contains(r#"name="email""#));
+/// assert!(get_nth_form(html, 2).is_empty());
+/// ```
+pub fn get_nth_form(html: &str, n: usize) -> String {
+    match get_forms(html).into_iter().nth(n) {
+        Some((_, form)) => form.to_string(),
+        None => {
+            warn!("form {} not found", n);
+            "".to_string()
+        }
+    }
+}
+
+// Get the opening tag and the contents of each form of a page, in order. The tag is padded
+// with a leading space so its attributes can be extracted.
+fn get_forms(html: &str) -> Vec<(String, &str)> {
+    // Lazy match to avoid matching multiple forms.
+    let re = Regex::new(r#"(?s)<form(\s[^>]*)?>(.*?)</form>"#).unwrap();
+    re.captures_iter(html)
+        .map(|c| {
+            let tag = c.get(1).map_or("", |t| t.as_str());
+            (format!(" {}", tag), c.get(2).unwrap().as_str())
+        })
+        .collect()
+}
+
 /// Use a regular expression to get a specific form that has been encoded to replace a BigPipe placeholder,
 /// identified by the data-drupal-selector.
 ///
@@ -163,6 +459,85 @@ pub fn get_bigpipe_form(html: &str, name: &str) -> String {
     }
 }
 
+/// Extract the BigPipe no-JS urls of a page, such as `/big_pipe/no-js?destination=/node/1`.
+///
+/// BigPipe adds a `<meta http-equiv="Refresh">` in a `<noscript>` to pages with
+/// placeholders, redirecting browsers without JavaScript to this url. Refer to
+/// [`load_bigpipe_nojs`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_bigpipe_nojs_urls;
+///
+/// let html = r#"
+/// <noscript><meta http-equiv="Refresh" content="0; URL=/big_pipe/no-js?destination=/node/1%3Fpage%3D2&amp;foo=bar" /></noscript>
+/// "#;
+///
+/// assert_eq!(
+///     get_bigpipe_nojs_urls(html),
+///     vec!["/big_pipe/no-js?destination=/node/1%3Fpage%3D2&foo=bar".to_string()]
+/// );
+/// ```
+pub fn get_bigpipe_nojs_urls(html: &str) -> Vec<String> {
+    let re = Regex::new(r#"(?:https?://[^"'\s<>]*?)?/?big_pipe/no-js\?[^"'\s<>]*"#).unwrap();
+    let mut urls: Vec<String> = Vec::new();
+    for url in re.find_iter(html) {
+        let url = html_escape::decode_html_entities(url.as_str()).to_string();
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Request the BigPipe no-JS urls of a page, like a browser without JavaScript would.
+///
+/// BigPipe sets the `big_pipe_nojs` cookie and redirects back to the page, which is then
+/// rendered with its placeholders replaced in the html rather than streamed for JavaScript
+/// to replace. This exercises the uncacheable placeholder rendering, rather than only the
+/// cached page. The cookie is kept, so the Goose user's following pages are also rendered
+/// without JavaScript, until the cookie is cleared.
+///
+/// The requests are named `drupal: big_pipe no-js`, and the html of each page loaded is
+/// returned, with its static assets loaded.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::load_bigpipe_nojs;
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(load_front_page_without_js);
+///
+/// async fn load_front_page_without_js(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///
+///     // Render the placeholders of the page without JavaScript.
+///     let _pages = load_bigpipe_nojs(user, &html).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn load_bigpipe_nojs(
+    user: &mut GooseUser,
+    html: &str,
+) -> Result<Vec<String>, Box<TransactionError>> {
+    let mut pages = Vec::new();
+    for url in get_bigpipe_nojs_urls(html) {
+        let goose_request = GooseRequest::builder()
+            .path(url.as_str())
+            .name("drupal: big_pipe no-js")
+            .build();
+        let goose = user.request(goose_request).await?;
+        let page =
+            crate::validate_and_load_static_assets(user, goose, &crate::Validate::none()).await?;
+        pages.push(page);
+    }
+
+    Ok(pages)
+}
+
 /// Load a form value from an encoded form.
 ///
 /// Gets form values from forms that are returned by ajax callbacks or embedded by BigPipe.
@@ -302,397 +677,4875 @@ pub fn get_encoded_form_values<'a>(form: &str, elements: &'a [&str]) -> HashMap<
     form_elements
 }
 
-/// Parameters that define how to log into a Drupal website and validate
-/// that the user loged in successfully. For complete documentation, refer
-/// to [`LoginBuilder`].
-#[derive(Clone, Debug)]
-pub struct Login<'a> {
-    // Optionally set a default username.
-    username: &'a str,
-    // Optionally set a default password.
-    password: &'a str,
-    // Optionally set a custom log in path (otherwise defaults to
-    // `/user/login`).
-    url: &'a str,
-    // Optionally set a custom title to validate.
-    log_in_page_validation: Option<&'a crate::Validate<'a>>,
-    // Optionally set a custom title to validate.
-    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
-}
-impl<'a> Login<'a> {
-    /// Convenience function to bring [`LoginBuilder`] into scope.
-    pub fn builder() -> LoginBuilder<'a> {
-        LoginBuilder::new()
+/// Extract the name and value of every field of a form that a browser would submit,
+/// preserving the values the form was rendered with.
+///
+/// Checkboxes and radio buttons are only included if checked, select lists include their
+/// selected options (or the first option if none is selected), and buttons and file
+/// fields are skipped. Values are decoded from html entities.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_form_fields;
+///
+/// let form = r#"
+///   <input data-drupal-selector="edit-title-0-value" type="text" name="title[0][value]" value="Fish &amp; chips" />
+///   <input type="checkbox" name="status[value]" value="1" checked="checked" />
+///   <input type="checkbox" name="promote[value]" value="1" />
+///   <textarea name="body[0][value]">Crispy.</textarea>
+///   <select name="moderation_state[0][state]"><option value="draft">Draft</option><option value="published" selected="selected">Published</option></select>
+///   <input type="submit" name="op" value="Save" />
+/// "#;
+///
+/// let fields = get_form_fields(form);
+/// assert_eq!(
+///     fields,
+///     vec![
+///         ("title[0][value]".to_string(), "Fish & chips".to_string()),
+///         ("status[value]".to_string(), "1".to_string()),
+///         ("body[0][value]".to_string(), "Crispy.".to_string()),
+///         ("moderation_state[0][state]".to_string(), "published".to_string()),
+///     ]
+/// );
+/// ```
+pub fn get_form_fields(form_html: &str) -> Vec<(String, String)> {
+    let field = Regex::new(
+        r#"(?s)<input([^>]*)>|<textarea([^>]*)>(.*?)</textarea>|<select([^>]*)>(.*?)</select>"#,
+    )
+    .unwrap();
+    let option = Regex::new(r#"<option([^>]*)>"#).unwrap();
+
+    let mut fields = Vec::new();
+    for capture in field.captures_iter(form_html) {
+        if let Some(input) = capture.get(1) {
+            let input = input.as_str();
+            let input_type = get_attribute(input, "type").unwrap_or_else(|| "text".to_string());
+            match input_type.to_lowercase().as_str() {
+                "submit" | "button" | "image" | "reset" | "file" => continue,
+                "checkbox" | "radio" if !has_attribute(input, "checked") => continue,
+                _ => (),
+            }
+            if let Some(name) = get_attribute(input, "name") {
+                let value = get_attribute(input, "value").unwrap_or_default();
+                fields.push((name, value));
+            }
+        } else if let (Some(textarea), Some(text)) = (capture.get(2), capture.get(3)) {
+            if let Some(name) = get_attribute(textarea.as_str(), "name") {
+                let value = html_escape::decode_html_entities(text.as_str()).to_string();
+                fields.push((name, value));
+            }
+        } else if let (Some(select), Some(options)) = (capture.get(4), capture.get(5)) {
+            let name = match get_attribute(select.as_str(), "name") {
+                Some(name) => name,
+                None => continue,
+            };
+            let options: Vec<&str> = option
+                .captures_iter(options.as_str())
+                .map(|o| o.get(1).unwrap().as_str())
+                .collect();
+            let selected: Vec<&str> = options
+                .iter()
+                .copied()
+                .filter(|o| has_attribute(o, "selected"))
+                .collect();
+            let selected = if selected.is_empty() && !has_attribute(select.as_str(), "multiple") {
+                options.into_iter().take(1).collect()
+            } else {
+                selected
+            };
+            for o in selected {
+                fields.push((name.clone(), get_attribute(o, "value").unwrap_or_default()));
+            }
+        }
     }
+
+    fields
 }
 
-/// Used to build a [`Login`] object, necessary to invoke the [`log_in`] function.
-///
-/// Sets parameters that define how to log into a Drupal website and validate that
-/// the user loged in successfully.
-///
-/// This object is passed to the [`log_in`] function.
+// Get the decoded value of an attribute of an html tag, in double or single quotes.
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let re = Regex::new(&format!(r#"(?i)\s{name}\s*=\s*(?:"([^"]*)"|'([^']*)')"#)).unwrap();
+    re.captures(tag)
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|value| html_escape::decode_html_entities(value.as_str()).to_string())
+}
+
+// Whether an html tag has a boolean attribute, such as `checked` or `selected`.
+fn has_attribute(tag: &str, name: &str) -> bool {
+    let re = Regex::new(&format!(r#"(?i)\s{name}(\s|=|/|$)"#)).unwrap();
+    re.is_match(tag)
+}
+
+/// A form parsed from a page, with its action, method and the fields a browser would submit.
 ///
-/// The defined `username` and/or `password` can be dynamically overridden by setting
-/// the `GOOSE_USER` and/or `GOOSE_PASS` environment variables when starting the load
-/// test.
+/// Parsing a form once replaces extracting the `form_build_id`, `form_token` and `form_id`
+/// one by one with [`get_form`] and [`get_form_value`], and [`Form::submit`] posts all of
+/// the fields with any changes.
 ///
 /// # Example
 /// ```rust
-/// use goose_eggs::drupal::Login;
+/// use goose_eggs::drupal::Form;
 ///
-/// fn examples() {
-///     // Manually build a Login structure with custom default username and password,
-///     // and a custom log in path.
-///     let _login = Login::builder()
-///         .username("foo")
-///         .password("bar")
-///         .url("custom/user/login")
-///         .build();
-/// }
-pub struct LoginBuilder<'a> {
-    // Optionally set a default username.
-    username: &'a str,
-    // Optionally set a default password.
-    password: &'a str,
-    // Optionally set a custom default path (otherwise defaults to `/user/login`).
-    url: &'a str,
-    // Optionally perform validation of the page with the login form.
-    log_in_page_validation: Option<&'a crate::Validate<'a>>,
-    // Optionally perform validation once the user logs in.
-    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
+/// let html = r#"
+///   <form class="user-login-form" data-drupal-selector="user-login-form" action="/user/login?destination=/node/1&amp;foo=bar" method="post" id="user-login-form" accept-charset="UTF-8">
+///     <input data-drupal-selector="edit-name" type="text" id="edit-name" name="name" value="" />
+///     <input data-drupal-selector="edit-pass" type="password" id="edit-pass" name="pass" />
+///     <input autocomplete="off" type="hidden" name="form_build_id" value="form-bHZME2HeTuevNWQR5Y4pyP8jcAu2dfbHERwoscwnajM" />
+///     <input data-drupal-selector="edit-user-login-form" type="hidden" name="form_id" value="user_login_form" />
+///     <input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Log in" />
+///   </form>
+/// "#;
+///
+/// let form = Form::parse(html, "user-login-form").unwrap();
+/// assert_eq!(form.action, "/user/login?destination=/node/1&foo=bar");
+/// assert_eq!(form.method, "post");
+/// assert_eq!(form.value("form_id"), Some("user_login_form"));
+/// assert_eq!(form.value("pass"), Some(""));
+/// assert_eq!(form.value("op"), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Form {
+    /// The `data-drupal-selector` or id the form was found by.
+    pub name: String,
+    /// The path the form is submitted to, empty if the form has no action.
+    pub action: String,
+    /// The method the form is submitted with, in lowercase: `post` or `get`.
+    pub method: String,
+    /// The name and value of every field a browser would submit, in order. Refer to
+    /// [`get_form_fields`].
+    pub fields: Vec<(String, String)>,
 }
-impl<'a> LoginBuilder<'a> {
-    // Internally used when building to set defaults.
-    fn new() -> Self {
-        Self {
-            // Defaults to a username of "username".
-            username: "username",
-            // Defaults to a password of "password".
-            password: "search",
-            // Defaults to Drupal's standard login path of "user/login".
-            url: "user/login",
-            // Default tos performing no extra validation.
-            log_in_page_validation: None,
-            // Defaults to performing no extra validation.
-            logged_in_page_validation: None,
-        }
+
+impl Form {
+    /// Parse the form identified by its `data-drupal-selector` or id from a page, returning
+    /// `None` if the page has no such form.
+    pub fn parse(html: &str, name: &str) -> Option<Form> {
+        let re = Regex::new(&format!(
+            // Lazy match to avoid matching multiple forms.
+            r#"(?s)<form([^>]*\s(?:data-drupal-selector|id)="{}"[^>]*)>(.*?)</form>"#,
+            regex::escape(name)
+        ))
+        .unwrap();
+        let capture = re.captures(html)?;
+        // Attributes are matched after whitespace, so pad the tag.
+        let tag = format!(" {}", &capture[1]);
+        Some(Form {
+            name: name.to_string(),
+            action: get_attribute(&tag, "action").unwrap_or_default(),
+            method: get_attribute(&tag, "method")
+                .unwrap_or_else(|| "get".to_string())
+                .to_lowercase(),
+            fields: get_form_fields(&capture[2]),
+        })
     }
 
-    /// Used with [`Login::builder`] to configure login parameters.
-    ///
-    /// Defaults to `username`.
+    /// The first value of the named field, or `None` if the form won't submit the field.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set the value of the named field, replacing any values it had.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.fields.retain(|(n, _)| n != name);
+        self.fields.push((name.to_string(), value.to_string()));
+    }
+
+    /// Submit the form to its action with its method, after applying the `overrides` with
+    /// [`Form::set`]. Buttons aren't submitted unless included in the `overrides`, such as
+    /// `("op", "Save")`.
     ///
-    /// Once built, the resulting object is passed to the [`log_in`] function. The
-    /// username and password can still be overridden by the `GOOSE_USER` and
-    /// `GOOSE_PASS` environment variables.
+    /// The response is returned without validation, to be validated with
+    /// [`validate_and_load_static_assets`](crate::validate_and_load_static_assets).
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::Login;
-    ///
-    /// // Login with username "foo", and the default password of "password".
-    /// let _login = Login::builder()
-    ///     .username("foo")
-    ///     .build();
-    /// ```
-    pub fn username(mut self, username: impl Into<&'a str>) -> Self {
-        self.username = username.into();
-        self
-    }
-
-    /// Used with [`Login::builder`] to configure login parameters.
+    /// use goose::prelude::*;
+    /// use goose_eggs::drupal::Form;
+    /// use goose_eggs::{validate_and_load_static_assets, Validate};
     ///
-    /// Defaults to `password`.
+    /// transaction!(post_contact_form);
     ///
-    /// Once built, the resulting object is passed to the [`log_in`] function. The
-    /// username and password can still be overridden by the `GOOSE_USER` and
-    /// `GOOSE_PASS` environment variables.
+    /// async fn post_contact_form(user: &mut GooseUser) -> TransactionResult {
+    ///     let goose = user.get("contact/feedback").await?;
+    ///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
     ///
-    /// # Example
-    /// ```rust
-    /// use goose_eggs::drupal::Login;
+    ///     if let Some(form) = Form::parse(&html, "contact-message-feedback-form") {
+    ///         let goose = form
+    ///             .submit(
+    ///                 user,
+    ///                 &[
+    ///                     ("subject[0][value]", "Hello"),
+    ///                     ("message[0][value]", "Load testing the contact form."),
+    ///                     ("op", "Send message"),
+    ///                 ],
+    ///             )
+    ///             .await?;
+    ///         let validate = Validate::builder().text("Your message has been sent.").build();
+    ///         validate_and_load_static_assets(user, goose, &validate).await?;
+    ///     }
     ///
-    /// // Login with username "bar", and the default username of "username".
-    /// let _login = Login::builder()
-    ///     .password("bar")
-    ///     .build();
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn password(mut self, password: impl Into<&'a str>) -> Self {
-        self.password = password.into();
-        self
+    pub async fn submit(
+        &self,
+        user: &mut GooseUser,
+        overrides: &[(&str, &str)],
+    ) -> Result<GooseResponse, Box<TransactionError>> {
+        let mut form = self.clone();
+        for (name, value) in overrides {
+            form.set(name, value);
+        }
+        if form.method == "get" {
+            let request_builder = user
+                .get_request_builder(&GooseMethod::Get, &form.action)?
+                .query(&form.fields);
+            let goose_request = GooseRequest::builder()
+                .path(form.action.as_str())
+                .set_request_builder(request_builder)
+                .build();
+            user.request(goose_request).await
+        } else {
+            user.post_form(&form.action, &form.fields).await
+        }
     }
+}
 
-    /// Used with [`Login::builder`] to configure login parameters.
-    ///
-    /// Defaults to `user/login`.
-    ///
-    /// Once built, the resulting object is passed to the [`log_in`] function.
-    ///
-    /// # Example
-    /// ```rust
+/// The type of a message Drupal displays in the `messages` region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageType {
+    /// A status message, such as a confirmation that a form was saved.
+    Status,
+    /// A warning message.
+    Warning,
+    /// An error message, such as a form validation error.
+    Error,
+}
+
+/// A message Drupal displayed in the `messages` region, returned by [`get_messages`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    /// The type of the message.
+    pub message_type: MessageType,
+    /// The text of the message, without markup.
+    pub text: String,
+}
+
+/// Extract the status, warning and error messages Drupal displayed on the page, in the
+/// order they're displayed. Each item of a list of messages of the same type is returned
+/// as a separate [`Message`].
+///
+/// Messages are detected by their `messages--status`, `messages--warning` or
+/// `messages--error` class, or by their `data-drupal-message-type` attribute, so the
+/// messages of Drupal's core themes are all found. The text is taken from the elements
+/// the themes wrap it in, identified by the classes listed in `message_content`: typically
+/// the `message_content` of the profile returned by [`user_version_profile`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::{get_messages, DrupalVersionProfile, MessageType};
+///
+/// let html = r#"
+/// <div role="contentinfo" aria-label="Error message" class="messages messages--error">
+///   <div role="alert">
+///     <h2 class="visually-hidden">Error message</h2>
+///     <ul class="messages__list">
+///       <li class="messages__item">Title field is required.</li>
+///       <li class="messages__item">Body field is required.</li>
+///     </ul>
+///   </div>
+/// </div>
+/// <div class="messages messages--status" data-drupal-message-type="status">
+///   <div class="messages__content">Basic page <em>About</em> has been updated.</div>
+/// </div>
+/// "#;
+///
+/// let messages = get_messages(html, DrupalVersionProfile::DRUPAL_10.message_content);
+/// assert_eq!(messages.len(), 3);
+/// assert_eq!(messages[0].message_type, MessageType::Error);
+/// assert_eq!(messages[1].text, "Body field is required.");
+/// assert_eq!(messages[2].message_type, MessageType::Status);
+/// assert_eq!(messages[2].text, "Basic page About has been updated.");
+/// ```
+pub fn get_messages(html: &str, message_content: &[&str]) -> Vec<Message> {
+    let re = Regex::new(
+        r#"(?i)<div\s[^>]*?(?:class="[^"]*\bmessages--(status|warning|error)\b|data-drupal-message-type="(status|warning|error)")[^>]*>"#,
+    )
+    .unwrap();
+    // Visually hidden headings and close buttons aren't part of the message.
+    let hidden = Regex::new(r#"(?is)<h2[^>]*>.*?</h2>|<button[^>]*>.*?</button>"#).unwrap();
+    let items = Regex::new(r#"(?is)<li[^>]*>(.*?)</li>"#).unwrap();
+    let tags = Regex::new(r#"<[^>]*>"#).unwrap();
+
+    let mut messages = Vec::new();
+    for capture in re.captures_iter(html) {
+        let message_type = match capture
+            .get(1)
+            .or_else(|| capture.get(2))
+            .map(|t| t.as_str().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("warning") => MessageType::Warning,
+            Some("error") => MessageType::Error,
+            _ => MessageType::Status,
+        };
+        // The message starts with the matched div, get everything it contains.
+        let start = capture.get(0).unwrap().start();
+        let element = crate::get_element(&html[start..], "div").unwrap_or_default();
+        // Only keep the text of the message if the theme wraps it.
+        let element = message_content
+            .iter()
+            .find_map(|class| crate::get_element(&element, &format!(".{}", class)))
+            .unwrap_or(element);
+        let element = hidden.replace_all(&element, "");
+        let mut texts: Vec<&str> = items
+            .captures_iter(&element)
+            .map(|item| item.get(1).unwrap().as_str())
+            .collect();
+        if texts.is_empty() {
+            texts.push(&element);
+        }
+        for text in texts {
+            let text = tags.replace_all(text, " ");
+            let text = html_escape::decode_html_entities(&text)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !text.is_empty() {
+                messages.push(Message { message_type, text });
+            }
+        }
+    }
+    messages
+}
+
+// Describe why submitting a form failed, optionally followed by the error messages
+// Drupal displayed when it rendered the form again.
+fn form_failure(user: &GooseUser, description: String, html: &str, report_errors: bool) -> String {
+    if !report_errors {
+        return description;
+    }
+    let errors: Vec<String> = get_messages(html, user_version_profile(user).message_content)
+        .into_iter()
+        .filter(|message| message.message_type == MessageType::Error)
+        .map(|message| message.text)
+        .collect();
+    if errors.is_empty() {
+        description
+    } else {
+        format!("{}: {}", description, errors.join("; "))
+    }
+}
+
+/// The minimum time the Honeypot module requires between displaying and submitting a
+/// form by default, its `honeypot_time_restriction` setting.
+pub const HONEYPOT_TIME_RESTRICTION: Duration = Duration::from_secs(5);
+
+/// Extract the fields the Honeypot module adds to a form, which must be submitted
+/// unchanged: the hidden honeypot text fields with an empty value, and `honeypot_time`
+/// with its value if the form has a time restriction.
+///
+/// [`log_in`], [`search`], [`edit_node`] and [`webform_submit`] include these fields
+/// automatically, and wait out the time restriction before posting the form.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_honeypot_fields;
+///
+/// let form = r#"
+///   <div class="js-form-item form-item js-form-type-textfield form-item-name js-form-item-name">
+///     <input data-drupal-selector="edit-name" type="text" id="edit-name" name="name" value="" />
+///   </div>
+///   <div class="homepage-url-textfield js-form-wrapper form-wrapper" data-drupal-selector="edit-homepage-url-wrapper" style="display: none !important;" id="edit-homepage-url-wrapper">
+///     <div class="js-form-item form-item js-form-type-textfield form-item-homepage-url js-form-item-homepage-url">
+///       <label for="edit-homepage-url">Leave this field blank</label>
+///       <input autocomplete="off" data-drupal-selector="edit-homepage-url" type="text" id="edit-homepage-url" name="homepage_url" value="" size="20" maxlength="128" class="form-text" />
+///     </div>
+///   </div>
+///   <div style="display:none !important" id="edit-url-wrapper" class="url-textfield js-form-wrapper form-wrapper">
+///     <input name="url" type="text" value="" />
+///   </div>
+///   <input data-drupal-selector="edit-honeypot-time" type="hidden" name="honeypot_time" value="kdEF3nHyiC8JN5SBVMPTqZ" />
+/// "#;
+///
+/// assert_eq!(
+///     get_honeypot_fields(form),
+///     vec![
+///         ("homepage_url".to_string(), "".to_string()),
+///         ("url".to_string(), "".to_string()),
+///         ("honeypot_time".to_string(), "kdEF3nHyiC8JN5SBVMPTqZ".to_string()),
+///     ]
+/// );
+/// ```
+pub fn get_honeypot_fields(form_html: &str) -> Vec<(String, String)> {
+    // Honeypot hides each of its text fields in a wrapper classed after the field name,
+    // such as `url-textfield`, with an inline `display: none` style.
+    let div = Regex::new(r#"(?i)<div\s[^>]*>"#).unwrap();
+    let input = Regex::new(r#"(?i)<input\s[^>]*>"#).unwrap();
+    let mut fields = Vec::new();
+    for wrapper in div.find_iter(form_html) {
+        let class = get_attribute(wrapper.as_str(), "class").unwrap_or_default();
+        let style = get_attribute(wrapper.as_str(), "style").unwrap_or_default();
+        let hidden = style.replace(' ', "").contains("display:none");
+        if !hidden || !class.split_whitespace().any(|c| c.ends_with("-textfield")) {
+            continue;
+        }
+        // The field is the first input within the wrapper.
+        let name = input
+            .find(&form_html[wrapper.end()..])
+            .and_then(|field| get_attribute(field.as_str(), "name"));
+        if let Some(name) = name {
+            fields.push((name, String::new()));
+        }
+    }
+    if form_html.contains(r#"name="honeypot_time""#) {
+        fields.push((
+            "honeypot_time".to_string(),
+            get_form_value(form_html, "honeypot_time"),
+        ));
+    }
+
+    fields
+}
+
+// Honeypot rejects forms submitted before its time restriction has passed since they were
+// displayed, so wait out whatever remains of it if the form has a `honeypot_time`.
+async fn wait_for_honeypot(form_html: &str, displayed: Instant, time_restriction: Duration) {
+    if !form_html.contains(r#"name="honeypot_time""#) {
+        return;
+    }
+    let elapsed = displayed.elapsed();
+    if elapsed < time_restriction {
+        tokio::time::sleep(time_restriction - elapsed).await;
+    }
+}
+
+/// Extract the fields the CAPTCHA module adds to a form, `captcha_sid` and `captcha_token`,
+/// and add the `captcha_response` to post with them.
+///
+/// CAPTCHA challenges can't be solved by a load test, but sites can be configured for
+/// testing with a known answer, such as the "Test" challenge that accepts any response,
+/// or a math or image challenge with a fixed answer. [`log_in`], [`search`], [`edit_node`]
+/// and [`webform_submit`] apply this when configured with `captcha_response()`, for example
+/// [`LoginBuilder::captcha_response`].
+///
+/// Returns an empty vector if the form has no CAPTCHA.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_captcha_fields;
+///
+/// let form = r#"
+///   <input data-drupal-selector="edit-captcha-sid" type="hidden" name="captcha_sid" value="42" />
+///   <input data-drupal-selector="edit-captcha-token" type="hidden" name="captcha_token" value="VbyRoiwSk0E3yB-5TnfeQ3Qs6JfwACkeBlp_JyWDpAo" />
+///   <input autocomplete="off" data-drupal-selector="edit-captcha-response" type="text" name="captcha_response" value="" />
+/// "#;
+///
+/// assert_eq!(
+///     get_captcha_fields(form, "7"),
+///     vec![
+///         ("captcha_sid".to_string(), "42".to_string()),
+///         ("captcha_token".to_string(), "VbyRoiwSk0E3yB-5TnfeQ3Qs6JfwACkeBlp_JyWDpAo".to_string()),
+///         ("captcha_response".to_string(), "7".to_string()),
+///     ]
+/// );
+/// ```
+pub fn get_captcha_fields(form_html: &str, response: &str) -> Vec<(String, String)> {
+    if !form_html.contains(r#"name="captcha_sid""#) {
+        return Vec::new();
+    }
+    let mut fields = Vec::new();
+    for name in ["captcha_sid", "captcha_token"] {
+        if form_html.contains(&format!(r#"name="{}""#, name)) {
+            fields.push((name.to_string(), get_form_value(form_html, name)));
+        }
+    }
+    fields.push(("captcha_response".to_string(), response.to_string()));
+
+    fields
+}
+
+/// How to submit a form protected by the Antibot module without JavaScript, returned by
+/// [`get_antibot_form`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AntibotForm {
+    /// The path the form must be posted to, which Antibot moves to `data-action`.
+    pub action: String,
+    /// The value of the `antibot_key` field.
+    pub key: String,
+}
+
+/// Detect whether the Antibot module protects the named form of a page, returning the
+/// action and key its JavaScript would apply to the form.
+///
+/// Antibot replaces the action of protected forms with `/antibot`, which rejects posts.
+/// Once a human interacts with the page, its JavaScript restores the action from the
+/// `data-action` attribute and decodes the key from `drupalSettings` into the
+/// `antibot_key` field. The page must be the entire html, which includes the
+/// `drupalSettings`.
+///
+/// [`log_in`], [`search`], [`edit_node`] and [`webform_submit`] apply this when enabled
+/// with `antibot(true)`, for example [`LoginBuilder::antibot`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_antibot_form;
+///
+/// let html = r#"
+///   <form class="user-login-form" data-drupal-selector="user-login-form" data-action="/user/login" action="/antibot" method="post" id="user-login-form" accept-charset="UTF-8">
+///     <input data-drupal-selector="edit-antibot-key" type="hidden" name="antibot_key" value="" />
+///   </form>
+///   <script type="application/json" data-drupal-selector="drupal-settings-json">
+///     {"antibot":{"forms":{"user-login-form":{"id":"user-login-form","key":"cdab2f1e"}}}}
+///   </script>
+/// "#;
+///
+/// let antibot = get_antibot_form(html, "user-login-form").unwrap();
+/// assert_eq!(antibot.action, "/user/login");
+/// assert_eq!(antibot.key, "1e2fabcd");
+/// ```
+pub fn get_antibot_form(html: &str, form: &str) -> Option<AntibotForm> {
+    let re = Regex::new(&format!(
+        r#"(?s)<form[^>]*\s(data-drupal-selector|id)="{form}"[^>]*>"#
+    ))
+    .unwrap();
+    let tag = re.captures(html)?.get(0)?.as_str();
+    let action = get_attribute(tag, "data-action")?;
+    let id = get_attribute(tag, "id").unwrap_or_else(|| form.to_string());
+    let key = get_drupal_settings(html)["antibot"]["forms"][&id]["key"]
+        .as_str()
+        .map(|key| {
+            // Antibot's JavaScript reverses the key, then reverses each pair of characters.
+            let reversed: Vec<char> = key.chars().rev().collect();
+            reversed
+                .chunks(2)
+                .flat_map(|pair| pair.iter().rev())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(AntibotForm { action, key })
+}
+
+/// Supplies the code posted to the two-factor authentication form of the TFA module when
+/// logging in, such as a TOTP code computed from the shared secret of the Goose user.
+/// Refer to [`LoginBuilder::tfa`].
+///
+/// Implemented for closures that take the [`GooseUser`] logging in and return the code.
+pub trait TfaCode: Send + Sync {
+    /// Returns the code for the Goose user that is logging in.
+    fn code(&self, user: &GooseUser) -> String;
+}
+
+impl<F> TfaCode for F
+where
+    F: Fn(&GooseUser) -> String + Send + Sync,
+{
+    fn code(&self, user: &GooseUser) -> String {
+        self(user)
+    }
+}
+
+impl std::fmt::Debug for dyn TfaCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TfaCode")
+    }
+}
+
+/// Parameters that define how to log into a Drupal website and validate
+/// that the user loged in successfully. For complete documentation, refer
+/// to [`LoginBuilder`].
+#[derive(Clone, Debug)]
+pub struct Login<'a> {
+    // Optionally set a default username.
+    username: &'a str,
+    // Optionally set a default password.
+    password: &'a str,
+    // Optionally set a custom log in path (otherwise defaults to the version profile).
+    url: Option<&'a str>,
+    // Optionally set the id of the log in form (otherwise defaults to the version profile).
+    form: Option<&'a str>,
+    // Optionally set values extracted from the log in form and posted with it.
+    form_values: Option<&'a [&'a str]>,
+    // Optionally set a custom title to validate.
+    log_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally set a custom title to validate.
+    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the log in form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the log in form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the log in form.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+    // Optionally supply the code of the two-factor authentication step.
+    tfa: Option<Arc<dyn TfaCode>>,
+    // Optionally set the label of the log in button.
+    submit: Option<&'a str>,
+}
+impl<'a> Login<'a> {
+    /// Convenience function to bring [`LoginBuilder`] into scope.
+    pub fn builder() -> LoginBuilder<'a> {
+        LoginBuilder::new()
+    }
+}
+
+/// Used to build a [`Login`] object, necessary to invoke the [`log_in`] function.
+///
+/// Sets parameters that define how to log into a Drupal website and validate that
+/// the user loged in successfully.
+///
+/// This object is passed to the [`log_in`] function.
+///
+/// The defined `username` and/or `password` can be dynamically overridden by setting
+/// the `GOOSE_USER` and/or `GOOSE_PASS` environment variables when starting the load
+/// test.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::Login;
+///
+/// fn examples() {
+///     // Manually build a Login structure with custom default username and password,
+///     // and a custom log in path.
+///     let _login = Login::builder()
+///         .username("foo")
+///         .password("bar")
+///         .url("custom/user/login")
+///         .build();
+/// }
+pub struct LoginBuilder<'a> {
+    // Optionally set a default username.
+    username: &'a str,
+    // Optionally set a default password.
+    password: &'a str,
+    // Optionally set a custom log in path (otherwise defaults to the version profile).
+    url: Option<&'a str>,
+    // Optionally set the id of the log in form (otherwise defaults to the version profile).
+    form: Option<&'a str>,
+    // Optionally set values extracted from the log in form and posted with it.
+    form_values: Option<&'a [&'a str]>,
+    // Optionally perform validation of the page with the login form.
+    log_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally perform validation once the user logs in.
+    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the log in form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the log in form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the log in form.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+    // Optionally supply the code of the two-factor authentication step.
+    tfa: Option<Arc<dyn TfaCode>>,
+    // Optionally set the label of the log in button.
+    submit: Option<&'a str>,
+}
+impl<'a> LoginBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to a username of "username".
+            username: "username",
+            // Defaults to a password of "password".
+            password: "search",
+            // Defaults to the log in path of the version profile, "user/login".
+            url: None,
+            // Defaults to the id of the log in form of the version profile.
+            form: None,
+            // Defaults to the form values required by the version profile.
+            form_values: None,
+            // Default tos performing no extra validation.
+            log_in_page_validation: None,
+            // Defaults to performing no extra validation.
+            logged_in_page_validation: None,
+            // Defaults to the default Honeypot time restriction.
+            honeypot_time_restriction: HONEYPOT_TIME_RESTRICTION,
+            // Defaults to ignoring Antibot.
+            antibot: false,
+            // Defaults to not answering a CAPTCHA.
+            captcha_response: None,
+            // Defaults to not including error messages in the failure.
+            report_errors: false,
+            // Defaults to not expecting a two-factor authentication step.
+            tfa: None,
+            // Defaults to the log in button label of the version profile.
+            submit: None,
+        }
+    }
+
+    /// Used with [`Login::builder`] to configure login parameters.
+    ///
+    /// Defaults to `username`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function. The
+    /// username and password can still be overridden by the `GOOSE_USER` and
+    /// `GOOSE_PASS` environment variables.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Login with username "foo", and the default password of "password".
+    /// let _login = Login::builder()
+    ///     .username("foo")
+    ///     .build();
+    /// ```
+    pub fn username(mut self, username: impl Into<&'a str>) -> Self {
+        self.username = username.into();
+        self
+    }
+
+    /// Used with [`Login::builder`] to configure login parameters.
+    ///
+    /// Defaults to `password`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function. The
+    /// username and password can still be overridden by the `GOOSE_USER` and
+    /// `GOOSE_PASS` environment variables.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Login with username "bar", and the default username of "username".
+    /// let _login = Login::builder()
+    ///     .password("bar")
+    ///     .build();
+    /// ```
+    pub fn password(mut self, password: impl Into<&'a str>) -> Self {
+        self.password = password.into();
+        self
+    }
+
+    /// Used with [`Login::builder`] to configure login parameters.
+    ///
+    /// Defaults to `user/login`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Login at a custom path.
+    /// let _login = Login::builder()
+    ///     .password("custom/user/login")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Used with [`Login::builder`] to set the id of the log in form.
+    ///
+    /// Defaults to `user-login-form`, the id of the log in form since Drupal 8.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Log in with a custom log in form.
+    /// let _login = Login::builder()
+    ///     .form("custom-login-form")
+    ///     .build();
+    /// ```
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = Some(form.into());
+        self
+    }
+
+    /// Used with [`Login::builder`] to set form values that are extracted from the log
+    /// in form and posted with the username and password. The log in fails if any of
+    /// them is missing from the form. A `form_token` is also posted if the form has one.
+    ///
+    /// Defaults to form values required by Drupal 8 and later: `&["form_build_id", "form_id"]`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
     /// use goose_eggs::drupal::Login;
     ///
-    /// // Login at a custom path.
-    /// let _login = Login::builder()
-    ///     .password("custom/user/login")
+    /// // Log in with a form that has no `form_build_id`.
+    /// let _login = Login::builder()
+    ///     .form_values(&["form_id"])
+    ///     .build();
+    /// ```
+    pub fn form_values(mut self, form_values: &'a [&'a str]) -> Self {
+        self.form_values = Some(form_values);
+        self
+    }
+
+    /// Used with [`Login::builder`] to set the log in path, form id, form values and log
+    /// in button label from a [`DrupalVersionProfile`].
+    ///
+    /// Defaults to the profile of the Goose user when logging in, see
+    /// [`user_version_profile`].
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::{DrupalVersionProfile, Login};
+    ///
+    /// let _login = Login::builder()
+    ///     .profile(&DrupalVersionProfile::DRUPAL_9)
+    ///     .build();
+    /// ```
+    pub fn profile(mut self, profile: &DrupalVersionProfile) -> Self {
+        self.url = Some(profile.login_url);
+        self.form = Some(profile.login_form);
+        self.form_values = Some(profile.login_form_values);
+        self.submit = Some(profile.login_submit);
+        self
+    }
+
+    /// Used with [`Login::builder`] to log into a Drupal 7 website, where the log in
+    /// form has an id of `user-login` instead of `user-login-form`.
+    ///
+    /// This is a shortcut for `.profile(&DrupalVersionProfile::DRUPAL_7)`. Drupal 7
+    /// requires the same form values as later versions, and the `form_token` it adds for
+    /// users with a session is posted when present.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Log into a Drupal 7 website.
+    /// let _login = Login::builder()
+    ///     .username("foo")
+    ///     .password("bar")
+    ///     .drupal_7()
+    ///     .build();
+    /// ```
+    pub fn drupal_7(self) -> Self {
+        self.profile(&DrupalVersionProfile::DRUPAL_7)
+    }
+
+    /// Used with [`Login::builder`] to tell the [`log_in`] function to perform extra
+    /// validation of the page containing the log in form.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that `<form class="user-login-form"` exists on the log in page, and it
+    /// will load all static assets on the page with the log in form.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{drupal, Validate};
+    ///
+    /// // This validation is done by default.
+    /// let validate = Validate::builder()
+    ///     .text(r#"<form class="user-login-form"#)
+    ///     .build();
+    /// let _login = drupal::Login::builder()
+    ///     .log_in_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn log_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.log_in_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`Login::builder`] to tell the [`log_in`] function to perform extra
+    /// validation of the page returned once the user logs in.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that the username of the user that logged in is in the title, and will
+    /// load all static assets on the returned page.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Use Drupal's default search form to search for "foo bar".
+    /// let search_params = SearchParams::builder()
+    ///     .keys("foo bar")
+    ///     .build();
+    /// ```
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{drupal, Validate};
+    ///
+    /// let username = "foo";
+    ///
+    /// // This validation is done by default.
+    /// let validate = Validate::builder()
+    ///     .title(username)
+    ///     .build();
+    /// let _login = drupal::Login::builder()
+    ///     .username(username)
+    ///     .logged_in_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn logged_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.logged_in_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`Login::builder`] to set how long to wait between loading the log in
+    /// form and posting it, if the Honeypot module added a `honeypot_time` to the form.
+    /// Honeypot rejects forms posted sooner than its `honeypot_time_restriction` setting.
+    ///
+    /// Defaults to [`HONEYPOT_TIME_RESTRICTION`], Honeypot's default of 5 seconds. Set
+    /// `Duration::ZERO` to post immediately, if the time restriction is disabled.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    /// use std::time::Duration;
+    ///
+    /// // The website's Honeypot time restriction is 2 seconds.
+    /// let _login = Login::builder()
+    ///     .honeypot_time_restriction(Duration::from_secs(2))
+    ///     .build();
+    /// ```
+    pub fn honeypot_time_restriction(mut self, honeypot_time_restriction: Duration) -> Self {
+        self.honeypot_time_restriction = honeypot_time_restriction;
+        self
+    }
+
+    /// Used with [`Login::builder`] to submit the log in form the way Antibot's
+    /// JavaScript would, if the Antibot module protects it. Otherwise Antibot rejects the
+    /// log in with a `403`. Refer to [`get_antibot_form`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// let _login = Login::builder()
+    ///     .antibot(true)
+    ///     .build();
+    /// ```
+    pub fn antibot(mut self, antibot: bool) -> Self {
+        self.antibot = antibot;
+        self
+    }
+
+    /// Used with [`Login::builder`] to set the answer posted to the CAPTCHA of the log in
+    /// form, if the CAPTCHA module protects it, for websites that are configured for testing
+    /// with a known answer. Refer to [`get_captcha_fields`].
+    ///
+    /// Defaults to `None`, so a CAPTCHA is not answered.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // The website's math CAPTCHA always asks for 3 + 4.
+    /// let _login = Login::builder()
+    ///     .captcha_response("7")
+    ///     .build();
+    /// ```
+    pub fn captcha_response(mut self, captcha_response: impl Into<&'a str>) -> Self {
+        self.captcha_response = Some(captcha_response.into());
+        self
+    }
+
+    /// Used with [`Login::builder`] to include the error messages Drupal displayed in the
+    /// failure, if logging in fails. Refer to [`get_messages`].
+    ///
+    /// Defaults to `false`, as the messages can include submitted values and make each
+    /// failure distinct in the Goose metrics.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// let _login = Login::builder()
+    ///     .report_errors(true)
+    ///     .build();
+    /// ```
+    pub fn report_errors(mut self, report_errors: bool) -> Self {
+        self.report_errors = report_errors;
+        self
+    }
+
+    /// Used with [`Login::builder`] to complete the two-factor authentication step of the
+    /// TFA module after posting the username and password. The log in then lands on the
+    /// `tfa-entry-form`, which is submitted with the `code` supplied by the [`TfaCode`],
+    /// and the page it redirects to is validated as the logged in page.
+    ///
+    /// Defaults to `None`, so no two-factor authentication step is expected.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// // Each Goose user is given its current code by the environment.
+    /// let _login = Login::builder()
+    ///     .tfa(|user: &GooseUser| {
+    ///         std::env::var(format!("GOOSE_TFA_CODE_{}", user.weighted_users_index))
+    ///             .unwrap_or_default()
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn tfa(mut self, tfa: impl TfaCode + 'static) -> Self {
+        self.tfa = Some(Arc::new(tfa));
+        self
+    }
+
+    /// Used with [`Login::builder`] to set the label of the log in button, posted as `op`.
+    ///
+    /// Defaults to `Log in`.
+    ///
+    /// Once built, the resulting object is passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::Login;
+    ///
+    /// let _login = Login::builder()
+    ///     .submit("Sign in")
+    ///     .build();
+    /// ```
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = Some(submit.into());
+        self
+    }
+
+    /// Build the [`Login`] object which is then passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Use the default search form to search for `example keys`.
+    /// let search_params = SearchParams::builder()
+    ///     .keys("example keys")
+    ///     .build();
+    /// ```
+    pub fn build(self) -> Login<'a> {
+        let Self {
+            username,
+            password,
+            url,
+            form,
+            form_values,
+            log_in_page_validation,
+            logged_in_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+            tfa,
+            submit,
+        } = self;
+        Login {
+            username,
+            password,
+            url,
+            form,
+            form_values,
+            log_in_page_validation,
+            logged_in_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+            tfa,
+            submit,
+        }
+    }
+}
+
+/// Log into a Drupal website.
+///
+/// The reference to a GooseUser object is from a transaction function. The optional
+/// pointer to a [`Login`] object can be created to override the username, password,
+/// login url, or expected page title after log in.
+///
+/// If no default username is set in the [`Login`] object, the function will default to
+/// a username of `username` which can be overridden by the `GOOSE_USER` environment variable.
+/// If no default password is set in the [`Login`] object, the function will default to
+/// a password of `password` which can be overridden by the `GOOSE_PASS` environment variable.
+/// If no default url is set in the [`Login`] object, the function will default to a url
+/// of `/user/login`. If no default title is set in the [`Login`] object, the function
+/// will verify that the title includes the username after login. To log into a Drupal 7
+/// website, build the [`Login`] object with [`LoginBuilder::drupal_7`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{log_in, Login};
+///
+/// transaction!(login).set_on_start();
+///
+/// async fn login(user: &mut GooseUser) -> TransactionResult {
+///     // By default log in with `foo`:`bar`.
+///     let login = Login::builder()
+///         .username("foo")
+///         .password("bar")
+///         .build();
+///     let _html = log_in(user, &login).await?;
+///
+///     Ok(())
+/// }
+///
+/// ```
+pub async fn log_in(
+    user: &mut GooseUser,
+    login: &Login<'_>,
+) -> Result<String, Box<TransactionError>> {
+    // Use the `GOOSE_USER` environment variable if it's set, otherwise use the specified
+    // (or default) login username.
+    let username = env::var("GOOSE_USER").unwrap_or_else(|_| login.username.to_string());
+
+    // Use the `GOOSE_PASS` environment variable if it's set, otherwise use the specified
+    // (or default) login password.
+    let password = env::var("GOOSE_PASS").unwrap_or_else(|_| login.password.to_string());
+
+    log_in_as(user, login, username, password).await
+}
+
+// Log into a Drupal website with the given username and password.
+async fn log_in_as(
+    user: &mut GooseUser,
+    login: &Login<'_>,
+    username: String,
+    password: String,
+) -> Result<String, Box<TransactionError>> {
+    // Logging in starts a new session, with a new session token.
+    forget_session_token(user);
+    EXPIRED_SESSIONS.lock().unwrap().remove(&user_key(user));
+
+    // Anything not set on the builder defaults to the version profile of the user.
+    let profile = user_version_profile(user);
+    let login_url = login.url.unwrap_or(profile.login_url);
+    let login_form_id = login.form.unwrap_or(profile.login_form);
+    let login_form_values = login.form_values.unwrap_or(profile.login_form_values);
+    let login_submit = login.submit.unwrap_or(profile.login_submit);
+
+    // By default verify that the log in form exists on the page.
+    let default_validation = crate::Validate::builder().form(login_form_id).build();
+    let validate = if let Some(validation) = login.log_in_page_validation {
+        validation
+    } else {
+        &default_validation
+    };
+
+    // Load the log in page.
+    let displayed = Instant::now();
+    let goose = if let Some(validate_status) = validate.status.as_ref() {
+        // Build request manually if validating a specific status code.
+        let goose_request = GooseRequest::builder()
+            .path(login_url)
+            .expect_status_code(validate_status.status_code)
+            .build();
+        user.request(goose_request).await.unwrap()
+    } else {
+        // Otherwise follow default validation rules for status codes.
+        user.get(login_url).await.unwrap()
+    };
+
+    let mut login_request = goose.request.clone();
+    let login_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    // A web page can have multiple forms, so first get the correct form.
+    let login_form = get_form(&login_page, login_form_id);
+    if login_form.is_empty() {
+        user.set_failure(
+            &format!("{}: no {} on page", login_url, login_form_id),
+            &mut login_request,
+            None,
+            Some(&login_page),
+        )?;
+        // Return an empty string as log-in failed. Enable the debug log to
+        // determine why.
+        return Ok("".to_string());
+    }
+
+    // Now extract the form values, such as form_build_id and form_id, in order to
+    // POST to the log in form.
+    let form_values = get_form_values(&login_form, login_form_values);
+    for name in login_form_values {
+        // A missing form element is returned as "none".
+        if form_values[name].is_empty() || form_values[name] == "none" {
+            user.set_failure(
+                &format!("{}: no {} on page", login_url, name),
+                &mut login_request,
+                None,
+                Some(&login_form),
+            )?;
+            // Return an empty string as log-in failed. Enable the debug log to
+            // determine why.
+            return Ok("".to_string());
+        }
+    }
+
+    // By default verify that the username is in the title of the logged in page.
+    let default_validation = crate::Validate::builder().title(login.username).build();
+    let validate = if let Some(validation) = login.logged_in_page_validation {
+        validation
+    } else {
+        &default_validation
+    };
+
+    // Build log in form with username and password from environment.
+    let mut params = vec![("name", username), ("pass", password)];
+    for value in login_form_values {
+        params.push((*value, form_values[value].to_string()));
+    }
+    // Drupal adds a form_token to forms displayed to users with a session.
+    if login_form.contains(r#"name="form_token""#) && !login_form_values.contains(&"form_token") {
+        params.push(("form_token", get_form_value(&login_form, "form_token")));
+    }
+    // Include the Honeypot fields unchanged, and wait out its time restriction.
+    let honeypot_fields = get_honeypot_fields(&login_form);
+    for (name, value) in &honeypot_fields {
+        params.push((name.as_str(), value.to_string()));
+    }
+    wait_for_honeypot(&login_form, displayed, login.honeypot_time_restriction).await;
+    // Optionally post where Antibot's JavaScript would, with its key.
+    let antibot = match login.antibot {
+        true => get_antibot_form(&login_page, login_form_id),
+        false => None,
+    };
+    if let Some(antibot) = &antibot {
+        params.push(("antibot_key", antibot.key.clone()));
+    }
+    // Optionally answer the CAPTCHA.
+    let captcha_fields = match login.captcha_response {
+        Some(response) => get_captcha_fields(&login_form, response),
+        None => Vec::new(),
+    };
+    for (name, value) in &captcha_fields {
+        params.push((name.as_str(), value.to_string()));
+    }
+    let post_url = antibot.as_ref().map_or(login_url, |a| a.action.as_str());
+    params.push(("op", login_submit.to_string()));
+    // Post the log in form.
+    let mut logged_in_user = if let Some(validate_status) = validate.status.as_ref() {
+        // Build request manually if validating a specific status code.
+        let url = user.build_url(post_url)?;
+        // A request builder object is necessary to post a form.
+        let reqwest_request_builder = user.client.post(&url);
+        let goose_request = GooseRequest::builder()
+            .path(post_url)
+            .method(GooseMethod::Post)
+            .expect_status_code(validate_status.status_code)
+            .set_request_builder(reqwest_request_builder.form(&params))
+            .build();
+        user.request(goose_request).await.unwrap()
+    } else {
+        user.post_form(post_url, &params).await?
+    };
+
+    // A successful log in is redirected.
+    if !logged_in_user.request.redirected {
+        // There was an error, get the headers and html if any to aid in debugging.
+        let headers;
+        let html = match logged_in_user.response {
+            Ok(r) => {
+                headers = Some(r.headers().clone());
+                r.text().await.unwrap_or_else(|e| e.to_string())
+            }
+            Err(e) => {
+                headers = None;
+                e.to_string()
+            }
+        };
+        let failure = form_failure(
+            user,
+            format!(
+                "{}: login failed (check `GOOSE_USER` and `GOOSE_PASS`)",
+                logged_in_user.request.final_url
+            ),
+            &html,
+            login.report_errors,
+        );
+        user.set_failure(
+            &failure,
+            &mut logged_in_user.request,
+            headers.as_ref(),
+            Some(&html),
+        )?;
+        // Return the html that was loaded, even though log-in failed. Enable
+        // the debug_log to determine why log-in failed.
+        return Ok(html);
+    }
+
+    // Optionally complete the two-factor authentication step.
+    if let Some(tfa) = &login.tfa {
+        let mut tfa_request = logged_in_user.request.clone();
+        let tfa_url = logged_in_user.request.final_url.clone();
+        let tfa_page =
+            crate::validate_and_load_static_assets(user, logged_in_user, &crate::Validate::none())
+                .await?;
+        let mut tfa_form = match Form::parse(&tfa_page, "tfa-entry-form") {
+            Some(tfa_form) => tfa_form,
+            None => {
+                user.set_failure(
+                    &format!("{}: no tfa-entry-form on page", tfa_url),
+                    &mut tfa_request,
+                    None,
+                    Some(&tfa_page),
+                )?;
+                // Return the html that was loaded, even though log-in failed.
+                return Ok(tfa_page);
+            }
+        };
+        if tfa_form.action.is_empty() {
+            tfa_form.action = tfa_url;
+        }
+        let code = tfa.code(user);
+        logged_in_user = tfa_form
+            .submit(user, &[("code", &code), ("op", "Verify")])
+            .await?;
+
+        // A successful two-factor authentication is redirected.
+        if !logged_in_user.request.redirected {
+            let headers;
+            let html = match logged_in_user.response {
+                Ok(r) => {
+                    headers = Some(r.headers().clone());
+                    r.text().await.unwrap_or_else(|e| e.to_string())
+                }
+                Err(e) => {
+                    headers = None;
+                    e.to_string()
+                }
+            };
+            let failure = form_failure(
+                user,
+                format!(
+                    "{}: two-factor authentication failed",
+                    logged_in_user.request.final_url
+                ),
+                &html,
+                login.report_errors,
+            );
+            user.set_failure(
+                &failure,
+                &mut logged_in_user.request,
+                headers.as_ref(),
+                Some(&html),
+            )?;
+            // Return the html that was loaded, even though log-in failed.
+            return Ok(html);
+        }
+    }
+
+    // Check the title to verify that the user is actually logged in.
+    let logged_in_page =
+        crate::validate_and_load_static_assets(user, logged_in_user, validate).await?;
+
+    Ok(logged_in_page)
+}
+
+/// Log out of a Drupal website.
+///
+/// Loads the `logout_url` of the [`DrupalVersionProfile`] of the Goose user, see
+/// [`user_version_profile`]. Since Drupal 10.1 logging out requires a CSRF token, so
+/// `user/logout/confirm` displays a `user-logout-confirm` form which is submitted with its
+/// `form_token`. Earlier versions of Drupal log the user out directly at `user/logout`.
+/// Static assets are loaded on each page.
+///
+/// Once logged out, the function validates that the page is no longer displayed to a
+/// logged in user (its body does not have the `user-logged-in` class), and forgets the
+/// static assets loaded by the Goose user with [`forget_loaded_assets`](crate::forget_loaded_assets)
+/// so the anonymous session starts with an empty browser cache. The session token cached
+/// by [`get_session_token`] is also forgotten.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{log_in, log_out, Login};
+///
+/// transaction!(login_and_logout);
+///
+/// async fn login_and_logout(user: &mut GooseUser) -> TransactionResult {
+///     let login = Login::builder()
+///         .username("foo")
+///         .password("bar")
+///         .build();
+///     let _html = log_in(user, &login).await?;
+///
+///     // Browse the website as an authenticated user, then log out to continue as an
+///     // anonymous user.
+///     let _html = log_out(user).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn log_out(user: &mut GooseUser) -> Result<String, Box<TransactionError>> {
+    // Load the log out page.
+    let profile = user_version_profile(user);
+    let goose = user.get(profile.logout_url).await?;
+    let mut logout_request = goose.request.clone();
+    let logout_url = goose.request.final_url.clone();
+    let no_validation = crate::Validate::none();
+    let mut logged_out_page =
+        crate::validate_and_load_static_assets(user, goose, &no_validation).await?;
+
+    // Since Drupal 10.1 the log out must be confirmed with a form protected by a CSRF token.
+    let logout_form = profile
+        .logout_form
+        .map(|form| get_form(&logged_out_page, form))
+        .unwrap_or_default();
+    if !logout_form.is_empty() {
+        let names = ["form_build_id", "form_token", "form_id"];
+        let form_values = get_form_values(&logout_form, &names);
+        for name in names {
+            // A missing form element is returned as "none".
+            if form_values[name].is_empty() || form_values[name] == "none" {
+                user.set_failure(
+                    &format!("{}: no {} on page", logout_url, name),
+                    &mut logout_request,
+                    None,
+                    Some(&logout_form),
+                )?;
+                // Return an empty string as log-out failed. Enable the debug log to
+                // determine why.
+                return Ok("".to_string());
+            }
+        }
+
+        let params = [
+            ("form_build_id", form_values["form_build_id"].as_str()),
+            ("form_token", form_values["form_token"].as_str()),
+            ("form_id", form_values["form_id"].as_str()),
+            ("op", profile.logout_submit),
+        ];
+        let goose = user.post_form(&logout_url, &params).await?;
+        logout_request = goose.request.clone();
+        logged_out_page =
+            crate::validate_and_load_static_assets(user, goose, &no_validation).await?;
+    }
+
+    // Verify that the user is no longer logged in.
+    if logged_out_page.contains("user-logged-in") {
+        user.set_failure(
+            &format!("{}: logout failed, user still logged in", logout_url),
+            &mut logout_request,
+            None,
+            Some(&logged_out_page),
+        )?;
+        return Ok(logged_out_page);
+    }
+
+    // The anonymous session starts with an empty browser cache, and a new session token.
+    crate::forget_loaded_assets(user);
+    forget_session_token(user);
+
+    Ok(logged_out_page)
+}
+
+/// Get the CSRF token of the Goose user's session, which Drupal requires in the
+/// `X-CSRF-Token` header of REST and JSON:API writes with cookie authentication.
+///
+/// The token is fetched from `/session/token` with [`rest::get_csrf_token`] the first
+/// time, and then cached per Goose user until the user logs in or out with [`log_in`]
+/// or [`log_out`], or [`forget_session_token`] is called. To attach the token to a
+/// request, use [`add_session_token`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::get_session_token;
+///
+/// transaction!(session_token);
+///
+/// async fn session_token(user: &mut GooseUser) -> TransactionResult {
+///     // Only the first call requests `/session/token`.
+///     let token = get_session_token(user).await?;
+///     assert_eq!(token, get_session_token(user).await?);
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_session_token(user: &mut GooseUser) -> Result<String, Box<TransactionError>> {
+    let cached = SESSION_TOKENS.lock().unwrap().get(&user_key(user)).cloned();
+    if let Some(token) = cached {
+        return Ok(token);
+    }
+
+    let token = rest::get_csrf_token(user).await?;
+    if !token.is_empty() {
+        SESSION_TOKENS
+            .lock()
+            .unwrap()
+            .insert(user_key(user), token.clone());
+    }
+
+    Ok(token)
+}
+
+/// Forget the session token cached for the Goose user by [`get_session_token`], so it is
+/// fetched again. Needed if the session changes without [`log_in`] or [`log_out`].
+pub fn forget_session_token(user: &GooseUser) {
+    SESSION_TOKENS.lock().unwrap().remove(&user_key(user));
+}
+
+/// Forget the session state kept for every Goose user: the session tokens cached by
+/// [`get_session_token`], the expired sessions detected by [`SessionExpiration`], and the
+/// cookies of sessions that can be exported with [`export_session`]. The state is kept
+/// for the life of the process, separately for each load test, so invoke this before
+/// running another load test in the same process.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::reset_sessions;
+///
+/// reset_sessions();
+/// ```
+pub fn reset_sessions() {
+    SESSION_TOKENS.lock().unwrap().clear();
+    EXPIRED_SESSIONS.lock().unwrap().clear();
+    crate::reset_cookie_jars();
+}
+
+/// Attach the session token of the Goose user, from [`get_session_token`], to a request
+/// in the `X-CSRF-Token` header.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::add_session_token;
+///
+/// transaction!(delete_article);
+///
+/// async fn delete_article(user: &mut GooseUser) -> TransactionResult {
+///     let path = "jsonapi/node/article/b8e2fa12-1a1e-4c5f-9f0e-6f1e5d0ab2b1";
+///     let request_builder = user.get_request_builder(&GooseMethod::Delete, path)?;
+///     let request_builder = add_session_token(user, request_builder).await?;
+///     let goose_request = GooseRequest::builder()
+///         .method(GooseMethod::Delete)
+///         .path(path)
+///         .set_request_builder(request_builder)
+///         .expect_status_code(204)
+///         .build();
+///     let _goose = user.request(goose_request).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn add_session_token(
+    user: &mut GooseUser,
+    request_builder: reqwest::RequestBuilder,
+) -> Result<reqwest::RequestBuilder, Box<TransactionError>> {
+    let token = get_session_token(user).await?;
+    Ok(request_builder.header("x-csrf-token", token))
+}
+
+/// The cookies of a Goose user's session, exported with [`export_session`] and imported
+/// with [`import_session`].
+///
+/// Sessions serialize with serde, so a pool of logged in sessions can be generated once,
+/// saved, and distributed to Gaggle workers, rather than every Goose user logging in when
+/// the load test starts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// The url of the website the cookies were set by.
+    pub url: String,
+    /// The cookies of the session, as sent in the `Cookie` header.
+    pub cookies: String,
+}
+
+/// Replace the client of the Goose user with one whose cookies can be exported with
+/// [`export_session`], starting a new session. Invoke before [`log_in`].
+///
+/// Any custom client configured with
+/// [`GooseUser::set_client_builder`](goose::goose::GooseUser::set_client_builder) is
+/// replaced.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{enable_session_export, export_session, log_in, Login};
+///
+/// transaction!(generate_session).set_on_start();
+///
+/// async fn generate_session(user: &mut GooseUser) -> TransactionResult {
+///     enable_session_export(user)?;
+///     let _html = log_in(user, &Login::builder().build()).await?;
+///
+///     // Save the session so workers can import it.
+///     if let Some(session) = export_session(user) {
+///         println!("{}", serde_json::to_string(&session).unwrap());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub fn enable_session_export(user: &mut GooseUser) -> Result<(), Box<TransactionError>> {
+    forget_session_token(user);
+    crate::enable_cookie_jar(user)
+}
+
+/// Export the cookies of the Goose user's session, to import it into another Goose user
+/// with [`import_session`]. Returns `None` unless the Goose user's client was replaced
+/// with [`enable_session_export`], [`import_session`] or
+/// [`enable_cookie_jar`](crate::enable_cookie_jar), or if the website set no cookies.
+///
+/// Refer to the [`enable_session_export`] example.
+pub fn export_session(user: &GooseUser) -> Option<Session> {
+    let jar = crate::cookie_jar(user)?;
+    let cookies = jar.cookies(&user.base_url)?;
+    Some(Session {
+        url: user.base_url.to_string(),
+        cookies: cookies.to_str().ok()?.to_string(),
+    })
+}
+
+/// Import a session exported with [`export_session`], so the Goose user continues the
+/// session without logging in. The client of the Goose user is replaced with one that
+/// sends the cookies of the session, and whose session can again be exported.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{import_session, Session};
+///
+/// transaction!(continue_session).set_on_start();
+///
+/// async fn continue_session(user: &mut GooseUser) -> TransactionResult {
+///     // Sessions generated before the load test started, one per Goose user.
+///     let sessions: Vec<Session> =
+///         serde_json::from_str(&std::fs::read_to_string("sessions.json").unwrap()).unwrap();
+///     let session = &sessions[user.weighted_users_index % sessions.len()];
+///     import_session(user, session)?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn import_session(
+    user: &mut GooseUser,
+    session: &Session,
+) -> Result<(), Box<TransactionError>> {
+    let url = reqwest::Url::parse(&session.url).unwrap_or_else(|_| user.base_url.clone());
+    let jar = Jar::default();
+    for cookie in session.cookies.split(';') {
+        let cookie = cookie.trim();
+        if !cookie.is_empty() {
+            jar.add_cookie_str(cookie, &url);
+        }
+    }
+
+    // The session changed, along with its session token.
+    forget_session_token(user);
+    EXPIRED_SESSIONS.lock().unwrap().remove(&user_key(user));
+    crate::set_cookie_jar(user, Arc::new(jar))
+}
+
+/// A [`ValidationHook`](crate::ValidationHook) that detects when the session of a Goose
+/// user expired during a long running load test, so the user can log in again with
+/// [`log_in_if_expired`]. Also used by [`validate_logged_in`].
+///
+/// A session is considered expired if the response has a `403` status code, or if the
+/// page includes one of the markers. By default the markers are the log in form, and
+/// Drupal's access denied message.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{log_in_if_expired, Login, SessionExpiration};
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(edit_content);
+///
+/// async fn edit_content(user: &mut GooseUser) -> TransactionResult {
+///     // Log in again if the session expired during a previous transaction.
+///     let login = Login::builder().username("editor").password("secret").build();
+///     log_in_if_expired(user, &login).await?;
+///
+///     let goose = user.get("admin/content").await?;
+///     let validate = Validate::builder()
+///         .title("Content")
+///         .hook(SessionExpiration::default().marker("Your session has expired"))
+///         .build();
+///     let _html = validate_and_load_static_assets(user, goose, &validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SessionExpiration {
+    // Text on a page indicating that the user is no longer logged in.
+    markers: Vec<String>,
+}
+
+impl Default for SessionExpiration {
+    fn default() -> Self {
+        SessionExpiration {
+            markers: vec![
+                r#"data-drupal-selector="user-login-form""#.to_string(),
+                "You are not authorized to access this page.".to_string(),
+            ],
+        }
+    }
+}
+
+impl SessionExpiration {
+    /// Add text that indicates that the user is no longer logged in if it's on the page.
+    pub fn marker(mut self, marker: impl Into<String>) -> Self {
+        self.markers.push(marker.into());
+        self
+    }
+}
+
+impl crate::ValidationHook for SessionExpiration {
+    fn after(
+        &self,
+        user: &mut GooseUser,
+        request: &GooseRequestMetric,
+        _headers: &reqwest::header::HeaderMap,
+        body: &str,
+        _errors: &[crate::ValidationError],
+    ) {
+        if request.status_code == 403 || self.markers.iter().any(|m| body.contains(m.as_str())) {
+            EXPIRED_SESSIONS.lock().unwrap().insert(user_key(user));
+        }
+    }
+}
+
+/// Whether the [`SessionExpiration`] hook detected that the session of the Goose user
+/// expired since the user last logged in.
+pub fn session_expired(user: &GooseUser) -> bool {
+    EXPIRED_SESSIONS.lock().unwrap().contains(&user_key(user))
+}
+
+/// Log into a Drupal website again with [`log_in`], if the [`SessionExpiration`] hook
+/// detected that the session of the Goose user expired. Returns whether the user logged
+/// in again.
+///
+/// Refer to the [`SessionExpiration`] example.
+pub async fn log_in_if_expired(
+    user: &mut GooseUser,
+    login: &Login<'_>,
+) -> Result<bool, Box<TransactionError>> {
+    if !session_expired(user) {
+        return Ok(false);
+    }
+    info!("session expired, logging in again");
+    log_in(user, login).await?;
+
+    Ok(true)
+}
+
+/// Validate a page that requires the Goose user to be logged in, as with
+/// [`validate_and_load_static_assets`](crate::validate_and_load_static_assets). If the
+/// page indicates that the session expired, as detected by [`SessionExpiration`], the
+/// user logs in again with [`log_in`] and the request is retried.
+///
+/// Only `GET` and `HEAD` requests are retried. The request made with the expired session
+/// is still reported as failed, as it was.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{validate_logged_in, Login};
+/// use goose_eggs::Validate;
+///
+/// transaction!(view_content);
+///
+/// async fn view_content(user: &mut GooseUser) -> TransactionResult {
+///     let login = Login::builder().username("editor").password("secret").build();
+///     let goose = user.get("admin/content").await?;
+///     let validate = Validate::builder().title("Content").build();
+///     let _html = validate_logged_in(user, goose, &validate, &login).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn validate_logged_in<'a>(
+    user: &mut GooseUser,
+    goose: GooseResponse,
+    validate: &'a crate::Validate<'a>,
+    login: &Login<'_>,
+) -> Result<String, Box<TransactionError>> {
+    let request = goose.request.clone();
+    let mut validate = validate.clone();
+    validate.hooks.push(Arc::new(SessionExpiration::default()));
+
+    let html = crate::validate_and_load_static_assets(user, goose, &validate).await;
+    if !session_expired(user) || !matches!(request.raw.method, GooseMethod::Get | GooseMethod::Head)
+    {
+        return html;
+    }
+
+    // Log in again, then retry the request.
+    info!("{}: session expired, logging in again", request.raw.url);
+    log_in(user, login).await?;
+    let goose_request = GooseRequest::builder()
+        .method(request.raw.method.clone())
+        .path(request.raw.url.as_str())
+        .name(request.name.as_str())
+        .build();
+    let goose = user.request(goose_request).await?;
+    crate::validate_and_load_static_assets(user, goose, &validate).await
+}
+
+/// A username and password used to log into a Drupal website, handed out by an
+/// [`AccountPool`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+    /// The username of the account.
+    pub username: String,
+    /// The password of the account.
+    pub password: String,
+}
+
+/// A pool of accounts that hands each Goose user a distinct account to log in with, so
+/// virtual users don't all share a single account and serialize on Drupal's flood control
+/// and session handling.
+///
+/// By default accounts are assigned round-robin by Goose user, so if there are fewer
+/// accounts than Goose users some accounts are shared. With [`AccountPool::exclusive`] each
+/// account is checked out by one Goose user at a time, and released on log out.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{AccountPool, Login};
+/// use std::sync::OnceLock;
+///
+/// static ACCOUNTS: OnceLock<AccountPool> = OnceLock::new();
+///
+/// transaction!(login).set_on_start();
+/// transaction!(logout).set_on_stop();
+///
+/// async fn login(user: &mut GooseUser) -> TransactionResult {
+///     let accounts = ACCOUNTS.get_or_init(|| {
+///         AccountPool::new(vec![("editor1", "secret1"), ("editor2", "secret2")]).exclusive()
+///     });
+///     let _html = accounts.log_in(user, &Login::builder().build()).await?;
+///
+///     Ok(())
+/// }
+///
+/// async fn logout(user: &mut GooseUser) -> TransactionResult {
+///     if let Some(accounts) = ACCOUNTS.get() {
+///         let _html = accounts.log_out(user).await?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct AccountPool {
+    // The accounts in the pool.
+    accounts: Vec<Account>,
+    // Whether each account is checked out by only one Goose user at a time.
+    exclusive: bool,
+    // The index of the account checked out by each Goose user, keyed by user index.
+    checked_out: Mutex<BTreeMap<usize, usize>>,
+}
+
+impl AccountPool {
+    /// Create a pool of accounts from username and password pairs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::AccountPool;
+    ///
+    /// let accounts = AccountPool::new(vec![("editor1", "secret1"), ("editor2", "secret2")]);
+    /// assert_eq!(accounts.len(), 2);
+    /// ```
+    pub fn new(accounts: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
+        AccountPool {
+            accounts: accounts
+                .into_iter()
+                .map(|(username, password)| Account {
+                    username: username.into(),
+                    password: password.into(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a pool of accounts from a file with one `username:password` per line. Empty
+    /// lines and lines starting with `#` are ignored.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use goose_eggs::drupal::AccountPool;
+    ///
+    /// let accounts = AccountPool::from_file("accounts.txt").expect("failed to read accounts");
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?, '\n'))
+    }
+
+    /// Create a pool of accounts from an environment variable with comma separated
+    /// `username:password` pairs. The pool is empty if the variable isn't set.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::AccountPool;
+    ///
+    /// std::env::set_var("GOOSE_ACCOUNTS", "editor1:secret1,editor2:secret2");
+    /// let accounts = AccountPool::from_env("GOOSE_ACCOUNTS");
+    /// assert_eq!(accounts.len(), 2);
+    /// ```
+    pub fn from_env(name: &str) -> Self {
+        Self::parse(&env::var(name).unwrap_or_default(), ',')
+    }
+
+    // Parse `username:password` pairs, ignoring empty lines and comments.
+    fn parse(accounts: &str, separator: char) -> Self {
+        Self::new(
+            accounts
+                .split(separator)
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| line.split_once(':'))
+                .collect(),
+        )
+    }
+
+    /// Check out each account to only one Goose user at a time. Goose users can't log in
+    /// while all accounts are checked out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::AccountPool;
+    ///
+    /// let _accounts = AccountPool::from_env("GOOSE_ACCOUNTS").exclusive();
+    /// ```
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+
+    /// How many accounts are in the pool.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Whether the pool has no accounts.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Check out an account for the Goose user, returning the account already checked out
+    /// by the user if any. Returns `None` if the pool is empty, or if it's exclusive and
+    /// all accounts are checked out.
+    pub fn checkout(&self, user: &GooseUser) -> Option<Account> {
+        if self.accounts.is_empty() {
+            return None;
+        }
+        let mut checked_out = self.checked_out.lock().unwrap();
+        if let Some(index) = checked_out.get(&user.weighted_users_index) {
+            return Some(self.accounts[*index].clone());
+        }
+        let index = if self.exclusive {
+            (0..self.accounts.len()).find(|index| !checked_out.values().any(|i| i == index))?
+        } else {
+            user.weighted_users_index % self.accounts.len()
+        };
+        checked_out.insert(user.weighted_users_index, index);
+        Some(self.accounts[index].clone())
+    }
+
+    /// Release the account checked out by the Goose user, if any, so it can be checked out
+    /// by another Goose user.
+    pub fn release(&self, user: &GooseUser) {
+        self.checked_out
+            .lock()
+            .unwrap()
+            .remove(&user.weighted_users_index);
+    }
+
+    /// Log into a Drupal website with the account checked out by the Goose user, as with
+    /// [`log_in`]. The username and password of the account override those of the
+    /// [`Login`] object and the `GOOSE_USER` and `GOOSE_PASS` environment variables.
+    ///
+    /// Refer to the [`AccountPool`] example.
+    pub async fn log_in(
+        &self,
+        user: &mut GooseUser,
+        login: &Login<'_>,
+    ) -> Result<String, Box<TransactionError>> {
+        let account = match self.checkout(user) {
+            Some(account) => account,
+            None => {
+                warn!("no account available to log in");
+                // Return an empty string as log-in failed.
+                return Ok("".to_string());
+            }
+        };
+        let account_login = Login {
+            username: &account.username,
+            password: &account.password,
+            ..login.clone()
+        };
+        log_in_as(
+            user,
+            &account_login,
+            account.username.clone(),
+            account.password.clone(),
+        )
+        .await
+    }
+
+    /// Log out of a Drupal website with [`log_out`], and release the account checked out
+    /// by the Goose user.
+    ///
+    /// Refer to the [`AccountPool`] example.
+    pub async fn log_out(&self, user: &mut GooseUser) -> Result<String, Box<TransactionError>> {
+        let logged_out_page = log_out(user).await;
+        self.release(user);
+        logged_out_page
+    }
+}
+
+/// Parameters that define how to make and validate a search. For complete documentation,
+/// refer to [`SearchParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct SearchParams<'a> {
+    // The word or words to search for.
+    //
+    // Default to `""`, an empty String.
+    keys: &'a str,
+    // Optionally set a custom path to the search form.
+    //
+    // Defaults to the search path of the version profile, `search`.
+    url: Option<&'a str>,
+    // Optionally specify a custom array of form values to scrape and post.
+    //
+    // Defaults to the form values of the version profile, `["form_build_id", "form_id"]`.
+    form_values: Option<&'a [&'a str]>,
+    // Optionally validate the page with the search form.
+    //
+    // Defaults to doing no validation.
+    search_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally set a custom `op` name for the search button.
+    //
+    // Defaults to the search button label of the version profile, `Search`.
+    submit: Option<&'a str>,
+    // Optionally validate the search results page.
+    //
+    // Defaults to doing no validation.
+    results_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the search form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the search form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the search form.
+    captcha_response: Option<&'a str>,
+}
+impl<'a> SearchParams<'a> {
+    /// Convenience function to bring [`SearchParamsBuilder`] into scope.
+    pub fn builder() -> SearchParamsBuilder<'a> {
+        SearchParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`SearchParams`] object, necessary to invoke the [`search`] function.
+///
+/// Performing a search on a Drupal 8+ website is generally as simple as follow:
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal;
+///
+/// transaction!(search);
+///
+/// async fn search(user: &mut GooseUser) -> TransactionResult {
+///     // Define the search parameters.
+///     let search_params = drupal::SearchParams::builder()
+///         // Search for the keys "search terms".
+///         .keys("search terms")
+///         .build();
+///
+///     // Perform the actual search.
+///     let _search_results_html = drupal::search(user, &search_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// The builder can also be used to customize the search parameters, for example:
+///
+/// # Customized Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::{Validate, drupal};
+///
+/// transaction!(search);
+///
+/// async fn search(user: &mut GooseUser) -> TransactionResult {
+///     // Define the search parameters.
+///     // Verify that the search form page has a title that includes "Custom Search".
+///     let validate_search_page = Validate::builder()
+///         .title("Custom Search")
+///         .build();
+///     // Verify that the search results page has a title that includes the search terms.
+///     let validate_results_page = Validate::builder()
+///         .title("search terms")
+///         .build();
+///     let search_params = drupal::SearchParams::builder()
+///         // Search for the keys "search terms".
+///         .keys("search terms")
+///         // Use a search form on a custom path.
+///         .url("custom/search/path")
+///         // Perform the search page validation defined above.
+///         .search_page_validation(&validate_search_page)
+///         // Use a submit button named "Custom Search".
+///         .submit("Custom Search")
+///         // Perform the search results validation defined above.
+///         .results_page_validation(&validate_results_page)
+///         .build();
+///
+///     // Perform the actual search.
+///     let _search_results_html = drupal::search(user, &search_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct SearchParamsBuilder<'a> {
+    keys: &'a str,
+    url: Option<&'a str>,
+    form_values: Option<&'a [&'a str]>,
+    search_page_validation: Option<&'a crate::Validate<'a>>,
+    submit: Option<&'a str>,
+    results_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the search form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the search form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the search form.
+    captcha_response: Option<&'a str>,
+}
+impl<'a> SearchParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to empty search keys.
+            keys: "",
+            // Defaults to the search path of the version profile, "search".
+            url: None,
+            // Defaults to form values required by the version profile.
+            form_values: None,
+            // Defaults to no extra search page validation.
+            search_page_validation: None,
+            // Defaults to the search button label of the version profile, "Search".
+            submit: None,
+            // Defaults to no extra results page validation.
+            results_page_validation: None,
+            // Defaults to the default Honeypot time restriction.
+            honeypot_time_restriction: HONEYPOT_TIME_RESTRICTION,
+            // Defaults to ignoring Antibot.
+            antibot: false,
+            // Defaults to not answering a CAPTCHA.
+            captcha_response: None,
+        }
+    }
+
+    /// Used with [`SearchParams::builder`] to set the keys to search for.
+    ///
+    /// Defaults to `""`, an empty search string.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Use Drupal's default search form to search for "foo bar".
+    /// let search_params = SearchParams::builder()
+    ///     .keys("foo bar")
+    ///     .build();
+    /// ```
+    pub fn keys(mut self, keys: impl Into<&'a str>) -> Self {
+        self.keys = keys.into();
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set the url the search form is on.
+    ///
+    /// Defaults to `search`, Drupal's default path for the search form.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Use a search form on custom path `custom/search/path`.
+    /// let search_params = SearchParams::builder()
+    ///     .url("custom/search/path")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set the search path, form values and search
+    /// button label from a [`DrupalVersionProfile`].
+    ///
+    /// Defaults to the profile of the Goose user when searching, see
+    /// [`user_version_profile`].
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::{DrupalVersionProfile, SearchParams};
+    ///
+    /// // Perform a search on a Drupal 7 website.
+    /// let search_params = SearchParams::builder()
+    ///     .profile(&DrupalVersionProfile::DRUPAL_7)
+    ///     .keys("foo")
+    ///     .build();
+    /// ```
+    pub fn profile(mut self, profile: &DrupalVersionProfile) -> Self {
+        self.url = Some(profile.search_url);
+        self.form_values = Some(profile.search_form_values);
+        self.submit = Some(profile.search_submit);
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set form_values that are extracted from
+    /// the search form and used when POSTing the search.
+    ///
+    /// Defaults to form values required by Drupal 8 and 9: `&["form_build_id", "form_id"]`.
+    /// See the example below for how to perform a search on a Drupal 7 website.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Perform a search on a Drupal 7 website.
+    /// let search_params = SearchParams::builder()
+    ///     // Searching on Drupal 7 also requires `form_token`.
+    ///     .form_values(&["form_token", "form_build_id", "form_id"])
+    ///     .build();
+    /// ```
+    pub fn form_values(mut self, form_values: &'a [&'a str]) -> Self {
+        self.form_values = Some(form_values);
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to tell the [`search`] function to perform
+    /// extra validation of the page containing the search form.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that the search request returns a valid HTTP response code, and it will
+    /// load all static assets on the page with the search form.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Validate the title of the search page.
+    /// let validate_search_page = Validate::builder()
+    ///     .title("Custom Search")
+    ///     .build();
+    /// let search_params = SearchParams::builder()
+    ///     .search_page_validation(&validate_search_page)
+    ///     .build();
+    /// ```
+    pub fn search_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.search_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set a custom search form submit `op`.
+    ///
+    /// Defaults to Drupal's standard search button name of `Search`.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Perform a search with a custom search button.
+    /// let search_params = SearchParams::builder()
+    ///     // Searching on Drupal 7 also requires `form_token`.
+    ///     .submit("Custom Search")
+    ///     .build();
+    /// ```
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = Some(submit.into());
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to tell the [`search`] function to validate
+    /// the page title containing the search results.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that the search response returns a valid HTTP response code, and it will
+    /// load all static assets on the returned search results page.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Validate that the search terms are in the title of the search results.
+    /// let validate_results_page = Validate::builder()
+    ///     .title("foo")
+    ///     .build();
+    /// let search_params = SearchParams::builder()
+    ///     .keys("foo")
+    ///     .results_page_validation(&validate_results_page)
+    ///     .build();
+    /// ```
+    pub fn results_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.results_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set how long to wait between loading the
+    /// search form and posting it, if the Honeypot module protects the form. Refer to
+    /// [`LoginBuilder::honeypot_time_restriction`].
+    ///
+    /// Defaults to [`HONEYPOT_TIME_RESTRICTION`].
+    pub fn honeypot_time_restriction(mut self, honeypot_time_restriction: Duration) -> Self {
+        self.honeypot_time_restriction = honeypot_time_restriction;
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to submit the search form the way Antibot's
+    /// JavaScript would, if the Antibot module protects it. Refer to
+    /// [`LoginBuilder::antibot`].
+    ///
+    /// Defaults to `false`.
+    pub fn antibot(mut self, antibot: bool) -> Self {
+        self.antibot = antibot;
+        self
+    }
+
+    /// Used with [`SearchParams::builder`] to set the answer posted to the CAPTCHA of the
+    /// search form, if the CAPTCHA module protects it. Refer to
+    /// [`LoginBuilder::captcha_response`].
+    ///
+    /// Defaults to `None`.
+    pub fn captcha_response(mut self, captcha_response: impl Into<&'a str>) -> Self {
+        self.captcha_response = Some(captcha_response.into());
+        self
+    }
+
+    /// Build the [`SearchParams`] object which is then passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::SearchParams;
+    ///
+    /// // Use the default search form to search for `example keys`.
+    /// let search_params = SearchParams::builder()
+    ///     .keys("example keys")
+    ///     .build();
+    /// ```
+    pub fn build(self) -> SearchParams<'a> {
+        let Self {
+            keys,
+            url,
+            form_values,
+            search_page_validation,
+            submit,
+            results_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+        } = self;
+        SearchParams {
+            keys,
+            url,
+            form_values,
+            search_page_validation,
+            submit,
+            results_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+        }
+    }
+}
+
+/// Perform a simple Drupal-powered search.
+///
+/// In the following example, [`SearchParamsBuilder::keys`] is used to configure the
+/// keys being searched for, and [`SearchParamsBuilder::search_page_validation`] is
+/// used to validate that the page with the search form has a title containing `Search`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal;
+///
+/// transaction!(search);
+///
+/// async fn search(user: &mut GooseUser) -> TransactionResult {
+///     // Use the default search form to search for "foo", validating that the
+///     // search page has a title of Search.
+///     let validate_search_page = &goose_eggs::Validate::builder()
+///         .title("Search")
+///         .build();
+///     let search_params = drupal::SearchParams::builder()
+///         .keys("foo")
+///         .search_page_validation(validate_search_page)
+///         .build();
+///     // Perform the actual search.
+///     let _search_results = drupal::search(user, &search_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn search<'a>(
+    user: &mut GooseUser,
+    params: &'a SearchParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    // Anything not set on the builder defaults to the version profile of the user.
+    let profile = user_version_profile(user);
+    let search_url = params.url.unwrap_or(profile.search_url);
+    let search_form_values = params.form_values.unwrap_or(profile.search_form_values);
+
+    // Load the search page.
+    let displayed = Instant::now();
+    let goose = user.get(search_url).await?;
+
+    // Optionally validate the page with the search form.
+    let no_validation = crate::Validate::none();
+    let validate = if let Some(validation) = params.search_page_validation {
+        validation
+    } else {
+        &no_validation
+    };
+    let search_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    // Extract the search form from the page.
+    let search_form = get_form(&search_page, "search-form");
+
+    // Extract values from the search form.
+    let form_values = get_form_values(&search_form, search_form_values);
+
+    // Build search form.
+    let keys = params.keys.to_string();
+    let submit = params.submit.unwrap_or(profile.search_submit).to_string();
+    let mut search_params = vec![("keys", keys), ("op", submit)];
+    for value in search_form_values {
+        search_params.push((*value, form_values.get(value).unwrap().to_string()));
+    }
+    // Include the Honeypot fields unchanged, and wait out its time restriction.
+    let honeypot_fields = get_honeypot_fields(&search_form);
+    for (name, value) in &honeypot_fields {
+        search_params.push((name.as_str(), value.to_string()));
+    }
+    wait_for_honeypot(&search_form, displayed, params.honeypot_time_restriction).await;
+
+    // Optionally post where Antibot's JavaScript would, with its key.
+    let antibot = match params.antibot {
+        true => get_antibot_form(&search_page, "search-form"),
+        false => None,
+    };
+    if let Some(antibot) = &antibot {
+        search_params.push(("antibot_key", antibot.key.clone()));
+    }
+    // Optionally answer the CAPTCHA.
+    let captcha_fields = match params.captcha_response {
+        Some(response) => get_captcha_fields(&search_form, response),
+        None => Vec::new(),
+    };
+    for (name, value) in &captcha_fields {
+        search_params.push((name.as_str(), value.to_string()));
+    }
+    let post_url = antibot.as_ref().map_or(search_url, |a| a.action.as_str());
+
+    // Perform the search.
+    let goose = user.post_form(post_url, &search_params).await?;
+
+    // Optionally validate the search results page.
+    let validate = if let Some(validation) = params.results_page_validation {
+        validation
+    } else {
+        &no_validation
+    };
+    let search_results = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    // Return the search results.
+    Ok(search_results)
+}
+
+/// Parameters that define how to edit a node and validate that it was saved. For complete
+/// documentation, refer to [`EditParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct EditParams<'a> {
+    // The id of the node to edit.
+    nid: usize,
+    // Optionally set a custom path to the edit form.
+    url: Option<&'a str>,
+    // Optionally set the id of the edit form.
+    form: Option<&'a str>,
+    // Field values to change.
+    fields: &'a [(&'a str, &'a str)],
+    // Optionally set the name of the submit button.
+    submit: Option<&'a str>,
+    // Optionally validate the page with the edit form.
+    edit_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned once the node is saved.
+    saved_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the edit form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the edit form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the edit form.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> EditParams<'a> {
+    /// Convenience function to bring [`EditParamsBuilder`] into scope.
+    pub fn builder() -> EditParamsBuilder<'a> {
+        EditParamsBuilder::new()
+    }
+}
+
+/// Used to build an [`EditParams`] object, necessary to invoke the [`edit_node`] function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::EditParams;
+///
+/// // Change the title of node 3, keeping all other values as they are.
+/// let _edit_params = EditParams::builder()
+///     .nid(3)
+///     .fields(&[("title[0][value]", "New title")])
+///     .build();
+/// ```
+pub struct EditParamsBuilder<'a> {
+    // The id of the node to edit.
+    nid: usize,
+    // Optionally set a custom path to the edit form.
+    url: Option<&'a str>,
+    // Optionally set the id of the edit form.
+    form: Option<&'a str>,
+    // Field values to change.
+    fields: &'a [(&'a str, &'a str)],
+    // Optionally set the name of the submit button.
+    submit: Option<&'a str>,
+    // Optionally validate the page with the edit form.
+    edit_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned once the node is saved.
+    saved_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the edit form, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the edit form the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the edit form.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> EditParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to node 1.
+            nid: 1,
+            // Defaults to `node/{nid}/edit`.
+            url: None,
+            // Defaults to detecting the node edit form.
+            form: None,
+            // Defaults to saving the node without changes.
+            fields: &[],
+            // Defaults to the save button label of the version profile, "Save".
+            submit: None,
+            // Defaults to performing no extra validation.
+            edit_page_validation: None,
+            // Defaults to performing no extra validation.
+            saved_page_validation: None,
+            // Defaults to the default Honeypot time restriction.
+            honeypot_time_restriction: HONEYPOT_TIME_RESTRICTION,
+            // Defaults to ignoring Antibot.
+            antibot: false,
+            // Defaults to not answering a CAPTCHA.
+            captcha_response: None,
+            // Defaults to not including error messages in the failure.
+            report_errors: false,
+        }
+    }
+
+    /// Used with [`EditParams::builder`] to set the id of the node to edit.
+    ///
+    /// Defaults to `1`.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Edit node 12.
+    /// let _edit_params = EditParams::builder()
+    ///     .nid(12)
+    ///     .build();
+    /// ```
+    pub fn nid(mut self, nid: usize) -> Self {
+        self.nid = nid;
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set the path of the edit form, for example
+    /// to include a language prefix.
+    ///
+    /// Defaults to `node/{nid}/edit`.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Edit the English translation of node 12.
+    /// let _edit_params = EditParams::builder()
+    ///     .nid(12)
+    ///     .url("en/node/12/edit")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set the id of the edit form.
+    ///
+    /// Defaults to the first form on the page with an id of `node-{type}-edit-form`.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Edit an article.
+    /// let _edit_params = EditParams::builder()
+    ///     .form("node-article-edit-form")
+    ///     .build();
+    /// ```
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = Some(form.into());
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set field values to change, identified by the
+    /// name of the form element. All other fields keep the values the form was rendered
+    /// with.
+    ///
+    /// Defaults to no changes, saving the node as it is.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Change the title and unpublish the node.
+    /// let _edit_params = EditParams::builder()
+    ///     .fields(&[("title[0][value]", "New title"), ("status[value]", "0")])
+    ///     .build();
+    /// ```
+    pub fn fields(mut self, fields: &'a [(&'a str, &'a str)]) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set a custom edit form submit `op`.
+    ///
+    /// Defaults to Drupal's standard save button name of `Save`.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Save a translation.
+    /// let _edit_params = EditParams::builder()
+    ///     .submit("Save (this translation)")
+    ///     .build();
+    /// ```
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = Some(submit.into());
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set the edit form submit `op` from a
+    /// [`DrupalVersionProfile`].
+    ///
+    /// Defaults to the profile of the Goose user when editing, see
+    /// [`user_version_profile`].
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::{DrupalVersionProfile, EditParams};
+    ///
+    /// let _edit_params = EditParams::builder()
+    ///     .profile(&DrupalVersionProfile::DRUPAL_7)
+    ///     .build();
+    /// ```
+    pub fn profile(mut self, profile: &DrupalVersionProfile) -> Self {
+        self.submit = Some(profile.save_submit);
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to tell the [`edit_node`] function to perform
+    /// extra validation of the page containing the edit form.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that the edit form exists, and it will load all static assets on the page
+    /// with the edit form.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Validate the title of the edit page.
+    /// let validate_edit_page = Validate::builder()
+    ///     .title("Edit Article")
+    ///     .build();
+    /// let _edit_params = EditParams::builder()
+    ///     .edit_page_validation(&validate_edit_page)
+    ///     .build();
+    /// ```
+    pub fn edit_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.edit_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to tell the [`edit_node`] function to perform
+    /// extra validation of the page returned once the node is saved.
+    ///
+    /// Defaults to `None`, so no extra validation is performed. By default it will still
+    /// validate that saving the node redirected, and it will load all static assets on
+    /// the returned page.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](../struct.Validate.html) object.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Validate that the new title is displayed once the node is saved.
+    /// let validate_saved_page = Validate::builder()
+    ///     .title("New title")
+    ///     .build();
+    /// let _edit_params = EditParams::builder()
+    ///     .fields(&[("title[0][value]", "New title")])
+    ///     .saved_page_validation(&validate_saved_page)
+    ///     .build();
+    /// ```
+    pub fn saved_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.saved_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set how long to wait between loading the
+    /// edit form and posting it, if the Honeypot module protects the form. Refer to
+    /// [`LoginBuilder::honeypot_time_restriction`].
+    ///
+    /// Defaults to [`HONEYPOT_TIME_RESTRICTION`].
+    pub fn honeypot_time_restriction(mut self, honeypot_time_restriction: Duration) -> Self {
+        self.honeypot_time_restriction = honeypot_time_restriction;
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to submit the edit form the way Antibot's
+    /// JavaScript would, if the Antibot module protects it. Refer to
+    /// [`LoginBuilder::antibot`].
+    ///
+    /// Defaults to `false`.
+    pub fn antibot(mut self, antibot: bool) -> Self {
+        self.antibot = antibot;
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to set the answer posted to the CAPTCHA of the
+    /// edit form, if the CAPTCHA module protects it. Refer to
+    /// [`LoginBuilder::captcha_response`].
+    ///
+    /// Defaults to `None`.
+    pub fn captcha_response(mut self, captcha_response: impl Into<&'a str>) -> Self {
+        self.captcha_response = Some(captcha_response.into());
+        self
+    }
+
+    /// Used with [`EditParams::builder`] to include the error messages Drupal displayed in the
+    /// failure, if saving the node fails. Refer to [`get_messages`].
+    ///
+    /// Defaults to `false`, as the messages can include submitted values and make each
+    /// failure distinct in the Goose metrics.
+    ///
+    /// Once built, the resulting object is passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// let _params = EditParams::builder()
+    ///     .nid(3)
+    ///     .report_errors(true)
+    ///     .build();
+    /// ```
+    pub fn report_errors(mut self, report_errors: bool) -> Self {
+        self.report_errors = report_errors;
+        self
+    }
+
+    /// Build the [`EditParams`] object which is then passed to the [`edit_node`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::EditParams;
+    ///
+    /// // Save node 3 without changes.
+    /// let _edit_params = EditParams::builder()
+    ///     .nid(3)
+    ///     .build();
+    /// ```
+    pub fn build(self) -> EditParams<'a> {
+        let Self {
+            nid,
+            url,
+            form,
+            fields,
+            submit,
+            edit_page_validation,
+            saved_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+        } = self;
+        EditParams {
+            nid,
+            url,
+            form,
+            fields,
+            submit,
+            edit_page_validation,
+            saved_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+        }
+    }
+}
+
+/// Edit a Drupal node.
+///
+/// Loads the edit form of the node, preserves the values of all fields as extracted
+/// with [`get_form_fields`], applies the values set with [`EditParamsBuilder::fields`],
+/// and submits the form. If the page includes an `update_build_id` command for the
+/// form, the updated `form_build_id` is submitted. Saving the node is expected to
+/// redirect.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal;
+///
+/// transaction!(edit_article);
+///
+/// async fn edit_article(user: &mut GooseUser) -> TransactionResult {
+///     // Change the title of node 3, validating that it's displayed once saved.
+///     let validate_saved_page = goose_eggs::Validate::builder()
+///         .title("New title")
+///         .build();
+///     let edit_params = drupal::EditParams::builder()
+///         .nid(3)
+///         .fields(&[("title[0][value]", "New title")])
+///         .saved_page_validation(&validate_saved_page)
+///         .build();
+///     let _saved_page = drupal::edit_node(user, &edit_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn edit_node<'a>(
+    user: &mut GooseUser,
+    params: &'a EditParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    let default_url = format!("node/{}/edit", params.nid);
+    let url = params.url.unwrap_or(&default_url);
+
+    // Load the edit page.
+    let displayed = Instant::now();
+    let goose = user.get(url).await?;
+    let mut edit_request = goose.request.clone();
+
+    // Optionally validate the page with the edit form.
+    let no_validation = crate::Validate::none();
+    let validate = if let Some(validation) = params.edit_page_validation {
+        validation
+    } else {
+        &no_validation
+    };
+    let edit_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    // Extract the edit form from the page, detecting it if not configured.
+    let form_name = match params.form {
+        Some(form) => Some(form.to_string()),
+        None => Regex::new(r#"<form[^>]*(data-drupal-selector|id)="(node-[\w-]+-edit-form)""#)
+            .unwrap()
+            .captures(&edit_page)
+            .map(|c| c[2].to_string()),
+    };
+    let edit_form = form_name
+        .as_ref()
+        .map(|name| get_form(&edit_page, name))
+        .unwrap_or_default();
+    if edit_form.is_empty() {
+        user.set_failure(
+            &format!("{}: no node edit form on page", url),
+            &mut edit_request,
+            None,
+            Some(&edit_page),
+        )?;
+        // Return an empty string as editing failed. Enable the debug log to
+        // determine why.
+        return Ok("".to_string());
+    }
+
+    // Preserve the existing field values, then apply the changes.
+    let mut fields = form_name
+        .as_ref()
+        .and_then(|name| Form::parse(&edit_page, name))
+        .map(|form| form.fields)
+        .unwrap_or_default();
+    for (name, value) in params.fields {
+        fields.retain(|(n, _)| n != name);
+        fields.push((name.to_string(), value.to_string()));
+    }
+
+    // Submit the updated form_build_id if it was changed.
+    if edit_page.contains(r#""command":"update_build_id""#) {
+        let form_build_id = get_form_value(&edit_form, "form_build_id");
+        let updated_build_id = get_updated_build_id(&edit_page, &form_build_id);
+        if updated_build_id != "none" {
+            for (name, value) in fields.iter_mut() {
+                if name == "form_build_id" {
+                    *value = updated_build_id.clone();
+                }
+            }
+        }
+    }
+    let submit = params
+        .submit
+        .unwrap_or_else(|| user_version_profile(user).save_submit);
+    fields.push(("op".to_string(), submit.to_string()));
+
+    // Honeypot fields are preserved, but its time restriction must also pass.
+    wait_for_honeypot(&edit_form, displayed, params.honeypot_time_restriction).await;
+
+    // Optionally post where Antibot's JavaScript would, with its key.
+    let antibot = match (params.antibot, &form_name) {
+        (true, Some(name)) => get_antibot_form(&edit_page, name),
+        _ => None,
+    };
+    if let Some(antibot) = &antibot {
+        fields.retain(|(n, _)| n != "antibot_key");
+        fields.push(("antibot_key".to_string(), antibot.key.clone()));
+    }
+    let post_url = antibot.as_ref().map_or(url, |a| a.action.as_str());
+    // Optionally answer the CAPTCHA.
+    if let Some(response) = params.captcha_response {
+        for (name, value) in get_captcha_fields(&edit_form, response) {
+            fields.retain(|(n, _)| n != &name);
+            fields.push((name, value));
+        }
+    }
+
+    // Save the node.
+    let mut saved_node = user.post_form(post_url, &fields).await?;
+
+    // A successful node save is redirected.
+    if !saved_node.request.redirected {
+        // There was an error, get the headers and html if any to aid in debugging.
+        let headers;
+        let html = match saved_node.response {
+            Ok(r) => {
+                headers = Some(r.headers().clone());
+                r.text().await.unwrap_or_else(|e| e.to_string())
+            }
+            Err(e) => {
+                headers = None;
+                e.to_string()
+            }
+        };
+        let failure = form_failure(
+            user,
+            format!("{}: saving node failed", saved_node.request.final_url),
+            &html,
+            params.report_errors,
+        );
+        user.set_failure(
+            &failure,
+            &mut saved_node.request,
+            headers.as_ref(),
+            Some(&html),
+        )?;
+        // Return the html that was loaded, even though saving failed.
+        return Ok(html);
+    }
+
+    // Optionally validate the page returned once the node is saved.
+    let validate = if let Some(validation) = params.saved_page_validation {
+        validation
+    } else {
+        &no_validation
+    };
+    let saved_page = crate::validate_and_load_static_assets(user, saved_node, validate).await?;
+
+    Ok(saved_page)
+}
+
+/// Parameters that define how to upload a file to a managed file field. For complete
+/// documentation, refer to [`UploadParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct UploadParams<'a> {
+    // The path the form is posted to.
+    url: &'a str,
+    // The id of the form with the managed file field.
+    form: &'a str,
+    // The machine name of the managed file field.
+    field: &'a str,
+    // The delta of the managed file field item.
+    delta: usize,
+    // The name of the uploaded file.
+    file_name: &'a str,
+    // The content of the uploaded file.
+    content: &'a [u8],
+    // The content type of the uploaded file.
+    content_type: &'a str,
+}
+impl<'a> UploadParams<'a> {
+    /// Convenience function to bring [`UploadParamsBuilder`] into scope.
+    pub fn builder() -> UploadParamsBuilder<'a> {
+        UploadParamsBuilder::new()
+    }
+}
+
+/// Used to build an [`UploadParams`] object, necessary to invoke the [`upload_file`] function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::UploadParams;
+///
+/// // Upload an image to the image field of the article form.
+/// let _upload_params = UploadParams::builder()
+///     .url("node/add/article")
+///     .form("node-article-form")
+///     .field("field_image")
+///     .file("image.png", b"\x89PNG\r\n\x1a\n")
+///     .content_type("image/png")
+///     .build();
+/// ```
+pub struct UploadParamsBuilder<'a> {
+    // The path the form is posted to.
+    url: &'a str,
+    // The id of the form with the managed file field.
+    form: &'a str,
+    // The machine name of the managed file field.
+    field: &'a str,
+    // The delta of the managed file field item.
+    delta: usize,
+    // The name of the uploaded file.
+    file_name: &'a str,
+    // The content of the uploaded file.
+    content: &'a [u8],
+    // The content type of the uploaded file.
+    content_type: &'a str,
+}
+impl<'a> UploadParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the form to add an article.
+            url: "node/add/article",
+            // Defaults to the id of the form to add an article.
+            form: "node-article-form",
+            // Defaults to the image field of an article.
+            field: "field_image",
+            // Defaults to the first item of the field.
+            delta: 0,
+            // Defaults to an empty file named "upload.txt".
+            file_name: "upload.txt",
+            content: &[],
+            // Defaults to a generic content type.
+            content_type: "application/octet-stream",
+        }
+    }
+
+    /// Used with [`UploadParams::builder`] to set the path the form is posted to.
+    ///
+    /// Defaults to `node/add/article`.
+    ///
+    /// Once built, the resulting object is passed to the [`upload_file`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::UploadParams;
+    ///
+    /// // Upload a file while editing node 3.
+    /// let _upload_params = UploadParams::builder()
+    ///     .url("node/3/edit")
+    ///     .form("node-article-edit-form")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`UploadParams::builder`] to set the id of the form with the managed
+    /// file field.
+    ///
+    /// Defaults to `node-article-form`.
+    ///
+    /// Once built, the resulting object is passed to the [`upload_file`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::UploadParams;
+    ///
+    /// // Upload a file to a page.
+    /// let _upload_params = UploadParams::builder()
+    ///     .url("node/add/page")
+    ///     .form("node-page-form")
+    ///     .build();
+    /// ```
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = form.into();
+        self
+    }
+
+    /// Used with [`UploadParams::builder`] to set the machine name of the managed file
+    /// field, and optionally the delta of the field item.
+    ///
+    /// Defaults to `field_image`, with a delta of `0`.
+    ///
+    /// Once built, the resulting object is passed to the [`upload_file`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::UploadParams;
+    ///
+    /// // Upload a second attachment.
+    /// let _upload_params = UploadParams::builder()
+    ///     .field("field_attachments")
+    ///     .delta(1)
+    ///     .build();
+    /// ```
+    pub fn field(mut self, field: impl Into<&'a str>) -> Self {
+        self.field = field.into();
+        self
+    }
+
+    /// Used with [`UploadParams::builder`] to set the delta of the field item to upload
+    /// the file to. Refer to [`UploadParamsBuilder::field`].
+    pub fn delta(mut self, delta: usize) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Used with [`UploadParams::builder`] to set the name and content of the uploaded
+    /// file.
+    ///
+    /// Defaults to an empty file named `upload.txt`.
+    ///
+    /// Once built, the resulting object is passed to the [`upload_file`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::UploadParams;
+    ///
+    /// // Upload a small text file.
+    /// let _upload_params = UploadParams::builder()
+    ///     .field("field_attachments")
+    ///     .file("notes.txt", b"Load testing notes.")
+    ///     .content_type("text/plain")
+    ///     .build();
+    /// ```
+    pub fn file(mut self, file_name: impl Into<&'a str>, content: &'a [u8]) -> Self {
+        self.file_name = file_name.into();
+        self.content = content;
+        self
+    }
+
+    /// Used with [`UploadParams::builder`] to set the content type of the uploaded file.
+    /// Refer to [`UploadParamsBuilder::file`].
+    ///
+    /// Defaults to `application/octet-stream`.
+    pub fn content_type(mut self, content_type: impl Into<&'a str>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// Build the [`UploadParams`] object which is then passed to the [`upload_file`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::UploadParams;
+    ///
+    /// // Upload an empty file to the image field of the article form.
+    /// let _upload_params = UploadParams::builder().build();
+    /// ```
+    pub fn build(self) -> UploadParams<'a> {
+        let Self {
+            url,
+            form,
+            field,
+            delta,
+            file_name,
+            content,
+            content_type,
+        } = self;
+        UploadParams {
+            url,
+            form,
+            field,
+            delta,
+            file_name,
+            content,
+            content_type,
+        }
+    }
+}
+
+/// A file uploaded to a managed file field with [`upload_file`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// The id of the uploaded file.
+    pub fid: String,
+    /// The `form_build_id` of the form after the upload.
+    pub form_build_id: String,
+    /// The fields of the form after the upload, including the updated `form_build_id` and
+    /// the `fids` of the managed file field. Add an `op` to submit the form.
+    pub fields: Vec<(String, String)>,
+}
+
+/// Upload a file to a managed file field, the way the AJAX upload button does.
+///
+/// Extracts the form from the html of the page, which was typically returned by
+/// [`validate_and_load_static_assets`](crate::validate_and_load_static_assets), and posts
+/// all its fields with the file to the AJAX endpoint of the field. The returned commands are
+/// parsed for the updated `form_build_id`, using [`get_updated_build_id`], and for the
+/// id of the uploaded file. The returned [`UploadedFile`] contains the fields to submit the
+/// form with.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal;
+///
+/// transaction!(add_article_with_image);
+///
+/// async fn add_article_with_image(user: &mut GooseUser) -> TransactionResult {
+///     // Load the form to add an article.
+///     let goose = user.get("node/add/article").await?;
+///     let html = goose_eggs::validate_and_load_static_assets(
+///         user,
+///         goose,
+///         &goose_eggs::Validate::none(),
+///     )
+///     .await?;
+///
+///     // Upload an image.
+///     let upload_params = drupal::UploadParams::builder()
+///         .file("image.png", b"\x89PNG\r\n\x1a\n")
+///         .content_type("image/png")
+///         .build();
+///     let uploaded = drupal::upload_file(user, &html, &upload_params).await?;
+///
+///     // Save the article with the uploaded image.
+///     let mut fields = uploaded.fields;
+///     fields.push(("title[0][value]".to_string(), "Article with image".to_string()));
+///     fields.push(("field_image[0][alt]".to_string(), "An image".to_string()));
+///     fields.push(("op".to_string(), "Save".to_string()));
+///     let _goose = user.post_form("node/add/article", &fields).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn upload_file<'a>(
+    user: &mut GooseUser,
+    html: &str,
+    params: &'a UploadParams<'a>,
+) -> Result<UploadedFile, Box<TransactionError>> {
+    let form = get_form(html, params.form);
+    let mut fields = get_form_fields(&form);
+    let form_build_id = get_form_value(&form, "form_build_id");
+
+    // The AJAX endpoint of the managed file field, and its upload button.
+    let path = format!(
+        "{}{}element_parents={}/widget/{}&ajax_form=1&_wrapper_format=drupal_ajax",
+        params.url,
+        if params.url.contains('?') { "&" } else { "?" },
+        params.field,
+        params.delta
+    );
+    let button = format!("{}_{}_upload_button", params.field, params.delta);
+    let mut upload_fields = fields.clone();
+    upload_fields.push(("_triggering_element_name".to_string(), button));
+    upload_fields.push((
+        "_triggering_element_value".to_string(),
+        "Upload".to_string(),
+    ));
+    upload_fields.push(("_drupal_ajax".to_string(), "1".to_string()));
+
+    // Post the form and the file as multipart/form-data.
+    let boundary = format!("goose-eggs-{:016x}", rand::random::<u64>());
+    let body = multipart_body(
+        &boundary,
+        &upload_fields,
+        &format!("files[{}_{}]", params.field, params.delta),
+        params.file_name,
+        params.content_type,
+        params.content,
+    );
+    let url = user.build_url(&path)?;
+    let reqwest_request_builder = user
+        .client
+        .post(&url)
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(body);
+    let goose_request = GooseRequest::builder()
+        .path(&*path)
+        .method(GooseMethod::Post)
+        .set_request_builder(reqwest_request_builder)
+        .build();
+    let mut goose = user.request(goose_request).await?;
+    let commands = match goose.response {
+        Ok(response) => response.text().await.unwrap_or_default(),
+        Err(e) => e.to_string(),
+    };
+
+    // Drupal changes the form_build_id after the upload.
+    let updated_build_id = get_updated_build_id(&commands, &form_build_id);
+    let form_build_id = if updated_build_id == "none" {
+        form_build_id
+    } else {
+        updated_build_id
+    };
+
+    // The inserted widget includes the id of the uploaded file.
+    let widget_fields: Vec<(String, String)> =
+        match serde_json::from_str::<serde_json::Value>(&commands) {
+            Ok(serde_json::Value::Array(commands)) => commands
+                .iter()
+                .filter(|c| c["command"] == "insert")
+                .filter_map(|c| c["data"].as_str())
+                .flat_map(get_form_fields)
+                .collect(),
+            _ => Vec::new(),
+        };
+    let fids = format!("{}[{}][fids]", params.field, params.delta);
+    let fid = widget_fields
+        .iter()
+        .find(|(name, value)| name == &fids && !value.is_empty())
+        .map(|(_, value)| value.to_string());
+    let fid = match fid {
+        Some(fid) => fid,
+        None => {
+            user.set_failure(
+                &format!("{}: upload of {} failed", params.url, params.file_name),
+                &mut goose.request,
+                None,
+                Some(&commands),
+            )?;
+            // Return an empty UploadedFile as the upload failed. Enable the debug
+            // log to determine why.
+            return Ok(UploadedFile::default());
+        }
+    };
+
+    // Replace the fields of the widget, and the form_build_id.
+    fields.retain(|(name, _)| !widget_fields.iter().any(|(n, _)| n == name));
+    fields.extend(widget_fields);
+    for (name, value) in fields.iter_mut() {
+        if name == "form_build_id" {
+            *value = form_build_id.clone();
+        }
+    }
+
+    Ok(UploadedFile {
+        fid,
+        form_build_id,
+        fields,
+    })
+}
+
+// Encode form fields and a file as a multipart/form-data body.
+fn multipart_body(
+    boundary: &str,
+    fields: &[(String, String)],
+    file_field: &str,
+    file_name: &str,
+    content_type: &str,
+    content: &[u8],
+) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, name, value
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, file_field, file_name, content_type
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+/// Parameters that define how to upload a file with the Media Library and insert the media
+/// in a form. For complete documentation, refer to [`MediaLibraryParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct MediaLibraryParams<'a> {
+    // The path the form is posted to.
+    url: &'a str,
+    // The id of the form with the media library field.
+    form: &'a str,
+    // The machine name of the media library field.
+    field: &'a str,
+    // The name of the uploaded file.
+    file_name: &'a str,
+    // The content of the uploaded file.
+    content: &'a [u8],
+    // The content type of the uploaded file.
+    content_type: &'a str,
+    // Values of the fields of the media, such as the alternative text.
+    media_fields: &'a [(&'a str, &'a str)],
+    // The name of the button that saves the media and inserts it.
+    save: &'a str,
+}
+impl<'a> MediaLibraryParams<'a> {
+    /// Convenience function to bring [`MediaLibraryParamsBuilder`] into scope.
+    pub fn builder() -> MediaLibraryParamsBuilder<'a> {
+        MediaLibraryParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`MediaLibraryParams`] object, necessary to invoke the
+/// [`media_library_upload`] function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::MediaLibraryParams;
+///
+/// // Upload an image to the media field of the recipe form.
+/// let _media_params = MediaLibraryParams::builder()
+///     .url("node/add/recipe")
+///     .form("node-recipe-form")
+///     .field("field_media_image")
+///     .file("quiche.png", b"\x89PNG\r\n\x1a\n")
+///     .content_type("image/png")
+///     .media_fields(&[("media[0][fields][field_media_image][0][alt]", "A quiche")])
+///     .build();
+/// ```
+pub struct MediaLibraryParamsBuilder<'a> {
+    // The path the form is posted to.
+    url: &'a str,
+    // The id of the form with the media library field.
+    form: &'a str,
+    // The machine name of the media library field.
+    field: &'a str,
+    // The name of the uploaded file.
+    file_name: &'a str,
+    // The content of the uploaded file.
+    content: &'a [u8],
+    // The content type of the uploaded file.
+    content_type: &'a str,
+    // Values of the fields of the media, such as the alternative text.
+    media_fields: &'a [(&'a str, &'a str)],
+    // The name of the button that saves the media and inserts it.
+    save: &'a str,
+}
+impl<'a> MediaLibraryParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the form to add an article.
+            url: "node/add/article",
+            // Defaults to the id of the form to add an article.
+            form: "node-article-form",
+            // Defaults to the media image field of the standard profile.
+            field: "field_media_image",
+            // Defaults to an empty file named "upload.txt".
+            file_name: "upload.txt",
+            content: &[],
+            // Defaults to a generic content type.
+            content_type: "application/octet-stream",
+            // Defaults to no media field values.
+            media_fields: &[],
+            // Defaults to the Media Library button that saves and inserts the media.
+            save: "Save and insert",
+        }
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the path the form is posted to.
+    ///
+    /// Defaults to `node/add/article`. Refer to the [`MediaLibraryParamsBuilder`] example.
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the id of the form with the media
+    /// library field.
+    ///
+    /// Defaults to `node-article-form`. Refer to the [`MediaLibraryParamsBuilder`] example.
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = form.into();
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the machine name of the media
+    /// reference field that uses the Media Library widget.
+    ///
+    /// Defaults to `field_media_image`. Refer to the [`MediaLibraryParamsBuilder`] example.
+    pub fn field(mut self, field: impl Into<&'a str>) -> Self {
+        self.field = field.into();
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the name and content of the
+    /// uploaded file.
+    ///
+    /// Defaults to an empty file named `upload.txt`. Refer to the
+    /// [`MediaLibraryParamsBuilder`] example.
+    pub fn file(mut self, file_name: impl Into<&'a str>, content: &'a [u8]) -> Self {
+        self.file_name = file_name.into();
+        self.content = content;
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the content type of the uploaded
+    /// file.
+    ///
+    /// Defaults to `application/octet-stream`. Refer to the [`MediaLibraryParamsBuilder`]
+    /// example.
+    pub fn content_type(mut self, content_type: impl Into<&'a str>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the values of the fields of the
+    /// media, which the Media Library asks for once the file is uploaded. Required fields,
+    /// such as the alternative text of images, must be set.
+    ///
+    /// Defaults to no values, so the media is saved with the values the form is rendered
+    /// with. Refer to the [`MediaLibraryParamsBuilder`] example.
+    pub fn media_fields(mut self, media_fields: &'a [(&'a str, &'a str)]) -> Self {
+        self.media_fields = media_fields;
+        self
+    }
+
+    /// Used with [`MediaLibraryParams::builder`] to set the name of the button that saves
+    /// the media and inserts it in the form.
+    ///
+    /// Defaults to `Save and insert`.
+    pub fn save(mut self, save: impl Into<&'a str>) -> Self {
+        self.save = save.into();
+        self
+    }
+
+    /// Build the [`MediaLibraryParams`] object which is then passed to the
+    /// [`media_library_upload`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::MediaLibraryParams;
+    ///
+    /// // Use the defaults, uploading to the media image field of the article form.
+    /// let _media_params = MediaLibraryParams::builder().build();
+    /// ```
+    pub fn build(self) -> MediaLibraryParams<'a> {
+        let Self {
+            url,
+            form,
+            field,
+            file_name,
+            content,
+            content_type,
+            media_fields,
+            save,
+        } = self;
+        MediaLibraryParams {
+            url,
+            form,
+            field,
+            file_name,
+            content,
+            content_type,
+            media_fields,
+            save,
+        }
+    }
+}
+
+/// Media created with the Media Library and inserted in a form with
+/// [`media_library_upload`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InsertedMedia {
+    /// The id of the media.
+    pub mid: String,
+    /// The `form_build_id` of the form after inserting the media.
+    pub form_build_id: String,
+    /// The fields of the form after inserting the media, including the updated
+    /// `form_build_id` and the media selected in the field. Add an `op` to submit the form.
+    pub fields: Vec<(String, String)>,
+}
+
+/// Upload a file with the Media Library and insert the resulting media in a form, the way
+/// an editor does with the Media Library dialog.
+///
+/// Extracts the form from the html of the page, and triggers the AJAX elements that
+/// Drupal's JavaScript would, as declared in `drupalSettings`: the button that opens the
+/// dialog, the upload of the file in the dialog, the button that saves the media with its
+/// field values and inserts it, and the button that updates the field of the form. The
+/// file is uploaded to the media type the dialog opens with. The returned [`InsertedMedia`]
+/// contains the fields to submit the form with.
+///
+/// The requests are named `drupal: media library`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{media_library_upload, MediaLibraryParams};
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(create_article_with_media);
+///
+/// async fn create_article_with_media(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("node/add/article").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///
+///     let media_params = MediaLibraryParams::builder()
+///         .file("image.png", b"\x89PNG\r\n\x1a\n")
+///         .content_type("image/png")
+///         .media_fields(&[("media[0][fields][field_media_image][0][alt]", "Goose")])
+///         .build();
+///     let media = media_library_upload(user, &html, &media_params).await?;
+///
+///     // Submit the article with the media.
+///     let mut fields = media.fields;
+///     fields.push(("title[0][value]".to_string(), "Load test".to_string()));
+///     fields.push(("op".to_string(), "Save".to_string()));
+///     let _goose = user.post_form("node/add/article", &fields).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn media_library_upload<'a>(
+    user: &mut GooseUser,
+    html: &str,
+    params: &'a MediaLibraryParams<'a>,
+) -> Result<InsertedMedia, Box<TransactionError>> {
+    let form = get_form(html, params.form);
+    let mut fields = get_form_fields(&form);
+    let mut form_build_id = get_form_value(&form, "form_build_id");
+    let page_settings = get_all_drupal_settings(html);
+    // AJAX elements that aren't declared post to the form.
+    let default_url = format!(
+        "{}{}ajax_form=1",
+        params.url,
+        if params.url.contains('?') { "&" } else { "?" }
+    );
+
+    // Open the Media Library dialog.
+    let open_button = format!("{}-media-library-open-button", params.field);
+    let (url, name, value) = find_ajax_element(&page_settings, |name, _| name == open_button)
+        .unwrap_or_else(|| (default_url.clone(), open_button, "Add media".to_string()));
+    let (commands, mut request) = post_ajax(user, &url, &fields, (&name, &value), None).await?;
+    let dialog = commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "openDialog")
+        .find_map(|c| c["data"].as_str())
+        .unwrap_or_default()
+        .to_string();
+    let mut dialog_settings = ajax_settings(&commands);
+
+    // Find the form of the dialog that uploads files, and its file field.
+    let input = Regex::new(r#"<input([^>]*)>"#).unwrap();
+    let upload = get_forms(&dialog).into_iter().find_map(|(_, add_form)| {
+        input
+            .captures_iter(add_form)
+            .map(|c| format!(" {}", &c[1]))
+            .find(|i| get_attribute(i, "type").as_deref() == Some("file"))
+            .and_then(|i| get_attribute(&i, "name"))
+            .map(|file_field| (get_form_fields(add_form), file_field))
+    });
+    let upload_button =
+        find_ajax_element(&dialog_settings, |name, _| name.ends_with("_upload_button"));
+    let ((add_fields, file_field), (url, name, value)) = match (upload, upload_button) {
+        (Some(upload), Some(upload_button)) => (upload, upload_button),
+        _ => {
+            user.set_failure(
+                &format!("{}: no media library upload form", params.url),
+                &mut request,
+                None,
+                Some(&dialog),
+            )?;
+            // Return an empty InsertedMedia as the upload failed. Enable the debug log
+            // to determine why.
+            return Ok(InsertedMedia::default());
+        }
+    };
+
+    // Upload the file, which adds the fields of the media to the form.
+    let file = (
+        file_field.as_str(),
+        params.file_name,
+        params.content_type,
+        params.content,
+    );
+    let (commands, mut request) =
+        post_ajax(user, &url, &add_fields, (&name, &value), Some(file)).await?;
+    dialog_settings.extend(ajax_settings(&commands));
+    let mut media_fields = inserted_html(&commands)
+        .iter()
+        .flat_map(|html| get_forms(html))
+        .map(|(_, add_form)| get_form_fields(add_form))
+        .next()
+        .unwrap_or_default();
+    for (name, value) in params.media_fields {
+        media_fields.retain(|(n, _)| n != name);
+        media_fields.push((name.to_string(), value.to_string()));
+    }
+
+    // Save the media and insert it.
+    let (url, name, value) =
+        match find_ajax_element(&dialog_settings, |_, value| value == params.save) {
+            Some(save_button) => save_button,
+            None => {
+                user.set_failure(
+                    &format!("{}: upload of {} failed", params.url, params.file_name),
+                    &mut request,
+                    None,
+                    Some(&commands.to_string()),
+                )?;
+                // Return an empty InsertedMedia as the upload failed. Enable the debug log
+                // to determine why.
+                return Ok(InsertedMedia::default());
+            }
+        };
+    let (commands, mut request) =
+        post_ajax(user, &url, &media_fields, (&name, &value), None).await?;
+    // The dialog sets the id of the media on the field, then updates the field.
+    let mid = commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "invoke" && c["method"] == "val")
+        .filter(|c| {
+            c["selector"]
+                .as_str()
+                .is_some_and(|s| s.contains("data-media-library-widget-value"))
+        })
+        .find_map(|c| c["args"][0].as_str())
+        .map(|mid| mid.to_string());
+    let mid = match mid {
+        Some(mid) => mid,
+        None => {
+            user.set_failure(
+                &format!("{}: saving media {} failed", params.url, params.file_name),
+                &mut request,
+                None,
+                Some(&commands.to_string()),
+            )?;
+            // Return an empty InsertedMedia as saving failed. Enable the debug log to
+            // determine why.
+            return Ok(InsertedMedia::default());
+        }
+    };
+
+    // Update the field of the form with the media.
+    let selection = format!("{}[media_library_selection]", params.field);
+    fields.retain(|(name, _)| name != &selection);
+    fields.push((selection, mid.clone()));
+    let update_button = format!("{}-media-library-update-widget", params.field);
+    let (url, name, value) = find_ajax_element(&page_settings, |name, _| name == update_button)
+        .unwrap_or_else(|| (default_url, update_button, "Update widget".to_string()));
+    let (commands, _) = post_ajax(user, &url, &fields, (&name, &value), None).await?;
+
+    // Drupal changes the form_build_id, and replaces the widget with the media selected.
+    if let Some(updated_build_id) = commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "update_build_id" && c["old"] == form_build_id.as_str())
+        .find_map(|c| c["new"].as_str())
+    {
+        form_build_id = updated_build_id.to_string();
+    }
+    let widget_fields: Vec<(String, String)> = inserted_html(&commands)
+        .iter()
+        .flat_map(|html| get_form_fields(html))
+        .collect();
+    fields.retain(|(name, _)| !widget_fields.iter().any(|(n, _)| n == name));
+    fields.extend(widget_fields);
+    for (name, value) in fields.iter_mut() {
+        if name == "form_build_id" {
+            *value = form_build_id.clone();
+        }
+    }
+
+    Ok(InsertedMedia {
+        mid,
+        form_build_id,
+        fields,
+    })
+}
+
+// Find an AJAX element declared in `drupalSettings.ajax`, returning its url and the name
+// and value of its triggering element.
+fn find_ajax_element(
+    settings: &[serde_json::Value],
+    matches: impl Fn(&str, &str) -> bool,
+) -> Option<(String, String, String)> {
+    settings
+        .iter()
+        .filter_map(|settings| settings["ajax"].as_object())
+        .flat_map(|ajax| ajax.values())
+        .find_map(|element| {
+            let url = element["url"].as_str()?;
+            let name = element["submit"]["_triggering_element_name"].as_str()?;
+            let value = element["submit"]["_triggering_element_value"]
+                .as_str()
+                .unwrap_or_default();
+            if matches(name, value) {
+                Some((url.to_string(), name.to_string(), value.to_string()))
+            } else {
+                None
+            }
+        })
+}
+
+// The settings added by the settings commands of an AJAX response.
+fn ajax_settings(commands: &serde_json::Value) -> Vec<serde_json::Value> {
+    commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "settings")
+        .map(|c| c["settings"].clone())
+        .collect()
+}
+
+// The html inserted by the insert commands of an AJAX response.
+fn inserted_html(commands: &serde_json::Value) -> Vec<String> {
+    commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "insert")
+        .filter_map(|c| c["data"].as_str())
+        .map(|html| html.to_string())
+        .collect()
+}
+
+// Post form fields, and optionally a file, to an AJAX endpoint the way Drupal's JavaScript
+// does, returning the commands of the response and the request.
+async fn post_ajax(
+    user: &mut GooseUser,
+    url: &str,
+    fields: &[(String, String)],
+    triggering_element: (&str, &str),
+    file: Option<(&str, &str, &str, &[u8])>,
+) -> Result<(serde_json::Value, GooseRequestMetric), Box<TransactionError>> {
+    let path = if url.contains("_wrapper_format=") {
+        url.to_string()
+    } else {
+        format!(
+            "{}{}_wrapper_format=drupal_ajax",
+            url,
+            if url.contains('?') { "&" } else { "?" }
+        )
+    };
+    let mut fields = fields.to_vec();
+    fields.push((
+        "_triggering_element_name".to_string(),
+        triggering_element.0.to_string(),
+    ));
+    fields.push((
+        "_triggering_element_value".to_string(),
+        triggering_element.1.to_string(),
+    ));
+    fields.push(("_drupal_ajax".to_string(), "1".to_string()));
+
+    let request_url = user.build_url(&path)?;
+    let reqwest_request_builder = match file {
+        Some((file_field, file_name, content_type, content)) => {
+            let boundary = format!("goose-eggs-{:016x}", rand::random::<u64>());
+            let body = multipart_body(
+                &boundary,
+                &fields,
+                file_field,
+                file_name,
+                content_type,
+                content,
+            );
+            user.client
+                .post(&request_url)
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={}", boundary),
+                )
+                .body(body)
+        }
+        None => user.client.post(&request_url).form(&fields),
+    };
+    let goose_request = GooseRequest::builder()
+        .path(&*path)
+        .method(GooseMethod::Post)
+        .name("drupal: media library")
+        .set_request_builder(reqwest_request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let commands = match goose.response {
+        Ok(response) => response.text().await.unwrap_or_default(),
+        Err(e) => e.to_string(),
+    };
+
+    Ok((
+        serde_json::from_str(&commands).unwrap_or_default(),
+        goose.request,
+    ))
+}
+
+/// Parameters that define how to submit a webform and validate the confirmation. For
+/// complete documentation, refer to [`WebformParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct WebformParams<'a> {
+    // The machine name of the webform.
+    webform: &'a str,
+    // Optionally set a custom path to the webform.
+    url: Option<&'a str>,
+    // The values of the elements on each page of the webform.
+    pages: &'a [&'a [(&'a str, &'a str)]],
+    // The name of the button that goes to the next page.
+    next: &'a str,
+    // The name of the button that submits the webform.
+    submit: &'a str,
+    // Optionally validate the first page of the webform.
+    webform_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the confirmation page.
+    confirmation_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the webform, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the webform the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the webform.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> WebformParams<'a> {
+    /// Convenience function to bring [`WebformParamsBuilder`] into scope.
+    pub fn builder() -> WebformParamsBuilder<'a> {
+        WebformParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`WebformParams`] object, necessary to invoke the [`webform_submit`]
+/// function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::WebformParams;
+///
+/// // Submit the two page `lead` webform.
+/// let _webform_params = WebformParams::builder()
+///     .webform("lead")
+///     .pages(&[
+///         &[("name", "Jane Doe"), ("email", "jane@example.com")],
+///         &[("company", "Example"), ("interest", "load testing")],
+///     ])
+///     .build();
+/// ```
+pub struct WebformParamsBuilder<'a> {
+    // The machine name of the webform.
+    webform: &'a str,
+    // Optionally set a custom path to the webform.
+    url: Option<&'a str>,
+    // The values of the elements on each page of the webform.
+    pages: &'a [&'a [(&'a str, &'a str)]],
+    // The name of the button that goes to the next page.
+    next: &'a str,
+    // The name of the button that submits the webform.
+    submit: &'a str,
+    // Optionally validate the first page of the webform.
+    webform_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the confirmation page.
+    confirmation_page_validation: Option<&'a crate::Validate<'a>>,
+    // How long to wait before posting the webform, if protected by Honeypot.
+    honeypot_time_restriction: Duration,
+    // Whether to submit the webform the way Antibot's JavaScript would.
+    antibot: bool,
+    // Optionally answer the CAPTCHA of the webform.
+    captcha_response: Option<&'a str>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> WebformParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the contact webform installed with the Webform module.
+            webform: "contact",
+            // Defaults to `webform/{webform}`.
+            url: None,
+            // Defaults to a single page, submitted with its default values.
+            pages: &[],
+            // Defaults to the Webform module's standard next button name of "Next >".
+            next: "Next >",
+            // Defaults to the Webform module's standard submit button name of "Submit".
+            submit: "Submit",
+            // Defaults to performing no extra validation.
+            webform_page_validation: None,
+            // Defaults to performing no extra validation.
+            confirmation_page_validation: None,
+            // Defaults to the default Honeypot time restriction.
+            honeypot_time_restriction: HONEYPOT_TIME_RESTRICTION,
+            // Defaults to ignoring Antibot.
+            antibot: false,
+            // Defaults to not answering a CAPTCHA.
+            captcha_response: None,
+            // Defaults to not including error messages in the failure.
+            report_errors: false,
+        }
+    }
+
+    /// Used with [`WebformParams::builder`] to set the machine name of the webform.
+    ///
+    /// Defaults to `contact`.
+    ///
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Submit the `newsletter` webform.
+    /// let _webform_params = WebformParams::builder()
+    ///     .webform("newsletter")
+    ///     .build();
+    /// ```
+    pub fn webform(mut self, webform: impl Into<&'a str>) -> Self {
+        self.webform = webform.into();
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to set the path of the webform, for example
+    /// when it's displayed on a node.
+    ///
+    /// Defaults to `webform/{webform}`.
+    ///
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Submit the `newsletter` webform displayed on the front page.
+    /// let _webform_params = WebformParams::builder()
+    ///     .webform("newsletter")
+    ///     .url("/")
     ///     .build();
     /// ```
     pub fn url(mut self, url: impl Into<&'a str>) -> Self {
-        self.url = url.into();
+        self.url = Some(url.into());
         self
     }
 
-    /// Used with [`Login::builder`] to tell the [`log_in`] function to perform extra
-    /// validation of the page containing the log in form.
+    /// Used with [`WebformParams::builder`] to set the values of the elements on each page
+    /// of the webform, identified by the name of the form element. Elements that aren't
+    /// set keep the values they were rendered with.
+    ///
+    /// The webform is submitted from the last page, all prior pages are completed with the
+    /// next button. Defaults to a single page, submitted with its default values.
+    ///
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Complete the first page of a wizard, and submit from the second page.
+    /// let _webform_params = WebformParams::builder()
+    ///     .webform("survey")
+    ///     .pages(&[&[("rating", "5")], &[("comments", "Great!")]])
+    ///     .build();
+    /// ```
+    pub fn pages(mut self, pages: &'a [&'a [(&'a str, &'a str)]]) -> Self {
+        self.pages = pages;
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to set a custom next button `op`.
+    ///
+    /// Defaults to the Webform module's standard next button name of `Next >`.
+    ///
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Use custom wizard buttons.
+    /// let _webform_params = WebformParams::builder()
+    ///     .next("Continue")
+    ///     .submit("Send")
+    ///     .build();
+    /// ```
+    pub fn next(mut self, next: impl Into<&'a str>) -> Self {
+        self.next = next.into();
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to set a custom submit button `op`.
+    ///
+    /// Defaults to the Webform module's standard submit button name of `Submit`.
+    /// Refer to [`WebformParamsBuilder::next`].
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = submit.into();
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to tell the [`webform_submit`] function to
+    /// perform extra validation of the first page of the webform.
     ///
     /// Defaults to `None`, so no extra validation is performed. By default it will still
-    /// validate that `<form class="user-login-form"` exists on the log in page, and it
-    /// will load all static assets on the page with the log in form.
+    /// validate that the webform exists on every page of the wizard, and it will load all
+    /// static assets on each page.
     ///
     /// What validation should be performed is defined by passing a reference to a
     /// [`Validate`](../struct.Validate.html) object.
     ///
-    /// Once built, the resulting object is passed to the [`log_in`] function.
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::{drupal, Validate};
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::WebformParams;
     ///
-    /// // This validation is done by default.
-    /// let validate = Validate::builder()
-    ///     .text(r#"<form class="user-login-form"#)
+    /// // Validate the title of the webform.
+    /// let validate_webform_page = Validate::builder()
+    ///     .title("Contact")
     ///     .build();
-    /// let _login = drupal::Login::builder()
-    ///     .log_in_page_validation(&validate)
+    /// let _webform_params = WebformParams::builder()
+    ///     .webform_page_validation(&validate_webform_page)
     ///     .build();
     /// ```
-    pub fn log_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
-        self.log_in_page_validation = Some(validation);
+    pub fn webform_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.webform_page_validation = Some(validation);
         self
     }
 
-    /// Used with [`Login::builder`] to tell the [`log_in`] function to perform extra
-    /// validation of the page returned once the user logs in.
+    /// Used with [`WebformParams::builder`] to tell the [`webform_submit`] function to
+    /// perform extra validation of the confirmation page.
     ///
     /// Defaults to `None`, so no extra validation is performed. By default it will still
-    /// validate that the username of the user that logged in is in the title, and will
-    /// load all static assets on the returned page.
+    /// validate that the webform is no longer displayed once submitted, and it will load
+    /// all static assets on the confirmation page.
     ///
     /// What validation should be performed is defined by passing a reference to a
     /// [`Validate`](../struct.Validate.html) object.
     ///
-    /// Once built, the resulting object is passed to the [`log_in`] function.
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Validate the confirmation message.
+    /// let validate_confirmation_page = Validate::builder()
+    ///     .text("New submission added to Contact.")
+    ///     .build();
+    /// let _webform_params = WebformParams::builder()
+    ///     .confirmation_page_validation(&validate_confirmation_page)
+    ///     .build();
+    /// ```
+    pub fn confirmation_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.confirmation_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to set how long to wait between loading each
+    /// page of the webform and posting it, if the Honeypot module protects the webform.
+    /// Refer to [`LoginBuilder::honeypot_time_restriction`].
+    ///
+    /// Defaults to [`HONEYPOT_TIME_RESTRICTION`].
+    pub fn honeypot_time_restriction(mut self, honeypot_time_restriction: Duration) -> Self {
+        self.honeypot_time_restriction = honeypot_time_restriction;
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to submit each page of the webform the way
+    /// Antibot's JavaScript would, if the Antibot module protects it. Refer to
+    /// [`LoginBuilder::antibot`].
+    ///
+    /// Defaults to `false`.
+    pub fn antibot(mut self, antibot: bool) -> Self {
+        self.antibot = antibot;
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to set the answer posted to the CAPTCHA of the
+    /// webform, if the CAPTCHA module protects it. Refer to
+    /// [`LoginBuilder::captcha_response`].
+    ///
+    /// Defaults to `None`.
+    pub fn captcha_response(mut self, captcha_response: impl Into<&'a str>) -> Self {
+        self.captcha_response = Some(captcha_response.into());
+        self
+    }
+
+    /// Used with [`WebformParams::builder`] to include the error messages Drupal displayed in the
+    /// failure, if the submission fails. Refer to [`get_messages`].
+    ///
+    /// Defaults to `false`, as the messages can include submitted values and make each
+    /// failure distinct in the Goose metrics.
+    ///
+    /// Once built, the resulting object is passed to the [`webform_submit`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// let _params = WebformParams::builder()
+    ///     .webform("contact")
+    ///     .report_errors(true)
+    ///     .build();
+    /// ```
+    pub fn report_errors(mut self, report_errors: bool) -> Self {
+        self.report_errors = report_errors;
+        self
+    }
+
+    /// Build the [`WebformParams`] object which is then passed to the [`webform_submit`]
+    /// function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::WebformParams;
+    ///
+    /// // Submit the contact webform.
+    /// let _webform_params = WebformParams::builder()
+    ///     .pages(&[&[("name", "Jane Doe"), ("email", "jane@example.com")]])
+    ///     .build();
+    /// ```
+    pub fn build(self) -> WebformParams<'a> {
+        let Self {
+            webform,
+            url,
+            pages,
+            next,
+            submit,
+            webform_page_validation,
+            confirmation_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+        } = self;
+        WebformParams {
+            webform,
+            url,
+            pages,
+            next,
+            submit,
+            webform_page_validation,
+            confirmation_page_validation,
+            honeypot_time_restriction,
+            antibot,
+            captcha_response,
+            report_errors,
+        }
+    }
+}
+
+/// Submit a webform, walking through each page of a multi-page wizard.
+///
+/// Loads the webform and, for each page configured with [`WebformParamsBuilder::pages`],
+/// extracts the fields of the form with [`get_form_fields`], applies the configured
+/// values, and posts the form. All pages but the last are posted with the next button,
+/// carrying forward the `form_build_id` and the wizard state of the page returned. The
+/// last page is posted with the submit button, and the webform is expected to no longer
+/// be displayed on the confirmation page.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal;
+///
+/// transaction!(request_demo);
+///
+/// async fn request_demo(user: &mut GooseUser) -> TransactionResult {
+///     // Submit the two page `demo` webform, validating the confirmation message.
+///     let validate_confirmation_page = goose_eggs::Validate::builder()
+///         .text("Thank you")
+///         .build();
+///     let webform_params = drupal::WebformParams::builder()
+///         .webform("demo")
+///         .pages(&[
+///             &[("name", "Jane Doe"), ("email", "jane@example.com")],
+///             &[("company", "Example")],
+///         ])
+///         .confirmation_page_validation(&validate_confirmation_page)
+///         .build();
+///     let _confirmation = drupal::webform_submit(user, &webform_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn webform_submit<'a>(
+    user: &mut GooseUser,
+    params: &'a WebformParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    let default_url = format!("webform/{}", params.webform);
+    let url = params.url.unwrap_or(&default_url);
+    // The Webform module replaces underscores in the form id with dashes.
+    let form_name = format!(
+        "webform-submission-{}-add-form",
+        params.webform.replace('_', "-")
+    );
+
+    // Load the first page of the webform.
+    let mut displayed = Instant::now();
+    let goose = user.get(url).await?;
+    let no_validation = crate::Validate::none();
+    let validate = if let Some(validation) = params.webform_page_validation {
+        validation
+    } else {
+        &no_validation
+    };
+    let mut webform_request = goose.request.clone();
+    let mut webform_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    // Submit the default values if no pages are configured.
+    let default_page: &[&[(&str, &str)]] = &[&[]];
+    let pages = if params.pages.is_empty() {
+        default_page
+    } else {
+        params.pages
+    };
+
+    for (index, page) in pages.iter().enumerate() {
+        let webform = get_form(&webform_page, &form_name);
+        if webform.is_empty() {
+            user.set_failure(
+                &format!("{}: no {} on page {}", url, form_name, index + 1),
+                &mut webform_request,
+                None,
+                Some(&webform_page),
+            )?;
+            // Return an empty string as the submission failed. Enable the debug log
+            // to determine why.
+            return Ok("".to_string());
+        }
+
+        // Preserve the wizard state and existing values, then apply this page's values.
+        let mut fields = get_form_fields(&webform);
+        for (name, value) in page.iter() {
+            fields.retain(|(n, _)| n != name);
+            fields.push((name.to_string(), value.to_string()));
+        }
+        let last_page = index + 1 == pages.len();
+        let op = if last_page {
+            params.submit
+        } else {
+            params.next
+        };
+        fields.push(("op".to_string(), op.to_string()));
+
+        // Honeypot fields are preserved, but its time restriction must also pass.
+        wait_for_honeypot(&webform, displayed, params.honeypot_time_restriction).await;
+
+        // Optionally post where Antibot's JavaScript would, with its key.
+        let antibot = match params.antibot {
+            true => get_antibot_form(&webform_page, &form_name),
+            false => None,
+        };
+        if let Some(antibot) = &antibot {
+            fields.retain(|(n, _)| n != "antibot_key");
+            fields.push(("antibot_key".to_string(), antibot.key.clone()));
+        }
+        let post_url = antibot.as_ref().map_or(url, |a| a.action.as_str());
+        // Optionally answer the CAPTCHA.
+        if let Some(response) = params.captcha_response {
+            for (name, value) in get_captcha_fields(&webform, response) {
+                fields.retain(|(n, _)| n != &name);
+                fields.push((name, value));
+            }
+        }
+
+        displayed = Instant::now();
+        let goose = user.post_form(post_url, &fields).await?;
+        webform_request = goose.request.clone();
+        let validate = if last_page {
+            params
+                .confirmation_page_validation
+                .unwrap_or(&no_validation)
+        } else {
+            &no_validation
+        };
+        webform_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    }
+
+    // The webform is replaced by the confirmation once submitted.
+    if webform_page.contains(&form_name) {
+        let failure = form_failure(
+            user,
+            format!("{}: webform submission failed", url),
+            &webform_page,
+            params.report_errors,
+        );
+        user.set_failure(&failure, &mut webform_request, None, Some(&webform_page))?;
+    }
+
+    Ok(webform_page)
+}
+
+/// Parameters that define how to submit a contact form and validate the confirmation. For
+/// complete documentation, refer to [`ContactParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct ContactParams<'a> {
+    // The path of the contact form.
+    url: &'a str,
+    // The id of the contact form.
+    form: &'a str,
+    // The names of the sender name, sender email, subject and message fields.
+    field_names: [&'a str; 4],
+    // The name of the sender, posted if the form asks for it.
+    name: &'a str,
+    // The email address of the sender, posted if the form asks for it.
+    mail: &'a str,
+    // The subject of the message.
+    subject: &'a str,
+    // The message.
+    message: &'a str,
+    // The name of the submit button.
+    submit: &'a str,
+    // Texts displayed when flood control rejects the message.
+    flood_control: &'a [&'a str],
+    // Optionally validate the page with the contact form.
+    contact_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the confirmation page.
+    confirmation_page_validation: Option<&'a crate::Validate<'a>>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> ContactParams<'a> {
+    /// Convenience function to bring [`ContactParamsBuilder`] into scope.
+    pub fn builder() -> ContactParamsBuilder<'a> {
+        ContactParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`ContactParams`] object, necessary to invoke the [`contact`] function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::ContactParams;
+///
+/// let _contact_params = ContactParams::builder()
+///     .url("es/contact")
+///     .subject("Hola")
+///     .message("Comentarios sobre el sitio web.")
+///     .flood_control(&["No le está permitido enviar más"])
+///     .build();
+/// ```
+pub struct ContactParamsBuilder<'a> {
+    // The path of the contact form.
+    url: &'a str,
+    // The id of the contact form.
+    form: &'a str,
+    // The names of the sender name, sender email, subject and message fields.
+    field_names: [&'a str; 4],
+    // The name of the sender, posted if the form asks for it.
+    name: &'a str,
+    // The email address of the sender, posted if the form asks for it.
+    mail: &'a str,
+    // The subject of the message.
+    subject: &'a str,
+    // The message.
+    message: &'a str,
+    // The name of the submit button.
+    submit: &'a str,
+    // Texts displayed when flood control rejects the message.
+    flood_control: &'a [&'a str],
+    // Optionally validate the page with the contact form.
+    contact_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the confirmation page.
+    confirmation_page_validation: Option<&'a crate::Validate<'a>>,
+    // Whether to include error messages in the failure.
+    report_errors: bool,
+}
+impl<'a> ContactParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the site-wide contact form.
+            url: "contact",
+            // Defaults to the id of the default feedback form.
+            form: "contact-message-feedback-form",
+            // Defaults to the fields of core contact forms.
+            field_names: ["name", "mail", "subject[0][value]", "message[0][value]"],
+            // Defaults to a placeholder sender.
+            name: "Goose",
+            // Defaults to a placeholder email address.
+            mail: "goose@example.com",
+            // Defaults to a placeholder subject.
+            subject: "Load test",
+            // Defaults to a placeholder message.
+            message: "Sent by a Goose load test.",
+            // Defaults to the core contact form button.
+            submit: "Send message",
+            // Defaults to the English flood control message.
+            flood_control: &["You cannot send more than"],
+            // Defaults to not validating the page with the contact form.
+            contact_page_validation: None,
+            // Defaults to not validating the confirmation page.
+            confirmation_page_validation: None,
+            // Defaults to not including error messages in the failure.
+            report_errors: false,
+        }
+    }
+
+    /// Used with [`ContactParams::builder`] to set the path of the contact form.
+    ///
+    /// Defaults to `contact`, the site-wide contact form. Refer to the
+    /// [`ContactParamsBuilder`] example.
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the id of the contact form.
+    ///
+    /// Defaults to `contact-message-feedback-form`, the "Website feedback" form.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::ContactParams;
+    ///
+    /// let _contact_params = ContactParams::builder()
+    ///     .url("contact/sales")
+    ///     .form("contact-message-sales-form")
+    ///     .build();
+    /// ```
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = form.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the names of the sender name, sender
+    /// email, subject and message fields, for contact forms with custom fields.
+    ///
+    /// Defaults to `name`, `mail`, `subject[0][value]` and `message[0][value]`.
+    pub fn field_names(
+        mut self,
+        name: &'a str,
+        mail: &'a str,
+        subject: &'a str,
+        message: &'a str,
+    ) -> Self {
+        self.field_names = [name, mail, subject, message];
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the name of the sender. Only posted if
+    /// the form asks for it, as it doesn't for authenticated users.
+    ///
+    /// Defaults to `Goose`.
+    pub fn name(mut self, name: impl Into<&'a str>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the email address of the sender. Only
+    /// posted if the form asks for it, as it doesn't for authenticated users.
+    ///
+    /// Defaults to `goose@example.com`.
+    pub fn mail(mut self, mail: impl Into<&'a str>) -> Self {
+        self.mail = mail.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the subject of the message.
+    ///
+    /// Defaults to `Load test`.
+    pub fn subject(mut self, subject: impl Into<&'a str>) -> Self {
+        self.subject = subject.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the message.
+    ///
+    /// Defaults to `Sent by a Goose load test.`.
+    pub fn message(mut self, message: impl Into<&'a str>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the name of the submit button, which
+    /// is translated on multilingual websites.
+    ///
+    /// Defaults to `Send message`.
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = submit.into();
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set the texts displayed when flood control
+    /// rejects the message, as Drupal limits how many messages each visitor can send per
+    /// hour. A rejected message isn't a failure, as it's expected when load testing from
+    /// few IP addresses.
+    ///
+    /// Defaults to `&["You cannot send more than"]`. Refer to the [`ContactParamsBuilder`]
+    /// example.
+    pub fn flood_control(mut self, flood_control: &'a [&'a str]) -> Self {
+        self.flood_control = flood_control;
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set a custom validation of the page with the
+    /// contact form.
+    ///
+    /// Defaults to validating that the page has the contact form.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::ContactParams;
+    ///
+    /// let validate = Validate::builder().title("Website feedback").build();
+    /// let _contact_params = ContactParams::builder()
+    ///     .contact_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn contact_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.contact_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to set a custom validation of the confirmation
+    /// page. It isn't validated if flood control rejected the message.
+    ///
+    /// Defaults to `None`, so the confirmation page isn't validated.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::ContactParams;
     ///
-    /// // Use Drupal's default search form to search for "foo bar".
-    /// let search_params = SearchParams::builder()
-    ///     .keys("foo bar")
+    /// let validate = Validate::builder().text("Your message has been sent.").build();
+    /// let _contact_params = ContactParams::builder()
+    ///     .confirmation_page_validation(&validate)
     ///     .build();
     /// ```
+    pub fn confirmation_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.confirmation_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`ContactParams::builder`] to include the error messages Drupal displayed in the
+    /// failure, if sending the message fails. Refer to [`get_messages`].
+    ///
+    /// Defaults to `false`, as the messages can include submitted values and make each
+    /// failure distinct in the Goose metrics.
+    ///
+    /// Once built, the resulting object is passed to the [`contact`] function.
+    ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::{drupal, Validate};
-    ///
-    /// let username = "foo";
+    /// use goose_eggs::drupal::ContactParams;
     ///
-    /// // This validation is done by default.
-    /// let validate = Validate::builder()
-    ///     .title(username)
-    ///     .build();
-    /// let _login = drupal::Login::builder()
-    ///     .username(username)
-    ///     .logged_in_page_validation(&validate)
+    /// let _params = ContactParams::builder()
+    ///     .report_errors(true)
     ///     .build();
     /// ```
-    pub fn logged_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
-        self.logged_in_page_validation = Some(validation);
+    pub fn report_errors(mut self, report_errors: bool) -> Self {
+        self.report_errors = report_errors;
         self
     }
 
-    /// Build the [`Login`] object which is then passed to the [`log_in`] function.
+    /// Build the [`ContactParams`] object which is then passed to the [`contact`] function.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// use goose_eggs::drupal::ContactParams;
     ///
-    /// // Use the default search form to search for `example keys`.
-    /// let search_params = SearchParams::builder()
-    ///     .keys("example keys")
-    ///     .build();
+    /// // Send the default feedback message.
+    /// let _contact_params = ContactParams::builder().build();
     /// ```
-    pub fn build(self) -> Login<'a> {
+    pub fn build(self) -> ContactParams<'a> {
         let Self {
-            username,
-            password,
             url,
-            log_in_page_validation,
-            logged_in_page_validation,
+            form,
+            field_names,
+            name,
+            mail,
+            subject,
+            message,
+            submit,
+            flood_control,
+            contact_page_validation,
+            confirmation_page_validation,
+            report_errors,
         } = self;
-        Login {
-            username,
-            password,
+        ContactParams {
             url,
-            log_in_page_validation,
-            logged_in_page_validation,
+            form,
+            field_names,
+            name,
+            mail,
+            subject,
+            message,
+            submit,
+            flood_control,
+            contact_page_validation,
+            confirmation_page_validation,
+            report_errors,
         }
     }
 }
 
-/// Log into a Drupal website.
-///
-/// The reference to a GooseUser object is from a transaction function. The optional
-/// pointer to a [`Login`] object can be created to override the username, password,
-/// login url, or expected page title after log in.
+/// Load a contact form and send a message, returning the html of the confirmation page.
 ///
-/// If no default username is set in the [`Login`] object, the function will default to
-/// a username of `username` which can be overridden by the `GOOSE_USER` environment variable.
-/// If no default password is set in the [`Login`] object, the function will default to
-/// a password of `password` which can be overridden by the `GOOSE_PASS` environment variable.
-/// If no default url is set in the [`Login`] object, the function will default to a url
-/// of `/user/login`. If no default title is set in the [`Login`] object, the function
-/// will verify that the title includes the username after login.
+/// The existing values of the form are preserved. Drupal redirects once the message is sent,
+/// otherwise sending fails, unless flood control rejected the message as Drupal limits how
+/// many messages each visitor can send. Refer to [`ContactParamsBuilder::flood_control`].
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::drupal::{log_in, Login};
+/// use goose_eggs::drupal::{contact, ContactParams};
 ///
-/// transaction!(login).set_on_start();
+/// transaction!(send_feedback);
 ///
-/// async fn login(user: &mut GooseUser) -> TransactionResult {
-///     // By default log in with `foo`:`bar`.
-///     let login = Login::builder()
-///         .username("foo")
-///         .password("bar")
+/// async fn send_feedback(user: &mut GooseUser) -> TransactionResult {
+///     let contact_params = ContactParams::builder()
+///         .url("en/contact")
+///         .subject("Great recipes")
+///         .message("The quiche was delicious.")
 ///         .build();
-///     let _html = log_in(user, &login).await?;
+///     let _confirmation = contact(user, &contact_params).await?;
 ///
 ///     Ok(())
 /// }
-///
 /// ```
-pub async fn log_in(
+pub async fn contact<'a>(
     user: &mut GooseUser,
-    login: &Login<'_>,
+    params: &'a ContactParams<'a>,
 ) -> Result<String, Box<TransactionError>> {
-    // Use the `GOOSE_USER` environment variable if it's set, otherwise use the specified
-    // (or default) login username.
-    let username = env::var("GOOSE_USER").unwrap_or_else(|_| login.username.to_string());
-
-    // Use the `GOOSE_PASS` environment variable if it's set, otherwise use the specified
-    // (or default) login password.
-    let password = env::var("GOOSE_PASS").unwrap_or_else(|_| login.password.to_string());
-
-    // By default verify that the standard user-login-form exists on the page.
-    let default_validation = crate::Validate::builder()
-        .text(r#"<form class="user-login-form"#)
-        .build();
-    let validate = if let Some(validation) = login.log_in_page_validation {
-        validation
-    } else {
-        &default_validation
-    };
-
-    // Load the log in page.
-    let goose = if let Some(validate_status) = validate.status.as_ref() {
-        // Build request manually if validating a specific status code.
-        let goose_request = GooseRequest::builder()
-            .path(login.url)
-            .expect_status_code(validate_status.status_code)
-            .build();
-        user.request(goose_request).await.unwrap()
-    } else {
-        // Otherwise follow default validation rules for status codes.
-        user.get(login.url).await.unwrap()
-    };
-
-    let mut login_request = goose.request.clone();
-    let login_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
-
-    // A web page can have multiple forms, so first get the correct form.
-    let login_form = get_form(&login_page, "user-login-form");
-    if login_form.is_empty() {
-        user.set_failure(
-            &format!("{}: no user-login-form on page", login.url),
-            &mut login_request,
-            None,
-            Some(&login_page),
-        )?;
-        // Return an empty string as log-in failed. Enable the debug log to
-        // determine why.
-        return Ok("".to_string());
-    }
+    // Load the contact form.
+    let goose = user.get(params.url).await?;
+    let default_validation = crate::Validate::builder().form(params.form).build();
+    let validate = params
+        .contact_page_validation
+        .unwrap_or(&default_validation);
+    let contact_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
 
-    // Now extract the form_build_id in order to POST to the log in form.
-    let form_build_id = get_form_value(&login_form, "form_build_id");
-    if form_build_id.is_empty() {
-        user.set_failure(
-            &format!("{}: no form_build_id on page", login.url),
-            &mut login_request,
-            None,
-            Some(&login_form),
-        )?;
-        // Return an empty string as log-in failed. Enable the debug log to
-        // determine why.
-        return Ok("".to_string());
+    // Preserve the existing field values, then write the message.
+    let form = get_form(&contact_page, params.form);
+    let mut fields = get_form_fields(&form);
+    let [name_field, mail_field, subject_field, message_field] = params.field_names;
+    let mut values = vec![
+        (subject_field, params.subject),
+        (message_field, params.message),
+    ];
+    // Authenticated users send messages from their own account.
+    for (field, value) in [(name_field, params.name), (mail_field, params.mail)] {
+        if form.contains(&format!(r#"name="{}""#, field)) {
+            values.push((field, value));
+        }
     }
-
-    // Also extract the form_id (defaults to `user_login_form`).
-    let form_id = get_form_value(&login_form, "form_id");
-    if form_id.is_empty() {
-        user.set_failure(
-            &format!("{}: no form_id on page", login.url),
-            &mut login_request,
-            None,
-            Some(&login_form),
-        )?;
-        // Return an empty string as log-in failed. Enable the debug log to
-        // determine why.
-        return Ok("".to_string());
+    for (name, value) in values {
+        fields.retain(|(n, _)| n != name);
+        fields.push((name.to_string(), value.to_string()));
     }
+    fields.push(("op".to_string(), params.submit.to_string()));
 
-    // By default verify that the username is in the title of the logged in page.
-    let default_validation = crate::Validate::builder().title(login.username).build();
-    let validate = if let Some(validation) = login.logged_in_page_validation {
-        validation
-    } else {
-        &default_validation
-    };
-
-    // Build log in form with username and password from environment.
-    let params = [
-        ("name", &username),
-        ("pass", &password),
-        ("form_build_id", &form_build_id),
-        ("form_id", &form_id),
-        ("op", &"Log+in".to_string()),
-    ];
-    // Post the log in form.
-    let mut logged_in_user = if let Some(validate_status) = validate.status.as_ref() {
-        // Build request manually if validating a specific status code.
-        let url = user.build_url(login.url)?;
-        // A request builder object is necessary to post a form.
-        let reqwest_request_builder = user.client.post(&url);
-        let goose_request = GooseRequest::builder()
-            .path(login.url)
-            .method(GooseMethod::Post)
-            .expect_status_code(validate_status.status_code)
-            .set_request_builder(reqwest_request_builder.form(&params))
-            .build();
-        user.request(goose_request).await.unwrap()
-    } else {
-        user.post_form(login.url, &params).await?
-    };
+    let mut sent = user.post_form(params.url, &fields).await?;
 
-    // A successful log in is redirected.
-    if !logged_in_user.request.redirected {
-        // There was an error, get the headers and html if any to aid in debugging.
+    // A message that's sent is redirected.
+    if !sent.request.redirected {
+        // Get the headers and html to detect flood control, or to aid in debugging.
         let headers;
-        let html = match logged_in_user.response {
+        let html = match sent.response {
             Ok(r) => {
                 headers = Some(r.headers().clone());
                 r.text().await.unwrap_or_else(|e| e.to_string())
@@ -702,399 +5555,1355 @@ pub async fn log_in(
                 e.to_string()
             }
         };
+        if params.flood_control.iter().any(|text| html.contains(text)) {
+            // Flood control rejected the message, but a real user would still load the
+            // static assets of the page.
+            info!("{}: message rejected by flood control", params.url);
+            crate::load_static_elements(user, &html).await;
+        } else {
+            let failure = form_failure(
+                user,
+                format!("{}: sending message failed", sent.request.final_url),
+                &html,
+                params.report_errors,
+            );
+            user.set_failure(&failure, &mut sent.request, headers.as_ref(), Some(&html))?;
+        }
+        // Return the html that was loaded, even though the message wasn't sent.
+        return Ok(html);
+    }
+
+    // Optionally validate the confirmation page.
+    let no_validation = crate::Validate::none();
+    let validate = params
+        .confirmation_page_validation
+        .unwrap_or(&no_validation);
+    crate::validate_and_load_static_assets(user, sent, validate).await
+}
+
+/// Send a query to an endpoint of the Drupal GraphQL module, returning the `data` of the
+/// response.
+///
+/// The query and its variables are posted as json, and the request is named
+/// `graphql: {endpoint}`. The response must have a `200` status code and be valid JSON,
+/// and the request fails if the response includes `errors`. If the request doesn't load,
+/// or the response has no `data`, [`Value::Null`](serde_json::Value::Null) is returned.
+///
+/// To send queries as automatic persisted queries, use [`graphql_persisted_query`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::graphql_query;
+/// use serde_json::json;
+///
+/// transaction!(load_article);
+///
+/// async fn load_article(user: &mut GooseUser) -> TransactionResult {
+///     let query = "query article($id: ID!) { article(id: $id) { title } }";
+///     let data = graphql_query(user, "graphql", query, &json!({"id": "3"})).await?;
+///
+///     if let Some(title) = data.pointer("/article/title") {
+///         println!("{}", title);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn graphql_query(
+    user: &mut GooseUser,
+    endpoint: &str,
+    query: &str,
+    variables: &serde_json::Value,
+) -> Result<serde_json::Value, Box<TransactionError>> {
+    let body = serde_json::json!({ "query": query, "variables": variables });
+    let (json, mut request) = send_graphql(user, endpoint, &body).await?;
+    graphql_data(user, endpoint, json, &mut request)
+}
+
+/// Send a query to an endpoint of the Drupal GraphQL module as an automatic persisted
+/// query, returning the `data` of the response.
+///
+/// Only the SHA-256 hash of the query is sent at first. If the server doesn't know the
+/// query yet, it responds with a `PersistedQueryNotFound` error and the query is sent
+/// again with its text, so the server can persist it. Otherwise behaves like
+/// [`graphql_query`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::graphql_persisted_query;
+/// use serde_json::json;
+///
+/// transaction!(list_articles);
+///
+/// async fn list_articles(user: &mut GooseUser) -> TransactionResult {
+///     let query = "query articles($limit: Int) { articles(limit: $limit) { items { title } } }";
+///     let _data = graphql_persisted_query(user, "graphql", query, &json!({"limit": 10})).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn graphql_persisted_query(
+    user: &mut GooseUser,
+    endpoint: &str,
+    query: &str,
+    variables: &serde_json::Value,
+) -> Result<serde_json::Value, Box<TransactionError>> {
+    let hash = Sha256::digest(query.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    let extensions = serde_json::json!({
+        "persistedQuery": { "version": 1, "sha256Hash": hash }
+    });
+
+    // First send only the hash of the query.
+    let body = serde_json::json!({ "variables": variables, "extensions": extensions });
+    let (mut json, mut request) = send_graphql(user, endpoint, &body).await?;
+
+    // Send the query with its hash if the server hasn't persisted it yet.
+    let not_found = json["errors"].as_array().is_some_and(|errors| {
+        errors.iter().any(|error| {
+            error["message"] == "PersistedQueryNotFound"
+                || error["extensions"]["code"] == "PERSISTED_QUERY_NOT_FOUND"
+        })
+    });
+    if not_found {
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables,
+            "extensions": extensions,
+        });
+        let (retried_json, retried_request) = send_graphql(user, endpoint, &body).await?;
+        json = retried_json;
+        request = retried_request;
+    }
+
+    graphql_data(user, endpoint, json, &mut request)
+}
+
+// Post a GraphQL request, returning the validated json response and the request.
+async fn send_graphql(
+    user: &mut GooseUser,
+    endpoint: &str,
+    body: &serde_json::Value,
+) -> Result<(serde_json::Value, GooseRequestMetric), Box<TransactionError>> {
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Post, endpoint)?
+        .header("content-type", "application/json")
+        .body(body.to_string());
+    let name = format!("graphql: {}", endpoint);
+    let goose_request = GooseRequest::builder()
+        .method(GooseMethod::Post)
+        .path(endpoint)
+        .name(name.as_str())
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let request = goose.request.clone();
+
+    let validate = crate::Validate::builder().status(200).build();
+    let json = crate::validate_json(user, goose, &validate).await?;
+
+    Ok((json, request))
+}
+
+// Fail the request if the GraphQL response has errors, and return its data.
+fn graphql_data(
+    user: &mut GooseUser,
+    endpoint: &str,
+    json: serde_json::Value,
+    request: &mut GooseRequestMetric,
+) -> Result<serde_json::Value, Box<TransactionError>> {
+    if let Some(errors) = json["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .map(|error| error["message"].as_str().unwrap_or("unknown error"))
+            .collect();
         user.set_failure(
-            &format!(
-                "{}: login failed (check `GOOSE_USER` and `GOOSE_PASS`)",
-                logged_in_user.request.final_url
-            ),
-            &mut logged_in_user.request,
-            headers.as_ref(),
-            Some(&html),
+            &format!("{}: graphql errors: {}", endpoint, messages.join(", ")),
+            request,
+            None,
+            Some(&json.to_string()),
         )?;
-        // Return the html that was loaded, even though log-in failed. Enable
-        // the debug_log to determine why log-in failed.
-        return Ok(html);
     }
 
-    // Check the title to verify that the user is actually logged in.
-    let logged_in_page =
-        crate::validate_and_load_static_assets(user, logged_in_user, validate).await?;
+    Ok(json["data"].clone())
+}
+
+/// Load the translation of the current page in another language, from the translations it
+/// declares with `<link rel="alternate" hreflang="...">`, returning `None` if the page isn't
+/// translated to the language. Refer to [`get_hreflang_links`](crate::get_hreflang_links).
+///
+/// Drupal declares the translations of content entities, so a scenario can visit a node in
+/// one language and then in the others without knowing the translated paths. The requests
+/// are named `drupal: translation {langcode}`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::load_translation;
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(read_recipe_in_spanish);
+///
+/// async fn read_recipe_in_spanish(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("en/recipes/deep-mediterranean-quiche").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///
+///     let validate = Validate::builder().header_value("content-language", "es").build();
+///     let _spanish = load_translation(user, &html, "es", &validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn load_translation<'a>(
+    user: &mut GooseUser,
+    html: &str,
+    langcode: &str,
+    validate: &'a crate::Validate<'a>,
+) -> Result<Option<String>, Box<TransactionError>> {
+    let url = match crate::get_hreflang_links(html).remove(langcode) {
+        Some(url) => url,
+        None => return Ok(None),
+    };
+    let name = format!("drupal: translation {}", langcode);
+    let goose_request = GooseRequest::builder()
+        .path(url.as_str())
+        .name(name.as_str())
+        .build();
+    let goose = user.request(goose_request).await?;
+    let translation = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    Ok(Some(translation))
+}
+
+/// Extract the link to the next page of a listing, from the `rel="next"` link of its pager,
+/// such as `?page=2`. Returns `None` on the last page.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_pager_next;
+///
+/// let html = r#"
+///   <nav class="pager" role="navigation">
+///     <ul class="pager__items js-pager__items">
+///       <li class="pager__item pager__item--next">
+///         <a href="?search=quiche&amp;page=2" title="Go to next page" rel="next">Next</a>
+///       </li>
+///     </ul>
+///   </nav>
+/// "#;
+///
+/// assert_eq!(get_pager_next(html).unwrap(), "?search=quiche&page=2");
+/// ```
+pub fn get_pager_next(html: &str) -> Option<String> {
+    let re = Regex::new(r#"(?i)<(?:a|link)(\s[^>]*\srel=["']next["'][^>]*)>"#).unwrap();
+    let tag = re.captures(html)?;
+    get_attribute(&tag[1], "href")
+}
+
+/// Load a listing page and follow its pager, loading up to `max_pages` pages in total
+/// with their static assets, and return the html of each page.
+///
+/// Pagers link to the next page with a query string such as `?page=2`, which is followed
+/// from the path of the current page. Deep pages query the database with large offsets,
+/// which is often slow. Each page is validated with `validate`, and the requests are named
+/// after `start_url` so all the pages of the listing are aggregated in the Goose metrics.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::follow_pager;
+/// use goose_eggs::Validate;
+///
+/// transaction!(browse_articles);
+///
+/// async fn browse_articles(user: &mut GooseUser) -> TransactionResult {
+///     let validate = Validate::builder().status(200).text("article").build();
+///     let _pages = follow_pager(user, "en/articles", 10, &validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn follow_pager<'a>(
+    user: &mut GooseUser,
+    start_url: &str,
+    max_pages: usize,
+    validate: &'a crate::Validate<'a>,
+) -> Result<Vec<String>, Box<TransactionError>> {
+    let mut pages = Vec::new();
+    let mut url = start_url.to_string();
+    while pages.len() < max_pages {
+        let goose_request = GooseRequest::builder()
+            .path(url.as_str())
+            .name(start_url)
+            .build();
+        let goose = user.request(goose_request).await?;
+        let page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+        let next = get_pager_next(&page);
+        pages.push(page);
+        url = match next {
+            // Pagers link to the next page with only a query string.
+            Some(next) if next.starts_with('?') => {
+                format!("{}{}", url.split('?').next().unwrap_or_default(), next)
+            }
+            Some(next) => next,
+            None => break,
+        };
+    }
 
-    Ok(logged_in_page)
+    Ok(pages)
 }
 
-/// Parameters that define how to make and validate a search. For complete documentation,
-/// refer to [`SearchParamsBuilder`].
-#[derive(Clone, Debug)]
-pub struct SearchParams<'a> {
-    // The word or words to search for.
-    //
-    // Default to `""`, an empty String.
-    keys: &'a str,
-    // Optionally set a custom path to the search form.
-    //
-    // Defaults to `search`.
-    url: &'a str,
-    // Optionally specify a custom array of form values to scrape and post.
-    //
-    // Defaults to `["form_build_id", "form_id"]` (Drupal 8+ defaults).
-    form_values: &'a [&'a str],
-    // Optionally validate the page with the search form.
-    //
-    // Defaults to doing no validation.
-    search_page_validation: Option<&'a crate::Validate<'a>>,
-    // Optionally set a custom `op` name for the search button.
-    //
-    // Defaults to `Search`.
-    submit: &'a str,
-    // Optionally validate the search results page.
-    //
-    // Defaults to doing no validation.
-    results_page_validation: Option<&'a crate::Validate<'a>>,
-}
-impl<'a> SearchParams<'a> {
-    /// Convenience function to bring [`SearchParamsBuilder`] into scope.
-    pub fn builder() -> SearchParamsBuilder<'a> {
-        SearchParamsBuilder::new()
-    }
+/// Extract the autocomplete path of a named form field, from its `data-autocomplete-path`
+/// attribute, such as `/entity_reference_autocomplete/taxonomy_term/default:taxonomy_term/{key}`.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_autocomplete_path;
+///
+/// let form = r#"
+///   <input data-drupal-selector="edit-field-tags-target-id" class="form-autocomplete form-text" data-autocomplete-path="/entity_reference_autocomplete/taxonomy_term/default%3Ataxonomy_term/oD2lBpTaGRHT9lETgR2n5cIk7hU_8ZcCfq9kG3Ik0KY" type="text" name="field_tags[target_id]" value="" />
+/// "#;
+///
+/// assert_eq!(
+///     get_autocomplete_path(form, "field_tags[target_id]").unwrap(),
+///     "/entity_reference_autocomplete/taxonomy_term/default%3Ataxonomy_term/oD2lBpTaGRHT9lETgR2n5cIk7hU_8ZcCfq9kG3Ik0KY"
+/// );
+/// ```
+pub fn get_autocomplete_path(form_html: &str, name: &str) -> Option<String> {
+    let re = Regex::new(r#"<input([^>]*)>"#).unwrap();
+    let input = re
+        .captures_iter(form_html)
+        .map(|c| format!(" {}", &c[1]))
+        .find(|input| get_attribute(input, "name").as_deref() == Some(name))?;
+    get_attribute(&input, "data-autocomplete-path")
 }
 
-/// Used to build a [`SearchParams`] object, necessary to invoke the [`search`] function.
+/// Request suggestions for a partial value from an entity reference autocomplete path,
+/// returning the first suggestion, such as `Vegetarian (12)`, or `None` if nothing matches.
 ///
-/// Performing a search on a Drupal 8+ website is generally as simple as follow:
+/// This is the value an editor selects, which entity reference fields accept when the form
+/// is submitted. The path is extracted from the form with [`get_autocomplete_path`]. The
+/// requests are named `drupal: autocomplete`.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::drupal;
+/// use goose_eggs::drupal::{autocomplete, get_autocomplete_path, get_form, get_form_fields};
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
 ///
-/// transaction!(search);
+/// transaction!(tag_article);
 ///
-/// async fn search(user: &mut GooseUser) -> TransactionResult {
-///     // Define the search parameters.
-///     let search_params = drupal::SearchParams::builder()
-///         // Search for the keys "search terms".
-///         .keys("search terms")
-///         .build();
+/// async fn tag_article(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("node/add/article").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///     let form = get_form(&html, "node-article-form");
 ///
-///     // Perform the actual search.
-///     let _search_results_html = drupal::search(user, &search_params).await?;
+///     let mut fields = get_form_fields(&form);
+///     if let Some(path) = get_autocomplete_path(&form, "field_tags[target_id]") {
+///         // Type part of a tag name, then select the first suggestion.
+///         if let Some(tag) = autocomplete(user, &path, "veg").await? {
+///             fields.push(("field_tags[target_id]".to_string(), tag));
+///         }
+///     }
 ///
 ///     Ok(())
 /// }
 /// ```
+pub async fn autocomplete(
+    user: &mut GooseUser,
+    field_path: &str,
+    partial: &str,
+) -> Result<Option<String>, Box<TransactionError>> {
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Get, field_path)?
+        .query(&[("q", partial)]);
+    let goose_request = GooseRequest::builder()
+        .path(field_path)
+        .name("drupal: autocomplete")
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+
+    let validate = crate::Validate::builder().status(200).build();
+    let suggestions = crate::validate_json(user, goose, &validate).await?;
+
+    // Suggestions are a list of the value to submit and the label to display.
+    Ok(suggestions
+        .as_array()
+        .and_then(|suggestions| suggestions.first())
+        .and_then(|suggestion| suggestion["value"].as_str())
+        .map(|value| html_escape::decode_html_entities(value).to_string()))
+}
+
+/// Run Drupal's cron by requesting `/cron/{cron_key}`, validating the `204` response.
 ///
-/// The builder can also be used to customize the search parameters, for example:
+/// The cron key is displayed on `/admin/config/system/cron`. The request is named
+/// `drupal: cron`, so the key isn't included in the Goose metrics, and cron runs can be
+/// compared with the latency of other requests made while it runs.
 ///
-/// # Customized Example
+/// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::{Validate, drupal};
-///
-/// transaction!(search);
+/// use goose_eggs::drupal::run_cron;
 ///
-/// async fn search(user: &mut GooseUser) -> TransactionResult {
-///     // Define the search parameters.
-///     // Verify that the search form page has a title that includes "Custom Search".
-///     let validate_search_page = Validate::builder()
-///         .title("Custom Search")
-///         .build();
-///     // Verify that the search results page has a title that includes the search terms.
-///     let validate_results_page = Validate::builder()
-///         .title("search terms")
-///         .build();
-///     let search_params = drupal::SearchParams::builder()
-///         // Search for the keys "search terms".
-///         .keys("search terms")
-///         // Use a search form on a custom path.
-///         .url("custom/search/path")
-///         // Perform the search page validation defined above.
-///         .search_page_validation(&validate_search_page)
-///         // Use a submit button named "Custom Search".
-///         .submit("Custom Search")
-///         // Perform the search results validation defined above.
-///         .results_page_validation(&validate_results_page)
-///         .build();
+/// // Run cron every 60 seconds while the load test is running.
+/// transaction!(cron);
 ///
-///     // Perform the actual search.
-///     let _search_results_html = drupal::search(user, &search_params).await?;
+/// async fn cron(user: &mut GooseUser) -> TransactionResult {
+///     run_cron(user, "xXdjwzNxYZCOvgU1wFaQFRd8Fv1OYuLXYU3RpnXTxJw").await?;
+///     tokio::time::sleep(std::time::Duration::from_secs(60)).await;
 ///
 ///     Ok(())
 /// }
 /// ```
-pub struct SearchParamsBuilder<'a> {
-    keys: &'a str,
-    url: &'a str,
-    form_values: &'a [&'a str],
-    search_page_validation: Option<&'a crate::Validate<'a>>,
-    submit: &'a str,
-    results_page_validation: Option<&'a crate::Validate<'a>>,
+pub async fn run_cron(user: &mut GooseUser, cron_key: &str) -> TransactionResult {
+    let path = format!("cron/{}", cron_key);
+    let goose_request = GooseRequest::builder()
+        .path(&*path)
+        .name("drupal: cron")
+        .expect_status_code(204)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let validate = crate::Validate::builder().status(204).build();
+    crate::validate_page(user, goose, &validate).await?;
+
+    Ok(())
 }
-impl<'a> SearchParamsBuilder<'a> {
+
+/// Parameters that define how to filter the content overview at `/admin/content`, and
+/// optionally which bulk action to perform. For complete documentation, refer to
+/// [`AdminContentParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct AdminContentParams<'a> {
+    // Filter by title.
+    title: &'a str,
+    // Filter by content type.
+    content_type: &'a str,
+    // Filter by published status.
+    status: &'a str,
+    // Filter by language.
+    langcode: &'a str,
+    // Optionally perform a bulk action.
+    action: Option<&'a str>,
+    // How many rows to perform the bulk action on.
+    rows: usize,
+    // Optionally validate the content overview.
+    overview_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned by the bulk action.
+    action_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> AdminContentParams<'a> {
+    /// Convenience function to bring [`AdminContentParamsBuilder`] into scope.
+    pub fn builder() -> AdminContentParamsBuilder<'a> {
+        AdminContentParamsBuilder::new()
+    }
+}
+
+/// Used to build an [`AdminContentParams`] object, necessary to invoke the
+/// [`admin_content`] function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::AdminContentParams;
+///
+/// // List unpublished articles, and publish the first two.
+/// let _content_params = AdminContentParams::builder()
+///     .content_type("article")
+///     .status("2")
+///     .action("node_publish_action")
+///     .rows(2)
+///     .build();
+/// ```
+pub struct AdminContentParamsBuilder<'a> {
+    // Filter by title.
+    title: &'a str,
+    // Filter by content type.
+    content_type: &'a str,
+    // Filter by published status.
+    status: &'a str,
+    // Filter by language.
+    langcode: &'a str,
+    // Optionally perform a bulk action.
+    action: Option<&'a str>,
+    // How many rows to perform the bulk action on.
+    rows: usize,
+    // Optionally validate the content overview.
+    overview_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned by the bulk action.
+    action_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> AdminContentParamsBuilder<'a> {
     // Internally used when building to set defaults.
     fn new() -> Self {
         Self {
-            // Defaults to empty search keys.
-            keys: "",
-            // Defaults to "search".
-            url: "search",
-            // Defaults to form values required by Drupal 8 and 9.
-            form_values: &["form_build_id", "form_id"],
-            // Defaults to no extra search page validation.
-            search_page_validation: None,
-            // Defaults to a search button named "Search".
-            submit: "Search",
-            // Defaults to no extra results page validation.
-            results_page_validation: None,
+            // Defaults to not filtering by title.
+            title: "",
+            // Defaults to all content types.
+            content_type: "All",
+            // Defaults to published and unpublished content.
+            status: "All",
+            // Defaults to all languages.
+            langcode: "All",
+            // Defaults to only listing content.
+            action: None,
+            // Defaults to the first row.
+            rows: 1,
+            // Defaults to validating that the content overview has the bulk form.
+            overview_page_validation: None,
+            // Defaults to not validating the page returned by the bulk action.
+            action_page_validation: None,
         }
     }
 
-    /// Used with [`SearchParams::builder`] to set the keys to search for.
+    /// Used with [`AdminContentParams::builder`] to filter the content by title, which
+    /// matches titles that contain it.
     ///
-    /// Defaults to `""`, an empty search string.
+    /// Defaults to not filtering by title.
     ///
-    /// Once built, the resulting object is passed to the [`search`] function.
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::AdminContentParams;
+    ///
+    /// let _content_params = AdminContentParams::builder()
+    ///     .title("quiche")
+    ///     .build();
+    /// ```
+    pub fn title(mut self, title: impl Into<&'a str>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to filter the content by the machine name
+    /// of its content type.
+    ///
+    /// Defaults to `All`. Refer to the [`AdminContentParamsBuilder`] example.
+    pub fn content_type(mut self, content_type: impl Into<&'a str>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to filter the content by status: `1` for
+    /// published content, and `2` for unpublished content.
+    ///
+    /// Defaults to `All`. Refer to the [`AdminContentParamsBuilder`] example.
+    pub fn status(mut self, status: impl Into<&'a str>) -> Self {
+        self.status = status.into();
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to filter the content by language code.
+    ///
+    /// Defaults to `All`.
+    pub fn langcode(mut self, langcode: impl Into<&'a str>) -> Self {
+        self.langcode = langcode.into();
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to set the id of a bulk action to perform
+    /// on the listed content, such as `node_unpublish_action` or `node_make_sticky_action`.
+    ///
+    /// Defaults to `None`, so content is only listed. Refer to the
+    /// [`AdminContentParamsBuilder`] example.
+    pub fn action(mut self, action: impl Into<&'a str>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to set how many of the listed rows to
+    /// select for the bulk action, starting from the first.
+    ///
+    /// Defaults to `1`. Refer to the [`AdminContentParamsBuilder`] example.
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Used with [`AdminContentParams::builder`] to set a custom validation of the content
+    /// overview.
+    ///
+    /// Defaults to validating that the page has the bulk form of the content overview,
+    /// `views-form-content-page-1`.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::AdminContentParams;
     ///
-    /// // Use Drupal's default search form to search for "foo bar".
-    /// let search_params = SearchParams::builder()
-    ///     .keys("foo bar")
+    /// let validate = Validate::builder().title("Content").text("Deep mediterranean quiche").build();
+    /// let _content_params = AdminContentParams::builder()
+    ///     .title("quiche")
+    ///     .overview_page_validation(&validate)
     ///     .build();
     /// ```
-    pub fn keys(mut self, keys: impl Into<&'a str>) -> Self {
-        self.keys = keys.into();
+    pub fn overview_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.overview_page_validation = Some(validation);
         self
     }
 
-    /// Used with [`SearchParams::builder`] to set the url the search form is on.
+    /// Used with [`AdminContentParams::builder`] to set a custom validation of the page
+    /// returned by the bulk action.
     ///
-    /// Defaults to `search`, Drupal's default path for the search form.
+    /// Defaults to `None`, so the page isn't validated.
     ///
-    /// Once built, the resulting object is passed to the [`search`] function.
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::AdminContentParams;
+    ///
+    /// let validate = Validate::builder().text("Make content sticky was applied to").build();
+    /// let _content_params = AdminContentParams::builder()
+    ///     .action("node_make_sticky_action")
+    ///     .action_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn action_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.action_page_validation = Some(validation);
+        self
+    }
+
+    /// Build the [`AdminContentParams`] object which is then passed to the
+    /// [`admin_content`] function.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// use goose_eggs::drupal::AdminContentParams;
+    ///
+    /// // List all content.
+    /// let _content_params = AdminContentParams::builder().build();
+    /// ```
+    pub fn build(self) -> AdminContentParams<'a> {
+        let Self {
+            title,
+            content_type,
+            status,
+            langcode,
+            action,
+            rows,
+            overview_page_validation,
+            action_page_validation,
+        } = self;
+        AdminContentParams {
+            title,
+            content_type,
+            status,
+            langcode,
+            action,
+            rows,
+            overview_page_validation,
+            action_page_validation,
+        }
+    }
+}
+
+/// Load the content overview at `/admin/content` filtered with its exposed filters, and
+/// optionally perform a bulk action on the listed content, returning the html of the last
+/// page loaded.
+///
+/// Listing and filtering content queries the database in ways that are cached less than
+/// the pages visitors see, so this is useful to load test editorial workflows. The bulk
+/// action is performed on the first rows listed, with the `Apply to selected items` button
+/// of the bulk form. Actions that ask for confirmation, such as deleting content, return
+/// the confirmation form.
+///
+/// The content overview requests are named `drupal: admin content`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::{admin_content, AdminContentParams};
+///
+/// transaction!(unpublish_oldest_article);
+///
+/// async fn unpublish_oldest_article(user: &mut GooseUser) -> TransactionResult {
+///     let content_params = AdminContentParams::builder()
+///         .content_type("article")
+///         .status("1")
+///         .action("node_unpublish_action")
+///         .build();
+///     let _html = admin_content(user, &content_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn admin_content<'a>(
+    user: &mut GooseUser,
+    params: &'a AdminContentParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    // Load the content overview, filtered like its exposed form does.
+    let filters = [
+        ("title", params.title),
+        ("type", params.content_type),
+        ("status", params.status),
+        ("langcode", params.langcode),
+    ];
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Get, "admin/content")?
+        .query(&filters);
+    let goose_request = GooseRequest::builder()
+        .path("admin/content")
+        .name("drupal: admin content")
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let mut overview_request = goose.request.clone();
+    let default_validation = crate::Validate::builder()
+        .form("views-form-content-page-1")
+        .build();
+    let validate = params
+        .overview_page_validation
+        .unwrap_or(&default_validation);
+    let overview = crate::validate_and_load_static_assets(user, goose, validate).await?;
+
+    let action = match params.action {
+        Some(action) => action,
+        None => return Ok(overview),
+    };
+
+    // Select the first rows of the bulk form.
+    let mut form = match Form::parse(&overview, "views-form-content-page-1") {
+        Some(form) => form,
+        None => {
+            user.set_failure(
+                "admin/content: no bulk form on page",
+                &mut overview_request,
+                None,
+                Some(&overview),
+            )?;
+            // Return the html that was loaded, even though the action failed.
+            return Ok(overview);
+        }
+    };
+    let checkbox = Regex::new(r#"<input([^>]*\sname="node_bulk_form\[\d+\]"[^>]*)>"#).unwrap();
+    let rows: Vec<(String, String)> = checkbox
+        .captures_iter(&overview)
+        .take(params.rows)
+        .filter_map(|c| {
+            let name = get_attribute(&c[1], "name")?;
+            let value = get_attribute(&c[1], "value")?;
+            Some((name, value))
+        })
+        .collect();
+    if rows.is_empty() {
+        user.set_failure(
+            "admin/content: no content to perform the bulk action on",
+            &mut overview_request,
+            None,
+            Some(&overview),
+        )?;
+        // Return the html that was loaded, even though the action failed.
+        return Ok(overview);
+    }
+    form.fields.extend(rows);
+
+    // Perform the bulk action.
+    let goose = form
+        .submit(
+            user,
+            &[("action", action), ("op", "Apply to selected items")],
+        )
+        .await?;
+    let no_validation = crate::Validate::none();
+    let validate = params.action_page_validation.unwrap_or(&no_validation);
+    crate::validate_and_load_static_assets(user, goose, validate).await
+}
+
+/// The most times [`process_batch`] polls a batch before giving up.
+pub const BATCH_MAX_POLLS: usize = 1000;
+
+/// Process a Batch API batch the way Drupal's JavaScript does, returning the html of the
+/// page the batch finishes on.
+///
+/// Forms that start long operations, such as imports and bulk operations, redirect to a
+/// batch url such as `/batch?id=12&op=start`, typically the `final_url` of the form
+/// submission. The batch is then processed by posting to `/batch?id=12&op=do` until it
+/// reports that it's complete, so the operations are load tested instead of only the form
+/// submission. The batch finishes at `/batch?id=12&op=finished`, which redirects to the
+/// destination of the form.
+///
+/// The polls are named `drupal: batch`, and the request that finishes the batch is named
+/// `drupal: batch finished`. Fails if the batch reports an error, if it isn't complete
+/// after [`BATCH_MAX_POLLS`] polls, or if it doesn't redirect once finished.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::process_batch;
+///
+/// transaction!(rebuild_permissions);
+///
+/// async fn rebuild_permissions(user: &mut GooseUser) -> TransactionResult {
+///     let params = [("op", "Rebuild permissions")];
+///     let goose = user.post_form("admin/reports/status/rebuild", &params).await?;
+///     let batch_url = goose.request.final_url.to_string();
+///
+///     if batch_url.contains("batch?") {
+///         let _html = process_batch(user, &batch_url).await?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn process_batch(
+    user: &mut GooseUser,
+    batch_url: &str,
+) -> Result<String, Box<TransactionError>> {
+    let path = batch_url.split('?').next().unwrap_or_default();
+    let id = Regex::new(r#"[?&]id=(\d+)"#)
+        .unwrap()
+        .captures(batch_url)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default();
+
+    // Post to the batch until it's complete.
+    let do_url = format!("{}?id={}&op=do", path, id);
+    let validate = crate::Validate::builder().status(200).build();
+    let mut polls = 0;
+    loop {
+        let request_builder = user.get_request_builder(&GooseMethod::Post, &do_url)?;
+        let goose_request = GooseRequest::builder()
+            .method(GooseMethod::Post)
+            .path(do_url.as_str())
+            .name("drupal: batch")
+            .set_request_builder(request_builder)
+            .build();
+        let goose = user.request(goose_request).await?;
+        let mut request = goose.request.clone();
+        let progress = crate::validate_json(user, goose, &validate).await?;
+        polls += 1;
+
+        // The percentage is a string, or a number with some versions of Drupal.
+        let percentage = match &progress["percentage"] {
+            serde_json::Value::String(percentage) => percentage.parse().unwrap_or(0.0),
+            percentage => percentage.as_f64().unwrap_or(0.0),
+        };
+        let error = if progress["status"] == false {
+            Some(format!(
+                "batch {} failed: {}",
+                id,
+                progress["data"]
+                    .as_str()
+                    .or_else(|| progress["message"].as_str())
+                    .unwrap_or_default()
+            ))
+        } else if percentage < 100.0 && polls >= BATCH_MAX_POLLS {
+            Some(format!("batch {} incomplete after {} polls", id, polls))
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            user.set_failure(&error, &mut request, None, None)?;
+            // Return an empty string as the batch failed. Enable the debug log to
+            // determine why.
+            return Ok("".to_string());
+        }
+        if percentage >= 100.0 {
+            break;
+        }
+    }
+
+    // Finish the batch, which redirects to the destination of the form.
+    let finished_url = format!("{}?id={}&op=finished", path, id);
+    let goose_request = GooseRequest::builder()
+        .path(finished_url.as_str())
+        .name("drupal: batch finished")
+        .build();
+    let goose = user.request(goose_request).await?;
+    let validate = crate::Validate::builder().redirect(true).build();
+    crate::validate_and_load_static_assets(user, goose, &validate).await
+}
+
+/// Parameters that define how to submit an administrative form and validate that the
+/// action completed. For complete documentation, refer to [`AdminFormParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct AdminFormParams<'a> {
+    // The path of the administrative form.
+    url: &'a str,
+    // The id of the form.
+    form: &'a str,
+    // The name of the submit button.
+    submit: &'a str,
+    // Field values to change.
+    fields: &'a [(&'a str, &'a str)],
+    // Text displayed once the action completes.
+    confirmation: &'a str,
+}
+impl<'a> AdminFormParams<'a> {
+    /// Convenience function to bring [`AdminFormParamsBuilder`] into scope.
+    pub fn builder() -> AdminFormParamsBuilder<'a> {
+        AdminFormParamsBuilder::new()
+    }
+}
+
+/// Used to build an [`AdminFormParams`] object, necessary to invoke the
+/// [`submit_admin_form`] function.
+///
+/// Defaults to clicking "Clear all caches" on the performance page, which can also be
+/// done with [`clear_caches`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::AdminFormParams;
+///
+/// // Put the website in maintenance mode.
+/// let _admin_form_params = AdminFormParams::builder()
+///     .url("admin/config/development/maintenance")
+///     .form("system-site-maintenance-mode")
+///     .submit("Save configuration")
+///     .fields(&[("maintenance_mode", "1")])
+///     .confirmation("The configuration options have been saved.")
+///     .build();
+/// ```
+pub struct AdminFormParamsBuilder<'a> {
+    // The path of the administrative form.
+    url: &'a str,
+    // The id of the form.
+    form: &'a str,
+    // The name of the submit button.
+    submit: &'a str,
+    // Field values to change.
+    fields: &'a [(&'a str, &'a str)],
+    // Text displayed once the action completes.
+    confirmation: &'a str,
+}
+impl<'a> AdminFormParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the performance page.
+            url: "admin/config/development/performance",
+            // Defaults to the id of the performance form.
+            form: "system-performance-settings",
+            // Defaults to the button that clears all caches.
+            submit: "Clear all caches",
+            // Defaults to submitting the form without changes.
+            fields: &[],
+            // Defaults to the message displayed once caches are cleared.
+            confirmation: "Caches cleared.",
+        }
+    }
+
+    /// Used with [`AdminFormParams::builder`] to set the path of the administrative form.
     ///
-    /// // Use a search form on custom path `custom/search/path`.
-    /// let search_params = SearchParams::builder()
-    ///     .url("custom/search/path")
-    ///     .build();
-    /// ```
+    /// Defaults to `admin/config/development/performance`. Refer to the
+    /// [`AdminFormParamsBuilder`] example.
     pub fn url(mut self, url: impl Into<&'a str>) -> Self {
         self.url = url.into();
         self
     }
 
-    /// Used with [`SearchParams::builder`] to set form_values that are extracted from
-    /// the search form and used when POSTing the search.
-    ///
-    /// Defaults to form values required by Drupal 8 and 9: `&["form_build_id", "form_id"]`.
-    /// See the example below for how to perform a search on a Drupal 7 website.
-    ///
-    /// Once built, the resulting object is passed to the [`search`] function.
-    ///
-    /// # Example
-    /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// Used with [`AdminFormParams::builder`] to set the id of the form.
     ///
-    /// // Perform a search on a Drupal 7 website.
-    /// let search_params = SearchParams::builder()
-    ///     // Searching on Drupal 7 also requires `form_token`.
-    ///     .form_values(&["form_token", "form_build_id", "form_id"])
-    ///     .build();
-    /// ```
-    pub fn form_values(mut self, form_values: &'a [&'a str]) -> Self {
-        self.form_values = form_values;
+    /// Defaults to `system-performance-settings`. Refer to the [`AdminFormParamsBuilder`]
+    /// example.
+    pub fn form(mut self, form: impl Into<&'a str>) -> Self {
+        self.form = form.into();
         self
     }
 
-    /// Used with [`SearchParams::builder`] to tell the [`search`] function to perform
-    /// extra validation of the page containing the search form.
-    ///
-    /// Defaults to `None`, so no extra validation is performed. By default it will still
-    /// validate that the search request returns a valid HTTP response code, and it will
-    /// load all static assets on the page with the search form.
-    ///
-    /// What validation should be performed is defined by passing a reference to a
-    /// [`Validate`](../struct.Validate.html) object.
-    ///
-    /// Once built, the resulting object is passed to the [`search`] function.
-    ///
-    /// # Example
-    /// ```rust
-    /// use goose_eggs::Validate;
-    /// use goose_eggs::drupal::SearchParams;
+    /// Used with [`AdminFormParams::builder`] to set the form submit `op`.
     ///
-    /// // Validate the title of the search page.
-    /// let validate_search_page = Validate::builder()
-    ///     .title("Custom Search")
-    ///     .build();
-    /// let search_params = SearchParams::builder()
-    ///     .search_page_validation(&validate_search_page)
-    ///     .build();
-    /// ```
-    pub fn search_page_validation(mut self, validation: &'a crate::Validate) -> Self {
-        self.search_page_validation = Some(validation);
+    /// Defaults to `Clear all caches`. Refer to the [`AdminFormParamsBuilder`] example.
+    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
+        self.submit = submit.into();
         self
     }
 
-    /// Used with [`SearchParams::builder`] to set a custom search form submit `op`.
-    ///
-    /// Defaults to Drupal's standard search button name of `Search`.
-    ///
-    /// Once built, the resulting object is passed to the [`search`] function.
-    ///
-    /// # Example
-    /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// Used with [`AdminFormParams::builder`] to set field values to change, identified by
+    /// the name of the form element. All other fields keep the values the form was
+    /// rendered with.
     ///
-    /// // Perform a search with a custom search button.
-    /// let search_params = SearchParams::builder()
-    ///     // Searching on Drupal 7 also requires `form_token`.
-    ///     .submit("Custom Search")
-    ///     .build();
-    /// ```
-    pub fn submit(mut self, submit: impl Into<&'a str>) -> Self {
-        self.submit = submit.into();
+    /// Defaults to no changes. Refer to the [`AdminFormParamsBuilder`] example.
+    pub fn fields(mut self, fields: &'a [(&'a str, &'a str)]) -> Self {
+        self.fields = fields;
         self
     }
 
-    /// Used with [`SearchParams::builder`] to tell the [`search`] function to validate
-    /// the page title containing the search results.
-    ///
-    /// Defaults to `None`, so no extra validation is performed. By default it will still
-    /// validate that the search response returns a valid HTTP response code, and it will
-    /// load all static assets on the returned search results page.
-    ///
-    /// What validation should be performed is defined by passing a reference to a
-    /// [`Validate`](../struct.Validate.html) object.
-    ///
-    /// Once built, the resulting object is passed to the [`search`] function.
-    ///
-    /// # Example
-    /// ```rust
-    /// use goose_eggs::Validate;
-    /// use goose_eggs::drupal::SearchParams;
+    /// Used with [`AdminFormParams::builder`] to set the text that is displayed once the
+    /// action completes.
     ///
-    /// // Validate that the search terms are in the title of the search results.
-    /// let validate_results_page = Validate::builder()
-    ///     .title("foo")
-    ///     .build();
-    /// let search_params = SearchParams::builder()
-    ///     .keys("foo")
-    ///     .results_page_validation(&validate_results_page)
-    ///     .build();
-    /// ```
-    pub fn results_page_validation(mut self, validation: &'a crate::Validate) -> Self {
-        self.results_page_validation = Some(validation);
+    /// Defaults to `Caches cleared.`. Refer to the [`AdminFormParamsBuilder`] example.
+    pub fn confirmation(mut self, confirmation: impl Into<&'a str>) -> Self {
+        self.confirmation = confirmation.into();
         self
     }
 
-    /// Build the [`SearchParams`] object which is then passed to the [`search`] function.
+    /// Build the [`AdminFormParams`] object which is then passed to the
+    /// [`submit_admin_form`] function.
     ///
     /// # Example
     /// ```rust
-    /// use goose_eggs::drupal::SearchParams;
+    /// use goose_eggs::drupal::AdminFormParams;
     ///
-    /// // Use the default search form to search for `example keys`.
-    /// let search_params = SearchParams::builder()
-    ///     .keys("example keys")
-    ///     .build();
+    /// // Clear all caches.
+    /// let _admin_form_params = AdminFormParams::builder().build();
     /// ```
-    pub fn build(self) -> SearchParams<'a> {
+    pub fn build(self) -> AdminFormParams<'a> {
         let Self {
-            keys,
             url,
-            form_values,
-            search_page_validation,
+            form,
             submit,
-            results_page_validation,
+            fields,
+            confirmation,
         } = self;
-        SearchParams {
-            keys,
+        AdminFormParams {
             url,
-            form_values,
-            search_page_validation,
+            form,
             submit,
-            results_page_validation,
+            fields,
+            confirmation,
         }
     }
 }
 
-/// Perform a simple Drupal-powered search.
+/// Submit an administrative form, such as "Clear all caches" on the performance page.
 ///
-/// In the following example, [`SearchParamsBuilder::keys`] is used to configure the
-/// keys being searched for, and [`SearchParamsBuilder::search_page_validation`] is
-/// used to validate that the page with the search form has a title containing `Search`.
+/// Loads the form, extracts its fields including the `form_build_id` and `form_token`
+/// with [`get_form_fields`], applies the values set with [`AdminFormParamsBuilder::fields`],
+/// and submits it. The page returned must include the confirmation text. The Goose user
+/// must be logged in with the necessary permissions, see [`log_in`].
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
 /// use goose_eggs::drupal;
 ///
-/// transaction!(search);
+/// transaction!(rebuild_router);
 ///
-/// async fn search(user: &mut GooseUser) -> TransactionResult {
-///     // Use the default search form to search for "foo", validating that the
-///     // search page has a title of Search.
-///     let validate_search_page = &goose_eggs::Validate::builder()
-///         .title("Search")
-///         .build();
-///     let search_params = drupal::SearchParams::builder()
-///         .keys("foo")
-///         .search_page_validation(validate_search_page)
+/// async fn rebuild_router(user: &mut GooseUser) -> TransactionResult {
+///     // Rebuild the router by saving the URL aliases settings.
+///     let admin_form_params = drupal::AdminFormParams::builder()
+///         .url("admin/config/search/path/settings")
+///         .form("path-alias-settings")
+///         .submit("Save configuration")
+///         .confirmation("The configuration options have been saved.")
 ///         .build();
-///     // Perform the actual search.
-///     let _search_results = drupal::search(user, &search_params).await?;
+///     let _html = drupal::submit_admin_form(user, &admin_form_params).await?;
 ///
 ///     Ok(())
 /// }
 /// ```
-pub async fn search<'a>(
+pub async fn submit_admin_form<'a>(
     user: &mut GooseUser,
-    params: &'a SearchParams<'a>,
+    params: &'a AdminFormParams<'a>,
 ) -> Result<String, Box<TransactionError>> {
-    // Load the search page.
+    // Load the administrative form.
     let goose = user.get(params.url).await?;
+    let mut form_request = goose.request.clone();
+    let validate = crate::Validate::builder().form(params.form).build();
+    let form_page = crate::validate_and_load_static_assets(user, goose, &validate).await?;
 
-    // Optionally validate the page with the search form.
-    let no_validation = crate::Validate::none();
-    let validate = if let Some(validation) = params.search_page_validation {
-        validation
-    } else {
-        &no_validation
-    };
-    let search_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    let form = get_form(&form_page, params.form);
+    let form_token = get_form_value(&form, "form_token");
+    // A missing form element is returned as "none".
+    if form_token.is_empty() || form_token == "none" {
+        user.set_failure(
+            &format!("{}: no form_token in {}", params.url, params.form),
+            &mut form_request,
+            None,
+            Some(&form_page),
+        )?;
+        // Return an empty string as the action failed. Enable the debug log to
+        // determine why.
+        return Ok("".to_string());
+    }
 
-    // Extract the search form from the page.
-    let search_form = get_form(&search_page, "search-form");
+    // Preserve the existing field values, then apply the changes.
+    let mut fields = get_form_fields(&form);
+    for (name, value) in params.fields {
+        fields.retain(|(n, _)| n != name);
+        fields.push((name.to_string(), value.to_string()));
+    }
+    fields.push(("op".to_string(), params.submit.to_string()));
 
-    // Extract values from the search form.
-    let form_values = get_form_values(&search_form, params.form_values);
+    // Submit the form, and validate that the action completed.
+    let goose = user.post_form(params.url, &fields).await?;
+    let validate = crate::Validate::builder().text(params.confirmation).build();
+    crate::validate_and_load_static_assets(user, goose, &validate).await
+}
 
-    // Build search form.
-    let keys = params.keys.to_string();
-    let submit = params.submit.to_string();
-    let mut search_params = vec![("keys", keys), ("op", submit)];
-    for value in params.form_values {
-        search_params.push((*value, form_values.get(value).unwrap().to_string()));
+/// Clear all caches, as if clicking "Clear all caches" on the performance page.
+///
+/// A shortcut for [`submit_admin_form`] with the default [`AdminFormParams`]. The Goose
+/// user must be logged in with the `administer site configuration` permission.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::clear_caches;
+///
+/// // Clear all caches during the load test, to measure how the website recovers.
+/// transaction!(cache_clear_storm);
+///
+/// async fn cache_clear_storm(user: &mut GooseUser) -> TransactionResult {
+///     let _html = clear_caches(user).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn clear_caches(user: &mut GooseUser) -> Result<String, Box<TransactionError>> {
+    submit_admin_form(user, &AdminFormParams::builder().build()).await
+}
+
+/// Start building a validation of a page that Drupal served from its page cache, as it
+/// does for anonymous visitors: the page has a `200` status code, the `x-generator` and
+/// `x-drupal-cache: HIT` headers, and no error markers. Add further validations, then
+/// build the [`Validate`](crate::Validate) object.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::validate_page_cached;
+/// use goose_eggs::validate_and_load_static_assets;
+///
+/// transaction!(load_front_page);
+///
+/// async fn load_front_page(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("").await?;
+///     let validate = validate_page_cached().title("Home").build();
+///     validate_and_load_static_assets(user, goose, &validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn validate_page_cached<'a>() -> crate::ValidateBuilder<'a> {
+    crate::Validate::builder()
+        .status(200)
+        .header("x-generator")
+        .header_value("x-drupal-cache", "HIT")
+        .no_error_markers()
+}
+
+/// Start building a validation of a page that Drupal rendered rather than serving it from
+/// its page cache or dynamic page cache, as it does for pages that can't be cached: the
+/// page has a `200` status code and the `x-generator` header, neither the
+/// `x-drupal-cache` nor the `x-drupal-dynamic-cache` header is a `HIT`, and the page has
+/// no error markers. Add further validations, then build the
+/// [`Validate`](crate::Validate) object.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::validate_page_uncached;
+/// use goose_eggs::validate_and_load_static_assets;
+///
+/// transaction!(load_cart);
+///
+/// async fn load_cart(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("cart").await?;
+///     let validate = validate_page_uncached().title("Shopping cart").build();
+///     validate_and_load_static_assets(user, goose, &validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn validate_page_uncached<'a>() -> crate::ValidateBuilder<'a> {
+    crate::Validate::builder()
+        .status(200)
+        .header("x-generator")
+        .not_header_value("x-drupal-cache", "HIT")
+        .not_header_value("x-drupal-dynamic-cache", "HIT")
+        .no_error_markers()
+}
+
+/// Whether the html is the page Drupal displays to visitors while the site is in
+/// maintenance mode, titled `Site under maintenance` and explaining that the site `is
+/// currently under maintenance`.
+///
+/// [`Validate`](crate::Validate) objects built with
+/// [`ValidateBuilder::not_maintenance`](crate::ValidateBuilder::not_maintenance) detect
+/// this page, reporting
+/// [`ValidationError::MaintenanceMode`](crate::ValidationError::MaintenanceMode) instead
+/// of each failed validation.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::is_maintenance_page;
+///
+/// let html = r#"<html><head><title>Site under maintenance | Umami</title></head>
+/// <body><p>Umami is currently under maintenance. We should be back shortly.</p></body></html>"#;
+/// assert!(is_maintenance_page(html));
+/// assert!(!is_maintenance_page("<html><head><title>Home | Umami</title></head></html>"));
+/// ```
+pub fn is_maintenance_page(html: &str) -> bool {
+    crate::valid_title(html, "Site under maintenance")
+        || crate::valid_text(html, "is currently under maintenance")
+}
+
+/// The `ajaxPageState` declared by a Drupal page in `drupalSettings`, or by a BigPipe
+/// placeholder replacement, listing the libraries attached to the page.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AjaxPageState {
+    /// The machine name of the active theme.
+    pub theme: String,
+    /// The libraries attached to the page, a comma separated list of library names, or
+    /// compressed since Drupal 10.1.
+    pub libraries: String,
+    /// The language of the page, if declared with `drupalSettings.path.currentLanguage`.
+    pub language: Option<String>,
+}
+
+/// Decode the `drupalSettings` json of the page, and of any BigPipe placeholder
+/// replacements embedded in the page, and return each distinct `ajaxPageState` declared.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_ajax_page_states;
+///
+/// let html = r#"
+/// <script type="application/json" data-drupal-selector="drupal-settings-json">
+///   {"path":{"currentLanguage":"en"},"ajaxPageState":{"libraries":"olivero\/global-styling,system\/base","theme":"olivero"}}
+/// </script>
+/// "#;
+///
+/// let states = get_ajax_page_states(html);
+/// assert_eq!(states[0].theme, "olivero");
+/// assert_eq!(states[0].libraries, "olivero/global-styling,system/base");
+/// assert_eq!(states[0].language.as_deref(), Some("en"));
+/// ```
+pub fn get_ajax_page_states(html: &str) -> Vec<AjaxPageState> {
+    let settings = get_all_drupal_settings(html);
+    let language = settings
+        .iter()
+        .find_map(|settings| settings["path"]["currentLanguage"].as_str())
+        .map(|language| language.to_string());
+    let mut states: Vec<AjaxPageState> = Vec::new();
+    for settings in &settings {
+        let state = &settings["ajaxPageState"];
+        if let (Some(theme), Some(libraries)) =
+            (state["theme"].as_str(), state["libraries"].as_str())
+        {
+            let state = AjaxPageState {
+                theme: theme.to_string(),
+                libraries: libraries.to_string(),
+                language: language.clone(),
+            };
+            if !states.contains(&state) {
+                states.push(state);
+            }
+        }
     }
+    states
+}
 
-    // Perform the search.
-    let goose = user.post_form(params.url, &search_params).await?;
+/// Decode the `drupalSettings` json of the page, the settings Drupal's JavaScript is
+/// initialized with, such as the `ajaxPageState` listing the libraries of the page, the
+/// `ajax` elements and the urls they post to, and the settings of contributed modules.
+///
+/// Settings added by BigPipe placeholder replacements embedded in the page are merged
+/// into the settings of the page, as Drupal's JavaScript does. Returns
+/// [`Value::Null`](serde_json::Value::Null) if the page has no `drupalSettings`.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::get_drupal_settings;
+///
+/// let html = r#"
+/// <script type="application/json" data-drupal-selector="drupal-settings-json">
+///   {"path":{"currentLanguage":"en"},"ajaxPageState":{"theme":"olivero","libraries":"system/base"}}
+/// </script>
+/// <script type="application/vnd.drupal-ajax" data-big-pipe-replacement-for-placeholder-with-id="callback=a">
+///   [{"command":"settings","settings":{"ajaxPageState":{"libraries":"system/base,core/drupal.dialog"}},"merge":true}]
+/// </script>
+/// "#;
+///
+/// let settings = get_drupal_settings(html);
+/// assert_eq!(settings["path"]["currentLanguage"], "en");
+/// assert_eq!(settings["ajaxPageState"]["theme"], "olivero");
+/// assert_eq!(
+///     settings["ajaxPageState"]["libraries"],
+///     "system/base,core/drupal.dialog"
+/// );
+/// assert!(get_drupal_settings("<html></html>").is_null());
+/// ```
+pub fn get_drupal_settings(html: &str) -> serde_json::Value {
+    let mut merged = serde_json::Value::Null;
+    for settings in get_all_drupal_settings(html) {
+        merge_settings(&mut merged, settings);
+    }
+    merged
+}
 
-    // Optionally validate the search results page.
-    let validate = if let Some(validation) = params.results_page_validation {
-        validation
-    } else {
-        &no_validation
-    };
-    let search_results = crate::validate_and_load_static_assets(user, goose, validate).await?;
+// Recursively merge settings into existing settings, replacing anything that isn't an
+// object.
+fn merge_settings(existing: &mut serde_json::Value, settings: serde_json::Value) {
+    match (existing, settings) {
+        (serde_json::Value::Object(existing), serde_json::Value::Object(settings)) => {
+            for (key, value) in settings {
+                merge_settings(
+                    existing.entry(key).or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+        (existing, settings) => *existing = settings,
+    }
+}
 
-    // Return the search results.
-    Ok(search_results)
+// Decode the `drupalSettings` json of the page, and of any BigPipe placeholder
+// replacements embedded in the page.
+fn get_all_drupal_settings(html: &str) -> Vec<serde_json::Value> {
+    let re = Regex::new(
+        r#"(?is)<script[^>]*\s(?:data-drupal-selector="drupal-settings-json"|data-big-pipe-replacement-for-placeholder-with-id="[^"]*")[^>]*>(.*?)</script>"#,
+    )
+    .unwrap();
+    // BigPipe replacements are a list of ajax commands, including settings commands.
+    let mut settings = Vec::new();
+    for capture in re.captures_iter(html) {
+        match serde_json::from_str::<serde_json::Value>(capture[1].trim()) {
+            Ok(serde_json::Value::Array(commands)) => settings.extend(
+                commands
+                    .into_iter()
+                    .filter(|command| command["command"] == "settings")
+                    .map(|command| command["settings"].clone()),
+            ),
+            Ok(value) => settings.push(value),
+            Err(e) => warn!("failed to decode drupalSettings: {}", e),
+        }
+    }
+    settings
+}
+
+// Build the urls of the css and js aggregates of the libraries declared by each
+// `ajaxPageState` of the page, unless the page already references them. Aggregates are
+// generated on demand by Drupal 10.1 and later, from the public files path.
+pub(crate) fn ajax_page_state_urls(html: &str, files_path: &str) -> Vec<(String, &'static str)> {
+    let files_path = files_path.trim_end_matches('/');
+    let mut urls = Vec::new();
+    for state in get_ajax_page_states(html) {
+        let included = Regex::new(&format!(
+            r#"include={}(?:["'&]|$)"#,
+            regex::escape(&state.libraries)
+        ))
+        .unwrap();
+        if included.is_match(html) {
+            continue;
+        }
+        let mut query = String::from("delta=0");
+        if let Some(language) = &state.language {
+            query.push_str(&format!("&language={}", language));
+        }
+        query.push_str(&format!(
+            "&theme={}&include={}",
+            state.theme, state.libraries
+        ));
+        urls.push((
+            format!("{}/css/css_ajaxpagestate.css?{}", files_path, query),
+            "css",
+        ));
+        for scope in ["header", "footer"] {
+            urls.push((
+                format!(
+                    "{}/js/js_ajaxpagestate.js?scope={}&{}",
+                    files_path, scope, query
+                ),
+                "js",
+            ));
+        }
+    }
+    urls
 }
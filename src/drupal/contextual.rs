@@ -0,0 +1,112 @@
+//! Functionality that's specific to Drupal's core Contextual Links module.
+//!
+//! Pages displayed to users with the `access contextual links` permission include an empty
+//! placeholder for each set of contextual links, which Drupal's JavaScript renders with a
+//! request to `/contextual/render`. These requests are made by every page an editor loads,
+//! and bypass the page cache.
+
+use goose::prelude::*;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::Validate;
+
+/// A contextual links placeholder on a page, returned by [`get_contextual_links`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextualLinks {
+    /// The id of the contextual links, from the `data-contextual-id` attribute.
+    pub id: String,
+    /// The token that protects the id, from the `data-contextual-token` attribute added
+    /// by Drupal 9.5 and later.
+    pub token: Option<String>,
+}
+
+/// Get the contextual links placeholders of a page, in order and without duplicates.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::contextual::get_contextual_links;
+///
+/// let html = r#"
+/// <div data-contextual-id="node:node=1:changed=1700000000&amp;langcode=en" data-contextual-token="Zk3q" data-drupal-ajax-container=""></div>
+/// <div data-contextual-id="block:block=olivero_content:langcode=en"></div>
+/// "#;
+///
+/// let links = get_contextual_links(html);
+/// assert_eq!(links.len(), 2);
+/// assert_eq!(links[0].id, "node:node=1:changed=1700000000&langcode=en");
+/// assert_eq!(links[0].token.as_deref(), Some("Zk3q"));
+/// assert_eq!(links[1].token, None);
+/// ```
+pub fn get_contextual_links(html: &str) -> Vec<ContextualLinks> {
+    let re = Regex::new(r#"<[^>]*\sdata-contextual-id="[^"]*"[^>]*>"#).unwrap();
+    let mut links: Vec<ContextualLinks> = Vec::new();
+    for tag in re.find_iter(html) {
+        let id = match super::get_attribute(tag.as_str(), "data-contextual-id") {
+            Some(id) => id,
+            None => continue,
+        };
+        if links.iter().any(|link| link.id == id) {
+            continue;
+        }
+        links.push(ContextualLinks {
+            id,
+            token: super::get_attribute(tag.as_str(), "data-contextual-token"),
+        });
+    }
+    links
+}
+
+/// Render the contextual links of a page the way Drupal's JavaScript does, posting the id
+/// and token of each placeholder to `/contextual/render`. Returns the rendered links keyed
+/// by id, or an empty object without making a request if the page has no placeholders.
+///
+/// The request is named `contextual: render`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::contextual::render_contextual_links;
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(load_front_page_as_editor);
+///
+/// async fn load_front_page_as_editor(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///     let _links = render_contextual_links(user, &html).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn render_contextual_links(
+    user: &mut GooseUser,
+    html: &str,
+) -> Result<Value, Box<TransactionError>> {
+    let links = get_contextual_links(html);
+    if links.is_empty() {
+        return Ok(Value::Object(Default::default()));
+    }
+
+    let mut fields = Vec::new();
+    for link in &links {
+        fields.push(("ids[]", link.id.as_str()));
+        if let Some(token) = &link.token {
+            fields.push(("tokens[]", token.as_str()));
+        }
+    }
+    let path = "contextual/render";
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Post, path)?
+        .form(&fields);
+    let goose_request = GooseRequest::builder()
+        .method(GooseMethod::Post)
+        .path(path)
+        .name("contextual: render")
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+
+    let validate = Validate::builder().status(200).build();
+    crate::validate_json(user, goose, &validate).await
+}
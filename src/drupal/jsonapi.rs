@@ -0,0 +1,420 @@
+//! Functionality that's specific to Drupal's JSON:API module.
+//!
+//! Decoupled Drupal websites serve most of their traffic as JSON:API documents rather than
+//! html pages. These helpers fetch resources and collections, following pagination links
+//! and including relationships, and validate that the responses are JSON:API documents.
+
+use goose::prelude::*;
+use serde_json::Value;
+
+use crate::Validate;
+
+/// The media type of JSON:API documents, used in the `Accept` header of each request.
+pub const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// Parameters that define which JSON:API resources to fetch and how to validate them. For
+/// complete documentation, refer to [`ResourceParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct ResourceParams<'a> {
+    // The resource type path, such as `node/article`.
+    resource: &'a str,
+    // The path prefix of JSON:API.
+    prefix: &'a str,
+    // Optionally limit how many resources are returned per page.
+    limit: Option<usize>,
+    // Relationships to include.
+    include: &'a [&'a str],
+    // How many pages of a collection to fetch.
+    max_pages: usize,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> ResourceParams<'a> {
+    /// Convenience function to bring [`ResourceParamsBuilder`] into scope.
+    pub fn builder() -> ResourceParamsBuilder<'a> {
+        ResourceParamsBuilder::new()
+    }
+
+    // The path of a collection, or of an individual resource, with the query string.
+    fn path(&self, id: Option<&str>) -> String {
+        let mut path = format!("{}/{}", self.prefix.trim_matches('/'), self.resource);
+        if let Some(id) = id {
+            path.push('/');
+            path.push_str(id);
+        }
+        let mut query = Vec::new();
+        if let (Some(limit), None) = (self.limit, id) {
+            query.push(format!("page[limit]={}", limit));
+        }
+        if !self.include.is_empty() {
+            query.push(format!("include={}", self.include.join(",")));
+        }
+        if !query.is_empty() {
+            path.push('?');
+            path.push_str(&query.join("&"));
+        }
+        path
+    }
+}
+
+/// Used to build a [`ResourceParams`] object, necessary to invoke the [`get_collection`]
+/// and [`get_resource`] functions.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::jsonapi::ResourceParams;
+///
+/// // Fetch the first three pages of ten articles, including their authors.
+/// let _params = ResourceParams::builder()
+///     .resource("node/article")
+///     .limit(10)
+///     .include(&["uid"])
+///     .max_pages(3)
+///     .build();
+/// ```
+pub struct ResourceParamsBuilder<'a> {
+    // The resource type path, such as `node/article`.
+    resource: &'a str,
+    // The path prefix of JSON:API.
+    prefix: &'a str,
+    // Optionally limit how many resources are returned per page.
+    limit: Option<usize>,
+    // Relationships to include.
+    include: &'a [&'a str],
+    // How many pages of a collection to fetch.
+    max_pages: usize,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> ResourceParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to articles.
+            resource: "node/article",
+            // Defaults to JSON:API's standard path prefix of "jsonapi".
+            prefix: "jsonapi",
+            // Defaults to the page size of the website.
+            limit: None,
+            // Defaults to including no relationships.
+            include: &[],
+            // Defaults to fetching only the first page of a collection.
+            max_pages: 1,
+            // Defaults to validating that the response is a JSON:API document.
+            validation: None,
+        }
+    }
+
+    /// Used with [`ResourceParams::builder`] to set the resource type path, the entity
+    /// type and bundle separated by a slash.
+    ///
+    /// Defaults to `node/article`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Fetch recipes.
+    /// let _params = ResourceParams::builder()
+    ///     .resource("node/recipe")
+    ///     .build();
+    /// ```
+    pub fn resource(mut self, resource: impl Into<&'a str>) -> Self {
+        self.resource = resource.into();
+        self
+    }
+
+    /// Used with [`ResourceParams::builder`] to set a custom JSON:API path prefix.
+    ///
+    /// Defaults to `jsonapi`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // JSON:API is served at a custom path.
+    /// let _params = ResourceParams::builder()
+    ///     .prefix("api")
+    ///     .build();
+    /// ```
+    pub fn prefix(mut self, prefix: impl Into<&'a str>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Used with [`ResourceParams::builder`] to limit how many resources are returned per
+    /// page of a collection, with `page[limit]`.
+    ///
+    /// Defaults to `None`, so the page size of the website is used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Fetch five articles per page.
+    /// let _params = ResourceParams::builder()
+    ///     .limit(5)
+    ///     .build();
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Used with [`ResourceParams::builder`] to include related resources in the response,
+    /// with `include`.
+    ///
+    /// Defaults to including no relationships.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Include the image and tags of articles.
+    /// let _params = ResourceParams::builder()
+    ///     .include(&["field_image", "field_tags"])
+    ///     .build();
+    /// ```
+    pub fn include(mut self, include: &'a [&'a str]) -> Self {
+        self.include = include;
+        self
+    }
+
+    /// Used with [`ResourceParams::builder`] to set how many pages of a collection
+    /// [`get_collection`] fetches, following the `next` link of each page.
+    ///
+    /// Defaults to `1`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Fetch up to five pages of articles.
+    /// let _params = ResourceParams::builder()
+    ///     .max_pages(5)
+    ///     .build();
+    /// ```
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages.max(1);
+        self
+    }
+
+    /// Used with [`ResourceParams::builder`] to set a custom validation of each response.
+    ///
+    /// Defaults to `None`, so it validates that the response has a `200` status code and a
+    /// JSON:API `Content-Type`, and that it's a JSON:API document with `data`.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](crate::Validate) object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Also validate that each article has a title.
+    /// let validate = Validate::builder()
+    ///     .status(200)
+    ///     .json_pointer("/data/0/attributes/title")
+    ///     .build();
+    /// let _params = ResourceParams::builder()
+    ///     .validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn validation(mut self, validation: &'a Validate) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// Build the [`ResourceParams`] object which is then passed to the [`get_collection`]
+    /// and [`get_resource`] functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::jsonapi::ResourceParams;
+    ///
+    /// // Fetch the first page of articles.
+    /// let _params = ResourceParams::builder().build();
+    /// ```
+    pub fn build(self) -> ResourceParams<'a> {
+        let Self {
+            resource,
+            prefix,
+            limit,
+            include,
+            max_pages,
+            validation,
+        } = self;
+        ResourceParams {
+            resource,
+            prefix,
+            limit,
+            include,
+            max_pages,
+            validation,
+        }
+    }
+}
+
+/// The resources fetched by [`get_collection`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Collection {
+    /// The primary data of each page.
+    pub data: Vec<Value>,
+    /// The related resources included in each page.
+    pub included: Vec<Value>,
+    /// How many pages were fetched.
+    pub pages: usize,
+}
+
+/// Whether a JSON value is a JSON:API document, with either top-level `data` or `errors`,
+/// but not both.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::jsonapi::valid_document;
+/// use serde_json::json;
+///
+/// assert!(valid_document(&json!({"jsonapi": {"version": "1.0"}, "data": []})));
+/// assert!(valid_document(&json!({"errors": [{"status": "404"}]})));
+/// assert!(!valid_document(&json!({"articles": []})));
+/// ```
+pub fn valid_document(json: &Value) -> bool {
+    let data = json
+        .get("data")
+        .is_some_and(|data| data.is_array() || data.is_object() || data.is_null());
+    let errors = json.get("errors").is_some_and(Value::is_array);
+    data != errors
+}
+
+// Fetch and validate a JSON:API document.
+async fn get_document(
+    user: &mut GooseUser,
+    params: &ResourceParams<'_>,
+    path: &str,
+) -> Result<Value, Box<TransactionError>> {
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Get, path)?
+        .header("accept", MEDIA_TYPE);
+    let name = format!("jsonapi: {}", params.resource);
+    let goose_request = GooseRequest::builder()
+        .set_request_builder(request_builder)
+        .path(path)
+        .name(name.as_str())
+        .build();
+    let goose = user.request(goose_request).await?;
+    let mut request = goose.request.clone();
+
+    // By default validate that the response is a JSON:API document with data.
+    let default_validation = Validate::builder()
+        .status(200)
+        .header_value("content-type", MEDIA_TYPE)
+        .json_pointer("/data")
+        .build();
+    let validate = params.validation.unwrap_or(&default_validation);
+    let json = crate::validate_json(user, goose, validate).await?;
+
+    if !json.is_null() && !valid_document(&json) {
+        user.set_failure(
+            &format!("{}: not a JSON:API document", path),
+            &mut request,
+            None,
+            Some(&json.to_string()),
+        )?;
+    }
+
+    Ok(json)
+}
+
+/// Fetch a collection of JSON:API resources, following the `next` link of each page up to
+/// [`ResourceParamsBuilder::max_pages`].
+///
+/// Each request is named `jsonapi: {resource}`, so all pages are aggregated in the Goose
+/// metrics.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::jsonapi::{get_collection, ResourceParams};
+///
+/// transaction!(list_articles);
+///
+/// async fn list_articles(user: &mut GooseUser) -> TransactionResult {
+///     // Fetch the first two pages of articles, with their images.
+///     let params = ResourceParams::builder()
+///         .resource("node/article")
+///         .limit(10)
+///         .include(&["field_image"])
+///         .max_pages(2)
+///         .build();
+///     let articles = get_collection(user, &params).await?;
+///
+///     for article in &articles.data {
+///         if let Some(title) = article.pointer("/attributes/title") {
+///             println!("{}", title);
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_collection(
+    user: &mut GooseUser,
+    params: &ResourceParams<'_>,
+) -> Result<Collection, Box<TransactionError>> {
+    let mut collection = Collection::default();
+    let mut path = params.path(None);
+    loop {
+        let json = get_document(user, params, &path).await?;
+        collection.pages += 1;
+        if let Some(data) = json.get("data").and_then(Value::as_array) {
+            collection.data.extend(data.iter().cloned());
+        }
+        if let Some(included) = json.get("included").and_then(Value::as_array) {
+            collection.included.extend(included.iter().cloned());
+        }
+
+        // Follow the link to the next page, if any.
+        match json.pointer("/links/next/href").and_then(Value::as_str) {
+            Some(next) if collection.pages < params.max_pages => path = next.to_string(),
+            _ => break,
+        }
+    }
+
+    Ok(collection)
+}
+
+/// Fetch an individual JSON:API resource by id, returning the entire document so
+/// included resources are available.
+///
+/// The request is named `jsonapi: {resource}`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::jsonapi::{get_resource, ResourceParams};
+///
+/// transaction!(load_article);
+///
+/// async fn load_article(user: &mut GooseUser) -> TransactionResult {
+///     let params = ResourceParams::builder()
+///         .resource("node/article")
+///         .include(&["uid"])
+///         .build();
+///     let article = get_resource(user, &params, "b8e2fa12-1a1e-4c5f-9f0e-6f1e5d0ab2b1").await?;
+///
+///     if let Some(author) = article.pointer("/included/0/attributes/display_name") {
+///         println!("{}", author);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_resource(
+    user: &mut GooseUser,
+    params: &ResourceParams<'_>,
+    id: &str,
+) -> Result<Value, Box<TransactionError>> {
+    let path = params.path(Some(id));
+    get_document(user, params, &path).await
+}
@@ -0,0 +1,247 @@
+//! Functionality that's specific to Drupal's Quick Edit module, a core module until
+//! Drupal 10 and a contributed module since.
+//!
+//! Editors using Quick Edit fetch the metadata of every editable field on each page they
+//! load, then load, submit and save field forms with AJAX requests. None of these requests
+//! are cached, and saving also invalidates the cached pages that display the entity.
+
+use goose::metrics::GooseRequestMetric;
+use goose::prelude::*;
+use regex::Regex;
+use serde_json::Value;
+
+use super::Form;
+use crate::Validate;
+
+/// Get the ids of the fields a page lets editors change with Quick Edit, such as
+/// `node/1/body/en/full`, in order and without duplicates.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::quickedit::get_quickedit_fields;
+///
+/// let html = r#"
+/// <article data-quickedit-entity-id="node/1">
+///   <h1><span data-quickedit-field-id="node/1/title/en/full">About</span></h1>
+///   <div data-quickedit-field-id="node/1/body/en/full"><p>Body</p></div>
+/// </article>
+/// "#;
+///
+/// assert_eq!(
+///     get_quickedit_fields(html),
+///     vec!["node/1/title/en/full", "node/1/body/en/full"]
+/// );
+/// ```
+pub fn get_quickedit_fields(html: &str) -> Vec<String> {
+    let re = Regex::new(r#"\sdata-quickedit-field-id="([^"]*)""#).unwrap();
+    let mut fields: Vec<String> = Vec::new();
+    for capture in re.captures_iter(html) {
+        let field = html_escape::decode_html_entities(&capture[1]).to_string();
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+// Post fields to a Quick Edit endpoint, returning the validated json response and the
+// request.
+async fn post(
+    user: &mut GooseUser,
+    path: &str,
+    name: &str,
+    fields: &[(String, String)],
+) -> Result<(Value, GooseRequestMetric), Box<TransactionError>> {
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Post, path)?
+        .form(fields);
+    let goose_request = GooseRequest::builder()
+        .method(GooseMethod::Post)
+        .path(path)
+        .name(name)
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let request = goose.request.clone();
+
+    let validate = Validate::builder().status(200).build();
+    let json = crate::validate_json(user, goose, &validate).await?;
+    Ok((json, request))
+}
+
+// Whether the AJAX commands include the named command.
+fn has_command(commands: &Value, command: &str) -> bool {
+    commands
+        .as_array()
+        .is_some_and(|commands| commands.iter().any(|c| c["command"] == command))
+}
+
+/// Fetch the Quick Edit metadata of fields, as its JavaScript does for the fields of each
+/// page, returning the metadata keyed by field id. Use [`get_quickedit_fields`] to get the
+/// fields of a page.
+///
+/// The request is named `quickedit: metadata`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::quickedit::{get_metadata, get_quickedit_fields};
+/// use goose_eggs::{validate_and_load_static_assets, Validate};
+///
+/// transaction!(load_node_as_editor);
+///
+/// async fn load_node_as_editor(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("node/1").await?;
+///     let html = validate_and_load_static_assets(user, goose, &Validate::none()).await?;
+///     let _metadata = get_metadata(user, &get_quickedit_fields(&html)).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_metadata(
+    user: &mut GooseUser,
+    fields: &[String],
+) -> Result<Value, Box<TransactionError>> {
+    let fields: Vec<(String, String)> = fields
+        .iter()
+        .map(|field| ("fields[]".to_string(), field.to_string()))
+        .collect();
+    let (metadata, _) = post(user, "quickedit/metadata", "quickedit: metadata", &fields).await?;
+    Ok(metadata)
+}
+
+/// Load the Quick Edit form of a field, as its JavaScript does when an editor starts
+/// editing the field. Returns `None` if the response has no form.
+///
+/// The request is named `quickedit: form`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::quickedit::get_field_form;
+///
+/// transaction!(edit_body);
+///
+/// async fn edit_body(user: &mut GooseUser) -> TransactionResult {
+///     if let Some(form) = get_field_form(user, "node/1/body/en/full").await? {
+///         println!("{:?}", form.value("body[0][value]"));
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_field_form(
+    user: &mut GooseUser,
+    field: &str,
+) -> Result<Option<Form>, Box<TransactionError>> {
+    let (form, _) = load_field_form(user, field).await?;
+    Ok(form)
+}
+
+// Load the Quick Edit form of a field, returning the form if any and the request.
+async fn load_field_form(
+    user: &mut GooseUser,
+    field: &str,
+) -> Result<(Option<Form>, GooseRequestMetric), Box<TransactionError>> {
+    let path = format!("quickedit/form/{}?_wrapper_format=drupal_ajax", field);
+    let fields = [
+        ("nocssjs".to_string(), "true".to_string()),
+        ("reset".to_string(), "true".to_string()),
+    ];
+    let (commands, request) = post(user, &path, "quickedit: form", &fields).await?;
+    let form = commands
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|c| c["command"] == "quickeditFieldForm")
+        .find_map(|c| Form::parse(c["data"].as_str()?, "quickedit-field-form"));
+    Ok((form, request))
+}
+
+/// Change a field with Quick Edit and save its entity, as its JavaScript does: the form of
+/// the field is loaded, submitted with the `values`, and then the entity is saved. Returns
+/// whether the entity was saved, setting a failure if it was not.
+///
+/// The requests are named `quickedit: form`, `quickedit: submit` and `quickedit: save`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::quickedit::save_field;
+///
+/// transaction!(change_title);
+///
+/// async fn change_title(user: &mut GooseUser) -> TransactionResult {
+///     let _saved = save_field(
+///         user,
+///         "node/1/title/en/full",
+///         &[("title[0][value]", "Changed with Quick Edit")],
+///     )
+///     .await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn save_field(
+    user: &mut GooseUser,
+    field: &str,
+    values: &[(&str, &str)],
+) -> Result<bool, Box<TransactionError>> {
+    let path = format!("quickedit/form/{}?_wrapper_format=drupal_ajax", field);
+    let mut form = match load_field_form(user, field).await? {
+        (Some(form), _) => form,
+        (None, mut request) => {
+            user.set_failure(
+                &format!("{}: no quickedit-field-form", path),
+                &mut request,
+                None,
+                None,
+            )?;
+            return Ok(false);
+        }
+    };
+
+    // Submit the changed field, which Quick Edit stores until the entity is saved.
+    for (name, value) in values {
+        form.set(name, value);
+    }
+    let mut fields = form.fields;
+    for (name, value) in [
+        ("nocssjs", "true"),
+        ("_triggering_element_name", "op"),
+        ("_triggering_element_value", "Save"),
+        ("_drupal_ajax", "1"),
+    ] {
+        fields.push((name.to_string(), value.to_string()));
+    }
+    let (commands, mut request) = post(user, &path, "quickedit: submit", &fields).await?;
+    if !has_command(&commands, "quickeditFieldFormSaved") {
+        user.set_failure(
+            &format!("{}: field not saved", path),
+            &mut request,
+            None,
+            Some(&commands.to_string()),
+        )?;
+        return Ok(false);
+    }
+
+    // Save the entity, identified by the first two parts of the field id.
+    let entity: Vec<&str> = field.splitn(3, '/').take(2).collect();
+    let path = format!(
+        "quickedit/entity/{}?_wrapper_format=drupal_ajax",
+        entity.join("/")
+    );
+    let fields = [("nocssjs".to_string(), "true".to_string())];
+    let (commands, mut request) = post(user, &path, "quickedit: save", &fields).await?;
+    if !has_command(&commands, "quickeditEntitySaved") {
+        user.set_failure(
+            &format!("{}: entity not saved", path),
+            &mut request,
+            None,
+            Some(&commands.to_string()),
+        )?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,389 @@
+//! Functionality that's specific to Drupal's core RESTful Web Services module.
+//!
+//! These helpers fetch, create and update entities with `?_format=json`, setting the
+//! `Content-Type` and `X-CSRF-Token` headers Drupal requires, and validate the responses.
+
+use goose::prelude::*;
+use serde_json::Value;
+
+use crate::Validate;
+
+/// Parameters that define which entities to request and how to validate the responses. For
+/// complete documentation, refer to [`EntityParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct EntityParams<'a> {
+    // The entity type, such as `node` or `user`.
+    entity_type: &'a str,
+    // The serialization format.
+    format: &'a str,
+    // Optionally set a custom path to create entities.
+    create_path: Option<&'a str>,
+    // Whether to send a CSRF token when creating and updating entities.
+    csrf_token: bool,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> EntityParams<'a> {
+    /// Convenience function to bring [`EntityParamsBuilder`] into scope.
+    pub fn builder() -> EntityParamsBuilder<'a> {
+        EntityParamsBuilder::new()
+    }
+
+    // The path of an individual entity, with the format.
+    fn entity_path(&self, id: &str) -> String {
+        format!("{}/{}?_format={}", self.entity_type, id, self.format)
+    }
+
+    // The path to create an entity, with the format.
+    fn create_path(&self) -> String {
+        let path = match (self.create_path, self.entity_type) {
+            (Some(path), _) => path.trim_start_matches('/').to_string(),
+            // Nodes are created at `/node` since Drupal 8.3.
+            (None, "node") => "node".to_string(),
+            (None, entity_type) => format!("entity/{}", entity_type),
+        };
+        format!("{}?_format={}", path, self.format)
+    }
+
+    // The content type of the serialization format.
+    fn content_type(&self) -> String {
+        match self.format {
+            "hal_json" => "application/hal+json".to_string(),
+            format => format!("application/{}", format),
+        }
+    }
+}
+
+/// Used to build an [`EntityParams`] object, necessary to invoke the [`get_entity`],
+/// [`post_entity`] and [`patch_entity`] functions.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::drupal::rest::EntityParams;
+///
+/// // Request users, authenticating with basic auth so no CSRF token is needed.
+/// let _params = EntityParams::builder()
+///     .entity_type("user")
+///     .csrf_token(false)
+///     .build();
+/// ```
+pub struct EntityParamsBuilder<'a> {
+    // The entity type, such as `node` or `user`.
+    entity_type: &'a str,
+    // The serialization format.
+    format: &'a str,
+    // Optionally set a custom path to create entities.
+    create_path: Option<&'a str>,
+    // Whether to send a CSRF token when creating and updating entities.
+    csrf_token: bool,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> EntityParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to nodes.
+            entity_type: "node",
+            // Defaults to the json format.
+            format: "json",
+            // Defaults to `/node` for nodes, and `/entity/{entity_type}` otherwise.
+            create_path: None,
+            // Defaults to sending a CSRF token, required with cookie authentication.
+            csrf_token: true,
+            // Defaults to validating the status code and content type.
+            validation: None,
+        }
+    }
+
+    /// Used with [`EntityParams::builder`] to set the entity type.
+    ///
+    /// Defaults to `node`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::rest::EntityParams;
+    ///
+    /// // Request taxonomy terms.
+    /// let _params = EntityParams::builder()
+    ///     .entity_type("taxonomy/term")
+    ///     .create_path("entity/taxonomy_term")
+    ///     .build();
+    /// ```
+    pub fn entity_type(mut self, entity_type: impl Into<&'a str>) -> Self {
+        self.entity_type = entity_type.into();
+        self
+    }
+
+    /// Used with [`EntityParams::builder`] to set the serialization format, passed with
+    /// `?_format=`. The `Content-Type` header is set accordingly.
+    ///
+    /// Defaults to `json`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::rest::EntityParams;
+    ///
+    /// // Request nodes serialized with HAL.
+    /// let _params = EntityParams::builder()
+    ///     .format("hal_json")
+    ///     .build();
+    /// ```
+    pub fn format(mut self, format: impl Into<&'a str>) -> Self {
+        self.format = format.into();
+        self
+    }
+
+    /// Used with [`EntityParams::builder`] to set the path [`post_entity`] creates
+    /// entities at.
+    ///
+    /// Defaults to `node` for nodes, and `entity/{entity_type}` for other entity types.
+    /// Refer to [`EntityParamsBuilder::entity_type`].
+    pub fn create_path(mut self, create_path: impl Into<&'a str>) -> Self {
+        self.create_path = Some(create_path.into());
+        self
+    }
+
+    /// Used with [`EntityParams::builder`] to configure whether [`post_entity`] and
+    /// [`patch_entity`] send the `X-CSRF-Token` header, from
+    /// [`get_session_token`](super::get_session_token).
+    ///
+    /// Defaults to `true`, as Drupal requires a CSRF token with cookie authentication.
+    /// Refer to the [`EntityParamsBuilder`] example.
+    pub fn csrf_token(mut self, csrf_token: bool) -> Self {
+        self.csrf_token = csrf_token;
+        self
+    }
+
+    /// Used with [`EntityParams::builder`] to set a custom validation of each response.
+    ///
+    /// Defaults to `None`, so it validates that the response has a `200` status code, or
+    /// `201` when creating an entity, and a `Content-Type` matching the format.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](crate::Validate) object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::drupal::rest::EntityParams;
+    ///
+    /// // Also validate that nodes have a title.
+    /// let validate = Validate::builder()
+    ///     .status(200)
+    ///     .json_pointer("/title/0/value")
+    ///     .build();
+    /// let _params = EntityParams::builder()
+    ///     .validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn validation(mut self, validation: &'a Validate) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// Build the [`EntityParams`] object which is then passed to the [`get_entity`],
+    /// [`post_entity`] and [`patch_entity`] functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::drupal::rest::EntityParams;
+    ///
+    /// // Request nodes in json.
+    /// let _params = EntityParams::builder().build();
+    /// ```
+    pub fn build(self) -> EntityParams<'a> {
+        let Self {
+            entity_type,
+            format,
+            create_path,
+            csrf_token,
+            validation,
+        } = self;
+        EntityParams {
+            entity_type,
+            format,
+            create_path,
+            csrf_token,
+            validation,
+        }
+    }
+}
+
+/// Fetch the CSRF token Drupal requires to create, update and delete entities with cookie
+/// authentication, from `/session/token`.
+///
+/// A new token is fetched with each call, use [`get_session_token`](super::get_session_token)
+/// to cache it per Goose user.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::rest::get_csrf_token;
+///
+/// transaction!(delete_node);
+///
+/// async fn delete_node(user: &mut GooseUser) -> TransactionResult {
+///     let token = get_csrf_token(user).await?;
+///     let request_builder = user
+///         .get_request_builder(&GooseMethod::Delete, "node/3?_format=json")?
+///         .header("x-csrf-token", token);
+///     let goose_request = GooseRequest::builder()
+///         .method(GooseMethod::Delete)
+///         .path("node/3?_format=json")
+///         .set_request_builder(request_builder)
+///         .expect_status_code(204)
+///         .build();
+///     let _goose = user.request(goose_request).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_csrf_token(user: &mut GooseUser) -> Result<String, Box<TransactionError>> {
+    let goose = user.get_named("session/token", "rest: csrf token").await?;
+    let mut request = goose.request.clone();
+    let token = match goose.response {
+        Ok(response) => response.text().await.unwrap_or_default(),
+        Err(_) => String::new(),
+    };
+    if token.is_empty() {
+        user.set_failure("session/token: no csrf token", &mut request, None, None)?;
+    }
+
+    Ok(token)
+}
+
+// Send a request with an optional json body, and validate the json response.
+async fn send(
+    user: &mut GooseUser,
+    params: &EntityParams<'_>,
+    method: GooseMethod,
+    path: &str,
+    body: Option<&Value>,
+) -> Result<Value, Box<TransactionError>> {
+    let mut request_builder = user.get_request_builder(&method, path)?;
+    if let Some(body) = body {
+        request_builder = request_builder
+            .header("content-type", params.content_type())
+            .body(body.to_string());
+        if params.csrf_token {
+            request_builder = super::add_session_token(user, request_builder).await?;
+        }
+    }
+    let status = match method {
+        GooseMethod::Post => 201,
+        _ => 200,
+    };
+    let name = format!("rest: {} {}", method, params.entity_type);
+    let goose_request = GooseRequest::builder()
+        .method(method)
+        .path(path)
+        .name(name.as_str())
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+
+    // By default validate the status code and content type.
+    let content_type = params.content_type();
+    let default_validation = Validate::builder()
+        .status(status)
+        .header_value("content-type", content_type.as_str())
+        .build();
+    let validate = params.validation.unwrap_or(&default_validation);
+    crate::validate_json(user, goose, validate).await
+}
+
+/// Fetch an entity by id, returning the serialized entity.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::rest::{get_entity, EntityParams};
+///
+/// transaction!(load_node);
+///
+/// async fn load_node(user: &mut GooseUser) -> TransactionResult {
+///     let params = EntityParams::builder().build();
+///     let node = get_entity(user, &params, "3").await?;
+///
+///     if let Some(title) = node.pointer("/title/0/value") {
+///         println!("{}", title);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_entity(
+    user: &mut GooseUser,
+    params: &EntityParams<'_>,
+    id: &str,
+) -> Result<Value, Box<TransactionError>> {
+    let path = params.entity_path(id);
+    send(user, params, GooseMethod::Get, &path, None).await
+}
+
+/// Create an entity, returning the serialized entity that was created.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::rest::{post_entity, EntityParams};
+/// use serde_json::json;
+///
+/// transaction!(create_article);
+///
+/// async fn create_article(user: &mut GooseUser) -> TransactionResult {
+///     let params = EntityParams::builder().build();
+///     let article = json!({
+///         "type": [{"target_id": "article"}],
+///         "title": [{"value": "Created with REST"}],
+///     });
+///     let node = post_entity(user, &params, &article).await?;
+///
+///     if let Some(nid) = node.pointer("/nid/0/value") {
+///         println!("created node {}", nid);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn post_entity(
+    user: &mut GooseUser,
+    params: &EntityParams<'_>,
+    entity: &Value,
+) -> Result<Value, Box<TransactionError>> {
+    let path = params.create_path();
+    send(user, params, GooseMethod::Post, &path, Some(entity)).await
+}
+
+/// Update an entity by id, returning the serialized entity that was updated. Only the
+/// fields included in `entity` are changed, and nodes must include their `type`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::drupal::rest::{patch_entity, EntityParams};
+/// use serde_json::json;
+///
+/// transaction!(update_article);
+///
+/// async fn update_article(user: &mut GooseUser) -> TransactionResult {
+///     let params = EntityParams::builder().build();
+///     let changes = json!({
+///         "type": [{"target_id": "article"}],
+///         "title": [{"value": "Updated with REST"}],
+///     });
+///     let _node = patch_entity(user, &params, "3", &changes).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn patch_entity(
+    user: &mut GooseUser,
+    params: &EntityParams<'_>,
+    id: &str,
+    entity: &Value,
+) -> Result<Value, Box<TransactionError>> {
+    let path = params.entity_path(id);
+    send(user, params, GooseMethod::Patch, &path, Some(entity)).await
+}
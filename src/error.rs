@@ -0,0 +1,394 @@
+//! Errors returned when validating a response.
+
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::fmt;
+
+/// A validation that failed, with context describing what was expected and what was found.
+///
+/// The [`Display`](fmt::Display) implementation provides the message used when reporting
+/// the failure to Goose.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{Validate, ValidationError};
+/// use reqwest::header::HeaderMap;
+/// use reqwest::StatusCode;
+///
+/// let validate = Validate::builder().status(200).text("foo").build();
+/// let errors = validate
+///     .check(false, StatusCode::OK, &HeaderMap::new(), b"bar")
+///     .unwrap_err();
+///
+/// match &errors[0] {
+///     ValidationError::MissingText { text, .. } => assert_eq!(text, "foo"),
+///     error => panic!("unexpected error: {}", error),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The request was expected to redirect, but did not.
+    NotRedirected,
+    /// The request redirected, but was not expected to.
+    UnexpectedRedirect,
+    /// The response status code is not the expected status code.
+    WrongStatus {
+        /// The expected status code.
+        expected: u16,
+        /// The status code of the response.
+        actual: StatusCode,
+    },
+    /// The response status code is a status code that was not expected.
+    UnexpectedStatus {
+        /// The status code of the response.
+        status: StatusCode,
+    },
+    /// An expected header is not included in the response.
+    HeaderMissing {
+        /// The name of the header.
+        header: String,
+    },
+    /// A header that was not expected is included in the response.
+    UnexpectedHeader {
+        /// The name of the header.
+        header: String,
+    },
+    /// A header does not contain the expected value.
+    HeaderValueMismatch {
+        /// The name of the header.
+        header: String,
+        /// The expected value.
+        value: String,
+    },
+    /// A header contains a value that was not expected.
+    UnexpectedHeaderValue {
+        /// The name of the header.
+        header: String,
+        /// The unexpected value.
+        value: String,
+    },
+    /// None of a set of alternative headers is included in the response.
+    HeadersMissing {
+        /// The names of the alternative headers.
+        headers: Vec<String>,
+    },
+    /// The `Cache-Control` max-age is missing or less than the expected minimum.
+    CacheMaxAgeTooLow {
+        /// The minimum expected max-age, in seconds.
+        expected: u64,
+        /// The max-age of the response, if any.
+        actual: Option<u64>,
+    },
+    /// The response is not served with the expected content encoding.
+    ContentEncodingMismatch {
+        /// The expected content encoding, or `compressed` for any compression.
+        expected: String,
+        /// The content encoding of the response.
+        served: String,
+    },
+    /// The response is served with a content encoding that was not expected.
+    UnexpectedContentEncoding {
+        /// The unexpected content encoding, or `compressed` for any compression.
+        encoding: String,
+        /// The content encoding of the response.
+        served: String,
+    },
+    /// An expected byte sequence is not in the response body.
+    BytesMissing {
+        /// The expected byte sequence.
+        bytes: Vec<u8>,
+    },
+    /// A byte sequence that was not expected is in the response body.
+    UnexpectedBytes {
+        /// The unexpected byte sequence.
+        bytes: Vec<u8>,
+    },
+    /// The response body does not start with the expected byte sequence.
+    MagicBytesMismatch {
+        /// The expected byte sequence.
+        expected: Vec<u8>,
+        /// The bytes the response body starts with.
+        actual: Vec<u8>,
+    },
+    /// The SHA-256 checksum of the response body does not match.
+    ChecksumMismatch {
+        /// The expected checksum.
+        expected: String,
+        /// The checksum of the response body.
+        actual: String,
+    },
+    /// The length of the response body does not match the `Content-Length` header.
+    ContentLengthMismatch {
+        /// The length declared in the `Content-Length` header.
+        expected: usize,
+        /// The length of the response body.
+        actual: usize,
+    },
+    /// The title does not contain the expected text.
+    TitleMismatch {
+        /// The expected title text.
+        expected: String,
+        /// The title of the page, if any.
+        actual: Option<String>,
+    },
+    /// The title contains text that was not expected.
+    UnexpectedTitle {
+        /// The unexpected title text.
+        title: String,
+    },
+    /// The canonical link does not match the expected url.
+    CanonicalMismatch {
+        /// The expected canonical url.
+        expected: String,
+        /// The canonical url of the page, if any.
+        actual: Option<String>,
+    },
+    /// An expected meta tag is not in the html header.
+    MetaMissing {
+        /// The name or property of the meta tag.
+        name: String,
+    },
+    /// No charset is declared in the response header or html.
+    CharsetMissing {
+        /// The expected charset.
+        expected: String,
+    },
+    /// A charset other than the expected charset is declared.
+    CharsetMismatch {
+        /// The expected charset.
+        expected: String,
+        /// The charset declared in the `Content-Type` header, if any.
+        header: Option<String>,
+        /// The charset declared in the html, if any.
+        html: Option<String>,
+    },
+    /// The html is truncated.
+    MalformedHtml,
+    /// The page is not similar enough to the golden snapshot.
+    SnapshotMismatch {
+        /// The minimum percentage of lines expected to match the snapshot.
+        expected: u8,
+        /// The percentage of lines that match the snapshot.
+        similarity: u8,
+    },
+    /// An expected form is not on the page.
+    FormMissing {
+        /// The `id` or `data-drupal-selector` of the form.
+        name: String,
+    },
+    /// An error marker is on the page.
+    ErrorMarker {
+        /// The error marker found on the page.
+        marker: String,
+    },
+    /// The site returned its maintenance mode page.
+    MaintenanceMode,
+    /// An expected text is not on the page.
+    MissingText {
+        /// The expected text, or its name if one was provided.
+        text: String,
+        /// The selector of the element the text was expected in, if any.
+        selector: Option<String>,
+    },
+    /// A text that was not expected is on the page.
+    UnexpectedText {
+        /// The unexpected text, or its name if one was provided.
+        text: String,
+        /// The selector of the element the text was found in, if any.
+        selector: Option<String>,
+    },
+    /// No element matches the selector.
+    ElementMissing {
+        /// The selector.
+        selector: String,
+    },
+    /// None of a group of texts is on the page.
+    AnyTextMissing {
+        /// The group of texts.
+        texts: Vec<String>,
+    },
+    /// A hop in a redirect chain did not redirect as expected.
+    RedirectMismatch {
+        /// The position of the hop in the redirect chain, starting at 1.
+        hop: usize,
+        /// The expected redirect status code.
+        expected_status: u16,
+        /// Text the `Location` header was expected to contain.
+        expected_location: String,
+        /// The status code of the response.
+        status: StatusCode,
+        /// The `Location` header of the response, if any.
+        location: Option<String>,
+    },
+    /// The response body is not valid JSON.
+    JsonInvalid {
+        /// The reason the body could not be parsed.
+        reason: String,
+    },
+    /// The JSON pointer does not resolve to a value.
+    JsonPointerMissing {
+        /// The JSON pointer.
+        pointer: String,
+    },
+    /// The JSON pointer resolves to a value other than the expected value.
+    JsonValueMismatch {
+        /// The JSON pointer.
+        pointer: String,
+        /// The expected value.
+        expected: Value,
+        /// The value the JSON pointer resolves to.
+        actual: Value,
+    },
+    /// The response body could not be read.
+    BodyUnreadable {
+        /// The reason the body could not be read.
+        reason: String,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NotRedirected => write!(f, "did not redirect"),
+            ValidationError::UnexpectedRedirect => write!(f, "redirected unexpectedly"),
+            ValidationError::WrongStatus { expected, actual } => {
+                write!(f, "response status != {}]: {}", expected, actual)
+            }
+            ValidationError::UnexpectedStatus { status } => {
+                write!(f, "response status == {}]: {}", status.as_u16(), status)
+            }
+            ValidationError::HeaderMissing { header } => {
+                write!(f, "header not included in response: {:?}", header)
+            }
+            ValidationError::UnexpectedHeader { header } => {
+                write!(f, "header included in response: {:?}", header)
+            }
+            ValidationError::HeaderValueMismatch { value, .. } => {
+                write!(f, "header does not contain expected value: {:?}", value)
+            }
+            ValidationError::UnexpectedHeaderValue { value, .. } => {
+                write!(f, "header contains unexpected value: {:?}", value)
+            }
+            ValidationError::HeadersMissing { headers } => match headers.as_slice() {
+                [first, second] => write!(
+                    f,
+                    "neither {:?} nor {:?} header included in response",
+                    first, second
+                ),
+                _ => write!(f, "none of the headers included in response: {:?}", headers),
+            },
+            ValidationError::CacheMaxAgeTooLow { expected, actual } => {
+                write!(
+                    f,
+                    "cache-control max-age {:?} less than {}",
+                    actual, expected
+                )
+            }
+            ValidationError::ContentEncodingMismatch { expected, served } => {
+                write!(f, "response not {}, content-encoding: {}", expected, served)
+            }
+            ValidationError::UnexpectedContentEncoding { encoding, served } => write!(
+                f,
+                "response unexpectedly {}, content-encoding: {}",
+                encoding, served
+            ),
+            ValidationError::BytesMissing { bytes } => {
+                write!(f, "bytes not found in body: {:?}", bytes)
+            }
+            ValidationError::UnexpectedBytes { bytes } => {
+                write!(f, "bytes found in body: {:?}", bytes)
+            }
+            ValidationError::MagicBytesMismatch { expected, actual } => {
+                write!(f, "body starts with {:?}, expected {:?}", actual, expected)
+            }
+            ValidationError::ChecksumMismatch { expected, actual } => {
+                write!(f, "sha256 checksum {} does not match: {}", actual, expected)
+            }
+            ValidationError::ContentLengthMismatch { expected, actual } => write!(
+                f,
+                "body length {} does not match content-length: {}",
+                actual, expected
+            ),
+            ValidationError::TitleMismatch { expected, .. } => {
+                write!(f, "title not found: {}", expected)
+            }
+            ValidationError::UnexpectedTitle { title } => write!(f, "title found: {}", title),
+            ValidationError::CanonicalMismatch { expected, actual } => write!(
+                f,
+                "canonical link {:?} does not match: {}",
+                actual, expected
+            ),
+            ValidationError::MetaMissing { name } => write!(f, "meta tag not found: {}", name),
+            ValidationError::CharsetMissing { expected } => {
+                write!(f, "charset not declared: {}", expected)
+            }
+            ValidationError::CharsetMismatch {
+                expected,
+                header,
+                html,
+            } => write!(
+                f,
+                "charset mismatch, expected {}, header declares {:?}, html declares {:?}",
+                expected, header, html
+            ),
+            ValidationError::MalformedHtml => write!(f, "html is truncated"),
+            ValidationError::SnapshotMismatch {
+                expected,
+                similarity,
+            } => write!(
+                f,
+                "page {}% similar to snapshot, expected {}%",
+                similarity, expected
+            ),
+            ValidationError::FormMissing { name } => write!(f, "form not found on page: {}", name),
+            ValidationError::ErrorMarker { marker } => {
+                write!(f, "error marker found on page: {}", marker)
+            }
+            ValidationError::MaintenanceMode => write!(f, "site in maintenance mode"),
+            ValidationError::MissingText { text, selector } => match selector {
+                Some(selector) => write!(f, "text not found in {}: {}", selector, text),
+                None => write!(f, "text not found on page: {}", text),
+            },
+            ValidationError::UnexpectedText { text, selector } => match selector {
+                Some(selector) => write!(f, "text found in {}: {}", selector, text),
+                None => write!(f, "text found on page: {}", text),
+            },
+            ValidationError::ElementMissing { selector } => {
+                write!(f, "element not found on page: {}", selector)
+            }
+            ValidationError::AnyTextMissing { texts } => {
+                write!(f, "none of the texts found on page: {:?}", texts)
+            }
+            ValidationError::RedirectMismatch {
+                hop,
+                expected_status,
+                expected_location,
+                status,
+                location,
+            } => write!(
+                f,
+                "redirect {} expected {} to {}, got {} to {:?}",
+                hop, expected_status, expected_location, status, location
+            ),
+            ValidationError::JsonInvalid { reason } => write!(f, "invalid json: {}", reason),
+            ValidationError::JsonPointerMissing { pointer } => {
+                write!(f, "json pointer not found: {}", pointer)
+            }
+            ValidationError::JsonValueMismatch {
+                pointer,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "json pointer {} is {}, expected {}",
+                pointer, actual, expected
+            ),
+            ValidationError::BodyUnreadable { reason } => {
+                write!(f, "failed to parse page: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
@@ -8,16 +8,83 @@
 //! ## Feature flags
 //! * `default`: use the native TLS implementation for `goose` and `reqwest`
 //! * `rustls-tls`: use the TLS implemenation provided by `rustls`
+//! * `html-parser`: parse html with [`scraper`](https://docs.rs/scraper), enabling
+//!   `validate_and_parse_page` which returns the parsed document alongside the body, and
+//!   extracting `src=` and css `href=` static assets with the parser instead of regular
+//!   expressions, also finding single-quoted and unquoted attributes
 
-use goose::goose::GooseResponse;
+use futures::future::join_all;
+use goose::goose::{GooseMethod, GooseResponse};
+use goose::logger::GooseLog;
+use goose::metrics::{GooseMetric, GooseRequestMetric};
 use goose::prelude::*;
 use http::Uri;
-use log::info;
+use log::{info, warn};
 use regex::Regex;
+use reqwest::cookie::Jar;
 use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::assets::{AssetLimit, MediaRequest};
+
+mod assets;
 pub mod drupal;
+mod error;
+mod metrics;
 pub mod text;
+pub mod wordpress;
+
+pub use crate::assets::{
+    forget_loaded_assets, reset_loaded_assets, LoadAssets, LoadAssetsBuilder, LoadedAsset,
+    LoadedAssets,
+};
+pub use crate::error::ValidationError;
+pub use crate::metrics::{
+    asset_metrics, reset_asset_metrics, reset_validation_metrics, validation_metrics, AssetMetrics,
+    ValidationMetrics, ValidationTiming,
+};
+
+/// Text that indicates a server-side error leaked into a page, validated by
+/// [`ValidateBuilder::no_error_markers`].
+pub const DEFAULT_ERROR_MARKERS: &[&str] = &[
+    "Fatal error: ",
+    "Warning: ",
+    "Notice: ",
+    "Deprecated: ",
+    "Stack trace:",
+    "EntityStorageException",
+];
+
+// Counts failed validations that were logged as warnings instead of failing the request.
+static VALIDATION_WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+// Counts failed responses written to disk, to give each file a unique name.
+static CAPTURED_FAILURES: AtomicUsize = AtomicUsize::new(0);
+
+// Identifies a Goose user in state kept across requests. Users are numbered from 0 in
+// every load test, so the hash of the load test keeps the state of a user from leaking
+// into a user with the same index in another load test.
+pub(crate) type UserKey = (u64, usize);
+
+// Returns the key of the Goose user's state kept across requests.
+pub(crate) fn user_key(user: &GooseUser) -> UserKey {
+    (user.load_test_hash, user.weighted_users_index)
+}
+
+// The cookie jar shared by the clients of each Goose user of each load test, once enabled
+// with `enable_cookie_jar`.
+static COOKIE_JARS: Mutex<BTreeMap<UserKey, Arc<Jar>>> = Mutex::new(BTreeMap::new());
 
 /// Validate that the status code is equal or not equal to a specified value.
 #[derive(Clone, Debug)]
@@ -34,7 +101,7 @@ struct ValidateTitle<'a> {
     // Whether to validate that the title contains or does not contain the specified value.
     exists: bool,
     // Title text to validate
-    title: &'a str,
+    title: Cow<'a, str>,
 }
 
 /// Validate that the specified text exists or does not exist on the page.
@@ -43,7 +110,18 @@ struct ValidateText<'a> {
     // Whether to validate that the page contains or does not contain the specified text.
     exists: bool,
     // Text to validate
-    text: &'a str,
+    text: Cow<'a, str>,
+    // Optional label used in place of the text when validation fails
+    name: Option<Cow<'a, str>>,
+    // Optional selector of the element the text must be found in
+    selector: Option<Cow<'a, str>>,
+}
+
+/// Validate that at least one of the specified texts exists on the page.
+#[derive(Clone, Debug)]
+struct ValidateAnyText<'a> {
+    // Texts to validate, at least one of which must be on the page
+    texts: Vec<Cow<'a, str>>,
 }
 
 /// Validate that the specified header exists or does not exist, optionally containing a specified value.
@@ -52,14 +130,118 @@ struct ValidateHeader<'a> {
     // Whether to validate that the page contains or does not contain the specified header.
     exists: bool,
     // Header to validate
-    header: &'a str,
+    header: Cow<'a, str>,
     // Header value to validate
-    value: &'a str,
+    value: Cow<'a, str>,
+}
+
+/// Validate that the specified byte sequence exists or does not exist in the response body.
+#[derive(Clone, Debug)]
+struct ValidateBytes<'a> {
+    // Whether to validate that the body contains or does not contain the byte sequence.
+    exists: bool,
+    // Byte sequence to validate
+    bytes: Cow<'a, [u8]>,
+}
+
+/// Validate that the response is or is not served with a specified content encoding.
+#[derive(Clone, Debug)]
+struct ValidateContentEncoding<'a> {
+    // Whether to validate that the response is or is not served with the content encoding.
+    exists: bool,
+    // Content encoding to validate, or any compression if empty
+    encoding: Cow<'a, str>,
+}
+
+/// Validate that the response body is similar to a golden snapshot.
+#[derive(Clone, Debug)]
+struct ValidateSnapshot<'a> {
+    // The expected response body
+    golden: Cow<'a, str>,
+    // Minimum percentage of lines that must match the snapshot
+    min_similarity: u8,
+}
+
+// Convert borrowed data into owned data, used to build a `Validate<'static>`.
+fn owned<B: ?Sized + ToOwned>(cow: Cow<'_, B>) -> Cow<'static, B> {
+    Cow::Owned(cow.into_owned())
+}
+
+/// Validate that a value exists at the specified JSON pointer, optionally equal to a specified value.
+#[derive(Clone, Debug)]
+struct ValidateJson<'a> {
+    // JSON pointer to validate
+    pointer: Cow<'a, str>,
+    // Optional value the JSON pointer must resolve to
+    value: Option<Value>,
+}
+
+/// Hooks invoked before and after a response is validated by [`validate_page`],
+/// [`validate_json`] or [`validate_and_load_static_assets`], making it possible to add
+/// logging or tracing, or to extract values from the response into per-user state.
+///
+/// Hooks are added with [`ValidateBuilder::hook`]. Both methods do nothing by default.
+///
+/// # Example
+/// ```rust
+/// use goose::metrics::GooseRequestMetric;
+/// use goose::prelude::*;
+/// use goose_eggs::{Validate, ValidationError, ValidationHook};
+/// use reqwest::header::HeaderMap;
+///
+/// struct LogFailures;
+///
+/// impl ValidationHook for LogFailures {
+///     fn after(
+///         &self,
+///         _user: &mut GooseUser,
+///         request: &GooseRequestMetric,
+///         _headers: &HeaderMap,
+///         body: &str,
+///         errors: &[ValidationError],
+///     ) {
+///         if !errors.is_empty() {
+///             eprintln!("{} failed with {} byte body: {:?}", request.raw.url, body.len(), errors);
+///         }
+///     }
+/// }
+///
+/// let _validate = Validate::builder()
+///     .title("Home page")
+///     .hook(LogFailures)
+///     .build();
+/// ```
+pub trait ValidationHook: Send + Sync {
+    /// Invoked when the response is received, before the body is downloaded and the
+    /// response is validated.
+    fn before(&self, _user: &mut GooseUser, _request: &GooseRequestMetric, _headers: &HeaderMap) {}
+
+    /// Invoked after the response is validated, with every validation that failed.
+    fn after(
+        &self,
+        _user: &mut GooseUser,
+        _request: &GooseRequestMetric,
+        _headers: &HeaderMap,
+        _body: &str,
+        _errors: &[ValidationError],
+    ) {
+    }
+}
+
+impl fmt::Debug for dyn ValidationHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ValidationHook")
+    }
 }
 
 /// Define one or more items to be validated in a web page response. For complete
 /// documentation, refer to [`ValidateBuilder`].
 ///
+/// Validations can borrow `&str` or own `String` values, so a [`Validate`] object can be
+/// built from runtime data such as formatted strings or configuration files. Use
+/// [`Validate::into_owned`] to get a `Validate<'static>` that can be stored in structs
+/// or statics.
+///
 /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
 #[derive(Clone, Debug)]
 pub struct Validate<'a> {
@@ -69,10 +251,74 @@ pub struct Validate<'a> {
     title: Option<ValidateTitle<'a>>,
     /// Optionally validate arbitrary texts in the response html.
     texts: Vec<ValidateText<'a>>,
+    /// Optionally validate that at least one of a group of texts is in the response html.
+    any_texts: Vec<ValidateAnyText<'a>>,
+    /// Optionally validate the canonical link in the response html header.
+    canonical: Option<Cow<'a, str>>,
+    /// Optionally validate meta tags in the response html header.
+    metas: Vec<Cow<'a, str>>,
     /// Optionally validate the response headers.
     headers: Vec<ValidateHeader<'a>>,
     /// Optionally validate whether or not the page redirects
     redirect: Option<bool>,
+    /// Optionally validate that the response html is not truncated or garbled.
+    parseable_html: bool,
+    /// Optionally validate the charset declared in the response header and html.
+    charset: Option<Cow<'a, str>>,
+    /// Optionally validate the response content encoding.
+    content_encodings: Vec<ValidateContentEncoding<'a>>,
+    /// Optionally validate that the response includes cache validators and Cache-Control.
+    cacheable: bool,
+    /// Optionally validate the minimum Cache-Control max-age.
+    cache_control_max_age: Option<u64>,
+    /// Optionally validate arbitrary byte sequences in the response body.
+    bytes: Vec<ValidateBytes<'a>>,
+    /// Optionally log failed validations as warnings instead of failing the request.
+    warn_only: bool,
+    /// Optionally report all failed validations together instead of only the first.
+    collect_all: bool,
+    /// Optionally validate that no error markers are in the response html.
+    error_markers: Vec<Cow<'a, str>>,
+    /// Optionally validate that common security headers are included in the response.
+    security_headers: bool,
+    /// Optionally validate that the response isn't the maintenance mode page.
+    not_maintenance: bool,
+    /// Optionally validate that forms are in the response html.
+    forms: Vec<Cow<'a, str>>,
+    /// Optionally validate values in a JSON response body.
+    json: Vec<ValidateJson<'a>>,
+    /// Optionally skip downloading the response body.
+    ignore_body: bool,
+    /// Optionally format failure messages with a custom template.
+    on_failure: Option<Cow<'a, str>>,
+    /// Optionally name the validation in validation metrics.
+    name: Option<Cow<'a, str>>,
+    /// Whether to also report the named validation in the Goose metrics.
+    goose_metric: bool,
+    /// Optionally re-issue the request when validation fails.
+    retries: usize,
+    /// How long to wait before re-issuing the request when validation fails.
+    backoff: Duration,
+    /// Optionally invoke hooks before and after validating the response.
+    hooks: Vec<Arc<dyn ValidationHook>>,
+    /// Optionally validate the byte sequence the response body starts with.
+    magic_bytes: Option<Cow<'a, [u8]>>,
+    /// Optionally validate the SHA-256 checksum of the response body.
+    sha256: Option<Cow<'a, str>>,
+    /// Optionally tolerate this many failures per Goose user before reporting them.
+    tolerate_failures: Option<usize>,
+    /// Optionally tolerate failures per Goose user while within this percentage.
+    tolerate_failure_rate: Option<f32>,
+    /// Optionally write failed responses to files in this directory.
+    capture_dir: Option<Cow<'a, str>>,
+    /// Optionally record how long each phase of validation takes.
+    timed: bool,
+    /// Optionally compare the response body against a golden snapshot.
+    snapshot: Option<ValidateSnapshot<'a>>,
+    /// Regular expressions matching regions ignored when comparing against the snapshot.
+    snapshot_ignores: Vec<Cow<'a, str>>,
+    /// Optionally configure how static assets are loaded.
+    assets: Option<LoadAssets>,
 }
 impl<'a> Validate<'a> {
     /// Convenience function to bring [`ValidateBuilder`] into scope.
@@ -95,6 +341,598 @@ impl<'a> Validate<'a> {
     pub fn none() -> Validate<'a> {
         Validate::builder().build()
     }
+
+    /// Merge two [`Validate`] objects into a new [`Validate`] object.
+    ///
+    /// Texts, headers, meta tags, content encodings and byte sequences from both objects
+    /// are validated. Other validations set on `self` take precedence over those set on
+    /// `other`, and options such as [`ValidateBuilder::warn_only`] are only taken from
+    /// `self`. See [`ValidateBuilder::extend`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let base = Validate::builder().status(200).not_text("Warning:").build();
+    /// let page = Validate::builder().title("Home page").build();
+    ///
+    /// let _validate = page.merge(&base);
+    /// ```
+    pub fn merge(&self, other: &Validate<'a>) -> Validate<'a> {
+        Validate::builder()
+            .options(self)
+            .extend(self)
+            .extend(other)
+            .build()
+    }
+
+    /// Convert into a [`Validate`] object that owns all of its data, so it no longer
+    /// borrows from the strings it was built from.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// fn home_page_validation(site_name: &str) -> Validate<'static> {
+    ///     let title = format!("Home | {}", site_name);
+    ///     Validate::builder()
+    ///         .title(title.as_str())
+    ///         .text(format!("Welcome to {}", site_name))
+    ///         .build()
+    ///         .into_owned()
+    /// }
+    ///
+    /// let _validate = home_page_validation("Umami");
+    /// ```
+    pub fn into_owned(self) -> Validate<'static> {
+        Validate {
+            status: self.status,
+            title: self.title.map(|t| ValidateTitle {
+                exists: t.exists,
+                title: owned(t.title),
+            }),
+            texts: self
+                .texts
+                .into_iter()
+                .map(|t| ValidateText {
+                    exists: t.exists,
+                    text: owned(t.text),
+                    name: t.name.map(owned),
+                    selector: t.selector.map(owned),
+                })
+                .collect(),
+            any_texts: self
+                .any_texts
+                .into_iter()
+                .map(|t| ValidateAnyText {
+                    texts: t.texts.into_iter().map(owned).collect(),
+                })
+                .collect(),
+            canonical: self.canonical.map(owned),
+            metas: self.metas.into_iter().map(owned).collect(),
+            headers: self
+                .headers
+                .into_iter()
+                .map(|h| ValidateHeader {
+                    exists: h.exists,
+                    header: owned(h.header),
+                    value: owned(h.value),
+                })
+                .collect(),
+            redirect: self.redirect,
+            parseable_html: self.parseable_html,
+            charset: self.charset.map(owned),
+            content_encodings: self
+                .content_encodings
+                .into_iter()
+                .map(|e| ValidateContentEncoding {
+                    exists: e.exists,
+                    encoding: owned(e.encoding),
+                })
+                .collect(),
+            cacheable: self.cacheable,
+            cache_control_max_age: self.cache_control_max_age,
+            bytes: self
+                .bytes
+                .into_iter()
+                .map(|b| ValidateBytes {
+                    exists: b.exists,
+                    bytes: owned(b.bytes),
+                })
+                .collect(),
+            warn_only: self.warn_only,
+            collect_all: self.collect_all,
+            error_markers: self.error_markers.into_iter().map(owned).collect(),
+            security_headers: self.security_headers,
+            not_maintenance: self.not_maintenance,
+            forms: self.forms.into_iter().map(owned).collect(),
+            json: self
+                .json
+                .into_iter()
+                .map(|j| ValidateJson {
+                    pointer: owned(j.pointer),
+                    value: j.value,
+                })
+                .collect(),
+            ignore_body: self.ignore_body,
+            on_failure: self.on_failure.map(owned),
+            name: self.name.map(owned),
+            goose_metric: self.goose_metric,
+            retries: self.retries,
+            backoff: self.backoff,
+            hooks: self.hooks,
+            magic_bytes: self.magic_bytes.map(owned),
+            sha256: self.sha256.map(owned),
+            tolerate_failures: self.tolerate_failures,
+            tolerate_failure_rate: self.tolerate_failure_rate,
+            capture_dir: self.capture_dir.map(owned),
+            timed: self.timed,
+            snapshot: self.snapshot.map(|s| ValidateSnapshot {
+                golden: owned(s.golden),
+                min_similarity: s.min_similarity,
+            }),
+            snapshot_ignores: self.snapshot_ignores.into_iter().map(owned).collect(),
+            assets: self.assets,
+        }
+    }
+
+    /// Validate a response, returning every validation that failed.
+    ///
+    /// This is useful when writing custom transactions that need to handle failed
+    /// validations programmatically instead of reporting them to Goose. Generally it's
+    /// preferred to invoke [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{Validate, ValidationError};
+    /// use reqwest::header::HeaderMap;
+    /// use reqwest::StatusCode;
+    ///
+    /// let validate = Validate::builder().status(200).title("Home").build();
+    /// let html = "<html><head><title>Home</title></head></html>";
+    /// assert!(validate.check(false, StatusCode::OK, &HeaderMap::new(), html.as_bytes()).is_ok());
+    ///
+    /// let errors = validate
+    ///     .check(false, StatusCode::NOT_FOUND, &HeaderMap::new(), html.as_bytes())
+    ///     .unwrap_err();
+    /// assert!(matches!(errors[0], ValidationError::WrongStatus { expected: 200, .. }));
+    /// ```
+    pub fn check(
+        &self,
+        redirected: bool,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = self.response_errors(redirected, status, headers);
+        errors.extend(self.bytes_errors(body));
+        let body = String::from_utf8_lossy(body);
+        errors.extend(self.body_errors(headers, &body));
+        if !self.json.is_empty() {
+            errors.extend(self.json_errors(&body).1);
+        }
+        self.maintenance_errors(&body, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Replace the failed validations with a single error if configured to detect the
+    // maintenance mode page and the response is that page, as every other validation is
+    // expected to fail.
+    fn maintenance_errors(&self, html: &str, errors: &mut Vec<ValidationError>) {
+        if self.not_maintenance && drupal::is_maintenance_page(html) {
+            *errors = vec![ValidationError::MaintenanceMode];
+        }
+    }
+
+    // Validate everything that can be validated without the response body, returning
+    // each validation that failed.
+    fn response_errors(
+        &self,
+        redirected: bool,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        // Validate whether or not the request redirected.
+        if let Some(redirect) = self.redirect {
+            if redirected != redirect {
+                if redirect {
+                    errors.push(ValidationError::NotRedirected);
+                // Unexpected redirect happened.
+                } else {
+                    errors.push(ValidationError::UnexpectedRedirect);
+                }
+            }
+        }
+
+        // Validate status code if defined.
+        if let Some(validate_status) = self.status.as_ref() {
+            // If equals is false, error if response.status == status
+            if !validate_status.equals && status == validate_status.status_code {
+                errors.push(ValidationError::UnexpectedStatus { status });
+            // If equals is true, error if response.status != status
+            } else if validate_status.equals && status != validate_status.status_code {
+                errors.push(ValidationError::WrongStatus {
+                    expected: validate_status.status_code,
+                    actual: status,
+                });
+            }
+        }
+
+        // Validate headers if defined.
+        for validate_header in &self.headers {
+            if !validate_header.exists {
+                if header_is_set(headers, &validate_header.header) {
+                    errors.push(ValidationError::UnexpectedHeader {
+                        header: validate_header.header.to_string(),
+                    });
+                } else if !validate_header.value.is_empty()
+                    && valid_header_value(
+                        headers,
+                        (&validate_header.header, &validate_header.value),
+                    )
+                {
+                    errors.push(ValidationError::UnexpectedHeaderValue {
+                        header: validate_header.header.to_string(),
+                        value: validate_header.value.to_string(),
+                    });
+                }
+            } else if !header_is_set(headers, &validate_header.header) {
+                errors.push(ValidationError::HeaderMissing {
+                    header: validate_header.header.to_string(),
+                });
+            } else if !validate_header.value.is_empty()
+                && !valid_header_value(headers, (&validate_header.header, &validate_header.value))
+            {
+                errors.push(ValidationError::HeaderValueMismatch {
+                    header: validate_header.header.to_string(),
+                    value: validate_header.value.to_string(),
+                });
+            }
+        }
+
+        // Validate security headers if enabled.
+        if self.security_headers {
+            if !header_is_set(headers, "strict-transport-security") {
+                errors.push(ValidationError::HeaderMissing {
+                    header: "strict-transport-security".to_string(),
+                });
+            }
+            if !valid_header_value(headers, ("x-content-type-options", "nosniff")) {
+                errors.push(ValidationError::HeaderValueMismatch {
+                    header: "x-content-type-options".to_string(),
+                    value: "nosniff".to_string(),
+                });
+            }
+            if !header_is_set(headers, "x-frame-options")
+                && !header_is_set(headers, "content-security-policy")
+            {
+                errors.push(ValidationError::HeadersMissing {
+                    headers: vec![
+                        "x-frame-options".to_string(),
+                        "content-security-policy".to_string(),
+                    ],
+                });
+            }
+        }
+
+        // Validate cache headers if enabled.
+        if self.cacheable {
+            if !header_is_set(headers, "cache-control") {
+                errors.push(ValidationError::HeaderMissing {
+                    header: "cache-control".to_string(),
+                });
+            } else if !header_is_set(headers, "etag") && !header_is_set(headers, "last-modified") {
+                errors.push(ValidationError::HeadersMissing {
+                    headers: vec!["etag".to_string(), "last-modified".to_string()],
+                });
+            }
+        }
+
+        // Validate Cache-Control max-age if defined.
+        if let Some(seconds) = self.cache_control_max_age {
+            let max_age = get_cache_control_max_age(headers);
+            if !matches!(max_age, Some(max_age) if max_age >= seconds) {
+                errors.push(ValidationError::CacheMaxAgeTooLow {
+                    expected: seconds,
+                    actual: max_age,
+                });
+            }
+        }
+
+        // Validate content encoding if defined.
+        for validate_content_encoding in &self.content_encodings {
+            let encoding = if validate_content_encoding.encoding.is_empty() {
+                "compressed"
+            } else {
+                &validate_content_encoding.encoding
+            };
+            let served = headers
+                .get("content-encoding")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("none");
+            let valid = valid_content_encoding(headers, &validate_content_encoding.encoding);
+            if validate_content_encoding.exists && !valid {
+                errors.push(ValidationError::ContentEncodingMismatch {
+                    expected: encoding.to_string(),
+                    served: served.to_string(),
+                });
+            } else if !validate_content_encoding.exists && valid {
+                errors.push(ValidationError::UnexpectedContentEncoding {
+                    encoding: encoding.to_string(),
+                    served: served.to_string(),
+                });
+            }
+        }
+
+        errors
+    }
+
+    // Parse the response body as JSON and validate JSON pointers, returning the parsed
+    // body and each validation that failed.
+    fn json_errors(&self, body: &str) -> (Value, Vec<ValidationError>) {
+        let json: Value = match serde_json::from_str(body) {
+            Ok(json) => json,
+            Err(e) => {
+                return (
+                    Value::Null,
+                    vec![ValidationError::JsonInvalid {
+                        reason: e.to_string(),
+                    }],
+                )
+            }
+        };
+
+        let mut errors = Vec::new();
+        for validate_json in &self.json {
+            let actual = json.pointer(&validate_json.pointer);
+            match (&validate_json.value, actual) {
+                (_, None) => errors.push(ValidationError::JsonPointerMissing {
+                    pointer: validate_json.pointer.to_string(),
+                }),
+                (Some(expected), Some(actual)) if expected != actual => {
+                    errors.push(ValidationError::JsonValueMismatch {
+                        pointer: validate_json.pointer.to_string(),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    })
+                }
+                _ => (),
+            }
+        }
+        (json, errors)
+    }
+
+    // Validate byte sequences in the raw response body, returning each validation that
+    // failed.
+    fn bytes_errors(&self, body: &[u8]) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        for validate_bytes in &self.bytes {
+            if validate_bytes.exists && !valid_bytes(body, &validate_bytes.bytes) {
+                errors.push(ValidationError::BytesMissing {
+                    bytes: validate_bytes.bytes.to_vec(),
+                });
+            } else if !validate_bytes.exists && valid_bytes(body, &validate_bytes.bytes) {
+                errors.push(ValidationError::UnexpectedBytes {
+                    bytes: validate_bytes.bytes.to_vec(),
+                });
+            }
+        }
+
+        // Validate the magic bytes the body starts with if defined.
+        if let Some(magic_bytes) = self.magic_bytes.as_deref() {
+            if !body.starts_with(magic_bytes) {
+                errors.push(ValidationError::MagicBytesMismatch {
+                    expected: magic_bytes.to_vec(),
+                    actual: body[..magic_bytes.len().min(body.len())].to_vec(),
+                });
+            }
+        }
+
+        // Validate the checksum of the body if defined.
+        if let Some(sha256) = self.sha256.as_deref() {
+            let checksum = Sha256::digest(body)
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>();
+            if !checksum.eq_ignore_ascii_case(sha256) {
+                errors.push(ValidationError::ChecksumMismatch {
+                    expected: sha256.to_string(),
+                    actual: checksum,
+                });
+            }
+        }
+
+        errors
+    }
+
+    // Returns true if validations require the raw response body.
+    fn validates_bytes(&self) -> bool {
+        !self.bytes.is_empty() || self.magic_bytes.is_some() || self.sha256.is_some()
+    }
+
+    // Record how long a phase of validation took, if configured to time validations.
+    fn record_timing(&self, request_name: &str, phase: &str, elapsed: Duration) {
+        if self.timed {
+            let name = self.name.as_deref().unwrap_or(request_name);
+            metrics::record_timing(name, phase, elapsed);
+        }
+    }
+
+    // Tally the outcome for the Goose user, returning true if failures are within the
+    // configured thresholds and should only be logged as warnings.
+    fn tolerates(&self, user: &GooseUser, name: &str, errors: &[ValidationError]) -> bool {
+        if self.tolerate_failures.is_none() && self.tolerate_failure_rate.is_none() {
+            return false;
+        }
+        let (checked, failed) = metrics::record_user(user_key(user), name, !errors.is_empty());
+        let within_count = matches!(self.tolerate_failures, Some(count) if failed <= count);
+        let rate = failed as f32 / checked as f32 * 100.0;
+        let within_rate = matches!(self.tolerate_failure_rate, Some(percent) if rate <= percent);
+        !errors.is_empty() && (within_count || within_rate)
+    }
+
+    // Validate the response body, returning each validation that failed.
+    fn body_errors(&self, headers: &HeaderMap, html: &str) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        // Validate title if defined.
+        if let Some(validate_title) = self.title.as_ref() {
+            // Be sure the title doesn't contain the specified text.
+            if !validate_title.exists && valid_title(html, &validate_title.title) {
+                errors.push(ValidationError::UnexpectedTitle {
+                    title: validate_title.title.to_string(),
+                });
+            // Be sure the title contains the specified text.
+            } else if validate_title.exists && !valid_title(html, &validate_title.title) {
+                errors.push(ValidationError::TitleMismatch {
+                    expected: validate_title.title.to_string(),
+                    actual: get_title(html),
+                });
+            }
+        }
+
+        // Validate the page is similar to the golden snapshot if defined.
+        if let Some(snapshot) = self.snapshot.as_ref() {
+            let similarity = snapshot_similarity(&snapshot.golden, html, &self.snapshot_ignores);
+            if similarity < snapshot.min_similarity {
+                errors.push(ValidationError::SnapshotMismatch {
+                    expected: snapshot.min_similarity,
+                    similarity,
+                });
+            }
+        }
+
+        // Validate canonical link if defined.
+        if let Some(canonical) = self.canonical.as_deref() {
+            let html_header = get_html_header(html).unwrap_or_default();
+            let html_canonical = get_canonical(&html_header);
+            if html_canonical.as_deref() != Some(canonical) {
+                errors.push(ValidationError::CanonicalMismatch {
+                    expected: canonical.to_string(),
+                    actual: html_canonical,
+                });
+            }
+        }
+
+        // Validate meta tags if defined.
+        if !self.metas.is_empty() {
+            let html_header = get_html_header(html).unwrap_or_default();
+            for meta in &self.metas {
+                if get_meta(&html_header, meta).unwrap_or_default().is_empty() {
+                    errors.push(ValidationError::MetaMissing {
+                        name: meta.to_string(),
+                    });
+                }
+            }
+        }
+
+        // Validate charset if defined.
+        if let Some(charset) = self.charset.as_deref() {
+            let header_charset = get_header_charset(headers);
+            let html_header = get_html_header(html).unwrap_or_default();
+            let html_charset = get_charset(&html_header);
+            if header_charset.is_none() && html_charset.is_none() {
+                errors.push(ValidationError::CharsetMissing {
+                    expected: charset.to_string(),
+                });
+            } else if [&header_charset, &html_charset]
+                .iter()
+                .any(|declared| matches!(declared, Some(c) if !c.eq_ignore_ascii_case(charset)))
+            {
+                errors.push(ValidationError::CharsetMismatch {
+                    expected: charset.to_string(),
+                    header: header_charset,
+                    html: html_charset,
+                });
+            }
+        }
+
+        // Validate that the html is well-formed if enabled.
+        if self.parseable_html && !valid_html(html) {
+            errors.push(ValidationError::MalformedHtml);
+        }
+
+        // Validate that forms are in the body if defined.
+        for form in &self.forms {
+            if !valid_form(html, form) {
+                errors.push(ValidationError::FormMissing {
+                    name: form.to_string(),
+                });
+            }
+        }
+
+        // Validate that no error markers are in the body if defined.
+        for error_marker in &self.error_markers {
+            if valid_text(html, error_marker) {
+                errors.push(ValidationError::ErrorMarker {
+                    marker: error_marker.to_string(),
+                });
+            }
+        }
+
+        // Validate texts in body if defined.
+        for validate_text in &self.texts {
+            // Refer to named texts by their name in failure messages.
+            let label = validate_text.name.as_deref().unwrap_or(&validate_text.text);
+            // Optionally only search for the text within the selected element.
+            if let Some(selector) = validate_text.selector.as_deref() {
+                match get_element(html, selector) {
+                    Some(element) => {
+                        if !validate_text.exists && valid_text(&element, &validate_text.text) {
+                            errors.push(ValidationError::UnexpectedText {
+                                text: label.to_string(),
+                                selector: Some(selector.to_string()),
+                            });
+                        } else if validate_text.exists && !valid_text(&element, &validate_text.text)
+                        {
+                            errors.push(ValidationError::MissingText {
+                                text: label.to_string(),
+                                selector: Some(selector.to_string()),
+                            });
+                        }
+                    }
+                    None => errors.push(ValidationError::ElementMissing {
+                        selector: selector.to_string(),
+                    }),
+                }
+            } else if !validate_text.exists && valid_text(html, &validate_text.text) {
+                errors.push(ValidationError::UnexpectedText {
+                    text: label.to_string(),
+                    selector: None,
+                });
+            } else if validate_text.exists && !valid_text(html, &validate_text.text) {
+                errors.push(ValidationError::MissingText {
+                    text: label.to_string(),
+                    selector: None,
+                });
+            }
+        }
+
+        // Validate that at least one text in each group is in the body.
+        for validate_any_text in &self.any_texts {
+            if !validate_any_text
+                .texts
+                .iter()
+                .any(|text| valid_text(html, text))
+            {
+                errors.push(ValidationError::AnyTextMissing {
+                    texts: validate_any_text
+                        .texts
+                        .iter()
+                        .map(|text| text.to_string())
+                        .collect(),
+                });
+            }
+        }
+
+        errors
+    }
 }
 
 /// Used to build a [`Validate`] object, necessary to invoke the
@@ -140,10 +978,74 @@ pub struct ValidateBuilder<'a> {
     title: Option<ValidateTitle<'a>>,
     /// Optionally validate arbitrary texts in the response html.
     texts: Vec<ValidateText<'a>>,
+    /// Optionally validate that at least one of a group of texts is in the response html.
+    any_texts: Vec<ValidateAnyText<'a>>,
+    /// Optionally validate the canonical link in the response html header.
+    canonical: Option<Cow<'a, str>>,
+    /// Optionally validate meta tags in the response html header.
+    metas: Vec<Cow<'a, str>>,
     /// Optionally validate the response headers.
     headers: Vec<ValidateHeader<'a>>,
     /// Optionally validate whether or not the page redirects
     redirect: Option<bool>,
+    /// Optionally validate that the response html is not truncated or garbled.
+    parseable_html: bool,
+    /// Optionally validate the charset declared in the response header and html.
+    charset: Option<Cow<'a, str>>,
+    /// Optionally validate the response content encoding.
+    content_encodings: Vec<ValidateContentEncoding<'a>>,
+    /// Optionally validate that the response includes cache validators and Cache-Control.
+    cacheable: bool,
+    /// Optionally validate the minimum Cache-Control max-age.
+    cache_control_max_age: Option<u64>,
+    /// Optionally validate arbitrary byte sequences in the response body.
+    bytes: Vec<ValidateBytes<'a>>,
+    /// Optionally log failed validations as warnings instead of failing the request.
+    warn_only: bool,
+    /// Optionally report all failed validations together instead of only the first.
+    collect_all: bool,
+    /// Optionally validate that no error markers are in the response html.
+    error_markers: Vec<Cow<'a, str>>,
+    /// Optionally validate that common security headers are included in the response.
+    security_headers: bool,
+    /// Optionally validate that the response isn't the maintenance mode page.
+    not_maintenance: bool,
+    /// Optionally validate that forms are in the response html.
+    forms: Vec<Cow<'a, str>>,
+    /// Optionally validate values in a JSON response body.
+    json: Vec<ValidateJson<'a>>,
+    /// Optionally skip downloading the response body.
+    ignore_body: bool,
+    /// Optionally format failure messages with a custom template.
+    on_failure: Option<Cow<'a, str>>,
+    /// Optionally name the validation in validation metrics.
+    name: Option<Cow<'a, str>>,
+    /// Whether to also report the named validation in the Goose metrics.
+    goose_metric: bool,
+    /// Optionally re-issue the request when validation fails.
+    retries: usize,
+    /// How long to wait before re-issuing the request when validation fails.
+    backoff: Duration,
+    /// Optionally invoke hooks before and after validating the response.
+    hooks: Vec<Arc<dyn ValidationHook>>,
+    /// Optionally validate the byte sequence the response body starts with.
+    magic_bytes: Option<Cow<'a, [u8]>>,
+    /// Optionally validate the SHA-256 checksum of the response body.
+    sha256: Option<Cow<'a, str>>,
+    /// Optionally tolerate this many failures per Goose user before reporting them.
+    tolerate_failures: Option<usize>,
+    /// Optionally tolerate failures per Goose user while within this percentage.
+    tolerate_failure_rate: Option<f32>,
+    /// Optionally write failed responses to files in this directory.
+    capture_dir: Option<Cow<'a, str>>,
+    /// Optionally record how long each phase of validation takes.
+    timed: bool,
+    /// Optionally compare the response body against a golden snapshot.
+    snapshot: Option<ValidateSnapshot<'a>>,
+    /// Regular expressions matching regions ignored when comparing against the snapshot.
+    snapshot_ignores: Vec<Cow<'a, str>>,
+    /// Optionally configure how static assets are loaded.
+    assets: Option<LoadAssets>,
 }
 impl<'a> ValidateBuilder<'a> {
     // Internally used when building to set defaults.
@@ -152,8 +1054,40 @@ impl<'a> ValidateBuilder<'a> {
             status: None,
             title: None,
             texts: vec![],
+            any_texts: vec![],
+            canonical: None,
+            metas: vec![],
             headers: vec![],
             redirect: None,
+            parseable_html: false,
+            charset: None,
+            content_encodings: vec![],
+            cacheable: false,
+            cache_control_max_age: None,
+            bytes: vec![],
+            warn_only: false,
+            collect_all: false,
+            error_markers: vec![],
+            security_headers: false,
+            not_maintenance: false,
+            forms: vec![],
+            json: vec![],
+            ignore_body: false,
+            on_failure: None,
+            name: None,
+            goose_metric: false,
+            retries: 0,
+            backoff: Duration::ZERO,
+            hooks: vec![],
+            magic_bytes: None,
+            sha256: None,
+            tolerate_failures: None,
+            tolerate_failure_rate: None,
+            capture_dir: None,
+            timed: false,
+            snapshot: None,
+            snapshot_ignores: vec![],
+            assets: None,
         }
     }
 
@@ -210,7 +1144,7 @@ impl<'a> ValidateBuilder<'a> {
     ///     .title("Home page")
     ///     .build();
     /// ```
-    pub fn title(mut self, title: impl Into<&'a str>) -> Self {
+    pub fn title(mut self, title: impl Into<Cow<'a, str>>) -> Self {
         self.title = Some(ValidateTitle {
             exists: true,
             title: title.into(),
@@ -231,7 +1165,7 @@ impl<'a> ValidateBuilder<'a> {
     ///     .not_title("Home page")
     ///     .build();
     /// ```
-    pub fn not_title(mut self, title: impl Into<&'a str>) -> Self {
+    pub fn not_title(mut self, title: impl Into<Cow<'a, str>>) -> Self {
         self.title = Some(ValidateTitle {
             exists: false,
             title: title.into(),
@@ -265,8 +1199,13 @@ impl<'a> ValidateBuilder<'a> {
     ///     .text("another")
     ///     .build();
     /// ```
-    pub fn text(mut self, text: &'a str) -> Self {
-        self.texts.push(ValidateText { exists: true, text });
+    pub fn text(mut self, text: impl Into<Cow<'a, str>>) -> Self {
+        self.texts.push(ValidateText {
+            exists: true,
+            text: text.into(),
+            name: None,
+            selector: None,
+        });
         self
     }
 
@@ -298,16 +1237,21 @@ impl<'a> ValidateBuilder<'a> {
     ///     .text("this is on the page")
     ///     .build();
     /// ```
-    pub fn not_text(mut self, text: &'a str) -> Self {
+    pub fn not_text(mut self, text: impl Into<Cow<'a, str>>) -> Self {
         self.texts.push(ValidateText {
             exists: false,
-            text,
+            text: text.into(),
+            name: None,
+            selector: None,
         });
         self
     }
 
     /// Create a [`Validate`] object to validate that the response page contains the specified
-    /// texts.
+    /// text, using the specified name in place of the text if validation fails.
+    ///
+    /// This makes failure messages in the error summary say what semantically broke,
+    /// rather than including a long html fragment.
     ///
     /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
@@ -315,36 +1259,60 @@ impl<'a> ValidateBuilder<'a> {
     /// ```rust
     /// use goose_eggs::Validate;
     ///
+    /// // If validation fails the error is "text not found on page: cart badge".
     /// let _validate = Validate::builder()
-    ///     .texts(vec!["example", "another"])
+    ///     .text_named("cart badge", r#"<span class="cart-count">"#)
     ///     .build();
     /// ```
+    pub fn text_named(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        text: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.texts.push(ValidateText {
+            exists: true,
+            text: text.into(),
+            name: Some(name.into()),
+            selector: None,
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response page does not contain the
+    /// specified text, using the specified name in place of the text if validation fails.
     ///
-    /// It's possible to call this function multiple times (and together with `text()`, `not_text()`
-    /// and `not_texts()`) to validate that multiple texts do or do not appear on the page.
-    /// Alternatively you can call [`ValidateBuilder::texts`].
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
     /// # Example
     /// ```rust
     /// use goose_eggs::Validate;
     ///
+    /// // If validation fails the error is "text found on page: error message".
     /// let _validate = Validate::builder()
-    ///     .texts(vec!["example", "another"])
-    ///     .not_texts(vec!["foo", "bar"])
-    ///     .texts(vec!["also this", "and this"])
+    ///     .not_text_named("error message", r#"<div class="messages messages--error">"#)
     ///     .build();
     /// ```
-    ///
-    /// Alternatively you can call [`ValidateBuilder::text`].
-    pub fn texts(mut self, texts: Vec<&'a str>) -> Self {
-        for text in texts {
-            self = self.text(text);
-        }
+    pub fn not_text_named(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        text: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.texts.push(ValidateText {
+            exists: false,
+            text: text.into(),
+            name: Some(name.into()),
+            selector: None,
+        });
         self
     }
 
-    /// Create a [`Validate`] object to validate that the response page does not contains the
-    /// specified texts.
+    /// Create a [`Validate`] object to validate that the specified text is found within the
+    /// first element on the page matching the selector.
+    ///
+    /// The selector is an optional tag name, followed by an optional `#id` and any number
+    /// of `.class` names, for example `div#content`. Constraining the search avoids false
+    /// positives when the text also appears in menus or in JSON embedded in the page. See
+    /// [`get_element`].
     ///
     /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
@@ -353,38 +1321,53 @@ impl<'a> ValidateBuilder<'a> {
     /// use goose_eggs::Validate;
     ///
     /// let _validate = Validate::builder()
-    ///     .not_texts(vec!["example", "another"])
+    ///     .text_in("div#content", "Thanks for your order")
     ///     .build();
     /// ```
+    pub fn text_in(
+        mut self,
+        selector: impl Into<Cow<'a, str>>,
+        text: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.texts.push(ValidateText {
+            exists: true,
+            text: text.into(),
+            name: None,
+            selector: Some(selector.into()),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the specified text is not found within
+    /// the first element on the page matching the selector. Validation fails if no element
+    /// matches the selector.
     ///
-    /// It's possible to call this function multiple times (and together with `text()`, `not_text()`
-    /// and `texts()`) to validate that multiple texts do or do not appear on the page.
-    /// Alternatively you can call [`ValidateBuilder::texts`].
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
     /// # Example
     /// ```rust
     /// use goose_eggs::Validate;
     ///
     /// let _validate = Validate::builder()
-    ///     .not_texts(vec!["example", "another"])
-    ///     .texts(vec!["does include foo", "and bar"])
-    ///     .not_texts(vec!["but not this", "or this"])
+    ///     .not_text_in("div#content", "Your cart is empty")
     ///     .build();
     /// ```
-    ///
-    /// Alternatively you can call [`ValidateBuilder::text`].
-    pub fn not_texts(mut self, texts: Vec<&'a str>) -> Self {
-        for text in texts {
-            self = self.not_text(text);
-        }
+    pub fn not_text_in(
+        mut self,
+        selector: impl Into<Cow<'a, str>>,
+        text: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.texts.push(ValidateText {
+            exists: false,
+            text: text.into(),
+            name: None,
+            selector: Some(selector.into()),
+        });
         self
     }
 
-    /// Create a [`Validate`] object to validate that the response includes the specified
-    /// header.
-    ///
-    /// To validate that a header contains a specific value (instead of just validating
-    /// that it exists), use [`ValidateBuilder::header_value`].
+    /// Create a [`Validate`] object to validate that the response page contains the specified
+    /// texts.
     ///
     /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
@@ -393,15 +1376,278 @@ impl<'a> ValidateBuilder<'a> {
     /// use goose_eggs::Validate;
     ///
     /// let _validate = Validate::builder()
-    ///     .header("x-cache")
+    ///     .texts(vec!["example", "another"])
     ///     .build();
     /// ```
     ///
-    /// It's possible to call this function multiple times, and/or together with
-    /// [`ValidateBuilder::not_header`], [`ValidateBuilder::header_value`] and
-    /// [`ValidateBuilder::not_header_value`].
+    /// It's possible to call this function multiple times (and together with `text()`, `not_text()`
+    /// and `not_texts()`) to validate that multiple texts do or do not appear on the page.
+    /// Alternatively you can call [`ValidateBuilder::texts`].
     ///
-    /// # Multiple Example
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .texts(vec!["example", "another"])
+    ///     .not_texts(vec!["foo", "bar"])
+    ///     .texts(vec!["also this", "and this"])
+    ///     .build();
+    /// ```
+    ///
+    /// Alternatively you can call [`ValidateBuilder::text`].
+    pub fn texts(mut self, texts: Vec<impl Into<Cow<'a, str>>>) -> Self {
+        for text in texts {
+            self = self.text(text);
+        }
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response page does not contains the
+    /// specified texts.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .not_texts(vec!["example", "another"])
+    ///     .build();
+    /// ```
+    ///
+    /// It's possible to call this function multiple times (and together with `text()`, `not_text()`
+    /// and `texts()`) to validate that multiple texts do or do not appear on the page.
+    /// Alternatively you can call [`ValidateBuilder::texts`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .not_texts(vec!["example", "another"])
+    ///     .texts(vec!["does include foo", "and bar"])
+    ///     .not_texts(vec!["but not this", "or this"])
+    ///     .build();
+    /// ```
+    ///
+    /// Alternatively you can call [`ValidateBuilder::text`].
+    pub fn not_texts(mut self, texts: Vec<impl Into<Cow<'a, str>>>) -> Self {
+        for text in texts {
+            self = self.not_text(text);
+        }
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response body contains the
+    /// specified byte sequence.
+    ///
+    /// This makes it possible to validate non-UTF-8 responses such as images, PDFs or
+    /// protobuf. When a byte sequence is validated the raw body is used for all validations,
+    /// and the returned body is converted lossily to a [`String`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// // Validate the PNG signature.
+    /// let _validate = Validate::builder()
+    ///     .bytes(&[0x89, b'P', b'N', b'G'])
+    ///     .build();
+    /// ```
+    pub fn bytes(mut self, bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.bytes.push(ValidateBytes {
+            exists: true,
+            bytes: bytes.into(),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response body does not contain
+    /// the specified byte sequence.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// // Validate that the body does not contain a UTF-8 byte order mark.
+    /// let _validate = Validate::builder()
+    ///     .not_bytes(&[0xEF, 0xBB, 0xBF])
+    ///     .build();
+    /// ```
+    pub fn not_bytes(mut self, bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.bytes.push(ValidateBytes {
+            exists: false,
+            bytes: bytes.into(),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response body starts with the
+    /// specified byte sequence, such as the signature identifying a file format.
+    ///
+    /// This structure is passed to [`validate_download`], [`validate_page`] or
+    /// [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .magic_bytes(b"%PDF-")
+    ///     .build();
+    /// ```
+    pub fn magic_bytes(mut self, bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.magic_bytes = Some(bytes.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the SHA-256 checksum of the response
+    /// body equals the specified hex encoded checksum.
+    ///
+    /// This structure is passed to [`validate_download`], [`validate_page`] or
+    /// [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .sha256("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    ///     .build();
+    /// ```
+    pub fn sha256(mut self, checksum: impl Into<Cow<'a, str>>) -> Self {
+        self.sha256 = Some(checksum.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response page contains at least
+    /// one of the specified texts.
+    ///
+    /// This is useful for pages that have legitimate alternate states, for example a
+    /// product page that either has an "Add to cart" button or is "Out of stock".
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .any_text(vec!["Add to cart", "Out of stock"])
+    ///     .build();
+    /// ```
+    ///
+    /// It's possible to call this function multiple times (and together with `text()`,
+    /// `texts()`, `not_text()` and `not_texts()`), each group of texts is validated
+    /// separately.
+    ///
+    /// # Multiple Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .any_text(vec!["Add to cart", "Out of stock"])
+    ///     .any_text(vec!["Free shipping", "Shipping calculated at checkout"])
+    ///     .text("Product details")
+    ///     .build();
+    /// ```
+    pub fn any_text(mut self, texts: Vec<impl Into<Cow<'a, str>>>) -> Self {
+        self.any_texts.push(ValidateAnyText {
+            texts: texts.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response html header includes
+    /// a `<link rel="canonical">` tag with the specified `href`.
+    ///
+    /// The canonical link must match exactly, broken canonical URLs are a common
+    /// regression.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .canonical("https://example.com/en/recipes")
+    ///     .build();
+    /// ```
+    pub fn canonical(mut self, canonical: impl Into<Cow<'a, str>>) -> Self {
+        self.canonical = Some(canonical.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response html header includes
+    /// a `<meta>` tag with the specified `name` or `property`, and a non-empty `content`.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .meta("description")
+    ///     .build();
+    /// ```
+    ///
+    /// It's possible to call this function multiple times to validate multiple meta
+    /// tags. To validate common Open Graph and Twitter tags, use
+    /// [`ValidateBuilder::social_tags`].
+    pub fn meta(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.metas.push(name.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response html header includes
+    /// the `og:title`, `og:image` and `twitter:card` meta tags, useful for sites where
+    /// missing social tags are a release blocker.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .social_tags()
+    ///     .build();
+    /// ```
+    pub fn social_tags(self) -> Self {
+        self.meta("og:title").meta("og:image").meta("twitter:card")
+    }
+
+    /// Create a [`Validate`] object to validate that the response includes the specified
+    /// header.
+    ///
+    /// To validate that a header contains a specific value (instead of just validating
+    /// that it exists), use [`ValidateBuilder::header_value`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .header("x-cache")
+    ///     .build();
+    /// ```
+    ///
+    /// It's possible to call this function multiple times, and/or together with
+    /// [`ValidateBuilder::not_header`], [`ValidateBuilder::header_value`] and
+    /// [`ValidateBuilder::not_header_value`].
+    ///
+    /// # Multiple Example
     /// ```rust
     /// use goose_eggs::Validate;
     ///
@@ -410,11 +1656,11 @@ impl<'a> ValidateBuilder<'a> {
     ///     .header("x-generator")
     ///     .build();
     /// ```
-    pub fn header(mut self, header: impl Into<&'a str>) -> Self {
+    pub fn header(mut self, header: impl Into<Cow<'a, str>>) -> Self {
         self.headers.push(ValidateHeader {
             exists: true,
             header: header.into(),
-            value: "",
+            value: "".into(),
         });
         self
     }
@@ -449,11 +1695,11 @@ impl<'a> ValidateBuilder<'a> {
     ///     .header("x-generator")
     ///     .build();
     /// ```
-    pub fn not_header(mut self, header: impl Into<&'a str>) -> Self {
+    pub fn not_header(mut self, header: impl Into<Cow<'a, str>>) -> Self {
         self.headers.push(ValidateHeader {
             exists: false,
             header: header.into(),
-            value: "",
+            value: "".into(),
         });
         self
     }
@@ -492,7 +1738,11 @@ impl<'a> ValidateBuilder<'a> {
     ///     .header_value("x-drupal-cache", "HIT")
     ///     .build();
     /// ```
-    pub fn header_value(mut self, header: impl Into<&'a str>, value: impl Into<&'a str>) -> Self {
+    pub fn header_value(
+        mut self,
+        header: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
+    ) -> Self {
         self.headers.push(ValidateHeader {
             exists: true,
             header: header.into(),
@@ -537,8 +1787,8 @@ impl<'a> ValidateBuilder<'a> {
     /// ```
     pub fn not_header_value(
         mut self,
-        header: impl Into<&'a str>,
-        value: impl Into<&'a str>,
+        header: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
     ) -> Self {
         self.headers.push(ValidateHeader {
             exists: false,
@@ -548,7 +1798,12 @@ impl<'a> ValidateBuilder<'a> {
         self
     }
 
-    /// Create a [`Validate`] object to validate whether or not the response page redirected.
+    /// Create a [`Validate`] object to validate that the response is served compressed,
+    /// with any of the `gzip`, `br`, `deflate` or `zstd` content encodings.
+    ///
+    /// Note that when Goose negotiates gzip compression it transparently decompresses the
+    /// response and removes the `Content-Encoding` header. To validate gzip compression run
+    /// the load test with `--no-gzip` and set the `Accept-Encoding` request header manually.
     ///
     /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
@@ -556,156 +1811,2629 @@ impl<'a> ValidateBuilder<'a> {
     /// ```rust
     /// use goose_eggs::Validate;
     ///
-    /// // Verify the response redirected.
-    /// let _validate = Validate::builder().redirect(true).build();
+    /// let _validate = Validate::builder()
+    ///     .compressed()
+    ///     .build();
+    /// ```
+    pub fn compressed(mut self) -> Self {
+        self.content_encodings.push(ValidateContentEncoding {
+            exists: true,
+            encoding: "".into(),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response is served with the
+    /// specified content encoding, for example `br`.
     ///
-    /// // Verify the response did not redirect.
-    /// let _validate = Validate::builder().redirect(false).build();
+    /// Refer to [`ValidateBuilder::compressed`] for details about validating gzip
+    /// compression.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .content_encoding("br")
+    ///     .build();
     /// ```
-    pub fn redirect(mut self, redirect: impl Into<bool>) -> Self {
-        self.redirect = Some(redirect.into());
+    pub fn content_encoding(mut self, encoding: impl Into<Cow<'a, str>>) -> Self {
+        self.content_encodings.push(ValidateContentEncoding {
+            exists: true,
+            encoding: encoding.into(),
+        });
         self
     }
 
-    /// Build the [`Validate`] object which is then passed to the
-    /// [`validate_page`] or [`validate_and_load_static_assets`] functions.
+    /// Create a [`Validate`] object to validate that the response is not served with the
+    /// specified content encoding.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
     ///
     /// # Example
     /// ```rust
     /// use goose_eggs::Validate;
     ///
-    /// // Use the default search form to search for `example keys`.
     /// let _validate = Validate::builder()
-    ///     .text("example text")
+    ///     .not_content_encoding("deflate")
     ///     .build();
     /// ```
-    pub fn build(self) -> Validate<'a> {
-        let Self {
-            status,
-            title,
-            texts,
-            headers,
-            redirect,
-        } = self;
-        Validate {
-            status,
-            title,
-            texts,
-            headers,
-            redirect,
-        }
+    pub fn not_content_encoding(mut self, encoding: impl Into<Cow<'a, str>>) -> Self {
+        self.content_encodings.push(ValidateContentEncoding {
+            exists: false,
+            encoding: encoding.into(),
+        });
+        self
     }
-}
 
-/// Use a regular expression to get the HTML header from the web page.
-///
-/// # Example
-/// ```rust
-/// use goose_eggs::get_html_header;
+    /// Create a [`Validate`] object to validate that the response is friendly to reverse
+    /// proxies and CDNs: it must include a `Cache-Control` header, and at least one of
+    /// the `ETag` or `Last-Modified` cache validator headers.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .cacheable()
+    ///     .build();
+    /// ```
+    pub fn cacheable(mut self) -> Self {
+        self.cacheable = true;
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response includes common security
+    /// headers: `Strict-Transport-Security`, `X-Content-Type-Options: nosniff`, and at
+    /// least one of `X-Frame-Options` or `Content-Security-Policy` to prevent clickjacking.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .security_headers()
+    ///     .build();
+    /// ```
+    pub fn security_headers(mut self) -> Self {
+        self.security_headers = true;
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response isn't the page Drupal
+    /// displays while the site is in maintenance mode, detected with
+    /// [`drupal::is_maintenance_page`].
+    ///
+    /// Any other validations that fail on the maintenance mode page are replaced by a
+    /// single [`ValidationError::MaintenanceMode`], so a site switching to maintenance mode
+    /// during a load test is reported clearly. Without this validation the page isn't
+    /// checked for, and each failed validation is reported.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{Validate, ValidationError};
+    /// use reqwest::header::HeaderMap;
+    /// use reqwest::StatusCode;
+    ///
+    /// let html = r#"<html><head><title>Site under maintenance | Umami</title></head></html>"#;
+    /// let validate = Validate::builder().not_maintenance().build();
+    /// let errors = validate
+    ///     .check(false, StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new(), html.as_bytes())
+    ///     .unwrap_err();
+    /// assert!(matches!(errors[..], [ValidationError::MaintenanceMode]));
+    ///
+    /// // Other failed validations are replaced when the page is in maintenance mode.
+    /// let validate = Validate::builder().status(200).not_maintenance().build();
+    /// let errors = validate
+    ///     .check(false, StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new(), html.as_bytes())
+    ///     .unwrap_err();
+    /// assert!(matches!(errors[..], [ValidationError::MaintenanceMode]));
+    /// ```
+    pub fn not_maintenance(mut self) -> Self {
+        self.not_maintenance = true;
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response `Cache-Control` header
+    /// sets a `max-age` directive of at least the specified number of seconds.
+    ///
+    /// The directive is parsed and compared numerically, so for example a deployment that
+    /// accidentally ships `max-age=0` on pages that must be cached fails validation.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .cache_control_max_age_at_least(3600)
+    ///     .build();
+    /// ```
+    pub fn cache_control_max_age_at_least(mut self, seconds: u64) -> Self {
+        self.cache_control_max_age = Some(seconds);
+        self
+    }
+
+    /// Create a [`Validate`] object to validate whether or not the response page redirected.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// // Verify the response redirected.
+    /// let _validate = Validate::builder().redirect(true).build();
+    ///
+    /// // Verify the response did not redirect.
+    /// let _validate = Validate::builder().redirect(false).build();
+    /// ```
+    pub fn redirect(mut self, redirect: impl Into<bool>) -> Self {
+        self.redirect = Some(redirect.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response charset matches the
+    /// specified value (case insensitive).
+    ///
+    /// The charset is validated both in the `Content-Type` header and in the
+    /// `<meta charset>` tag of the html header. Validation fails if neither declares a
+    /// charset, or if either declares a different charset, catching pages where the two
+    /// disagree.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .charset("utf-8")
+    ///     .build();
+    /// ```
+    pub fn charset(mut self, charset: impl Into<Cow<'a, str>>) -> Self {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the JSON response body contains a
+    /// value at the specified [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+    ///
+    /// JSON validations are performed by [`validate_json`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .status(200)
+    ///     .json_pointer("/data/0/id")
+    ///     .build();
+    /// ```
+    pub fn json_pointer(mut self, pointer: impl Into<Cow<'a, str>>) -> Self {
+        self.json.push(ValidateJson {
+            pointer: pointer.into(),
+            value: None,
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the value at the specified
+    /// [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) in the JSON response
+    /// body equals the specified value.
+    ///
+    /// JSON validations are performed by [`validate_json`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .json_value("/data/0/type", "node--article")
+    ///     .json_value("/meta/count", 10)
+    ///     .build();
+    /// ```
+    pub fn json_value(mut self, pointer: impl Into<Cow<'a, str>>, value: impl Into<Value>) -> Self {
+        self.json.push(ValidateJson {
+            pointer: pointer.into(),
+            value: Some(value.into()),
+        });
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response page contains the
+    /// specified form, identified by its `id` or `data-drupal-selector` attribute. Refer
+    /// to [`valid_form`] for details.
+    ///
+    /// This is more robust than validating a raw html fragment such as
+    /// `text(r#"<form class="user-login-form"#)`, which breaks when attributes are reordered.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Log in")
+    ///     .form("user-login-form")
+    ///     .build();
+    /// ```
+    pub fn form(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.forms.push(name.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that none of the [`DEFAULT_ERROR_MARKERS`]
+    /// are in the response page, so PHP errors and notices leaking into otherwise valid
+    /// pages fail the request.
+    ///
+    /// Additional markers can be added with [`ValidateBuilder::error_marker`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .no_error_markers()
+    ///     .build();
+    /// ```
+    pub fn no_error_markers(mut self) -> Self {
+        self.error_markers.extend(
+            DEFAULT_ERROR_MARKERS
+                .iter()
+                .map(|marker| Cow::Borrowed(*marker)),
+        );
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the specified error marker is not in
+    /// the response page. It can be invoked multiple times to define a custom list of error
+    /// markers, with or without also invoking [`ValidateBuilder::no_error_markers`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .no_error_markers()
+    ///     .error_marker("The website encountered an unexpected error.")
+    ///     .build();
+    /// ```
+    pub fn error_marker(mut self, error_marker: impl Into<Cow<'a, str>>) -> Self {
+        self.error_markers.push(error_marker.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response html isn't truncated.
+    /// Refer to [`valid_html`] for details about what is validated.
+    ///
+    /// This is useful as substring validations can still pass when a proxy cuts off a
+    /// response mid-tag while under heavy load.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .text("example")
+    ///     .parseable_html()
+    ///     .build();
+    /// ```
+    pub fn parseable_html(mut self) -> Self {
+        self.parseable_html = true;
+        self
+    }
+
+    /// Don't download the response body, only validating the status code, headers and
+    /// whether or not the request redirected.
+    ///
+    /// Buffering large responses dominates memory and CPU usage when only the headers are
+    /// of interest. When the body is ignored, validations of the body (such as titles and
+    /// texts) are skipped, an empty [`String`] is returned by [`validate_page`], and
+    /// [`validate_and_load_static_assets`] doesn't load any static assets.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .status(200)
+    ///     .header_value("content-type", "application/pdf")
+    ///     .ignore_body()
+    ///     .build();
+    /// ```
+    pub fn ignore_body(mut self) -> Self {
+        self.ignore_body = true;
+        self
+    }
+
+    /// Re-issue the request up to the specified number of times if validation fails, or if
+    /// there is no response from the server. Only the final attempt is validated and
+    /// reported to Goose, distinguishing transient failures (such as a CDN serving a stale
+    /// error page) from real regressions.
+    ///
+    /// Only `GET` and `HEAD` requests are retried. Use [`ValidateBuilder::backoff`] to wait
+    /// before each retry.
+    ///
+    /// The retry is rebuilt from the request recorded by Goose: its method, url (including
+    /// the query string) and headers are sent again, and if the response had an error status
+    /// code that was expected with `expect_status_code` it's expected again. Other settings
+    /// of a custom request builder, such as a timeout, aren't applied to the retry. Requests
+    /// with sensitive headers, such as basic authentication, can't be rebuilt and aren't
+    /// retried.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use std::time::Duration;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .retries(2)
+    ///     .backoff(Duration::from_millis(500))
+    ///     .build();
+    /// ```
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Define how long to wait before each retry configured with
+    /// [`ValidateBuilder::retries`]. By default requests are retried immediately.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use std::time::Duration;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .retries(1)
+    ///     .backoff(Duration::from_secs(1))
+    ///     .build();
+    /// ```
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Add a [`ValidationHook`], invoked before and after the response is validated. It can
+    /// be invoked multiple times to add multiple hooks, which are invoked in order.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::metrics::GooseRequestMetric;
+    /// use goose::prelude::*;
+    /// use goose_eggs::{Validate, ValidationHook};
+    /// use reqwest::header::HeaderMap;
+    ///
+    /// struct LogRequests;
+    ///
+    /// impl ValidationHook for LogRequests {
+    ///     fn before(&self, _user: &mut GooseUser, request: &GooseRequestMetric, _headers: &HeaderMap) {
+    ///         println!("validating {}", request.raw.url);
+    ///     }
+    /// }
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .hook(LogRequests)
+    ///     .build();
+    /// ```
+    pub fn hook(mut self, hook: impl ValidationHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Name the validation in the counters returned by [`validation_metrics`].
+    ///
+    /// Every time a response is validated, the number of validated responses and the
+    /// number of times each validation failed are counted under this name, giving
+    /// per-validation failure rates. Unnamed validations are counted under the name of
+    /// the request they validate.
+    ///
+    /// To also count named validations in the Goose metrics, refer to
+    /// [`ValidateBuilder::goose_metric`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Checkout")
+    ///     .text("Payment information")
+    ///     .name("checkout step 2")
+    ///     .build();
+    /// ```
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Also count the validation named with [`ValidateBuilder::name`] in the Goose
+    /// metrics, so it's included in the final report and the html and json reports: each
+    /// validated response is recorded as a request named `validation: {name}`, successful
+    /// if it passed every validation and failed otherwise.
+    ///
+    /// These requests aren't sent to the server and are recorded with no response time,
+    /// but Goose counts them with all other requests, increasing the aggregated number of
+    /// requests and requests per second and lowering the aggregated response times. For
+    /// this reason named validations are only counted by [`validation_metrics`] unless
+    /// enabled.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Checkout")
+    ///     .name("checkout step 2")
+    ///     .goose_metric()
+    ///     .build();
+    /// ```
+    pub fn goose_metric(mut self) -> Self {
+        self.goose_metric = true;
+        self
+    }
+
+    /// Configure how static assets found on the page are loaded by
+    /// [`validate_and_load_static_assets`]. By default all local static assets are loaded.
+    ///
+    /// This structure is passed to [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{LoadAssets, Validate};
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Videos")
+    ///     .assets(LoadAssets::builder().media_range(64 * 1024).build())
+    ///     .build();
+    /// ```
+    pub fn assets(mut self, assets: LoadAssets) -> Self {
+        self.assets = Some(assets);
+        self
+    }
+
+    /// Write each failed response to a file in the specified directory, in addition to
+    /// reporting the failure to Goose.
+    ///
+    /// Each file is named with a timestamp and the Goose user, and contains the failure
+    /// message, the request metadata, the response headers and the full response body.
+    /// Unlike the Goose debug log the body is never truncated, making it easier to
+    /// inspect the html of pages that failed validation. The directory is created if it
+    /// does not exist.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .capture_failures("failures")
+    ///     .build();
+    /// ```
+    pub fn capture_failures(mut self, dir: impl Into<Cow<'a, str>>) -> Self {
+        self.capture_dir = Some(dir.into());
+        self
+    }
+
+    /// Create a [`Validate`] object to validate that the response body is similar to a
+    /// golden snapshot, flagging pages whose rendered output drifts under load.
+    ///
+    /// The snapshot and the response body are compared line by line, ignoring leading and
+    /// trailing whitespace and blank lines. Validation fails if the percentage of lines
+    /// the two have in common is less than `min_similarity`, so `100` requires the page
+    /// to match the snapshot exactly. Use [`ValidateBuilder::snapshot_ignore`] to ignore
+    /// regions that change on every request.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use reqwest::header::HeaderMap;
+    /// use reqwest::StatusCode;
+    ///
+    /// let golden = "<html>\n<title>Home</title>\n<p>Welcome</p>\n<p>Recipes</p>\n</html>";
+    /// let html = "<html>\n<title>Home</title>\n<p>Welcome</p>\n<p>Error</p>\n</html>";
+    ///
+    /// let validate = Validate::builder().snapshot(golden, 75).build();
+    /// assert!(validate.check(false, StatusCode::OK, &HeaderMap::new(), html.as_bytes()).is_ok());
+    ///
+    /// let validate = Validate::builder().snapshot(golden, 95).build();
+    /// assert!(validate.check(false, StatusCode::OK, &HeaderMap::new(), html.as_bytes()).is_err());
+    /// ```
+    pub fn snapshot(mut self, golden: impl Into<Cow<'a, str>>, min_similarity: u8) -> Self {
+        self.snapshot = Some(ValidateSnapshot {
+            golden: golden.into(),
+            min_similarity,
+        });
+        self
+    }
+
+    /// Ignore regions matching the specified regular expression when comparing the
+    /// response body against the snapshot defined with [`ValidateBuilder::snapshot`],
+    /// such as nonces, CSRF tokens and timestamps. Can be invoked multiple times.
+    ///
+    /// Invalid regular expressions are logged and ignored.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let golden = std::fs::read_to_string("tests/snapshots/front.html").unwrap_or_default();
+    ///
+    /// let _validate = Validate::builder()
+    ///     .snapshot(golden, 90)
+    ///     .snapshot_ignore(r#"name="form_build_id" value="[^"]*""#)
+    ///     .snapshot_ignore(r"\d{4}-\d{2}-\d{2}T[\d:]+")
+    ///     .build();
+    /// ```
+    pub fn snapshot_ignore(mut self, regex: impl Into<Cow<'a, str>>) -> Self {
+        self.snapshot_ignores.push(regex.into());
+        self
+    }
+
+    /// Define a template for the failure message reported when validation fails.
+    ///
+    /// By default failure messages are the url followed by the failed validation, so
+    /// errors in the Goose summary are grouped by url. A template makes it possible to
+    /// group errors by a meaningful step instead. `{reason}` is replaced with the failed
+    /// validation and `{url}` is replaced with the url of the request.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Checkout")
+    ///     .text("Payment information")
+    ///     .on_failure("checkout step 2 broken: {reason}")
+    ///     .build();
+    /// ```
+    pub fn on_failure(mut self, template: impl Into<Cow<'a, str>>) -> Self {
+        self.on_failure = Some(template.into());
+        self
+    }
+
+    /// Evaluate every configured validation and report all failures together in a single
+    /// failure message, instead of only reporting the first failed validation.
+    ///
+    /// By default validation stops at the first failure to avoid cascades of errors when
+    /// a page fails to load, but this means a page with multiple problems only reveals one
+    /// problem at a time.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .texts(vec!["foo", "bar"])
+    ///     .collect_all()
+    ///     .build();
+    /// ```
+    pub fn collect_all(mut self) -> Self {
+        self.collect_all = true;
+        self
+    }
+
+    /// Log failed validations as warnings instead of failing the request.
+    ///
+    /// Each failed validation is written to the Goose debug log and to the `warn` log
+    /// level, and increments the counter returned by [`validation_warnings`], but
+    /// `set_failure` is not invoked. This is useful while migrating a site, when some
+    /// validations are known to be flaky but are still worth tracking.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .text("Known to be flaky")
+    ///     .warn_only()
+    ///     .build();
+    /// ```
+    pub fn warn_only(mut self) -> Self {
+        self.warn_only = true;
+        self
+    }
+
+    /// Record how long each phase of validation takes, so it's possible to see when the
+    /// validations themselves become a bottleneck at high request rates.
+    ///
+    /// Phases are timed per validation name (see [`ValidateBuilder::name`]) and included
+    /// in [`validation_metrics`]: `body` is the time spent downloading the response body,
+    /// `validate` is the time spent validating the response, and `assets` is the time
+    /// spent extracting static assets from the page in
+    /// [`validate_and_load_static_assets`].
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .timed()
+    ///     .build();
+    /// ```
+    pub fn timed(mut self) -> Self {
+        self.timed = true;
+        self
+    }
+
+    /// Tolerate the specified number of failures per Goose user before reporting them.
+    ///
+    /// Failures are tallied per Goose user and per validation name (see
+    /// [`ValidateBuilder::name`]). Tolerated failures are logged as warnings, like
+    /// [`ValidateBuilder::warn_only`], and once a user exceeds the count further failures
+    /// are reported to Goose. This is useful when load testing environments with a known
+    /// small percentage of flaky backends.
+    ///
+    /// The tallies are kept until [`reset_validation_metrics`] is invoked.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .tolerate_failures(3)
+    ///     .build();
+    /// ```
+    pub fn tolerate_failures(mut self, count: usize) -> Self {
+        self.tolerate_failures = Some(count);
+        self
+    }
+
+    /// Tolerate failures per Goose user while they are no more than the specified
+    /// percentage of the responses validated for that user.
+    ///
+    /// When combined with [`ValidateBuilder::tolerate_failures`], a failure is tolerated
+    /// while it is within either threshold, so the count can allow for early failures
+    /// before enough responses have been validated for the rate to be meaningful.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let _validate = Validate::builder()
+    ///     .title("Home page")
+    ///     .tolerate_failures(2)
+    ///     .tolerate_failure_rate(5.0)
+    ///     .build();
+    /// ```
+    pub fn tolerate_failure_rate(mut self, percent: f32) -> Self {
+        self.tolerate_failure_rate = Some(percent);
+        self
+    }
+
+    /// Extend this builder with all validations from an existing [`Validate`] object.
+    ///
+    /// This makes it possible to define a site-wide base validation once and combine it
+    /// with per-page validations. Texts, headers, meta tags, content encodings and byte
+    /// sequences are appended. Other validations are only copied from `validate` if they
+    /// have not already been set on this builder.
+    ///
+    /// Only validations are copied. Options that change how the validation is performed
+    /// and reported, such as [`ValidateBuilder::warn_only`], [`ValidateBuilder::collect_all`],
+    /// [`ValidateBuilder::ignore_body`], [`ValidateBuilder::retries`] and
+    /// [`ValidateBuilder::name`], stay as set on this builder, so extending a strict
+    /// validation with a preset can't make it lenient.
+    ///
+    /// This structure is passed to [`validate_page`] or [`validate_and_load_static_assets`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// let base = Validate::builder()
+    ///     .status(200)
+    ///     .header_value("x-generator", "Drupal 9 (https://www.drupal.org)")
+    ///     .not_text("Warning:")
+    ///     .build();
+    ///
+    /// let _validate = Validate::builder()
+    ///     .extend(&base)
+    ///     .title("Home page")
+    ///     .build();
+    /// ```
+    pub fn extend(mut self, validate: &Validate<'a>) -> Self {
+        self.status = self.status.or_else(|| validate.status.clone());
+        self.title = self.title.or_else(|| validate.title.clone());
+        self.texts.extend(validate.texts.iter().cloned());
+        self.any_texts.extend(validate.any_texts.iter().cloned());
+        self.canonical = self.canonical.or_else(|| validate.canonical.clone());
+        self.metas.extend(validate.metas.iter().cloned());
+        self.headers.extend(validate.headers.iter().cloned());
+        self.redirect = self.redirect.or(validate.redirect);
+        self.parseable_html |= validate.parseable_html;
+        self.charset = self.charset.or_else(|| validate.charset.clone());
+        self.content_encodings
+            .extend(validate.content_encodings.iter().cloned());
+        self.cacheable |= validate.cacheable;
+        self.cache_control_max_age = self
+            .cache_control_max_age
+            .or(validate.cache_control_max_age);
+        self.bytes.extend(validate.bytes.iter().cloned());
+        self.error_markers
+            .extend(validate.error_markers.iter().cloned());
+        self.security_headers |= validate.security_headers;
+        self.not_maintenance |= validate.not_maintenance;
+        self.forms.extend(validate.forms.iter().cloned());
+        self.json.extend(validate.json.iter().cloned());
+        self.hooks.extend(validate.hooks.iter().cloned());
+        self.magic_bytes = self.magic_bytes.or_else(|| validate.magic_bytes.clone());
+        self.sha256 = self.sha256.or_else(|| validate.sha256.clone());
+        self.snapshot = self.snapshot.or_else(|| validate.snapshot.clone());
+        self.snapshot_ignores
+            .extend(validate.snapshot_ignores.iter().cloned());
+        self
+    }
+
+    // Copy the options that change how the validation is performed and reported, which
+    // aren't copied by extend().
+    fn options(mut self, validate: &Validate<'a>) -> Self {
+        self.warn_only = validate.warn_only;
+        self.collect_all = validate.collect_all;
+        self.ignore_body = validate.ignore_body;
+        self.on_failure = validate.on_failure.clone();
+        self.name = validate.name.clone();
+        self.goose_metric = validate.goose_metric;
+        self.retries = validate.retries;
+        self.backoff = validate.backoff;
+        self.tolerate_failures = validate.tolerate_failures;
+        self.tolerate_failure_rate = validate.tolerate_failure_rate;
+        self.capture_dir = validate.capture_dir.clone();
+        self.timed = validate.timed;
+        self.assets = validate.assets.clone();
+        self
+    }
+
+    /// Build the [`Validate`] object which is then passed to the
+    /// [`validate_page`] or [`validate_and_load_static_assets`] functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    ///
+    /// // Use the default search form to search for `example keys`.
+    /// let _validate = Validate::builder()
+    ///     .text("example text")
+    ///     .build();
+    /// ```
+    pub fn build(self) -> Validate<'a> {
+        let Self {
+            status,
+            title,
+            texts,
+            any_texts,
+            canonical,
+            metas,
+            headers,
+            redirect,
+            parseable_html,
+            charset,
+            content_encodings,
+            cacheable,
+            cache_control_max_age,
+            bytes,
+            warn_only,
+            collect_all,
+            error_markers,
+            security_headers,
+            not_maintenance,
+            forms,
+            json,
+            ignore_body,
+            on_failure,
+            name,
+            goose_metric,
+            retries,
+            backoff,
+            hooks,
+            magic_bytes,
+            sha256,
+            tolerate_failures,
+            tolerate_failure_rate,
+            capture_dir,
+            timed,
+            snapshot,
+            snapshot_ignores,
+            assets,
+        } = self;
+        Validate {
+            status,
+            title,
+            texts,
+            any_texts,
+            canonical,
+            metas,
+            headers,
+            redirect,
+            parseable_html,
+            charset,
+            content_encodings,
+            cacheable,
+            cache_control_max_age,
+            bytes,
+            warn_only,
+            collect_all,
+            error_markers,
+            security_headers,
+            not_maintenance,
+            forms,
+            json,
+            ignore_body,
+            on_failure,
+            name,
+            goose_metric,
+            retries,
+            backoff,
+            hooks,
+            magic_bytes,
+            sha256,
+            tolerate_failures,
+            tolerate_failure_rate,
+            capture_dir,
+            timed,
+            snapshot,
+            snapshot_ignores,
+            assets,
+        }
+    }
+}
+
+// The format used to deserialize a `Validate<'static>` from a configuration file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ValidateConfig {
+    status: Option<u16>,
+    not_status: Option<u16>,
+    title: Option<String>,
+    not_title: Option<String>,
+    texts: Vec<String>,
+    not_texts: Vec<String>,
+    text_in: BTreeMap<String, String>,
+    not_text_in: BTreeMap<String, String>,
+    any_texts: Vec<Vec<String>>,
+    canonical: Option<String>,
+    metas: Vec<String>,
+    headers: Vec<String>,
+    not_headers: Vec<String>,
+    header_values: BTreeMap<String, String>,
+    not_header_values: BTreeMap<String, String>,
+    redirect: Option<bool>,
+    parseable_html: bool,
+    charset: Option<String>,
+    compressed: bool,
+    content_encodings: Vec<String>,
+    not_content_encodings: Vec<String>,
+    forms: Vec<String>,
+    json_pointers: Vec<String>,
+    json_values: BTreeMap<String, Value>,
+    snapshot: Option<String>,
+    snapshot_similarity: Option<u8>,
+    snapshot_ignores: Vec<String>,
+    cacheable: bool,
+    security_headers: bool,
+    not_maintenance: bool,
+    cache_control_max_age_at_least: Option<u64>,
+    ignore_body: bool,
+    sha256: Option<String>,
+    bytes: Vec<Vec<u8>>,
+    not_bytes: Vec<Vec<u8>>,
+    magic_bytes: Option<Vec<u8>>,
+    no_error_markers: bool,
+    timed: bool,
+    name: Option<String>,
+    goose_metric: bool,
+    on_failure: Option<String>,
+    capture_failures: Option<String>,
+    retries: usize,
+    backoff: u64,
+    tolerate_failures: Option<usize>,
+    tolerate_failure_rate: Option<f32>,
+    error_markers: Vec<String>,
+    warn_only: bool,
+    collect_all: bool,
+}
+
+/// Deserialize a [`Validate`] object, for example from a YAML or JSON configuration file,
+/// so expected titles, texts and headers can be changed without recompiling the load test.
+///
+/// Each key matches the [`ValidateBuilder`] method of the same name. Keys that accept a
+/// list (`texts`, `not_texts`, `any_texts`, `metas`, `headers`, `not_headers`, `forms`,
+/// `json_pointers`, `content_encodings`, `not_content_encodings`, `snapshot_ignores` and
+/// `error_markers`)
+/// invoke the method once per item. `header_values` and `not_header_values` map header
+/// names to values, `text_in` and `not_text_in` map selectors to texts, and `json_values`
+/// maps JSON pointers to values.
+/// Byte sequences (`bytes` and `not_bytes`, which are lists, and `magic_bytes`) are arrays
+/// of byte values, and `backoff` is in milliseconds.
+/// Flags such as `parseable_html`, `compressed`, `cacheable`, `security_headers`,
+/// `not_maintenance`, `ignore_body`, `no_error_markers`, `timed`, `goose_metric`, `warn_only` and
+/// `collect_all` are booleans.
+/// Unknown keys are rejected.
+///
+/// Named texts ([`ValidateBuilder::text_named`]), hooks ([`ValidateBuilder::hook`]) and
+/// the static assets to load ([`ValidateBuilder::assets`]) can't be configured.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::Validate;
+///
+/// let config = r#"{
+///     "status": 200,
+///     "title": "Home page",
+///     "texts": ["Welcome", "Recipes"],
+///     "not_texts": ["Warning:"],
+///     "text_in": { "div#content": "Featured recipes" },
+///     "header_values": { "x-generator": "Drupal 9 (https://www.drupal.org)" },
+///     "retries": 1,
+///     "backoff": 500,
+///     "collect_all": true
+/// }"#;
+///
+/// let _validate: Validate = serde_json::from_str(config).unwrap();
+/// ```
+impl<'de> Deserialize<'de> for Validate<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = ValidateConfig::deserialize(deserializer)?;
+
+        let mut validate = Validate::builder();
+        if let Some(status) = config.status {
+            validate = validate.status(status);
+        }
+        if let Some(status) = config.not_status {
+            validate = validate.not_status(status);
+        }
+        if let Some(title) = config.title {
+            validate = validate.title(title);
+        }
+        if let Some(title) = config.not_title {
+            validate = validate.not_title(title);
+        }
+        validate = validate.texts(config.texts).not_texts(config.not_texts);
+        for (selector, text) in config.text_in {
+            validate = validate.text_in(selector, text);
+        }
+        for (selector, text) in config.not_text_in {
+            validate = validate.not_text_in(selector, text);
+        }
+        for texts in config.any_texts {
+            validate = validate.any_text(texts);
+        }
+        if let Some(canonical) = config.canonical {
+            validate = validate.canonical(canonical);
+        }
+        for meta in config.metas {
+            validate = validate.meta(meta);
+        }
+        for header in config.headers {
+            validate = validate.header(header);
+        }
+        for header in config.not_headers {
+            validate = validate.not_header(header);
+        }
+        for (header, value) in config.header_values {
+            validate = validate.header_value(header, value);
+        }
+        for (header, value) in config.not_header_values {
+            validate = validate.not_header_value(header, value);
+        }
+        if let Some(redirect) = config.redirect {
+            validate = validate.redirect(redirect);
+        }
+        if config.parseable_html {
+            validate = validate.parseable_html();
+        }
+        if let Some(charset) = config.charset {
+            validate = validate.charset(charset);
+        }
+        if config.compressed {
+            validate = validate.compressed();
+        }
+        for encoding in config.content_encodings {
+            validate = validate.content_encoding(encoding);
+        }
+        for encoding in config.not_content_encodings {
+            validate = validate.not_content_encoding(encoding);
+        }
+        for form in config.forms {
+            validate = validate.form(form);
+        }
+        for pointer in config.json_pointers {
+            validate = validate.json_pointer(pointer);
+        }
+        for (pointer, value) in config.json_values {
+            validate = validate.json_value(pointer, value);
+        }
+        if config.cacheable {
+            validate = validate.cacheable();
+        }
+        if config.security_headers {
+            validate = validate.security_headers();
+        }
+        if config.not_maintenance {
+            validate = validate.not_maintenance();
+        }
+        if let Some(seconds) = config.cache_control_max_age_at_least {
+            validate = validate.cache_control_max_age_at_least(seconds);
+        }
+        if config.ignore_body {
+            validate = validate.ignore_body();
+        }
+        if let Some(sha256) = config.sha256 {
+            validate = validate.sha256(sha256);
+        }
+        for bytes in config.bytes {
+            validate = validate.bytes(bytes);
+        }
+        for bytes in config.not_bytes {
+            validate = validate.not_bytes(bytes);
+        }
+        if let Some(bytes) = config.magic_bytes {
+            validate = validate.magic_bytes(bytes);
+        }
+        if let Some(golden) = config.snapshot {
+            validate = validate.snapshot(golden, config.snapshot_similarity.unwrap_or(100));
+        }
+        for regex in config.snapshot_ignores {
+            validate = validate.snapshot_ignore(regex);
+        }
+        if config.no_error_markers {
+            validate = validate.no_error_markers();
+        }
+        if config.timed {
+            validate = validate.timed();
+        }
+        for error_marker in config.error_markers {
+            validate = validate.error_marker(error_marker);
+        }
+        if let Some(name) = config.name {
+            validate = validate.name(name);
+        }
+        if config.goose_metric {
+            validate = validate.goose_metric();
+        }
+        if let Some(template) = config.on_failure {
+            validate = validate.on_failure(template);
+        }
+        if let Some(dir) = config.capture_failures {
+            validate = validate.capture_failures(dir);
+        }
+        validate = validate
+            .retries(config.retries)
+            .backoff(Duration::from_millis(config.backoff));
+        if let Some(count) = config.tolerate_failures {
+            validate = validate.tolerate_failures(count);
+        }
+        if let Some(percent) = config.tolerate_failure_rate {
+            validate = validate.tolerate_failure_rate(percent);
+        }
+        if config.warn_only {
+            validate = validate.warn_only();
+        }
+        if config.collect_all {
+            validate = validate.collect_all();
+        }
+
+        Ok(validate.build())
+    }
+}
+
+/// Expected validations for each language of a multilingual website, so a single
+/// transaction function can validate every translation of a page.
+///
+/// The language of a response is detected from the first segment of the request path
+/// (for example `es` in `/es/recipes`), or otherwise from the first language listed in the
+/// `Content-Language` response header. If no [`Validate`] object is defined for the
+/// detected language, the fallback [`Validate`] object is used.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::{validate_and_load_static_assets, LocalizedValidate, Validate};
+///
+/// transaction!(recipe_listing).set_on_start();
+///
+/// async fn recipe_listing(user: &mut GooseUser) -> TransactionResult {
+///     let localized = LocalizedValidate::new(Validate::builder().title("Recipes").build())
+///         .language("es", Validate::builder().title("Recetas").build());
+///
+///     let path = if user.weighted_users_index % 2 == 0 { "en/recipes/" } else { "es/recipes/" };
+///     let goose = user.get(path).await?;
+///     let validate = localized.select(&goose);
+///     validate_and_load_static_assets(user, goose, validate).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocalizedValidate<'a> {
+    // Validations keyed by lower case language code.
+    languages: BTreeMap<String, Validate<'a>>,
+    // Validation used when no language matches.
+    fallback: Validate<'a>,
+}
+impl<'a> LocalizedValidate<'a> {
+    /// Create a [`LocalizedValidate`] object, using the specified [`Validate`] object for
+    /// responses in languages that have no validations of their own.
+    pub fn new(fallback: Validate<'a>) -> Self {
+        LocalizedValidate {
+            languages: BTreeMap::new(),
+            fallback,
+        }
+    }
+
+    /// Use the specified [`Validate`] object for responses in the specified language, such
+    /// as `es` or `pt-br`. Language codes are not case sensitive.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{LocalizedValidate, Validate};
+    ///
+    /// let _localized = LocalizedValidate::new(Validate::builder().title("Home").build())
+    ///     .language("es", Validate::builder().title("Inicio").build())
+    ///     .language("pt-br", Validate::builder().title("Início").build());
+    /// ```
+    pub fn language(mut self, code: &str, validate: Validate<'a>) -> Self {
+        self.languages.insert(code.to_lowercase(), validate);
+        self
+    }
+
+    /// Select the [`Validate`] object matching the language of the response, which is then
+    /// passed to [`validate_page`], [`validate_and_load_static_assets`] or any other
+    /// validation function.
+    pub fn select(&self, goose: &GooseResponse) -> &Validate<'a> {
+        let headers = goose.response.as_ref().ok().map(|r| r.headers());
+        self.select_language(&goose.request.raw.url, headers)
+    }
+
+    /// Select the [`Validate`] object matching the language of the specified url or path,
+    /// or if it has no language prefix the language of the `Content-Language` header.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::{LocalizedValidate, Validate};
+    /// use reqwest::header::HeaderMap;
+    /// use reqwest::StatusCode;
+    ///
+    /// let localized = LocalizedValidate::new(Validate::builder().title("Home").build())
+    ///     .language("es", Validate::builder().title("Inicio").build());
+    ///
+    /// let html = "<html><head><title>Inicio</title></head></html>";
+    /// let validate = localized.select_language("https://example.com/es/", None);
+    /// assert!(validate.check(false, StatusCode::OK, &HeaderMap::new(), html.as_bytes()).is_ok());
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("content-language", "es-ES".parse().unwrap());
+    /// let validate = localized.select_language("/node/1", Some(&headers));
+    /// assert!(validate.check(false, StatusCode::OK, &headers, html.as_bytes()).is_ok());
+    /// ```
+    pub fn select_language(&self, url: &str, headers: Option<&HeaderMap>) -> &Validate<'a> {
+        // Detect the language from the first segment of the path.
+        let path = match url.parse::<Uri>() {
+            Ok(uri) => uri.path().to_string(),
+            Err(_) => url.to_string(),
+        };
+        let prefix = path
+            .trim_start_matches('/')
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(validate) = self.languages.get(&prefix) {
+            return validate;
+        }
+
+        // Otherwise detect the language from the Content-Language header.
+        let content_language = headers
+            .and_then(|h| h.get("content-language"))
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(|v| v.trim().to_lowercase());
+        if let Some(language) = content_language {
+            if let Some(validate) = self.languages.get(&language) {
+                return validate;
+            }
+            let primary = language.split('-').next().unwrap_or_default();
+            if let Some(validate) = self.languages.get(primary) {
+                return validate;
+            }
+        }
+
+        &self.fallback
+    }
+}
+
+// Returns the percentage of lines the golden snapshot and the html have in common, after
+// removing regions matching any of the ignore regular expressions.
+fn snapshot_similarity(golden: &str, html: &str, ignores: &[Cow<str>]) -> u8 {
+    let mut golden = golden.to_string();
+    let mut html = html.to_string();
+    for ignore in ignores {
+        match Regex::new(ignore) {
+            Ok(re) => {
+                golden = re.replace_all(&golden, "").to_string();
+                html = re.replace_all(&html, "").to_string();
+            }
+            Err(e) => warn!("invalid snapshot ignore regex {:?}: {}", ignore, e),
+        }
+    }
+
+    // Count how many times each line appears in the snapshot.
+    let mut golden_lines: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut total = 0;
+    for line in golden.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        *golden_lines.entry(line).or_insert(0) += 1;
+        total += 1;
+    }
+
+    // Count how many lines of the html are also in the snapshot.
+    let mut common = 0;
+    for line in html.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        total += 1;
+        if let Some(count) = golden_lines.get_mut(line).filter(|c| **c > 0) {
+            *count -= 1;
+            common += 1;
+        }
+    }
+
+    if total == 0 {
+        100
+    } else {
+        (common * 2 * 100 / total) as u8
+    }
+}
+
+/// Use a regular expression to get the HTML header from the web page.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::get_html_header;
+///
+/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
+/// // you'd use the entire html snippet returned from [`validate_page`] or
+/// // [`validate_and_load_static_assets`].
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <meta charset="utf-8" />
+///     <link rel="canonical" href="https://example.com/" />
+///     <link rel="shortlink" href="https://example.com/" />
+///     <meta name="Generator" content="Drupal 9 (https://www.drupal.org)" />
+///     <meta name="MobileOptimized" content="width" />
+///     <meta name="HandheldFriendly" content="true" />
+///     <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+///     <title>Example Website</title>
+///   </head>
+/// <body>
+///   This is the web page body.
+/// </body>
+/// </html>
+/// "#;
+///
+/// let html_header = get_html_header(html);
+/// assert!(!html_header.is_none());
+/// ```
+pub fn get_html_header(html: &str) -> Option<String> {
+    let re = Regex::new(r#"<head(.*?)</head>"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    // Return the entire html header, a subset of the received html.
+    re.captures(&line).map(|value| value[0].to_string())
+}
+
+/// Use a regular expression to get the web page title.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{get_html_header, get_title};
+///
+/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
+/// // you'd use the entire html snippet returned from [`validate_page`] or
+/// // [`validate_and_load_static_assets`].
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <meta charset="utf-8" />
+///     <link rel="canonical" href="https://example.com/" />
+///     <link rel="shortlink" href="https://example.com/" />
+///     <meta name="Generator" content="Drupal 9 (https://www.drupal.org)" />
+///     <meta name="MobileOptimized" content="width" />
+///     <meta name="HandheldFriendly" content="true" />
+///     <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+///     <title>Example Website</title>
+///   </head>
+/// <body>
+///   This is the web page body.
+/// </body>
+/// </html>
+/// "#;
+///
+/// // Start by extracting the HTML header from the HTML.
+/// let html_header = get_html_header(html).map_or_else(|| "".to_string(), |h| h.to_string());
+/// // Next extract the title from the HTML header.
+/// let title = get_title(&html_header).map_or_else(|| "".to_string(), |t| t.to_string());
+/// assert_eq!(title, "Example Website");
+/// ```
+pub fn get_title(html: &str) -> Option<String> {
+    let re = Regex::new(r#"<title>(.*?)</title>"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    // Return the entire title, a subset of the received html.
+    re.captures(&line).map(|value| value[1].to_string())
+}
+
+/// Use a regular expression to get the canonical link from the web page.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{get_canonical, get_html_header};
+///
+/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
+/// // you'd use the entire html snippet returned from [`validate_page`] or
+/// // [`validate_and_load_static_assets`].
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <meta charset="utf-8" />
+///     <link rel="canonical" href="https://example.com/" />
+///     <link rel="shortlink" href="https://example.com/" />
+///     <title>Example Website</title>
+///   </head>
+/// <body>
+///   This is the web page body.
+/// </body>
+/// </html>
+/// "#;
+///
+/// // Start by extracting the HTML header from the HTML.
+/// let html_header = get_html_header(html).unwrap_or_default();
+/// // Next extract the canonical link from the HTML header.
+/// let canonical = get_canonical(&html_header).unwrap_or_default();
+/// assert_eq!(canonical, "https://example.com/");
+/// ```
+pub fn get_canonical(html: &str) -> Option<String> {
+    // Find the link tag first, as the `rel` and `href` attributes can be in any order.
+    let link = Regex::new(r#"(?i)<link[^>]*?rel=["']canonical["'][^>]*?>"#).unwrap();
+    let href = Regex::new(r#"(?i)href=["'](.*?)["']"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    // Return the canonical link, a subset of the received html.
+    link.find(&line)
+        .and_then(|tag| href.captures(tag.as_str()))
+        .map(|value| value[1].to_string())
+}
+
+/// Use a regular expression to get the translations of the web page declared with
+/// `<link rel="alternate" hreflang="...">`, keyed by language code.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{get_hreflang_links, get_html_header};
+///
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <link rel="canonical" href="https://example.com/en/recipes/deep-mediterranean-quiche" />
+///     <link rel="alternate" hreflang="en" href="https://example.com/en/recipes/deep-mediterranean-quiche" />
+///     <link rel="alternate" hreflang="es" href="https://example.com/es/recipes/quiche-mediterraneo-profundo" />
+///     <title>Deep mediterranean quiche</title>
+///   </head>
+/// </html>
+/// "#;
+///
+/// let html_header = get_html_header(html).unwrap_or_default();
+/// let translations = get_hreflang_links(&html_header);
+/// assert_eq!(
+///     translations.get("es").unwrap(),
+///     "https://example.com/es/recipes/quiche-mediterraneo-profundo"
+/// );
+/// ```
+pub fn get_hreflang_links(html: &str) -> BTreeMap<String, String> {
+    // Find the link tags first, as the attributes can be in any order.
+    let link = Regex::new(r#"(?i)<link[^>]*?rel=["']alternate["'][^>]*?>"#).unwrap();
+    let hreflang = Regex::new(r#"(?i)hreflang=["'](.*?)["']"#).unwrap();
+    let href = Regex::new(r#"(?i)\shref=["'](.*?)["']"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    let mut links = BTreeMap::new();
+    for tag in link.find_iter(&line) {
+        if let (Some(language), Some(url)) =
+            (hreflang.captures(tag.as_str()), href.captures(tag.as_str()))
+        {
+            links.insert(
+                language[1].to_string(),
+                html_escape::decode_html_entities(&url[1]).to_string(),
+            );
+        }
+    }
+    links
+}
+
+/// Use a regular expression to get the content of a meta tag from the web page, matched
+/// by either its `name` or its `property` attribute.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{get_html_header, get_meta};
+///
+/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
+/// // you'd use the entire html snippet returned from [`validate_page`] or
+/// // [`validate_and_load_static_assets`].
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <meta charset="utf-8" />
+///     <meta name="Generator" content="Drupal 9 (https://www.drupal.org)" />
+///     <meta property="og:title" content="Example Website" />
+///     <title>Example Website</title>
+///   </head>
+/// <body>
+///   This is the web page body.
+/// </body>
+/// </html>
+/// "#;
+///
+/// let html_header = get_html_header(html).unwrap_or_default();
+/// let og_title = get_meta(&html_header, "og:title").unwrap_or_default();
+/// assert_eq!(og_title, "Example Website");
+/// ```
+pub fn get_meta(html: &str, name: &str) -> Option<String> {
+    // Find the meta tag first, as the attributes can be in any order.
+    let meta = Regex::new(&format!(
+        r#"(?i)<meta[^>]*?(name|property)=["']{}["'][^>]*?>"#,
+        regex::escape(name)
+    ))
+    .unwrap();
+    let content = Regex::new(r#"(?i)content=["'](.*?)["']"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    // Return the meta tag content, a subset of the received html.
+    meta.find(&line)
+        .and_then(|tag| content.captures(tag.as_str()))
+        .map(|value| value[1].to_string())
+}
+
+/// Use a regular expression to get the charset declared in the web page, either with
+/// `<meta charset="...">` or with `<meta http-equiv="Content-Type" content="...; charset=...">`.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{get_charset, get_html_header};
+///
+/// let html = r#"
+/// <html lang="en" dir="ltr">
+///   <head>
+///     <meta charset="utf-8" />
+///     <title>Example Website</title>
+///   </head>
+/// <body>
+///   This is the web page body.
+/// </body>
+/// </html>
+/// "#;
+///
+/// let html_header = get_html_header(html).unwrap_or_default();
+/// let charset = get_charset(&html_header).unwrap_or_default();
+/// assert_eq!(charset, "utf-8");
+/// ```
+pub fn get_charset(html: &str) -> Option<String> {
+    let re = Regex::new(r#"(?i)<meta[^>]*?charset=["']?([\w-]+)"#).unwrap();
+    // Strip carriage returns to simplify regex.
+    let line = html.replace('\n', "");
+    // Return the charset, a subset of the received html.
+    re.captures(&line).map(|value| value[1].to_string())
+}
+
+/// Get the inner html of the first element matching a simple selector.
+///
+/// The selector is an optional tag name, followed by an optional `#id` and any number of
+/// `.class` names, for example `div#content`, `#main`, `.messages` or `ul.menu.primary`.
+/// Returns [`None`] if no element matches. If the matching element is never closed, the
+/// remainder of the html is returned.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::get_element;
+///
+/// let html = r#"
+/// <nav><a href="/cart">Thanks for your order</a></nav>
+/// <div id="content" class="main"><div>Order complete</div></div>
+/// "#;
+///
+/// assert_eq!(get_element(html, "div#content").unwrap(), "<div>Order complete</div>");
+/// assert_eq!(get_element(html, ".main").unwrap(), "<div>Order complete</div>");
+/// assert!(get_element(html, "div#sidebar").is_none());
+/// ```
+pub fn get_element(html: &str, selector: &str) -> Option<String> {
+    // Split the selector into a tag name, an id and a list of classes.
+    let re = Regex::new(r#"([#.]?)([\w-]+)"#).unwrap();
+    let mut tag = None;
+    let mut id = None;
+    let mut classes = Vec::new();
+    for part in re.captures_iter(selector) {
+        match &part[1] {
+            "#" => id = Some(part[2].to_string()),
+            "." => classes.push(part[2].to_string()),
+            _ => tag = Some(part[2].to_string()),
+        }
+    }
+
+    let start_tags = Regex::new(r#"<([a-zA-Z][\w-]*)(\s[^>]*)?>"#).unwrap();
+    let id_attribute = Regex::new(r#"(?i)\sid\s*=\s*["']([^"']*)["']"#).unwrap();
+    let class_attribute = Regex::new(r#"(?i)\sclass\s*=\s*["']([^"']*)["']"#).unwrap();
+    for start_tag in start_tags.captures_iter(html) {
+        let name = &start_tag[1];
+        let attributes = start_tag.get(2).map_or("", |a| a.as_str());
+        if matches!(&tag, Some(tag) if !tag.eq_ignore_ascii_case(name)) {
+            continue;
+        }
+        if let Some(id) = &id {
+            if !matches!(id_attribute.captures(attributes), Some(value) if &value[1] == id) {
+                continue;
+            }
+        }
+        if !classes.is_empty() {
+            let class = class_attribute
+                .captures(attributes)
+                .map(|value| value[1].to_string())
+                .unwrap_or_default();
+            if !classes
+                .iter()
+                .all(|c| class.split_whitespace().any(|value| value == c))
+            {
+                continue;
+            }
+        }
+
+        // Find the matching closing tag, allowing for nested elements of the same type.
+        let start = start_tag.get(0).unwrap().end();
+        let nested = Regex::new(&format!(r#"(?i)<(/?){}[\s>]"#, regex::escape(name))).unwrap();
+        let mut depth = 1;
+        for nested_tag in nested.captures_iter(&html[start..]) {
+            if nested_tag[1].is_empty() {
+                depth += 1;
+            } else {
+                depth -= 1;
+                if depth == 0 {
+                    let end = start + nested_tag.get(0).unwrap().start();
+                    return Some(html[start..end].to_string());
+                }
+            }
+        }
+        return Some(html[start..].to_string());
+    }
+
+    None
+}
+
+/// Get the charset declared in the `Content-Type` header, if any.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::get_header_charset;
+/// use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html; charset=UTF-8"));
+/// assert_eq!(get_header_charset(&headers).unwrap(), "UTF-8");
+/// ```
+pub fn get_header_charset(headers: &HeaderMap) -> Option<String> {
+    let re = Regex::new(r#"(?i)charset=["']?([\w-]+)"#).unwrap();
+    let content_type = headers.get("content-type")?.to_str().ok()?;
+    re.captures(content_type).map(|value| value[1].to_string())
+}
+
+/// Returns a [`bool`] indicating whether or not the title (case insensitive) on the
+/// webpage contains the provided string.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// A valid title is found between `<title></title>` tags inside `<head></head>` tags.
+/// For example, if the title is as follows:
+/// ```html
+/// <head>
+///   <title>this is the title</title>
+/// </head>
+/// ```
+///
+/// Then a call to `valid_title("the title")` will return [`true`], whereas a call to
+/// `valid_title("foo")` will return [`false`].
+///
+/// This function is case insensitive, so in the above example calling
+/// `valid_title("The Title")` and `valid_title("THE TITLE")` will both also return
+/// [`true`]. The function only tests if the title includes the specified text, the
+/// title can also include other text and will still be considered valid.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::valid_title;
+///
+/// transaction!(validate_title).set_on_start();
+///
+/// async fn validate_title(user: &mut GooseUser) -> TransactionResult {
+///     let mut goose = user.get("/").await?;
+///
+///     match goose.response {
+///         Ok(response) => {
+///             // Copy the headers so we have them for logging if there are errors.
+///             let headers = &response.headers().clone();
+///             match response.text().await {
+///                 Ok(html) => {
+///                     // Confirm that the HTML header includes the expected title.
+///                     let title = "example";
+///                     if !valid_title(&html, title) {
+///                         return user.set_failure(
+///                             &format!("{}: title not found: {}", goose.request.raw.url, title),
+///                             &mut goose.request,
+///                             Some(headers),
+///                             Some(&html),
+///                         );
+///                     }
+///                 }
+///                 Err(e) => {
+///                     return user.set_failure(
+///                         &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
+///                         &mut goose.request,
+///                         Some(headers),
+///                         None,
+///                     );
+///                 }
+///             }
+///         }
+///         Err(e) => {
+///             return user.set_failure(
+///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
+///                 &mut goose.request,
+///                 None,
+///                 None,
+///             );
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub fn valid_title(html: &str, title: &str) -> bool {
+    // Extract the HTML header from the provided html.
+    let html_header = get_html_header(html).unwrap_or_default();
+    // Next extract the title from the HTML header.
+    let html_title = get_title(&html_header).unwrap_or_default();
+    // Finally, confirm that the title contains the expected text.
+    html_title
+        .to_ascii_lowercase()
+        .contains(title.to_ascii_lowercase().as_str())
+}
+
+/// Returns [`Ok`] if the title (case insensitive) on the webpage contains the provided
+/// string, otherwise returns a [`ValidationError::TitleMismatch`] including the title
+/// that was found.
+///
+/// This is equivalent to [`valid_title`], but describes what was found so custom
+/// transactions can report useful failure messages.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{check_title, ValidationError};
+///
+/// let html = "<html><head><title>Example Website</title></head></html>";
+/// assert!(check_title(html, "example").is_ok());
+///
+/// match check_title(html, "foo") {
+///     Err(ValidationError::TitleMismatch { actual, .. }) => {
+///         assert_eq!(actual.as_deref(), Some("Example Website"));
+///     }
+///     _ => panic!("title should not match"),
+/// }
+/// ```
+pub fn check_title(html: &str, title: &str) -> Result<(), ValidationError> {
+    if valid_title(html, title) {
+        Ok(())
+    } else {
+        Err(ValidationError::TitleMismatch {
+            expected: title.to_string(),
+            actual: get_html_header(html).and_then(|header| get_title(&header)),
+        })
+    }
+}
+
+/// Returns a [`bool`] indicating whether or not an arbitrary str (case sensitive) is found
+/// within the html.
+///
+/// Returns [`true`] if the expected str is found, otherwise returns [`false`].
+///
+/// This function is case sensitive, if the text "foo" is specified it will only match "foo",
+/// not "Foo" or "FOO".
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::valid_text;
+///
+/// transaction!(validate_text).set_on_start();
+///
+/// async fn validate_text(user: &mut GooseUser) -> TransactionResult {
+///     let mut goose = user.get("/").await?;
+///
+///     match goose.response {
+///         Ok(response) => {
+///             // Copy the headers so we have them for logging if there are errors.
+///             let headers = &response.headers().clone();
+///             match response.text().await {
+///                 Ok(html) => {
+///                     let text = r#"<code class="language-console">$ cargo new hello_world --bin"#;
+///                     if !valid_text(&html, text) {
+///                         return user.set_failure(
+///                             &format!("{}: text not found: {}", goose.request.raw.url, text),
+///                             &mut goose.request,
+///                             Some(headers),
+///                             Some(&html),
+///                         );
+///                     }
+///                 }
+///                 Err(e) => {
+///                     return user.set_failure(
+///                         &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
+///                         &mut goose.request,
+///                         Some(headers),
+///                         None,
+///                     );
+///                 }
+///             }
+///         }
+///         Err(e) => {
+///             return user.set_failure(
+///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
+///                 &mut goose.request,
+///                 None,
+///                 None,
+///             );
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub fn valid_text(html: &str, text: &str) -> bool {
+    html.contains(text)
+}
+
+/// Returns [`Ok`] if an arbitrary str (case sensitive) is found within the html, otherwise
+/// returns a [`ValidationError::MissingText`].
+///
+/// This is equivalent to [`valid_text`], but returns an error that can be displayed as a
+/// failure message by custom transactions.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::check_text;
+///
+/// let html = "<html><body>Example text</body></html>";
+/// assert!(check_text(html, "Example").is_ok());
+/// assert_eq!(
+///     check_text(html, "example").unwrap_err().to_string(),
+///     "text not found on page: example"
+/// );
+/// ```
+pub fn check_text(html: &str, text: &str) -> Result<(), ValidationError> {
+    if valid_text(html, text) {
+        Ok(())
+    } else {
+        Err(ValidationError::MissingText {
+            text: text.to_string(),
+            selector: None,
+        })
+    }
+}
+
+/// Returns a [`bool`] indicating whether or not a form identified by the specified `id` or
+/// `data-drupal-selector` attribute is found within the html.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::valid_form;
+///
+/// let html = r#"<form class="user-login-form" data-drupal-selector="user-login-form" action="/user/login" method="post" id="user-login-form">"#;
+/// assert!(valid_form(html, "user-login-form"));
+/// assert!(!valid_form(html, "search-block-form"));
+/// ```
+pub fn valid_form(html: &str, name: &str) -> bool {
+    let re = Regex::new(&format!(
+        r#"(?i)<form\s[^>]*(data-drupal-selector|id)\s*=\s*["']{}["']"#,
+        regex::escape(name)
+    ))
+    .unwrap();
+    re.is_match(html)
+}
+
+/// Returns a [`bool`] indicating whether or not an arbitrary byte sequence is found within
+/// the body.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::valid_bytes;
+///
+/// let body = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+/// assert!(valid_bytes(&body, &[0x89, b'P', b'N', b'G']));
+/// assert!(!valid_bytes(&body, b"%PDF"));
+/// ```
+pub fn valid_bytes(body: &[u8], bytes: &[u8]) -> bool {
+    bytes.is_empty() || body.windows(bytes.len()).any(|window| window == bytes)
+}
+
+/// Returns a [`bool`] indicating whether or not the html appears to have been fully
+/// delivered, rather than truncated.
+///
+/// This is a heuristic, not an html parser. Returns [`false`] if the html ends in the
+/// middle of a tag or a comment, if a `<script>`, `<style>`, `<textarea>` or `<title>`
+/// element is opened without being closed, or if the `<html>` root element is opened
+/// without being closed. Closing tags that html allows to be omitted, including `</head>`,
+/// `</body>` and `</p>`, are not required.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::valid_html;
+///
+/// assert!(valid_html("<html><head><title>Foo</title></head><body><p>Bar</body></html>"));
+/// // The head and body don't have to be closed.
+/// assert!(valid_html("<html><head><title>Foo</title><body><p>Bar</html>"));
+/// assert!(valid_html("<!DOCTYPE html><title>Foo</title><p>Bar"));
+/// // Truncated in the middle of a tag.
+/// assert!(!valid_html("<html><head><title>Foo</title></head><body><p>Bar</bo"));
+/// // Truncated in the middle of a script.
+/// assert!(!valid_html("<title>Foo</title><script>let bar = 1;"));
+/// // Truncated before the root element was closed.
+/// assert!(!valid_html("<html><head><title>Foo</title></head><body><p>Bar"));
+/// ```
+pub fn valid_html(html: &str) -> bool {
+    // The html must not end in the middle of a tag.
+    if let Some(open) = html.rfind('<') {
+        if !matches!(html.rfind('>'), Some(close) if close > open) {
+            info!("html truncated in the middle of a tag");
+            return false;
+        }
+    }
+
+    // The html must not end in the middle of a comment.
+    if let Some(open) = html.rfind("<!--") {
+        if !html[open..].contains("-->") {
+            info!("html truncated in the middle of a comment");
+            return false;
+        }
+    }
+
+    // Elements whose closing tag can't be omitted, and the root element, must be closed if
+    // opened.
+    for element in ["script", "style", "textarea", "title", "html"] {
+        let opened = Regex::new(&format!(r#"(?i)<{}[\s>]"#, element))
+            .unwrap()
+            .find_iter(html)
+            .count();
+        let closed = Regex::new(&format!(r#"(?i)</{}\s*>"#, element))
+            .unwrap()
+            .find_iter(html)
+            .count();
+        if opened > closed {
+            info!("html truncated before closing <{}>", element);
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns a [`bool`] indicating whether or not a header was set in the server Response.
+///
+/// Returns [`true`] if the expected header was set, otherwise returns [`false`].
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::header_is_set;
+///
+/// transaction!(validate_header).set_on_start();
+///
+/// async fn validate_header(user: &mut GooseUser) -> TransactionResult {
+///     let mut goose = user.get("/").await?;
+///
+///     match goose.response {
+///         Ok(response) => {
+///             // Copy the headers so we have them for logging if there are errors.
+///             let headers = &response.headers().clone();
+///             if !header_is_set(headers, "server") {
+///                 return user.set_failure(
+///                     &format!("{}: header not found: {}", goose.request.raw.url, "server"),
+///                     &mut goose.request,
+///                     Some(headers),
+///                     None,
+///                 );
+///             }
+///         }
+///         Err(e) => {
+///             return user.set_failure(
+///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
+///                 &mut goose.request,
+///                 None,
+///                 None,
+///             );
+///         }
+///     }
 ///
-/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
-/// // you'd use the entire html snippet returned from [`validate_page`] or
-/// // [`validate_and_load_static_assets`].
-/// let html = r#"
-/// <html lang="en" dir="ltr">
-///   <head>
-///     <meta charset="utf-8" />
-///     <link rel="canonical" href="https://example.com/" />
-///     <link rel="shortlink" href="https://example.com/" />
-///     <meta name="Generator" content="Drupal 9 (https://www.drupal.org)" />
-///     <meta name="MobileOptimized" content="width" />
-///     <meta name="HandheldFriendly" content="true" />
-///     <meta name="viewport" content="width=device-width, initial-scale=1.0" />
-///     <title>Example Website</title>
-///   </head>
-/// <body>
-///   This is the web page body.
-/// </body>
-/// </html>
-/// "#;
+///     Ok(())
+/// }
+/// ```
+pub fn header_is_set(headers: &HeaderMap, header: &str) -> bool {
+    headers.contains_key(header)
+}
+
+/// Returns a [`bool`] indicating whether or not a header contains an expected value.
 ///
-/// let html_header = get_html_header(html);
-/// assert!(!html_header.is_none());
+/// Returns [`true`] if the expected value was found, otherwise returns [`false`].
+///
+/// Expects a [`&str`] [`tuple`] with a length of 2 where the first defines the header
+/// name and the second defines the header value, ie `("name", "value")`.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::valid_header_value;
+///
+/// transaction!(validate_header_value).set_on_start();
+///
+/// async fn validate_header_value(user: &mut GooseUser) -> TransactionResult {
+///     let mut goose = user.get("/").await?;
+///
+///     match goose.response {
+///         Ok(response) => {
+///             // Copy the headers so we have them for logging if there are errors.
+///             let headers = &response.headers().clone();
+///             if !valid_header_value(headers, ("server", "nginx")) {
+///                 return user.set_failure(
+///                     &format!("{}: server header value not correct: {}", goose.request.raw.url, "nginx"),
+///                     &mut goose.request,
+///                     Some(headers),
+///                     None,
+///                 );
+///             }
+///         }
+///         Err(e) => {
+///             return user.set_failure(
+///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
+///                 &mut goose.request,
+///                 None,
+///                 None,
+///             );
+///         }
+///     }
+///
+///     Ok(())
+/// }
 /// ```
-pub fn get_html_header(html: &str) -> Option<String> {
-    let re = Regex::new(r#"<head(.*?)</head>"#).unwrap();
-    // Strip carriage returns to simplify regex.
-    let line = html.replace('\n', "");
-    // Return the entire html header, a subset of the received html.
-    re.captures(&line).map(|value| value[0].to_string())
+pub fn valid_header_value<'a>(headers: &HeaderMap, header: (&'a str, &'a str)) -> bool {
+    // A header name is required, exit early if it's empty.
+    if header.0.is_empty() {
+        info!("no header specified");
+        return false;
+    }
+
+    if header_is_set(headers, header.0) {
+        if header.1.is_empty() {
+            false
+        } else {
+            let header_value = match headers.get(header.0) {
+                // Extract the value of the header and try to convert to a &str.
+                Some(v) => v.to_str().unwrap_or(""),
+                None => "",
+            };
+            // Check if the desired value is in the header.
+            if header_value.contains(header.1) {
+                true
+            } else {
+                // Provide some extra debug.
+                info!(
+                    r#"header does not contain expected value: "{}: {}""#,
+                    header.0, header.1
+                );
+                false
+            }
+        }
+    } else {
+        info!("header ({}) not set", header.0);
+        false
+    }
 }
 
-/// Use a regular expression to get the web page title.
+/// Returns [`Ok`] if a header contains an expected value, otherwise returns a
+/// [`ValidationError::HeaderMissing`] if the header is not set or a
+/// [`ValidationError::HeaderValueMismatch`] if it doesn't contain the value.
+///
+/// This is equivalent to [`valid_header_value`], but describes what was found so custom
+/// transactions can report useful failure messages.
 ///
 /// # Example
 /// ```rust
-/// use goose_eggs::{get_html_header, get_title};
+/// use goose_eggs::{check_header_value, ValidationError};
+/// use reqwest::header::HeaderMap;
 ///
-/// // For this example we grab just a subset of a web page, enough to demonstrate. Normally
-/// // you'd use the entire html snippet returned from [`validate_page`] or
-/// // [`validate_and_load_static_assets`].
-/// let html = r#"
-/// <html lang="en" dir="ltr">
-///   <head>
-///     <meta charset="utf-8" />
-///     <link rel="canonical" href="https://example.com/" />
-///     <link rel="shortlink" href="https://example.com/" />
-///     <meta name="Generator" content="Drupal 9 (https://www.drupal.org)" />
-///     <meta name="MobileOptimized" content="width" />
-///     <meta name="HandheldFriendly" content="true" />
-///     <meta name="viewport" content="width=device-width, initial-scale=1.0" />
-///     <title>Example Website</title>
-///   </head>
-/// <body>
-///   This is the web page body.
-/// </body>
-/// </html>
-/// "#;
+/// let mut headers = HeaderMap::new();
+/// headers.insert("server", "nginx".parse().unwrap());
+/// assert!(check_header_value(&headers, ("server", "nginx")).is_ok());
+/// assert_eq!(
+///     check_header_value(&headers, ("server", "apache")),
+///     Err(ValidationError::HeaderValueMismatch {
+///         header: "server".to_string(),
+///         value: "apache".to_string(),
+///     })
+/// );
+/// assert!(matches!(
+///     check_header_value(&headers, ("x-cache", "HIT")),
+///     Err(ValidationError::HeaderMissing { .. })
+/// ));
+/// ```
+pub fn check_header_value(
+    headers: &HeaderMap,
+    header: (&str, &str),
+) -> Result<(), ValidationError> {
+    if valid_header_value(headers, header) {
+        Ok(())
+    } else if header_is_set(headers, header.0) {
+        Err(ValidationError::HeaderValueMismatch {
+            header: header.0.to_string(),
+            value: header.1.to_string(),
+        })
+    } else {
+        Err(ValidationError::HeaderMissing {
+            header: header.0.to_string(),
+        })
+    }
+}
+
+/// Returns a [`bool`] indicating whether or not the response was served with the specified
+/// content encoding (case insensitive).
+///
+/// If `encoding` is empty, returns [`true`] if the response was served with any of the
+/// `gzip`, `br`, `deflate` or `zstd` content encodings.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::valid_content_encoding;
+/// use reqwest::header::{HeaderMap, HeaderValue, CONTENT_ENCODING};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(CONTENT_ENCODING, HeaderValue::from_static("br"));
+/// assert!(valid_content_encoding(&headers, "br"));
+/// assert!(valid_content_encoding(&headers, ""));
+/// assert!(!valid_content_encoding(&headers, "gzip"));
+/// ```
+pub fn valid_content_encoding(headers: &HeaderMap, encoding: &str) -> bool {
+    let compressed = ["gzip", "br", "deflate", "zstd"];
+    headers
+        .get_all("content-encoding")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        // Multiple encodings can be applied, separated by commas.
+        .flat_map(|value| value.split(','))
+        .map(|value| value.trim())
+        .any(|value| {
+            if encoding.is_empty() {
+                compressed.iter().any(|c| value.eq_ignore_ascii_case(c))
+            } else {
+                value.eq_ignore_ascii_case(encoding)
+            }
+        })
+}
+
+/// Get the `max-age` directive from the `Cache-Control` header, if any.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::get_cache_control_max_age;
+/// use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(CACHE_CONTROL, HeaderValue::from_static("public, max-age=3600"));
+/// assert_eq!(get_cache_control_max_age(&headers), Some(3600));
+/// ```
+pub fn get_cache_control_max_age(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get_all("cache-control")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|directive| {
+            let mut parts = directive.trim().splitn(2, '=');
+            let name = parts.next()?.trim();
+            if name.eq_ignore_ascii_case("max-age") {
+                parts.next()?.trim().trim_matches('"').parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
+/// Helper to confirm the URI is valid and local.
+fn valid_local_uri(user: &mut GooseUser, uri: &str) -> bool {
+    resolve_uri(user, uri).is_some_and(|url| url.host_str() == user.base_url.host_str())
+}
+
+// Resolve the URI against the base_url like a browser would, including relative paths,
+// protocol-relative URIs, query strings and fragments. Returns None if the URI is empty or
+// doesn't resolve to an http or https url, such as inline `data:` URIs.
+fn resolve_uri(user: &GooseUser, uri: &str) -> Option<Url> {
+    let uri = uri.trim();
+    if uri.is_empty() {
+        return None;
+    }
+    let url = user.base_url.join(uri).ok()?;
+    matches!(url.scheme(), "http" | "https").then_some(url)
+}
+
+/// Extract all local static elements defined with a `src=` tag from the the provided html.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_src_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, src_urls(html))
+}
+
+// Extract the urls of all elements defined with a `src=` tag, local or not.
+#[cfg(not(feature = "html-parser"))]
+fn src_urls(html: &str) -> Vec<String> {
+    // Use a case-insensitive regular expression to find all src=<foo> in the html, where
+    // <foo> is the URL to image and js assets.
+    // @TODO: parse HTML5 srcset= also
+    attribute_urls(html, r#"(?i)\ssrc="(.*?)""#)
+}
+
+// Extract the urls of all elements defined with a `src=` tag, local or not.
+#[cfg(feature = "html-parser")]
+fn src_urls(html: &str) -> Vec<String> {
+    parsed_attribute_urls(html, "[src]", "src")
+        .into_iter()
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+// Extract the urls of all css assets defined with a `href=` tag, local or not.
+#[cfg(not(feature = "html-parser"))]
+fn css_href_urls(html: &str) -> Vec<String> {
+    // Use a case-insensitive regular expression to find all href=<foo> in the html, where
+    // <foo> is the URL to css assets.
+    attribute_urls(html, r#"(?i)href="(.*?\.css.*?)""#)
+}
+
+// Extract the urls of all css assets defined with a `href=` tag, local or not.
+#[cfg(feature = "html-parser")]
+fn css_href_urls(html: &str) -> Vec<String> {
+    parsed_attribute_urls(html, "[href]", "href")
+        .into_iter()
+        .filter(|url| url.to_ascii_lowercase().contains(".css"))
+        .collect()
+}
+
+// Parse the html and extract the value of the attribute of all elements matching the
+// selector, including elements within `<noscript>` blocks.
+#[cfg(feature = "html-parser")]
+fn parsed_attribute_urls(html: &str, selector: &str, attribute: &str) -> Vec<String> {
+    let document = scraper::Html::parse_document(html);
+    let selector = scraper::Selector::parse(selector).unwrap();
+    let mut urls: Vec<String> = document
+        .select(&selector)
+        .filter_map(|element| element.value().attr(attribute))
+        .map(|url| url.trim().to_string())
+        .collect();
+    // The parser treats the content of <noscript> blocks as text, as a browser with
+    // javascript enabled would, so it's parsed separately.
+    let noscript = scraper::Selector::parse("noscript").unwrap();
+    for block in document.select(&noscript) {
+        let fragment = scraper::Html::parse_fragment(&block.text().collect::<String>());
+        urls.extend(
+            fragment
+                .select(&selector)
+                .filter_map(|element| element.value().attr(attribute))
+                .map(|url| url.trim().to_string()),
+        );
+    }
+    urls
+}
+
+/// Extract all local css elements defined with a `href=` tag from the the provided html.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_css_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, css_href_urls(html))
+}
+
+/// Extract one local image candidate for each `<picture>` element in the provided html.
+///
+/// Media queries can't be evaluated, so the first `<source>` without a `media` attribute
+/// is preferred, otherwise the first `<source>`, and its first `srcset=` candidate is
+/// selected. Fallback `<img>` elements are extracted by [`get_src_elements`].
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_picture_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, picture_urls(html))
+}
+
+// Extract one image candidate for each `<picture>` element, local or not.
+fn picture_urls(html: &str) -> Vec<String> {
+    let picture = Regex::new(r#"(?is)<picture[\s>](.*?)</picture>"#).unwrap();
+    let source = Regex::new(r#"(?is)<source\s[^>]*>"#).unwrap();
+    let srcset = Regex::new(r#"(?i)srcset="(.*?)""#).unwrap();
+    let media = Regex::new(r#"(?i)\smedia="#).unwrap();
+    let mut elements: Vec<String> = Vec::new();
+    let html = html_escape::decode_html_entities(html);
+    for picture in picture.captures_iter(&html) {
+        let sources = source
+            .find_iter(&picture[1])
+            .map(|source| source.as_str())
+            .filter(|source| srcset.is_match(source))
+            .collect::<Vec<_>>();
+        let selected = sources
+            .iter()
+            .find(|source| !media.is_match(source))
+            .or_else(|| sources.first());
+        let candidate = selected
+            .and_then(|source| srcset.captures(source))
+            .and_then(|srcset| {
+                srcset[1]
+                    .split(',')
+                    .filter_map(|candidate| candidate.split_whitespace().next())
+                    .map(|url| url.to_string())
+                    .next()
+            });
+        elements.extend(candidate);
+    }
+    elements
+}
+
+/// Extract all local assets hinted with `<link rel="preload">` and an `as=` asset type, or
+/// with `<link rel="modulepreload">`, from the provided html, which browsers fetch even
+/// though they may not be referenced with a `src=` tag.
+///
+/// Documents hinted with `rel="prefetch"` and `rel="prerender"` are not extracted, as
+/// they're only fetched for a possible next navigation. Refer to
+/// [`LoadAssetsBuilder::prefetch`] to load them.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_preload_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    let urls = preload_urls(html)
+        .into_iter()
+        .filter(|(_, resource_type)| *resource_type != "prefetch")
+        .map(|(url, _)| url)
+        .collect();
+    local_urls(user, urls)
+}
+
+// Extract the urls of preload, prefetch and prerender hints, local or not, with the type
+// of each asset.
+fn preload_urls(html: &str) -> Vec<(String, &'static str)> {
+    let link = Regex::new(r#"(?is)<link\s[^>]*>"#).unwrap();
+    let rel =
+        Regex::new(r#"(?i)\srel="\s*(preload|modulepreload|prefetch|prerender)\s*""#).unwrap();
+    let as_type = Regex::new(r#"(?i)\sas="(.*?)""#).unwrap();
+    let mime_type = Regex::new(r#"(?i)\stype="(.*?)""#).unwrap();
+    let href = Regex::new(r#"(?i)\shref="(.*?)""#).unwrap();
+    let mut elements = Vec::new();
+    let html = html_escape::decode_html_entities(html);
+    for link in link.find_iter(&html) {
+        let link = link.as_str();
+        let rel = match rel.captures(link) {
+            Some(rel) => rel[1].to_ascii_lowercase(),
+            None => continue,
+        };
+        let url = match href.captures(link) {
+            Some(url) => url[1].to_string(),
+            None => continue,
+        };
+        let resource_type = preload_type(
+            &rel,
+            as_type.captures(link).map(|t| t[1].to_string()).as_deref(),
+            mime_type
+                .captures(link)
+                .map(|t| t[1].to_string())
+                .as_deref(),
+            &url,
+        );
+        if let Some(resource_type) = resource_type {
+            elements.push((url, resource_type));
+        }
+    }
+    elements
+}
+
+// Extract the urls of preload hints declared with `Link` response headers, such as
+// `</assets/app.css>; rel=preload; as=style`, local or not, with the type of each asset.
+fn link_header_urls(headers: &HeaderMap) -> Vec<(String, &'static str)> {
+    let link = Regex::new(r#"<([^>]*)>([^,<]*)"#).unwrap();
+    let mut elements = Vec::new();
+    let values = headers
+        .get_all("link")
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+    for value in values {
+        for link in link.captures_iter(value) {
+            let mut params = BTreeMap::new();
+            for param in link[2].split(';') {
+                if let Some((name, value)) = param.split_once('=') {
+                    params.insert(
+                        name.trim().to_ascii_lowercase(),
+                        value.trim().trim_matches('"').to_string(),
+                    );
+                }
+            }
+            let rel = params.get("rel").and_then(|rel| {
+                rel.split_whitespace()
+                    .map(|rel| rel.to_ascii_lowercase())
+                    .find(|rel| {
+                        ["preload", "modulepreload", "prefetch", "prerender"]
+                            .contains(&rel.as_str())
+                    })
+            });
+            if let Some(rel) = rel {
+                let url = link[1].trim().to_string();
+                let resource_type = preload_type(
+                    &rel,
+                    params.get("as").map(|t| t.as_str()),
+                    params.get("type").map(|t| t.as_str()),
+                    &url,
+                );
+                if let Some(resource_type) = resource_type {
+                    elements.push((url, resource_type));
+                }
+            }
+        }
+    }
+    elements
+}
+
+// Returns the type of a hinted asset, by the `rel`, `as` and `type` of the hint, or
+// otherwise by the file extension of its url. Documents prefetched or prerendered for the
+// next navigation are typed `prefetch`. Returns None for preload hints without an asset
+// type in `as`, which browsers ignore.
+fn preload_type(
+    rel: &str,
+    as_type: Option<&str>,
+    mime_type: Option<&str>,
+    url: &str,
+) -> Option<&'static str> {
+    match rel {
+        "modulepreload" => return Some("js"),
+        "prefetch" | "prerender" => return Some("prefetch"),
+        _ => (),
+    }
+    match as_type.map(|t| t.to_ascii_lowercase()).as_deref() {
+        Some("script") | Some("worker") => Some("js"),
+        Some("style") => Some("css"),
+        Some("font") => Some("font"),
+        Some("image") => Some("img"),
+        Some("audio") | Some("video") | Some("track") | Some("fetch") => Some(
+            mime_type
+                .and_then(content_type_asset_type)
+                .or_else(|| extension_asset_type(url))
+                .unwrap_or("preload"),
+        ),
+        _ => None,
+    }
+}
+
+/// Extract all local images that are lazy loaded by javascript, defined with a `data-src=`
+/// tag or with the first candidate of a `data-srcset=` tag, from the provided html.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] with a [`Validate`] object configured to load
+/// lazy loaded images with [`LoadAssetsBuilder::lazy_load`].
+pub async fn get_lazy_load_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, lazy_load_urls(html))
+}
+
+// Extract the urls of all lazy loaded images, local or not.
+fn lazy_load_urls(html: &str) -> Vec<String> {
+    let data_src = Regex::new(r#"(?i)\sdata-src(set)?="(.*?)""#).unwrap();
+    let mut elements: Vec<String> = Vec::new();
+    for url in data_src.captures_iter(html_escape::decode_html_entities(html).as_ref()) {
+        // Only the first candidate of a srcset is loaded.
+        let url = match url.get(1) {
+            Some(_) => url[2].split(',').find_map(|c| c.split_whitespace().next()),
+            None => Some(&url[2]),
+        };
+        if let Some(url) = url {
+            elements.push(url.to_string());
+        }
+    }
+    elements
+}
+
+/// Extract all local assets referenced with `url()` in inline `style=` attributes and in
+/// `<style>` blocks from the provided html, such as hero images declared with inline css.
 ///
-/// // Start by extracting the HTML header from the HTML.
-/// let html_header = get_html_header(html).map_or_else(|| "".to_string(), |h| h.to_string());
-/// // Next extract the title from the HTML header.
-/// let title = get_title(&html_header).map_or_else(|| "".to_string(), |t| t.to_string());
-/// assert_eq!(title, "Example Website");
-/// ```
-pub fn get_title(html: &str) -> Option<String> {
-    let re = Regex::new(r#"<title>(.*?)</title>"#).unwrap();
-    // Strip carriage returns to simplify regex.
-    let line = html.replace('\n', "");
-    // Return the entire title, a subset of the received html.
-    re.captures(&line).map(|value| value[1].to_string())
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_inline_style_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, inline_style_urls(html))
 }
 
-/// Returns a [`bool`] indicating whether or not the title (case insensitive) on the
-/// webpage contains the provided string.
+// Extract the urls of all assets referenced by inline css, local or not.
+fn inline_style_urls(html: &str) -> Vec<String> {
+    let style_attribute = Regex::new(r#"(?i)\sstyle="(.*?)""#).unwrap();
+    let style_block = Regex::new(r#"(?is)<style[^>]*>(.*?)</style>"#).unwrap();
+    let mut elements: Vec<String> = Vec::new();
+    let html = html_escape::decode_html_entities(html);
+    let styles = style_attribute
+        .captures_iter(&html)
+        .chain(style_block.captures_iter(&html));
+    for style in styles {
+        elements.extend(css_urls(&style[1]));
+    }
+    elements
+}
+
+// Extract all urls referenced with `url()` in the provided css, other than data urls.
+fn css_urls(css: &str) -> Vec<String> {
+    let url = Regex::new(r#"(?i)url\(\s*['"]?(.*?)['"]?\s*\)"#).unwrap();
+    url.captures_iter(css)
+        .map(|url| url[1].to_string())
+        .filter(|url| !url.is_empty() && !url.to_ascii_lowercase().starts_with("data:"))
+        .collect()
+}
+
+/// Extract all local documents embedded with `<object data=>` and `<embed src=>` elements,
+/// such as PDFs and SVGs, from the provided html.
 ///
 /// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_object_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, object_urls(html))
+}
+
+// Extract the urls of all embedded documents, local or not.
+fn object_urls(html: &str) -> Vec<String> {
+    attribute_urls(
+        html,
+        r#"(?i)<(?:object\s(?:[^>]*?\s)?data|embed\s(?:[^>]*?\s)?src)="(.*?)""#,
+    )
+}
+
+/// Extract all external local svg sprite files referenced with `<use href=>` or
+/// `<use xlink:href=>` from the provided html. Each sprite file is only extracted once,
+/// without the fragment identifying the icon, and references to icons defined within the
+/// page are ignored.
 ///
-/// A valid title is found between `<title></title>` tags inside `<head></head>` tags.
-/// For example, if the title is as follows:
-/// ```html
-/// <head>
-///   <title>this is the title</title>
-/// </head>
-/// ```
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_svg_use_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, svg_use_urls(html))
+}
+
+// Extract the urls of all external svg sprite files, local or not.
+fn svg_use_urls(html: &str) -> Vec<String> {
+    let svg_use = Regex::new(r#"(?i)<use\s(?:[^>]*?\s)?(?:xlink:)?href="(.*?)""#).unwrap();
+    let mut elements: Vec<String> = Vec::new();
+    for url in svg_use.captures_iter(html_escape::decode_html_entities(html).as_ref()) {
+        let url = url[1].split('#').next().unwrap_or_default().to_string();
+        if !url.is_empty() && !elements.contains(&url) {
+            elements.push(url);
+        }
+    }
+    elements
+}
+
+/// Extract all local video and audio assets defined with a `src=` tag on a `<video>` or
+/// `<audio>` element, or on a `<source>` element nested within one, from the provided html.
 ///
-/// Then a call to `valid_title("the title")` will return [`true`], whereas a call to
-/// `valid_title("foo")` will return [`false`].
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
+pub async fn get_media_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
+    local_urls(user, media_urls(html))
+}
+
+// Extract the urls of all video and audio assets, local or not.
+fn media_urls(html: &str) -> Vec<String> {
+    let media = Regex::new(r#"(?is)<(video|audio)[\s>].*?</(video|audio)>"#).unwrap();
+    let src = Regex::new(r#"(?i)<(?:video|audio|source)\s[^>]*?src="(.*?)""#).unwrap();
+    let mut elements: Vec<String> = Vec::new();
+    let html = html_escape::decode_html_entities(html);
+    for media in media.find_iter(&html) {
+        for url in src.captures_iter(media.as_str()) {
+            elements.push(url[1].to_string());
+        }
+    }
+    elements
+}
+
+/// Extract and load all local static elements from the the provided html.
 ///
-/// This function is case insensitive, so in the above example calling
-/// `valid_title("The Title")` and `valid_title("THE TITLE")` will both also return
-/// [`true`]. The function only tests if the title includes the specified text, the
-/// title can also include other text and will still be considered valid.
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] which in turn invokes this function.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::valid_title;
+/// use goose_eggs::load_static_elements;
 ///
-/// transaction!(validate_title).set_on_start();
+/// transaction!(load_page_and_static_elements).set_on_start();
 ///
-/// async fn validate_title(user: &mut GooseUser) -> TransactionResult {
+/// async fn load_page_and_static_elements(user: &mut GooseUser) -> TransactionResult {
 ///     let mut goose = user.get("/").await?;
 ///
 ///     match goose.response {
@@ -714,16 +4442,8 @@ pub fn get_title(html: &str) -> Option<String> {
 ///             let headers = &response.headers().clone();
 ///             match response.text().await {
 ///                 Ok(html) => {
-///                     // Confirm that the HTML header includes the expected title.
-///                     let title = "example";
-///                     if !valid_title(&html, title) {
-///                         return user.set_failure(
-///                             &format!("{}: title not found: {}", goose.request.raw.url, title),
-///                             &mut goose.request,
-///                             Some(headers),
-///                             Some(&html),
-///                         );
-///                     }
+///                     // Load all static elements on page.
+///                     load_static_elements(user, &html).await;
 ///                 }
 ///                 Err(e) => {
 ///                     return user.set_failure(
@@ -748,615 +4468,1560 @@ pub fn get_title(html: &str) -> Option<String> {
 ///     Ok(())
 /// }
 /// ```
-pub fn valid_title(html: &str, title: &str) -> bool {
-    // Extract the HTML header from the provided html.
-    let html_header = get_html_header(html).map_or_else(|| "".to_string(), |h| h);
-    // Next extract the title from the HTML header.
-    let html_title = get_title(&html_header).map_or_else(|| "".to_string(), |t| t);
-    // Finally, confirm that the title contains the expected text.
-    html_title
-        .to_ascii_lowercase()
-        .contains(title.to_ascii_lowercase().as_str())
+pub async fn load_static_elements(user: &mut GooseUser, html: &str) -> LoadedAssets {
+    load_assets(user, html, &LoadAssets::default()).await
+}
+
+/// Extract and load all local static elements from the the provided html, as configured
+/// by the [`LoadAssets`] object.
+///
+/// While you can invoke this function directly, it's generally preferred to invoke
+/// [`validate_and_load_static_assets`] with a [`Validate`] object built with
+/// [`ValidateBuilder::assets`], which in turn invokes this function.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::{load_assets, LoadAssets};
+///
+/// transaction!(load_media_page).set_on_start();
+///
+/// async fn load_media_page(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("/videos").await?;
+///
+///     if let Ok(response) = goose.response {
+///         if let Ok(html) = response.text().await {
+///             // Only request the headers of videos found on the page.
+///             load_assets(user, &html, &LoadAssets::builder().media_head().build()).await;
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn load_assets(user: &mut GooseUser, html: &str, assets: &LoadAssets) -> LoadedAssets {
+    let urls = extract_static_urls(user, html, None, assets).await;
+    load_static_urls(user, urls, assets, None).await
+}
+
+// Extract the urls of all local static assets, and of static assets on allowed hosts,
+// with the type of each asset. Assets preloaded with `Link` headers are also extracted
+// if the response headers are provided.
+async fn extract_static_urls(
+    user: &mut GooseUser,
+    html: &str,
+    headers: Option<&HeaderMap>,
+    assets: &LoadAssets,
+) -> Vec<(String, &'static str)> {
+    // Optionally ignore <noscript> blocks, which browsers with javascript don't render.
+    let html = if assets.skip_noscript {
+        let noscript = Regex::new(r#"(?is)<noscript[\s>].*?</noscript>"#).unwrap();
+        noscript.replace_all(html, "")
+    } else {
+        Cow::Borrowed(html)
+    };
+    let html = html.as_ref();
+
+    let mut urls = Vec::new();
+    let media_urls = media_urls(html);
+    let object_urls = object_urls(html);
+
+    // @TODO: parse HTML5 srcset= outside of <picture> also
+    for url in src_urls(html) {
+        // Video, audio and embedded sources are loaded as media and objects below.
+        if media_urls.contains(&url) || object_urls.contains(&url) {
+            continue;
+        }
+        let resource_type = extension_asset_type(&url).unwrap_or("img");
+        urls.push((url, resource_type));
+    }
+    for url in picture_urls(html) {
+        urls.push((url, "img"));
+    }
+    if assets.lazy_load {
+        for url in lazy_load_urls(html) {
+            urls.push((url, "img"));
+        }
+    }
+    for url in svg_use_urls(html) {
+        urls.push((url, "img"));
+    }
+    for url in inline_style_urls(html) {
+        // Fonts are typically declared with @font-face in <style> blocks.
+        let resource_type = extension_asset_type(&url).unwrap_or("img");
+        urls.push((url, resource_type));
+    }
+    for url in attribute_urls(html, r#"(?i)\sposter="(.*?)""#) {
+        urls.push((url, "img"));
+    }
+    for url in media_urls {
+        urls.push((url, "media"));
+    }
+    for url in object_urls {
+        urls.push((url, "object"));
+    }
+
+    for url in css_href_urls(html) {
+        urls.push((url, "css"));
+    }
+
+    urls.extend(preload_urls(html));
+    if let Some(headers) = headers {
+        urls.extend(link_header_urls(headers));
+    }
+    // Documents for the next navigation are only prefetched if configured.
+    if !assets.prefetch {
+        urls.retain(|(_, resource_type)| *resource_type != "prefetch");
+    }
+
+    // Optionally load the aggregates of Drupal libraries attached with ajaxPageState.
+    if let Some(files_path) = &assets.drupal_libraries {
+        urls.extend(drupal::ajax_page_state_urls(html, files_path));
+    }
+
+    // Optionally override the type of assets by file extension.
+    for (url, resource_type) in urls.iter_mut() {
+        if let Some(custom_type) = custom_asset_type(url, assets) {
+            *resource_type = custom_type;
+        }
+    }
+
+    // Only load local assets, and assets on hosts explicitly allowed, such as a CDN.
+    for (url, _) in urls.iter_mut() {
+        *url = normalize_uri(user, url);
+    }
+    urls.retain(|(url, resource_type)| {
+        assets.loads_type(resource_type) && valid_asset_uri(user, url, assets)
+    });
+
+    // Like a browser, only load each asset once per page even if it's referenced by
+    // several tags, comparing urls after resolving them against the base url.
+    let mut extracted = BTreeSet::new();
+    urls.retain(|(url, _)| {
+        let resolved = resolve_uri(user, url).map(|url| url.to_string());
+        extracted.insert(resolved.unwrap_or_else(|| url.clone()))
+    });
+
+    // Optionally limit how many assets are loaded, preserving the order of the page.
+    match assets.limit {
+        AssetLimit::All => (),
+        AssetLimit::First(max) => urls.truncate(max),
+        AssetLimit::Random(max) if max < urls.len() => {
+            let mut selected =
+                rand::seq::index::sample(&mut rand::thread_rng(), urls.len(), max).into_vec();
+            selected.sort_unstable();
+            urls = selected.into_iter().map(|i| urls[i].clone()).collect();
+        }
+        AssetLimit::Random(_) => (),
+    }
+
+    urls
+}
+
+// Extract the urls captured by the first group of the regular expression, local or not.
+fn attribute_urls(html: &str, regex: &str) -> Vec<String> {
+    let re = Regex::new(regex).unwrap();
+    re.captures_iter(html_escape::decode_html_entities(html).as_ref())
+        .map(|url| url[1].to_string())
+        .collect()
+}
+
+// Only keep the valid local urls, normalized to be requested.
+fn local_urls(user: &mut GooseUser, urls: Vec<String>) -> Vec<String> {
+    let mut urls: Vec<String> = urls.iter().map(|url| normalize_uri(user, url)).collect();
+    urls.retain(|url| valid_local_uri(user, url));
+    urls
+}
+
+// Normalize a URI to be requested: surrounding whitespace and the fragment, which browsers
+// don't request, are removed, and protocol-relative URIs such as `//cdn.example.com/foo.js`
+// are resolved against the scheme of the base_url. Other URIs are resolved when requested.
+fn normalize_uri(user: &GooseUser, uri: &str) -> String {
+    let uri = uri.trim();
+    let uri = uri.split('#').next().unwrap_or_default();
+    match uri.strip_prefix("//") {
+        Some(_) => format!("{}:{}", user.base_url.scheme(), uri),
+        None => uri.to_string(),
+    }
+}
+
+// Helper to confirm the URI is valid, and either local or on a host allowed to load
+// static assets from.
+fn valid_asset_uri(user: &mut GooseUser, uri: &str, assets: &LoadAssets) -> bool {
+    let allowed = |host: &str| {
+        assets
+            .allowed_hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+    };
+    valid_local_uri(user, uri)
+        || resolve_uri(user, uri).is_some_and(|url| url.host_str().is_some_and(allowed))
+}
+
+// A static asset to load: its url, its type, and how many css files deep it's referenced.
+type QueuedAsset = (String, &'static str, usize);
+
+// Goose users loading static assets over the extra connections of each Goose user, reused
+// from page to page.
+pub(crate) static ASSET_WORKERS: Mutex<BTreeMap<UserKey, Vec<GooseUser>>> =
+    Mutex::new(BTreeMap::new());
+
+// The scenario and transaction a request is attributed to in the Goose metrics.
+struct Attribution {
+    scenario_index: usize,
+    scenario_name: String,
+    transaction_index: String,
+    transaction_name: String,
+}
+impl Attribution {
+    // The attribution of a request made by a Goose user.
+    fn of(request: &GooseRequestMetric) -> Self {
+        Attribution {
+            scenario_index: request.scenario_index,
+            scenario_name: request.scenario_name.clone(),
+            transaction_index: request.transaction_index.clone(),
+            transaction_name: request.transaction_name.clone(),
+        }
+    }
+
+    // Attribute the request to the same scenario and transaction.
+    fn apply(&self, request: &mut GooseRequestMetric) {
+        request.scenario_index = self.scenario_index;
+        request.scenario_name = self.scenario_name.clone();
+        request.transaction_index = self.transaction_index.clone();
+        request.transaction_name = self.transaction_name.clone();
+    }
+}
+
+// Load the static assets extracted from a page, attributing the requests made over other
+// connections to the scenario and transaction of the page request if known.
+async fn load_static_urls(
+    user: &mut GooseUser,
+    urls: Vec<(String, &'static str)>,
+    assets: &LoadAssets,
+    attribution: Option<Attribution>,
+) -> LoadedAssets {
+    let started = Instant::now();
+    // Assets referenced by css files are queued after the assets referenced by the page.
+    let queue: Mutex<VecDeque<QueuedAsset>> =
+        Mutex::new(urls.into_iter().map(|(u, t)| (u, t, 0)).collect());
+    let loaded = Mutex::new(Vec::new());
+
+    if assets.parallel > 1 {
+        load_parallel_static_urls(user, &queue, &loaded, assets, attribution).await;
+    } else {
+        load_queued_static_urls(user, &queue, &loaded, assets, None).await;
+    }
+
+    LoadedAssets {
+        assets: loaded.into_inner().unwrap(),
+        duration: started.elapsed(),
+    }
+}
+
+// Load the queued static assets over several connections at once. The Goose user loads
+// assets over the first connection and Goose users sharing its client over the others,
+// each taking the next asset from the queue once done with the last. Without the attribution
+// of the page request, the attribution of the first asset loaded by the Goose user is used.
+async fn load_parallel_static_urls(
+    user: &mut GooseUser,
+    queue: &Mutex<VecDeque<QueuedAsset>>,
+    loaded: &Mutex<Vec<LoadedAsset>>,
+    assets: &LoadAssets,
+    attribution: Option<Attribution>,
+) {
+    let key = user_key(user);
+    let mut workers = ASSET_WORKERS
+        .lock()
+        .unwrap()
+        .remove(&key)
+        .unwrap_or_default();
+    workers.truncate(assets.parallel - 1);
+    while workers.len() < assets.parallel - 1 {
+        match asset_worker(user) {
+            Ok(worker) => workers.push(worker),
+            Err(e) => {
+                warn!("failed to load static assets in parallel: {}", e);
+                break;
+            }
+        }
+    }
+
+    // The metrics and logs of the other connections are collected, to be attributed to
+    // the scenario and transaction of the Goose user before they're forwarded.
+    let (metrics, worker_metrics) = flume::unbounded();
+    let (logs, worker_logs) = flume::unbounded();
+    for worker in workers.iter_mut() {
+        worker.client = user.client.clone();
+        worker.base_url = user.base_url.clone();
+        worker.throttle = user.throttle.clone();
+        worker.is_throttled = user.is_throttled;
+        worker.weighted_users_index = user.weighted_users_index;
+        worker.metrics_channel = user.metrics_channel.as_ref().map(|_| metrics.clone());
+        worker.logger = user.logger.as_ref().map(|_| logs.clone());
+    }
+
+    let attribution = Mutex::new(attribution);
+    let connections = workers
+        .iter_mut()
+        .map(|worker| load_queued_static_urls(worker, queue, loaded, assets, None));
+    join_all(
+        std::iter::once(load_queued_static_urls(
+            user,
+            queue,
+            loaded,
+            assets,
+            Some(&attribution),
+        ))
+        .chain(connections),
+    )
+    .await;
+
+    let attribution = attribution.into_inner().unwrap();
+    let attribute = |request: &mut GooseRequestMetric| {
+        if let Some(attribution) = &attribution {
+            attribution.apply(request);
+        }
+    };
+    for mut metric in worker_metrics.try_iter() {
+        if let GooseMetric::Request(request) = &mut metric {
+            attribute(request);
+        }
+        if let Some(metrics_channel) = &user.metrics_channel {
+            let _ = metrics_channel.send(metric);
+        }
+    }
+    for mut log in worker_logs.try_iter().flatten() {
+        match &mut log {
+            GooseLog::Request(request) => attribute(request),
+            GooseLog::Debug(debug) => debug.request.iter_mut().for_each(attribute),
+            _ => (),
+        }
+        if let Some(logger) = &user.logger {
+            let _ = logger.send(Some(log));
+        }
+    }
+
+    // Don't keep the channels of the load test open once it's done.
+    for worker in workers.iter_mut() {
+        worker.throttle = None;
+        worker.metrics_channel = None;
+        worker.logger = None;
+    }
+    ASSET_WORKERS.lock().unwrap().insert(key, workers);
+}
+
+// Make a conditional request, which succeeds with either a `304 Not Modified` or a `200 OK`
+// with the modified content. Goose only accepts a `304` if it's expected, and then fails a
+// `200`, so the metrics and logs of the request are collected to mark a `304` successful
+// before they're forwarded.
+async fn conditional_request(
+    user: &mut GooseUser,
+    request: GooseRequest<'_>,
+) -> Result<GooseResponse, Box<TransactionError>> {
+    let (metrics, request_metrics) = flume::unbounded();
+    let (logs, request_logs) = flume::unbounded();
+    let metrics_channel = user.metrics_channel.take();
+    let logger = user.logger.take();
+    user.metrics_channel = metrics_channel.as_ref().map(|_| metrics);
+    user.logger = logger.as_ref().map(|_| logs);
+    let goose = user.request(request).await;
+    user.metrics_channel = metrics_channel;
+    user.logger = logger;
+
+    let not_modified = |request: &mut GooseRequestMetric| {
+        if request.status_code == 304 {
+            request.success = true;
+            request.error.clear();
+        }
+    };
+    for mut metric in request_metrics.try_iter() {
+        if let GooseMetric::Request(request) = &mut metric {
+            not_modified(request);
+        }
+        if let Some(metrics_channel) = &user.metrics_channel {
+            let _ = metrics_channel.send(metric);
+        }
+    }
+    for mut log in request_logs.try_iter().flatten() {
+        if let GooseLog::Request(request) = &mut log {
+            not_modified(request);
+        }
+        if let Some(logger) = &user.logger {
+            let _ = logger.send(Some(log));
+        }
+    }
+
+    let mut goose = goose?;
+    not_modified(&mut goose.request);
+    Ok(goose)
+}
+
+// Load static assets from the queue until it's empty, queueing the assets referenced by
+// css files. Optionally remember the attribution of the first request made.
+async fn load_queued_static_urls(
+    user: &mut GooseUser,
+    queue: &Mutex<VecDeque<QueuedAsset>>,
+    loaded: &Mutex<Vec<LoadedAsset>>,
+    assets: &LoadAssets,
+    attribution: Option<&Mutex<Option<Attribution>>>,
+) {
+    loop {
+        let asset = match queue.lock().unwrap().pop_front() {
+            Some(asset) => asset,
+            None => break,
+        };
+        let (asset, references) = load_static_url(user, asset, assets, attribution).await;
+        loaded.lock().unwrap().extend(asset);
+        queue.lock().unwrap().extend(references);
+    }
+}
+
+// Create a Goose user sharing the client of the specified user, used to load static assets
+// over an extra connection. Its requests are attributed to the scenario and transaction of
+// the specified user when they're forwarded to the Goose metrics.
+fn asset_worker(user: &GooseUser) -> Result<GooseUser, GooseError> {
+    GooseUser::new(
+        0,
+        String::new(),
+        user.base_url.clone(),
+        &user.config,
+        user.load_test_hash,
+        Some(user.client.clone()),
+    )
+}
+
+// Load a static asset, returning the loaded asset if it wasn't skipped, and the assets it
+// references that are also to be loaded.
+async fn load_static_url(
+    user: &mut GooseUser,
+    (url, resource_type, depth): QueuedAsset,
+    assets: &LoadAssets,
+    attribution: Option<&Mutex<Option<Attribution>>>,
+) -> (Option<LoadedAsset>, Vec<QueuedAsset>) {
+    // Optionally skip assets this user already loaded on a previous page.
+    if assets.dedupe_across_pages && !assets::remember_loaded(user, &url) {
+        return (None, Vec::new());
+    }
+    // Optionally simulate assets served from the browser cache.
+    if assets.cache_hit_ratio > 0.0 && rand::random::<f32>() < assets.cache_hit_ratio {
+        return (None, Vec::new());
+    }
+    let started = Instant::now();
+    let name = format!("static asset: {}", resource_type);
+    let goose = match (resource_type, assets.media) {
+        ("media", MediaRequest::Head) => {
+            let request = GooseRequest::builder()
+                .method(GooseMethod::Head)
+                .path(url.as_str())
+                .name(name.as_str())
+                .build();
+            user.request(request).await
+        }
+        ("media", MediaRequest::Range(bytes)) => {
+            let request_builder = match user.get_request_builder(&GooseMethod::Get, &url) {
+                Ok(request_builder) => request_builder,
+                Err(_) => return (None, Vec::new()),
+            };
+            let request = GooseRequest::builder()
+                .set_request_builder(
+                    request_builder.header("range", format!("bytes=0-{}", bytes.saturating_sub(1))),
+                )
+                .path(url.as_str())
+                .name(name.as_str())
+                .build();
+            user.request(request).await
+        }
+        _ => match assets::cached_validators(user, &url).filter(|_| assets.conditional) {
+            // Revalidate assets cached by a previous request, accepting them whether or
+            // not they have been modified.
+            Some(validators) => {
+                let mut request_builder = match user.get_request_builder(&GooseMethod::Get, &url) {
+                    Ok(request_builder) => request_builder,
+                    Err(_) => return (None, Vec::new()),
+                };
+                for (header, value) in validators {
+                    request_builder = request_builder.header(header, value);
+                }
+                let request = GooseRequest::builder()
+                    .set_request_builder(request_builder)
+                    .path(url.as_str())
+                    .name(name.as_str())
+                    .build();
+                conditional_request(user, request).await
+            }
+            None => user.get_named(&url, &name).await,
+        },
+    };
+
+    // Optionally remember the validators of assets to revalidate them later.
+    if assets.conditional {
+        if let Ok(Ok(response)) = goose.as_ref().map(|goose| &goose.response) {
+            if response.status() == StatusCode::OK {
+                assets::remember_validators(user, &url, response.headers());
+            }
+        }
+    }
+
+    let mut loaded = LoadedAsset {
+        url,
+        resource_type,
+        status_code: 0,
+        success: false,
+        bytes: 0,
+        elapsed: Duration::ZERO,
+    };
+    let mut referenced = Vec::new();
+    if let Ok(mut goose) = goose {
+        if let Some(attribution) = attribution {
+            attribution
+                .lock()
+                .unwrap()
+                .get_or_insert_with(|| Attribution::of(&goose.request));
+        }
+        loaded.status_code = goose.request.status_code;
+        loaded.success = goose.request.success;
+        if let Ok(response) = goose.response {
+            let status = response.status();
+            let headers = response.headers().clone();
+            let served_as_css = response
+                .headers()
+                .get("content-type")
+                .and_then(|value| value.to_str().ok())
+                .and_then(content_type_asset_type)
+                == Some("css");
+            let css_url = response.url().clone();
+            if let Ok(body) = response.bytes().await {
+                loaded.bytes = body.len();
+                // Optionally validate assets, such as their CDN cache headers.
+                let url = &loaded.url;
+                let validations = assets
+                    .validations
+                    .iter()
+                    .filter(|(regex, _)| regex.is_match(url));
+                for (_, validate) in validations {
+                    let redirected = goose.request.redirected;
+                    let errors = validate
+                        .check(redirected, status, &headers, &body)
+                        .err()
+                        .unwrap_or_default();
+                    if !errors.is_empty() {
+                        loaded.success = false;
+                    }
+                    // Failed assets are reported, without failing the transaction.
+                    let _ = report_failure(
+                        user,
+                        validate,
+                        &errors,
+                        &mut goose.request,
+                        Some(&headers),
+                        None,
+                    );
+                }
+                // Optionally follow @import and url() references in css files, including
+                // css files without a .css extension that are served as css.
+                if depth < assets.css_depth && (resource_type == "css" || served_as_css) {
+                    let css = String::from_utf8_lossy(&body);
+                    referenced = css_asset_urls(user, &css_url, &css, assets)
+                        .into_iter()
+                        .map(|(url, resource_type)| (url, resource_type, depth + 1))
+                        .collect();
+                }
+            }
+        }
+    }
+    loaded.elapsed = started.elapsed();
+
+    (Some(loaded), referenced)
+}
+
+// The types of static assets by file extension, loaded as `static asset: <type>`.
+const ASSET_TYPES: &[(&str, &[&str])] = &[
+    ("js", &["js", "mjs"]),
+    ("css", &["css"]),
+    ("font", &["woff", "woff2", "ttf", "otf", "eot"]),
+    (
+        "img",
+        &[
+            "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico", "bmp",
+        ],
+    ),
+    (
+        "media",
+        &["mp4", "webm", "ogv", "ogg", "mp3", "wav", "m4a", "mov"],
+    ),
+    ("json", &["json"]),
+    ("map", &["map"]),
+];
+
+// The types of static assets by the prefix of their MIME type.
+const CONTENT_TYPES: &[(&str, &str)] = &[
+    ("text/css", "css"),
+    ("text/javascript", "js"),
+    ("application/javascript", "js"),
+    ("application/json", "json"),
+    ("font/", "font"),
+    ("image/", "img"),
+    ("video/", "media"),
+    ("audio/", "media"),
+];
+
+// Returns the type of a static asset by the file extension of its url, if known.
+fn extension_asset_type(url: &str) -> Option<&'static str> {
+    let extension = url_extension(url);
+    ASSET_TYPES
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map(|(resource_type, _)| *resource_type)
+}
+
+// Returns the type of a static asset by its MIME type, if known.
+fn content_type_asset_type(content_type: &str) -> Option<&'static str> {
+    let content_type = content_type.trim().to_ascii_lowercase();
+    CONTENT_TYPES
+        .iter()
+        .find(|(prefix, _)| content_type.starts_with(prefix))
+        .map(|(_, resource_type)| *resource_type)
+}
+
+// Returns the type of a static asset configured with `LoadAssetsBuilder::asset_type`, if any.
+fn custom_asset_type(url: &str, assets: &LoadAssets) -> Option<&'static str> {
+    let extension = url_extension(url);
+    assets
+        .asset_types
+        .iter()
+        .find(|(custom_extension, _)| *custom_extension == extension)
+        .map(|(_, resource_type)| *resource_type)
+}
+
+// Returns the lower case file extension of the path of a url, if any.
+fn url_extension(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    match path.rsplit_once('.') {
+        Some((_, extension)) if !extension.contains('/') => extension.to_ascii_lowercase(),
+        _ => String::new(),
+    }
+}
+
+// Extract the local urls of assets imported or referenced by a css file, and the urls of
+// assets on allowed hosts, resolved relative to the url of the css file, with the type
+// of each asset.
+fn css_asset_urls(
+    user: &mut GooseUser,
+    css_url: &Url,
+    css: &str,
+    assets: &LoadAssets,
+) -> Vec<(String, &'static str)> {
+    let import = Regex::new(r#"(?i)@import\s+['"](.*?)['"]"#).unwrap();
+    let imports = import.captures_iter(css).map(|url| url[1].to_string());
+    let mut elements = Vec::new();
+    for url in imports.chain(css_urls(css)) {
+        let url = match css_url.join(&url) {
+            Ok(url) => url.to_string(),
+            Err(_) => continue,
+        };
+        if valid_asset_uri(user, &url, assets) && !elements.iter().any(|(u, _)| *u == url) {
+            let resource_type = custom_asset_type(&url, assets)
+                .or_else(|| extension_asset_type(&url))
+                .unwrap_or("img");
+            if assets.loads_type(resource_type) {
+                elements.push((url, resource_type));
+            }
+        }
+    }
+    elements
+}
+
+/// Returns how many failed validations have been logged as warnings instead of failing
+/// the request, by [`Validate`] objects built with [`ValidateBuilder::warn_only`].
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::validation_warnings;
+///
+/// println!("validation warnings: {}", validation_warnings());
+/// ```
+pub fn validation_warnings() -> usize {
+    VALIDATION_WARNINGS.load(Ordering::Relaxed)
 }
 
-/// Returns a [`bool`] indicating whether or not an arbitrary str (case sensitive) is found
-/// within the html.
+// Record validation metrics and report failed validations, failing the request unless the
+// validation is configured to only log warnings.
+fn report_failure(
+    user: &GooseUser,
+    validate: &Validate,
+    errors: &[ValidationError],
+    request: &mut GooseRequestMetric,
+    headers: Option<&HeaderMap>,
+    body: Option<&str>,
+) -> Result<(), Box<TransactionError>> {
+    let name = validate.name.as_deref().unwrap_or(&request.name);
+    metrics::record(name, errors);
+    if let (Some(name), true) = (validate.name.as_deref(), validate.goose_metric) {
+        metrics::record_goose_metric(user, request, name, errors.is_empty())?;
+    }
+    let tolerated = validate.tolerates(user, name, errors);
+
+    // Unless configured to collect all failed validations, only report the first failed
+    // validation to avoid cascades of errors when a page fails to load.
+    let error = match errors {
+        [] => return Ok(()),
+        [error, ..] if !validate.collect_all || errors.len() == 1 => error.to_string(),
+        _ => format!(
+            "{} validations failed: {}",
+            errors.len(),
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+    };
+    // Optionally format the failure message with a custom template.
+    let error = match validate.on_failure.as_deref() {
+        Some(template) => template
+            .replace("{url}", &request.raw.url)
+            .replace("{reason}", &error),
+        None => format!("{}: {}", request.raw.url, error),
+    };
+
+    if let Some(dir) = validate.capture_dir.as_deref() {
+        capture_failure(dir, user, request, &error, headers, body);
+    }
+
+    if validate.warn_only || tolerated {
+        VALIDATION_WARNINGS.fetch_add(1, Ordering::Relaxed);
+        warn!("validation warning: {}", error);
+        user.log_debug(
+            &format!("validation warning: {}", error),
+            Some(request),
+            headers,
+            body,
+        )
+    } else {
+        user.set_failure(&error, request, headers, body)
+    }
+}
+
+// Write a failed response to a timestamped file in the specified directory. Failing to
+// write the file is logged, but doesn't fail the request.
+fn capture_failure(
+    dir: &str,
+    user: &GooseUser,
+    request: &GooseRequestMetric,
+    error: &str,
+    headers: Option<&HeaderMap>,
+    body: Option<&str>,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = Path::new(dir).join(format!(
+        "{}-user{}-{}.txt",
+        timestamp,
+        user.weighted_users_index,
+        CAPTURED_FAILURES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut capture = format!("{}\n\n", error);
+    capture.push_str(&serde_json::to_string_pretty(request).unwrap_or_default());
+    capture.push_str("\n\n");
+    for (name, value) in headers.into_iter().flatten() {
+        capture.push_str(&format!(
+            "{}: {}\n",
+            name,
+            value.to_str().unwrap_or_default()
+        ));
+    }
+    capture.push('\n');
+    capture.push_str(body.unwrap_or_default());
+
+    if let Err(e) = fs::create_dir_all(dir).and_then(|_| fs::write(&path, capture)) {
+        warn!("failed to capture failure to {}: {}", path.display(), e);
+    }
+}
+
+/// Validate the HTML response and return the HTML body.
 ///
-/// Returns [`true`] if the expected str is found, otherwise returns [`false`].
+/// What is validated is defined with the [`Validate`] structure.
 ///
-/// This function is case sensitive, if the text "foo" is specified it will only match "foo",
-/// not "Foo" or "FOO".
+/// If the page doesn't load, an empty [`String`] will be returned. If the page does load
+/// but validation fails, an Error is returned. If the page loads and there are no
+/// errors the body is returned as a [`String`].
 ///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+/// This function is invoked by [validate_and_load_static_assets], which then also invokes
+/// [load_static_elements] to better simulate a web browser loading a page.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::valid_text;
+/// use goose_eggs::{validate_page, Validate};
 ///
-/// transaction!(validate_text).set_on_start();
+/// transaction!(load_page).set_on_start();
 ///
-/// async fn validate_text(user: &mut GooseUser) -> TransactionResult {
+/// async fn load_page(user: &mut GooseUser) -> TransactionResult {
 ///     let mut goose = user.get("/").await?;
-///
-///     match goose.response {
-///         Ok(response) => {
-///             // Copy the headers so we have them for logging if there are errors.
-///             let headers = &response.headers().clone();
-///             match response.text().await {
-///                 Ok(html) => {
-///                     let text = r#"<code class="language-console">$ cargo new hello_world --bin"#;
-///                     if !valid_text(&html, text) {
-///                         return user.set_failure(
-///                             &format!("{}: text not found: {}", goose.request.raw.url, text),
-///                             &mut goose.request,
-///                             Some(headers),
-///                             Some(&html),
-///                         );
-///                     }
-///                 }
-///                 Err(e) => {
-///                     return user.set_failure(
-///                         &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
-///                         &mut goose.request,
-///                         Some(headers),
-///                         None,
-///                     );
-///                 }
-///             }
-///         }
-///         Err(e) => {
-///             return user.set_failure(
-///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
-///                 &mut goose.request,
-///                 None,
-///                 None,
-///             );
-///         }
-///     }
+///     validate_page(
+///         user,
+///         goose,
+///         // Validate title and other arbitrary text on the response html.
+///         &Validate::builder()
+///             .title("my page")
+///             .texts(vec!["foo", r#"<a href="bar">"#])
+///             .build(),
+///     ).await?;
 ///
 ///     Ok(())
 /// }
 /// ```
-pub fn valid_text(html: &str, text: &str) -> bool {
-    html.contains(text)
+pub async fn validate_page<'a>(
+    user: &mut GooseUser,
+    goose: GooseResponse,
+    validate: &'a Validate<'a>,
+) -> Result<String, Box<TransactionError>> {
+    let (html, _) = validate_page_with_headers(user, goose, validate).await?;
+    Ok(html)
 }
 
-/// Returns a [`bool`] indicating whether or not a header was set in the server Response.
+// Validate the HTML response as described in `validate_page`, also returning the headers of
+// the final response, which is a retry if the original response failed validation.
+async fn validate_page_with_headers<'a>(
+    user: &mut GooseUser,
+    mut goose: GooseResponse,
+    validate: &'a Validate<'a>,
+) -> Result<(String, Option<HeaderMap>), Box<TransactionError>> {
+    let mut retries = 0;
+    loop {
+        let GooseResponse {
+            mut request,
+            response,
+        } = goose;
+        match read_page(user, validate, &request, response).await {
+            Ok((errors, headers, html)) => {
+                if !errors.is_empty() {
+                    if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                        goose = retry;
+                        continue;
+                    }
+                }
+                report_failure(
+                    user,
+                    validate,
+                    &errors,
+                    &mut request,
+                    Some(&headers),
+                    Some(&html),
+                )?;
+                return Ok((html, Some(headers)));
+            }
+            Err(e) => {
+                if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                    goose = retry;
+                    continue;
+                }
+                user.set_failure(
+                    &format!("{}: no response from server: {}", request.raw.url, e),
+                    &mut request,
+                    None,
+                    None,
+                )?;
+                return Ok(("".to_string(), None));
+            }
+        }
+    }
+}
+
+// Read and validate a page, returning each validation that failed, the response headers
+// and the body.
+async fn read_page(
+    user: &mut GooseUser,
+    validate: &Validate<'_>,
+    request: &GooseRequestMetric,
+    response: Result<reqwest::Response, reqwest::Error>,
+) -> Result<(Vec<ValidationError>, HeaderMap, String), reqwest::Error> {
+    let response = response?;
+
+    // Copy the headers so we have them for validation and logging.
+    let headers = response.headers().clone();
+
+    for hook in &validate.hooks {
+        hook.before(user, request, &headers);
+    }
+
+    // Validate everything that doesn't require the response body.
+    let started = Instant::now();
+    let mut errors = validate.response_errors(request.redirected, response.status(), &headers);
+    let mut validating = started.elapsed();
+
+    // Extract the response body to validate and load static elements, unless configured
+    // to ignore it.
+    let started = Instant::now();
+    let mut validating_bytes = Duration::ZERO;
+    let body = if validate.ignore_body {
+        Ok("".to_string())
+    } else if !validate.validates_bytes() {
+        response.text().await
+    } else {
+        // Validating bytes requires the raw body, as decoding it as text mangles
+        // non-UTF-8 responses.
+        response.bytes().await.map(|body| {
+            let started = Instant::now();
+            errors.extend(validate.bytes_errors(&body));
+            validating_bytes = started.elapsed();
+            String::from_utf8_lossy(&body).to_string()
+        })
+    };
+    let reading = started.elapsed().saturating_sub(validating_bytes);
+    validate.record_timing(&request.name, "body", reading);
+    validating += validating_bytes;
+    let html = match body {
+        Ok(html) if validate.ignore_body => html,
+        Ok(html) => {
+            let started = Instant::now();
+            errors.extend(validate.body_errors(&headers, &html));
+            validate.maintenance_errors(&html, &mut errors);
+            validating += started.elapsed();
+            html
+        }
+        Err(e) => {
+            errors.push(ValidationError::BodyUnreadable {
+                reason: e.to_string(),
+            });
+            "".to_string()
+        }
+    };
+
+    validate.record_timing(&request.name, "validate", validating);
+
+    for hook in &validate.hooks {
+        hook.after(user, request, &headers, &html, &errors);
+    }
+
+    Ok((errors, headers, html))
+}
+
+// If retries are configured and remain, wait for the configured backoff and then re-issue
+// the request. Only GET and HEAD requests are retried, as the body of other requests is
+// not available to re-issue them.
+async fn retry(
+    user: &mut GooseUser,
+    validate: &Validate<'_>,
+    request: &GooseRequestMetric,
+    retries: &mut usize,
+) -> Result<Option<GooseResponse>, Box<TransactionError>> {
+    if *retries >= validate.retries
+        || !matches!(request.raw.method, GooseMethod::Get | GooseMethod::Head)
+    {
+        return Ok(None);
+    }
+    // Send the same headers again, unless they can't be recovered.
+    let mut headers = Vec::new();
+    for header in &request.raw.headers {
+        match parse_recorded_header(header) {
+            Some(header) => headers.push(header),
+            None => {
+                info!(
+                    "{}: validation failed, not retried with sensitive header",
+                    request.raw.url
+                );
+                return Ok(None);
+            }
+        }
+    }
+    *retries += 1;
+    info!(
+        "{}: validation failed, retry {} of {}",
+        request.raw.url, retries, validate.retries
+    );
+    tokio::time::sleep(validate.backoff).await;
+
+    let mut request_builder = user.get_request_builder(&request.raw.method, &request.raw.url)?;
+    for (name, value) in headers {
+        request_builder = request_builder.header(name, value);
+    }
+    let mut goose_request = GooseRequest::builder()
+        .method(request.raw.method.clone())
+        .path(request.raw.url.as_str())
+        .name(request.name.as_str())
+        .set_request_builder(request_builder);
+    // Goose only accepts an error status code if it was expected.
+    if request.success && !(200..300).contains(&request.status_code) {
+        goose_request = goose_request.expect_status_code(request.status_code);
+    }
+    Ok(Some(user.request(goose_request.build()).await?))
+}
+
+// Parse a request header recorded by Goose, formatted as `("name", "value")` with quotes
+// and non-ascii bytes of the value escaped. Returns `None` for values that were redacted
+// as sensitive.
+fn parse_recorded_header(header: &str) -> Option<(String, Vec<u8>)> {
+    let (name, mut value) = header
+        .strip_prefix("(\"")?
+        .strip_suffix("\")")?
+        .split_once("\", \"")?;
+    let mut bytes = Vec::new();
+    while let Some(escape) = value.find('\\') {
+        bytes.extend_from_slice(&value.as_bytes()[..escape]);
+        value = &value[escape + 1..];
+        if let Some(rest) = value.strip_prefix('"') {
+            bytes.push(b'"');
+            value = rest;
+        } else if let Some(byte) = value
+            .strip_prefix('x')
+            .and_then(|rest| rest.get(..2))
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            bytes.push(byte);
+            value = &value[3..];
+        } else {
+            bytes.push(b'\\');
+        }
+    }
+    bytes.extend_from_slice(value.as_bytes());
+    Some((name.to_string(), bytes))
+}
+
+/// A web page validated by [`validate_and_parse_page`], exposing both the raw body and the
+/// parsed html document so follow-up steps don't need to parse the same page again.
 ///
-/// Returns [`true`] if the expected header was set, otherwise returns [`false`].
+/// Only available when the `html-parser` feature is enabled.
+#[cfg(feature = "html-parser")]
+#[derive(Clone, Debug)]
+pub struct ParsedPage {
+    /// The raw html body of the page.
+    pub body: String,
+    /// The parsed html document.
+    pub document: scraper::Html,
+}
+
+/// Validate the HTML response and return both the raw body and the parsed document.
 ///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+/// This behaves exactly like [`validate_page`], but also parses the body once so that
+/// follow-up steps can query the document with CSS selectors instead of re-parsing the
+/// body. If the page doesn't load, an empty body and document are returned.
+///
+/// Only available when the `html-parser` feature is enabled.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::header_is_set;
+/// use goose_eggs::{validate_and_parse_page, Validate};
+/// use scraper::Selector;
 ///
-/// transaction!(validate_header).set_on_start();
+/// transaction!(load_page).set_on_start();
 ///
-/// async fn validate_header(user: &mut GooseUser) -> TransactionResult {
-///     let mut goose = user.get("/").await?;
+/// async fn load_page(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("/").await?;
+///     let page = validate_and_parse_page(
+///         user,
+///         goose,
+///         &Validate::builder().title("my page").build(),
+///     ).await?;
 ///
-///     match goose.response {
-///         Ok(response) => {
-///             // Copy the headers so we have them for logging if there are errors.
-///             let headers = &response.headers().clone();
-///             if !header_is_set(headers, "server") {
-///                 return user.set_failure(
-///                     &format!("{}: header not found: {}", goose.request.raw.url, "server"),
-///                     &mut goose.request,
-///                     Some(headers),
-///                     None,
-///                 );
-///             }
-///         }
-///         Err(e) => {
-///             return user.set_failure(
-///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
-///                 &mut goose.request,
-///                 None,
-///                 None,
-///             );
-///         }
+///     // Follow the first link in the main menu.
+///     let selector = Selector::parse("nav a").unwrap();
+///     let href = page
+///         .document
+///         .select(&selector)
+///         .next()
+///         .and_then(|link| link.value().attr("href"))
+///         .map(|href| href.to_string());
+///     if let Some(href) = href {
+///         let _goose = user.get(&href).await?;
 ///     }
 ///
 ///     Ok(())
 /// }
 /// ```
-pub fn header_is_set(headers: &HeaderMap, header: &str) -> bool {
-    headers.contains_key(header)
+#[cfg(feature = "html-parser")]
+pub async fn validate_and_parse_page<'a>(
+    user: &mut GooseUser,
+    goose: GooseResponse,
+    validate: &'a Validate<'a>,
+) -> Result<ParsedPage, Box<TransactionError>> {
+    let body = validate_page(user, goose, validate).await?;
+    let document = scraper::Html::parse_document(&body);
+    Ok(ParsedPage { body, document })
 }
 
-/// Returns a [`bool`] indicating whether or not a header contains an expected value.
+/// Validate the JSON response and return the parsed JSON body.
 ///
-/// Returns [`true`] if the expected value was found, otherwise returns [`false`].
-///
-/// Expects a [`&str`] [`tuple`] with a length of 2 where the first defines the header
-/// name and the second defines the header value, ie `("name", "value")`.
+/// What is validated is defined with the [`Validate`] structure, including JSON pointers
+/// defined with [`ValidateBuilder::json_pointer`] and [`ValidateBuilder::json_value`].
+/// Validation fails if the response body is not valid JSON. The body is also checked for
+/// texts, error markers and, with [`ValidateBuilder::not_maintenance`], the maintenance
+/// mode page, like the body of an html page.
 ///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_page`] or [`validate_and_load_static_assets`] which in turn invoke this function.
+/// If the request doesn't load, [`Value::Null`] will be returned. If the request does load
+/// but validation fails, an Error is returned. If the request loads and there are no
+/// errors the parsed body is returned as a [`Value`].
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::valid_header_value;
+/// use goose_eggs::{validate_json, Validate};
 ///
-/// transaction!(validate_header_value).set_on_start();
+/// transaction!(load_articles).set_on_start();
 ///
-/// async fn validate_header_value(user: &mut GooseUser) -> TransactionResult {
-///     let mut goose = user.get("/").await?;
+/// async fn load_articles(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("jsonapi/node/article").await?;
+///     let json = validate_json(
+///         user,
+///         goose,
+///         &Validate::builder()
+///             .status(200)
+///             .header_value("content-type", "application/vnd.api+json")
+///             .json_pointer("/data/0/id")
+///             .build(),
+///     ).await?;
 ///
-///     match goose.response {
-///         Ok(response) => {
-///             // Copy the headers so we have them for logging if there are errors.
-///             let headers = &response.headers().clone();
-///             if !valid_header_value(headers, ("server", "nginx")) {
-///                 return user.set_failure(
-///                     &format!("{}: server header value not correct: {}", goose.request.raw.url, "nginx"),
-///                     &mut goose.request,
-///                     Some(headers),
-///                     None,
-///                 );
-///             }
-///         }
-///         Err(e) => {
-///             return user.set_failure(
-///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
-///                 &mut goose.request,
-///                 None,
-///                 None,
-///             );
-///         }
+///     if let Some(id) = json.pointer("/data/0/id").and_then(|id| id.as_str()) {
+///         let _goose = user.get(&format!("jsonapi/node/article/{}", id)).await?;
 ///     }
 ///
 ///     Ok(())
 /// }
 /// ```
-pub fn valid_header_value<'a>(headers: &HeaderMap, header: (&'a str, &'a str)) -> bool {
-    // A header name is required, exit early if it's empty.
-    if header.0.is_empty() {
-        info!("no header specified");
-        return false;
+pub async fn validate_json<'a>(
+    user: &mut GooseUser,
+    mut goose: GooseResponse,
+    validate: &'a Validate<'a>,
+) -> Result<Value, Box<TransactionError>> {
+    let mut retries = 0;
+    loop {
+        let GooseResponse {
+            mut request,
+            response,
+        } = goose;
+        match read_json(user, validate, &request, response).await {
+            Ok((errors, headers, body, json)) => {
+                if !errors.is_empty() {
+                    if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                        goose = retry;
+                        continue;
+                    }
+                }
+                report_failure(
+                    user,
+                    validate,
+                    &errors,
+                    &mut request,
+                    Some(&headers),
+                    Some(&body),
+                )?;
+                return Ok(json);
+            }
+            Err(e) => {
+                if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                    goose = retry;
+                    continue;
+                }
+                user.set_failure(
+                    &format!("{}: no response from server: {}", request.raw.url, e),
+                    &mut request,
+                    None,
+                    None,
+                )?;
+                return Ok(Value::Null);
+            }
+        }
     }
+}
 
-    if header_is_set(headers, header.0) {
-        if header.1.is_empty() {
-            false
-        } else {
-            let header_value = match headers.get(header.0) {
-                // Extract the value of the header and try to convert to a &str.
-                Some(v) => v.to_str().unwrap_or(""),
-                None => "",
-            };
-            // Check if the desired value is in the header.
-            if header_value.contains(header.1) {
-                true
-            } else {
-                // Provide some extra debug.
-                info!(
-                    r#"header does not contain expected value: "{}: {}""#,
-                    header.0, header.1
-                );
-                false
-            }
+// Read and validate a JSON response, returning each validation that failed, the response
+// headers, the body and the parsed body.
+async fn read_json(
+    user: &mut GooseUser,
+    validate: &Validate<'_>,
+    request: &GooseRequestMetric,
+    response: Result<reqwest::Response, reqwest::Error>,
+) -> Result<(Vec<ValidationError>, HeaderMap, String, Value), reqwest::Error> {
+    let response = response?;
+
+    // Copy the headers so we have them for validation and logging.
+    let headers = response.headers().clone();
+
+    for hook in &validate.hooks {
+        hook.before(user, request, &headers);
+    }
+
+    // Validate everything that doesn't require the response body.
+    let mut errors = validate.response_errors(request.redirected, response.status(), &headers);
+
+    // Extract and parse the response body, validating it like an html page so error
+    // markers and the maintenance mode page are also detected.
+    let (body, json) = match response.text().await {
+        Ok(body) => {
+            errors.extend(validate.body_errors(&headers, &body));
+            let (json, json_errors) = validate.json_errors(&body);
+            errors.extend(json_errors);
+            validate.maintenance_errors(&body, &mut errors);
+            (body, json)
         }
-    } else {
-        info!("header ({}) not set", header.0);
-        false
+        Err(e) => {
+            errors.push(ValidationError::BodyUnreadable {
+                reason: e.to_string(),
+            });
+            ("".to_string(), Value::Null)
+        }
+    };
+
+    for hook in &validate.hooks {
+        hook.after(user, request, &headers, &body, &errors);
     }
+
+    Ok((errors, headers, body, json))
 }
 
-/// Helper to confirm the URI is valid and local.
-fn valid_local_uri(user: &mut GooseUser, uri: &str) -> bool {
-    match uri.parse::<Uri>() {
-        Ok(parsed_uri) => {
-            if let Some(parsed_host) = parsed_uri.host() {
-                if parsed_host == user.base_url.host_str().unwrap() {
-                    // The URI host matches the base_url.
-                    true
-                } else {
-                    // The URI host does not match the base_url.
-                    false
+/// Validate a file download and return the raw response body.
+///
+/// What is validated is defined with the [`Validate`] structure. Validations that
+/// require the body to be html or text (such as titles and texts) are not performed, but
+/// the status code, headers and byte validations such as [`ValidateBuilder::magic_bytes`],
+/// [`ValidateBuilder::sha256`] and [`ValidateBuilder::bytes`] are. In addition, if the
+/// response includes a `Content-Length` header it must match the length of the body.
+///
+/// The response to a `HEAD` request has no body, so only its status code and headers are
+/// validated and an empty [`Vec`] is returned.
+///
+/// If the request doesn't load, an empty [`Vec`] will be returned. If the request does
+/// load but validation fails, an Error is returned. If the request loads and there are no
+/// errors the body is returned.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::{validate_download, Validate};
+///
+/// transaction!(download_brochure).set_on_start();
+///
+/// async fn download_brochure(user: &mut GooseUser) -> TransactionResult {
+///     let goose = user.get("sites/default/files/brochure.pdf").await?;
+///     let _pdf = validate_download(
+///         user,
+///         goose,
+///         &Validate::builder()
+///             .status(200)
+///             .header_value("content-type", "application/pdf")
+///             .magic_bytes(b"%PDF-")
+///             .build(),
+///     ).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn validate_download<'a>(
+    user: &mut GooseUser,
+    mut goose: GooseResponse,
+    validate: &'a Validate<'a>,
+) -> Result<Vec<u8>, Box<TransactionError>> {
+    let mut retries = 0;
+    loop {
+        let GooseResponse {
+            mut request,
+            response,
+        } = goose;
+        match read_download(user, validate, &request, response).await {
+            Ok((errors, headers, body)) => {
+                if !errors.is_empty() {
+                    if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                        goose = retry;
+                        continue;
+                    }
                 }
-            } else {
-                // The URI is a valid relative path.
-                true
+                report_failure(user, validate, &errors, &mut request, Some(&headers), None)?;
+                return Ok(body);
             }
-        }
-        Err(_) => {
-            let url_leading = format!("/{}", uri);
-            match url_leading.parse::<Uri>() {
-                Ok(_) => {
-                    // The URI is a valid relative path (without a leading slash).
-                    true
-                }
-                Err(_) => {
-                    // The URI is not valid.
-                    false
+            Err(e) => {
+                if let Some(retry) = retry(user, validate, &request, &mut retries).await? {
+                    goose = retry;
+                    continue;
                 }
+                user.set_failure(
+                    &format!("{}: no response from server: {}", request.raw.url, e),
+                    &mut request,
+                    None,
+                    None,
+                )?;
+                return Ok(Vec::new());
             }
         }
     }
 }
 
-/// Extract all local static elements defined with a `src=` tag from the the provided html.
-///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_and_load_static_assets`] which in turn invokes this function.
-pub async fn get_src_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
-    // Use a case-insensitive regular expression to find all src=<foo> in the html, where
-    // <foo> is the URL to local image and js assets.
-    // @TODO: parse HTML5 srcset= also
-    let src_elements = Regex::new(r#"(?i)src="(.*?)""#).unwrap();
-    let mut elements: Vec<String> = Vec::new();
-    for url in src_elements.captures_iter(html_escape::decode_html_entities(html).as_ref()) {
-        if valid_local_uri(user, &url[1]) {
-            elements.push(url[1].to_string());
+// Read and validate a file download, returning each validation that failed, the response
+// headers and the raw body.
+async fn read_download(
+    user: &mut GooseUser,
+    validate: &Validate<'_>,
+    request: &GooseRequestMetric,
+    response: Result<reqwest::Response, reqwest::Error>,
+) -> Result<(Vec<ValidationError>, HeaderMap, Vec<u8>), reqwest::Error> {
+    let response = response?;
+
+    // Copy the headers so we have them for validation and logging.
+    let headers = response.headers().clone();
+
+    for hook in &validate.hooks {
+        hook.before(user, request, &headers);
+    }
+
+    // Validate everything that doesn't require the response body.
+    let mut errors = validate.response_errors(request.redirected, response.status(), &headers);
+
+    // The response to a HEAD request has no body, only the headers of the file.
+    if request.raw.method == GooseMethod::Head {
+        for hook in &validate.hooks {
+            hook.after(user, request, &headers, "", &errors);
         }
+        return Ok((errors, headers, Vec::new()));
     }
-    elements
-}
 
-/// Extract all local css elements defined with a `href=` tag from the the provided html.
-///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_and_load_static_assets`] which in turn invokes this function.
-pub async fn get_css_elements(user: &mut GooseUser, html: &str) -> Vec<String> {
-    // Use a case-insensitive regular expression to find all href=<foo> in the html, where
-    // <foo> is the URL to local css assets.
-    let css = Regex::new(r#"(?i)href="(.*?\.css.*?)""#).unwrap();
-    let mut elements: Vec<String> = Vec::new();
-    for url in css.captures_iter(html_escape::decode_html_entities(html).as_ref()) {
-        if valid_local_uri(user, &url[1]) {
-            elements.push(url[1].to_string());
+    // Validate that the entire file was downloaded, then validate its contents.
+    let content_length = headers
+        .get("content-length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    let body = match response.bytes().await {
+        Ok(body) => {
+            if let Some(content_length) = content_length {
+                if content_length != body.len() {
+                    errors.push(ValidationError::ContentLengthMismatch {
+                        expected: content_length,
+                        actual: body.len(),
+                    });
+                }
+            }
+            errors.extend(validate.bytes_errors(&body));
+            body.to_vec()
         }
+        Err(e) => {
+            errors.push(ValidationError::BodyUnreadable {
+                reason: e.to_string(),
+            });
+            Vec::new()
+        }
+    };
+
+    for hook in &validate.hooks {
+        hook.after(user, request, &headers, "", &errors);
     }
-    elements
+
+    Ok((errors, headers, body))
 }
 
-/// Extract and load all local static elements from the the provided html.
+/// An expected hop in a redirect chain, validated by [`validate_redirect_chain`].
+#[derive(Clone, Debug)]
+pub struct RedirectHop<'a> {
+    // The expected redirect status code.
+    status: u16,
+    // Text the `Location` header is expected to contain.
+    location: &'a str,
+}
+impl<'a> RedirectHop<'a> {
+    /// Expect a redirect with the specified status code to a `Location` containing the
+    /// specified text.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::RedirectHop;
+    ///
+    /// let _hop = RedirectHop::new(302, "/es");
+    /// ```
+    pub fn new(status: u16, location: &'a str) -> Self {
+        RedirectHop { status, location }
+    }
+}
+
+// Returns a client builder configured like the client of the Goose user, without a cookie
+// store.
+fn client_builder(user: &GooseUser) -> reqwest::ClientBuilder {
+    // The default timeout of Goose requests, unless configured with `--timeout`.
+    let timeout = user
+        .config
+        .timeout
+        .as_deref()
+        .and_then(|timeout| timeout.parse::<f64>().ok())
+        .filter(|timeout| *timeout > 0.0)
+        .unwrap_or(60.0);
+    reqwest::Client::builder()
+        .user_agent(concat!("goose-eggs/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs_f64(timeout))
+        .gzip(!user.config.no_gzip)
+}
+
+// Replace the client of the Goose user with one that stores cookies in the jar, shared
+// with the other clients built for the user.
+pub(crate) fn set_cookie_jar(
+    user: &mut GooseUser,
+    jar: Arc<Jar>,
+) -> Result<(), Box<TransactionError>> {
+    user.client = client_builder(user)
+        .cookie_provider(jar.clone())
+        .build()
+        .map_err(|e| Box::new(TransactionError::Reqwest(e)))?;
+    COOKIE_JARS.lock().unwrap().insert(user_key(user), jar);
+    Ok(())
+}
+
+// Returns the cookie jar of the Goose user, if enabled.
+pub(crate) fn cookie_jar(user: &GooseUser) -> Option<Arc<Jar>> {
+    COOKIE_JARS.lock().unwrap().get(&user_key(user)).cloned()
+}
+
+/// Replace the client of the Goose user with one whose cookie jar is shared with the
+/// clients goose-eggs builds for the user, such as the client following redirects in
+/// [`validate_redirect_chain`], starting a new session. Invoke before logging in.
 ///
-/// While you can invoke this function directly, it's generally preferred to invoke
-/// [`validate_and_load_static_assets`] which in turn invokes this function.
+/// Any custom client configured with
+/// [`GooseUser::set_client_builder`](goose::goose::GooseUser::set_client_builder) is
+/// replaced.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::load_static_elements;
-///
-/// transaction!(load_page_and_static_elements).set_on_start();
+/// use goose_eggs::enable_cookie_jar;
 ///
-/// async fn load_page_and_static_elements(user: &mut GooseUser) -> TransactionResult {
-///     let mut goose = user.get("/").await?;
+/// transaction!(start_session).set_on_start();
 ///
-///     match goose.response {
-///         Ok(response) => {
-///             // Copy the headers so we have them for logging if there are errors.
-///             let headers = &response.headers().clone();
-///             match response.text().await {
-///                 Ok(html) => {
-///                     // Load all static elements on page.
-///                     load_static_elements(user, &html).await;
-///                 }
-///                 Err(e) => {
-///                     return user.set_failure(
-///                         &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
-///                         &mut goose.request,
-///                         Some(headers),
-///                         None,
-///                     );
-///                 }
-///             }
-///         }
-///         Err(e) => {
-///             return user.set_failure(
-///                 &format!("{}: no response from server: {}", goose.request.raw.url, e),
-///                 &mut goose.request,
-///                 None,
-///                 None,
-///             );
-///         }
-///     }
+/// async fn start_session(user: &mut GooseUser) -> TransactionResult {
+///     enable_cookie_jar(user)?;
+///     let _goose = user.get("/user/login").await?;
 ///
 ///     Ok(())
 /// }
 /// ```
-pub async fn load_static_elements(user: &mut GooseUser, html: &str) {
-    // Use a case-insensitive regular expression to find all src=<foo> in the html, where
-    // <foo> is the URL to local image and js assets.
-    // @TODO: parse HTML5 srcset= also
-    for url in get_src_elements(user, html).await {
-        let is_js = url.contains(".js");
-        let resource_type = if is_js { "js" } else { "img" };
-        let _ = user
-            .get_named(&url, &("static asset: ".to_owned() + resource_type))
-            .await;
-    }
-
-    // Use a case-insensitive regular expression to find all href=<foo> in the html, where
-    // <foo> is the URL to local css assets.
-    for url in get_css_elements(user, html).await {
-        let _ = user.get_named(&url, "static asset: css").await;
-    }
+pub fn enable_cookie_jar(user: &mut GooseUser) -> Result<(), Box<TransactionError>> {
+    set_cookie_jar(user, Arc::new(Jar::default()))
 }
 
-/// Validate the HTML response and return the HTML body.
+/// Forget the cookie jars enabled with [`enable_cookie_jar`] for every Goose user, for
+/// example before running another load test in the same process.
 ///
-/// What is validated is defined with the [`Validate`] structure.
+/// # Example
+/// ```rust
+/// use goose_eggs::reset_cookie_jars;
 ///
-/// If the page doesn't load, an empty [`String`] will be returned. If the page does load
-/// but validation fails, an Error is returned. If the page loads and there are no
-/// errors the body is returned as a [`String`].
+/// reset_cookie_jars();
+/// ```
+pub fn reset_cookie_jars() {
+    COOKIE_JARS.lock().unwrap().clear();
+}
+
+/// Request a path that redirects, validating each hop of the redirect chain and then
+/// validating the final page, which is returned.
 ///
-/// This function is invoked by [validate_and_load_static_assets], which then also invokes
-/// [load_static_elements] to better simulate a web browser loading a page.
+/// Redirects are not followed automatically: each hop is requested individually so its
+/// status code and `Location` header can be validated, which is useful to load test
+/// language negotiation and vanity url redirects. Each hop is recorded in the Goose
+/// metrics. The final page is validated with [`validate_page`], and must not redirect
+/// again unless the [`Validate`] object allows it.
+///
+/// The redirect chain is followed with a client configured like the client of the Goose
+/// user, that doesn't follow redirects. If the cookie jar of the Goose user was enabled
+/// with [`enable_cookie_jar`] (or [`drupal::enable_session_export`] or
+/// [`drupal::import_session`]) the jar is shared, so the cookies of the user are sent
+/// with each hop and cookies set by the redirects are kept by the user. Otherwise the
+/// cookies of the user are not sent, and cookies set by the redirects are only sent
+/// along the redirect chain and to the final page.
 ///
 /// # Example
 /// ```rust
 /// use goose::prelude::*;
-/// use goose_eggs::{validate_page, Validate};
+/// use goose_eggs::{validate_redirect_chain, RedirectHop, Validate};
 ///
-/// transaction!(load_page).set_on_start();
+/// transaction!(vanity_url).set_on_start();
 ///
-/// async fn load_page(user: &mut GooseUser) -> TransactionResult {
-///     let mut goose = user.get("/").await?;
-///     validate_page(
+/// async fn vanity_url(user: &mut GooseUser) -> TransactionResult {
+///     let _html = validate_redirect_chain(
 ///         user,
-///         goose,
-///         // Validate title and other arbitrary text on the response html.
-///         &Validate::builder()
-///             .title("my page")
-///             .texts(vec!["foo", r#"<a href="bar">"#])
-///             .build(),
+///         "/recipes",
+///         &[RedirectHop::new(301, "/en/recipes"), RedirectHop::new(302, "/es/recetas")],
+///         &Validate::builder().status(200).title("Recetas").build(),
 ///     ).await?;
 ///
 ///     Ok(())
 /// }
 /// ```
-pub async fn validate_page<'a>(
+pub async fn validate_redirect_chain<'a>(
     user: &mut GooseUser,
-    mut goose: GooseResponse,
+    path: &str,
+    hops: &[RedirectHop<'_>],
     validate: &'a Validate<'a>,
 ) -> Result<String, Box<TransactionError>> {
-    let empty = "".to_string();
-    match goose.response {
-        Ok(response) => {
-            // Validate whether or not the request redirected.
-            if let Some(redirect) = validate.redirect {
-                if goose.request.redirected != redirect {
-                    // Get as much as we can from the response for useful debug logging.
-                    let headers = &response.headers().clone();
-                    let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                    let error = if redirect {
-                        format!("{}: did not redirect", goose.request.raw.url)
-                    // Unexpected redirect happened.
-                    } else {
-                        format!("{}: redirected unexpectedly", goose.request.raw.url)
-                    };
-                    user.set_failure(&error, &mut goose.request, Some(headers), Some(&html))?;
-                    // Exit as soon as validation fails, to avoid cascades of
-                    // errors whe na page fails to load.
-                    return Ok(html);
-                }
-            }
-
-            // Validate status code if defined.
-            if let Some(validate_status) = validate.status.as_ref() {
-                // If equals is false, error if response.status == status
-                if !validate_status.equals && response.status() == validate_status.status_code {
-                    // Get as much as we can from the response for useful debug logging.
-                    let headers = &response.headers().clone();
-                    let response_status = response.status();
-                    let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                    user.set_failure(
-                        &format!(
-                            "{}: response status == {}]: {}",
-                            goose.request.raw.url, validate_status.status_code, response_status
-                        ),
-                        &mut goose.request,
-                        Some(headers),
-                        Some(&html),
-                    )?;
-                    // Exit as soon as validation fails, to avoid cascades of
-                    // errors whe na page fails to load.
-                    return Ok(html);
-                // If equals is true, error if response.status != status
-                } else if validate_status.equals && response.status() != validate_status.status_code
-                {
-                    // Get as much as we can from the response for useful debug logging.
-                    let headers = &response.headers().clone();
-                    let response_status = response.status();
-                    let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                    user.set_failure(
-                        &format!(
-                            "{}: response status != {}]: {}",
-                            goose.request.raw.url, validate_status.status_code, response_status
-                        ),
-                        &mut goose.request,
-                        Some(headers),
-                        Some(&html),
-                    )?;
-                    // Exit as soon as validation fails, to avoid cascades of
-                    // errors whe na page fails to load.
-                    return Ok(html);
-                }
-            }
+    // Temporarily replace the client with one that doesn't follow redirects, sharing the
+    // cookies of the user if possible.
+    let jar = cookie_jar(user).unwrap_or_default();
+    let client = client_builder(user)
+        .redirect(reqwest::redirect::Policy::none())
+        .cookie_provider(jar)
+        .build()
+        .map_err(|e| Box::new(TransactionError::Reqwest(e)))?;
+    let client = std::mem::replace(&mut user.client, client);
+    let goose = follow_redirect_chain(user, path, hops, validate).await;
+    user.client = client;
 
-            // Validate headers if defined.
-            let headers = &response.headers().clone();
-            for validate_header in &validate.headers {
-                if !validate_header.exists {
-                    if header_is_set(headers, validate_header.header) {
-                        // Get as much as we can from the response for useful debug logging.
-                        let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                        user.set_failure(
-                            &format!(
-                                "{}: header included in response: {:?}",
-                                goose.request.raw.url, validate_header.header
-                            ),
-                            &mut goose.request,
-                            Some(headers),
-                            Some(&html),
-                        )?;
-                        // Exit as soon as validation fails, to avoid cascades of
-                        // errors when a page fails to load.
-                        return Ok(html);
-                    }
-                    if !validate_header.value.is_empty()
-                        && valid_header_value(
-                            headers,
-                            (validate_header.header, validate_header.value),
-                        )
-                    {
-                        // Get as much as we can from the response for useful debug logging.
-                        let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                        user.set_failure(
-                            &format!(
-                                "{}: header contains unexpected value: {:?}",
-                                goose.request.raw.url, validate_header.value
-                            ),
-                            &mut goose.request,
-                            Some(headers),
-                            Some(&html),
-                        )?;
-                        // Exit as soon as validation fails, to avoid cascades of
-                        // errors when a page fails to load.
-                        return Ok(html);
-                    }
-                } else {
-                    if !header_is_set(headers, validate_header.header) {
-                        // Get as much as we can from the response for useful debug logging.
-                        let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                        user.set_failure(
-                            &format!(
-                                "{}: header not included in response: {:?}",
-                                goose.request.raw.url, validate_header.header
-                            ),
-                            &mut goose.request,
-                            Some(headers),
-                            Some(&html),
-                        )?;
-                        // Exit as soon as validation fails, to avoid cascades of
-                        // errors when a page fails to load.
-                        return Ok(html);
-                    }
-                    if !validate_header.value.is_empty()
-                        && !valid_header_value(
-                            headers,
-                            (validate_header.header, validate_header.value),
-                        )
-                    {
-                        // Get as much as we can from the response for useful debug logging.
-                        let html = response.text().await.unwrap_or_else(|_| "".to_string());
-                        user.set_failure(
-                            &format!(
-                                "{}: header does not contain expected value: {:?}",
-                                goose.request.raw.url, validate_header.value
-                            ),
-                            &mut goose.request,
-                            Some(headers),
-                            Some(&html),
-                        )?;
-                        // Exit as soon as validation fails, to avoid cascades of
-                        // errors when a page fails to load.
-                        return Ok(html);
-                    }
-                }
-            }
+    match goose? {
+        Some(goose) => validate_page(user, goose, validate).await,
+        None => Ok("".to_string()),
+    }
+}
 
-            // Extract the response body to validate and load static elements.
-            match response.text().await {
-                Ok(html) => {
-                    // Validate title if defined.
-                    if let Some(validate_title) = validate.title.as_ref() {
-                        // Be sure the title doesn't contain the specified text.
-                        if !validate_title.exists && valid_title(&html, validate_title.title) {
-                            user.set_failure(
-                                &format!(
-                                    "{}: title found: {}",
-                                    goose.request.raw.url, validate_title.title
-                                ),
-                                &mut goose.request,
-                                Some(headers),
-                                Some(&html),
-                            )?;
-                            // Exit as soon as validation fails, to avoid cascades of
-                            // errors when a page fails to load.
-                            return Ok(html);
-                        // Be sure the title contains the specified text.
-                        } else if validate_title.exists && !valid_title(&html, validate_title.title)
-                        {
-                            user.set_failure(
-                                &format!(
-                                    "{}: title not found: {}",
-                                    goose.request.raw.url, validate_title.title
-                                ),
-                                &mut goose.request,
-                                Some(headers),
-                                Some(&html),
-                            )?;
-                            // Exit as soon as validation fails, to avoid cascades of
-                            // errors when a page fails to load.
-                            return Ok(html);
-                        }
-                    }
-                    // Validate texts in body if defined.
-                    for validate_text in &validate.texts {
-                        if !validate_text.exists && valid_text(&html, validate_text.text) {
-                            user.set_failure(
-                                &format!(
-                                    "{}: text found on page: {}",
-                                    goose.request.raw.url, validate_text.text
-                                ),
-                                &mut goose.request,
-                                Some(headers),
-                                Some(&html),
-                            )?;
-                            // Exit as soon as validation fails, to avoid cascades of
-                            // errors when a page fails to load.
-                            return Ok(html);
-                        } else if validate_text.exists && !valid_text(&html, validate_text.text) {
-                            user.set_failure(
-                                &format!(
-                                    "{}: text not found on page: {}",
-                                    goose.request.raw.url, validate_text.text
-                                ),
-                                &mut goose.request,
-                                Some(headers),
-                                Some(&html),
-                            )?;
-                            // Exit as soon as validation fails, to avoid cascades of
-                            // errors when a page fails to load.
-                            return Ok(html);
-                        }
-                    }
-                    Ok(html)
-                }
-                Err(e) => {
-                    user.set_failure(
-                        &format!("{}: failed to parse page: {}", goose.request.raw.url, e),
-                        &mut goose.request,
-                        Some(headers),
-                        None,
-                    )?;
-                    Ok(empty)
-                }
+// Request each hop of a redirect chain, returning the response to the final request, or
+// None if a hop failed validation and only a warning was logged.
+async fn follow_redirect_chain(
+    user: &mut GooseUser,
+    path: &str,
+    hops: &[RedirectHop<'_>],
+    validate: &Validate<'_>,
+) -> Result<Option<GooseResponse>, Box<TransactionError>> {
+    let mut url = user.build_url(path)?;
+    for (index, hop) in hops.iter().enumerate() {
+        // Expect the redirect status code so Goose doesn't record the redirect as an error.
+        let goose_request = GooseRequest::builder()
+            .path(url.as_str())
+            .expect_status_code(hop.status)
+            .build();
+        let mut goose = user.request(goose_request).await?;
+        let (status, location) = match &goose.response {
+            Ok(response) => (
+                response.status(),
+                response
+                    .headers()
+                    .get("location")
+                    .and_then(|location| location.to_str().ok())
+                    .map(|location| location.to_string()),
+            ),
+            Err(e) => {
+                user.set_failure(
+                    &format!("{}: no response from server: {}", goose.request.raw.url, e),
+                    &mut goose.request,
+                    None,
+                    None,
+                )?;
+                return Ok(None);
             }
+        };
+        if status != hop.status || !matches!(&location, Some(l) if l.contains(hop.location)) {
+            let error = ValidationError::RedirectMismatch {
+                hop: index + 1,
+                expected_status: hop.status,
+                expected_location: hop.location.to_string(),
+                status,
+                location,
+            };
+            report_failure(user, validate, &[error], &mut goose.request, None, None)?;
+            return Ok(None);
         }
-        Err(e) => {
-            user.set_failure(
-                &format!("{}: no response from server: {}", goose.request.raw.url, e),
-                &mut goose.request,
-                None,
-                None,
-            )?;
-            Ok(empty)
-        }
+        // The location can be relative to the current url.
+        url = match location.and_then(|l| reqwest::Url::parse(&url).ok()?.join(&l).ok()) {
+            Some(next) => next.to_string(),
+            None => return Ok(None),
+        };
     }
+    Ok(Some(user.get(&url).await?))
 }
 
 /// Validate the HTML response, extract and load all static elements on the page, and
@@ -1368,6 +6033,9 @@ pub async fn validate_page<'a>(
 /// but validation fails, an Error is returned. If the page loads and there are no
 /// errors the body is returned as a [`String`].
 ///
+/// Static elements preloaded with `Link` response headers, such as
+/// `</assets/app.css>; rel=preload; as=style`, are also loaded.
+///
 /// To only validate the page without also loading static elements, use instead
 /// [validate_page].
 ///
@@ -1398,9 +6066,19 @@ pub async fn validate_and_load_static_assets<'a>(
     goose: GooseResponse,
     validate: &'a Validate<'a>,
 ) -> Result<String, Box<TransactionError>> {
-    match validate_page(user, goose, validate).await {
-        Ok(html) => {
-            load_static_elements(user, &html).await;
+    let request_name = goose.request.name.clone();
+    // Attribute static assets loaded over other connections to the page's transaction.
+    let attribution = Attribution::of(&goose.request);
+    // Keep the headers of the validated response, to load assets preloaded with `Link`
+    // headers.
+    match validate_page_with_headers(user, goose, validate).await {
+        Ok((html, headers)) => {
+            let started = Instant::now();
+            let assets = validate.assets.clone().unwrap_or_default();
+            let urls = extract_static_urls(user, &html, headers.as_ref(), &assets).await;
+            validate.record_timing(&request_name, "assets", started.elapsed());
+            let loaded = load_static_urls(user, urls, &assets, Some(attribution)).await;
+            metrics::record_assets(&request_name, &loaded);
             Ok(html)
         }
         Err(e) => Err(e),
@@ -1416,6 +6094,22 @@ mod tests {
 
     const EMPTY_ARGS: Vec<&str> = vec![];
     const HOST: &str = "http://example.com";
+    const CDN: &str = r#"
+        <img src="http://another.com/example.png">
+        <script src="http://CDN.example.net/example.js"></script>
+        <img src="http://elsewhere.com/example.png">"#;
+    const TYPES: &str = r#"
+        <script src="/path/to/example.json"></script>
+        <script src="/path/to/example.min.js?v=1"></script>
+        <img src="/path/to/example.pdf">
+        <link rel="preload" href="/path/to/font" as="fetch" type="font/woff2">"#;
+
+    // Returns a Goose user of a load test against `HOST`.
+    fn test_user() -> GooseUser {
+        let configuration = GooseConfiguration::parse_args_default(&EMPTY_ARGS).unwrap();
+        let base_url = get_base_url(Some(HOST.to_string()), None, None).unwrap();
+        GooseUser::new(0, "".to_string(), base_url, &configuration, 0, None).unwrap()
+    }
 
     #[tokio::test]
     async fn get_static_elements() {
@@ -1502,4 +6196,388 @@ mod tests {
         }
         assert_eq!(urls.len(), 7);
     }
+
+    #[tokio::test]
+    async fn picture_static_elements() {
+        const PICTURE: &str = r#"
+            <!-- valid first candidate of the source without a media query -->
+            <picture>
+                <source media="(min-width: 800px)" srcset="/path/to/large.webp 1x, /path/to/large@2x.webp 2x">
+                <source srcset="/path/to/small.webp" type="image/webp">
+                <img alt="example picture">
+            </picture>
+            <!-- invalid candidate on different domain -->
+            <picture><source srcset="http://another.com/example.webp"></picture>
+            <!-- valid first candidate of the first source with a media query -->
+            <picture><source media="(min-width: 800px)" srcset="path/to/wide.avif 800w"></picture>"#;
+        let mut user = test_user();
+        let urls = get_picture_elements(&mut user, PICTURE).await;
+        assert_eq!(urls, vec!["/path/to/small.webp", "path/to/wide.avif"]);
+    }
+
+    #[tokio::test]
+    async fn media_static_elements() {
+        const MEDIA: &str = r#"
+            <video controls poster="/path/to/poster.jpg">
+                <source src="/path/to/example.webm" type="video/webm">
+                <source src="http://another.com/example.mp4" type="video/mp4">
+            </video>
+            <audio src="path/to/example.mp3"></audio>
+            <img src="/path/to/example.png" alt="not media" />"#;
+        let mut user = test_user();
+        let urls = get_media_elements(&mut user, MEDIA).await;
+        assert_eq!(urls, vec!["/path/to/example.webm", "path/to/example.mp3"]);
+    }
+
+    #[tokio::test]
+    async fn preload_static_elements() {
+        const PRELOAD: &str = r#"
+            <link rel="preload" href="/path/to/font.woff2" as="font" type="font/woff2" crossorigin>
+            <link as="script" rel="preload" href="path/to/example.js">
+            <link rel="modulepreload" href="/path/to/module.mjs">
+            <link rel="prefetch" href="/path/to/next.html">
+            <link rel="preload" href="/path/to/data.json">
+            <link rel="stylesheet" href="/path/to/example.css">"#;
+        let mut user = test_user();
+        let urls = get_preload_elements(&mut user, PRELOAD).await;
+        assert_eq!(
+            urls,
+            vec![
+                "/path/to/font.woff2",
+                "path/to/example.js",
+                "/path/to/module.mjs"
+            ]
+        );
+        let urls = preload_urls(PRELOAD);
+        assert_eq!(
+            urls,
+            vec![
+                ("/path/to/font.woff2".to_string(), "font"),
+                ("path/to/example.js".to_string(), "js"),
+                ("/path/to/module.mjs".to_string(), "js"),
+                ("/path/to/next.html".to_string(), "prefetch"),
+            ]
+        );
+        let urls = extract_static_urls(&mut user, PRELOAD, None, &LoadAssets::default()).await;
+        assert_eq!(urls.len(), 4);
+        let prefetch = LoadAssets::builder().prefetch().build();
+        let urls = extract_static_urls(&mut user, PRELOAD, None, &prefetch).await;
+        assert_eq!(urls.len(), 5);
+        assert!(urls.contains(&("/path/to/next.html".to_string(), "prefetch")));
+    }
+
+    #[tokio::test]
+    async fn inline_style_static_elements() {
+        const STYLE: &str = r#"
+            <style>.hero { background: url('/path/to/hero.jpg') no-repeat; }</style>
+            <div style="background-image:url(path/to/example.png)"></div>
+            <div style="background-image: url(&quot;data:image/png;base64,AAAA&quot;)"></div>
+            <div style="background-image: url(http://another.com/example.png)"></div>"#;
+        let mut user = test_user();
+        let urls = get_inline_style_elements(&mut user, STYLE).await;
+        assert_eq!(urls, vec!["path/to/example.png", "/path/to/hero.jpg"]);
+    }
+
+    #[tokio::test]
+    async fn css_static_elements() {
+        const CSS: &str = r#"
+            @import "base.css";
+            @import url('/themes/print.css?v=1') print;
+            @font-face { src: url(../fonts/example.woff2) format("woff2"); }
+            .icon { background: url("http://another.com/sprite.png"); }
+            .logo { background: url(data:image/png;base64,AAAA); }"#;
+        let mut user = test_user();
+        let css_url = Url::parse("http://example.com/themes/css/style.css").unwrap();
+        let urls = css_asset_urls(&mut user, &css_url, CSS, &LoadAssets::default());
+        assert_eq!(
+            urls,
+            vec![
+                ("http://example.com/themes/css/base.css".to_string(), "css"),
+                ("http://example.com/themes/print.css?v=1".to_string(), "css"),
+                (
+                    "http://example.com/themes/fonts/example.woff2".to_string(),
+                    "font"
+                ),
+            ]
+        );
+        let cdn = LoadAssets::builder().allow_host("another.com").build();
+        let urls = css_asset_urls(&mut user, &css_url, CSS, &cdn);
+        assert_eq!(urls.len(), 4);
+        assert_eq!(
+            urls[3],
+            ("http://another.com/sprite.png".to_string(), "img")
+        );
+    }
+
+    #[tokio::test]
+    async fn lazy_load_static_elements() {
+        const LAZY: &str = r#"
+            <img class="lazyload" data-src="/path/to/lazy.jpg" src="/path/to/placeholder.gif">
+            <img data-srcset="path/to/lazy-1x.jpg 1x, path/to/lazy-2x.jpg 2x">
+            <img data-src="http://another.com/lazy.jpg">"#;
+        let mut user = test_user();
+        let urls = get_lazy_load_elements(&mut user, LAZY).await;
+        assert_eq!(urls, vec!["/path/to/lazy.jpg", "path/to/lazy-1x.jpg"]);
+        let urls = get_src_elements(&mut user, LAZY).await;
+        assert_eq!(urls, vec!["/path/to/placeholder.gif"]);
+    }
+
+    #[tokio::test]
+    async fn object_static_elements() {
+        const OBJECT: &str = r#"
+            <object type="application/pdf" data="/path/to/example.pdf" width="600"></object>
+            <embed type="image/svg+xml" src="path/to/example.svg">
+            <object data="http://another.com/example.pdf"></object>
+            <object data="path/to/another.pdf"></object>"#;
+        let mut user = test_user();
+        let urls = get_object_elements(&mut user, OBJECT).await;
+        assert_eq!(
+            urls,
+            vec![
+                "/path/to/example.pdf",
+                "path/to/example.svg",
+                "path/to/another.pdf"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn svg_use_static_elements() {
+        const SVG: &str = r##"
+            <svg><use href="/path/to/icons.svg#arrow"></use></svg>
+            <svg><use xlink:href="/path/to/icons.svg#close"></use></svg>
+            <svg><use href="#inline-icon"></use></svg>"##;
+        let mut user = test_user();
+        let urls = get_svg_use_elements(&mut user, SVG).await;
+        assert_eq!(urls, vec!["/path/to/icons.svg"]);
+    }
+
+    #[tokio::test]
+    async fn duplicate_static_elements() {
+        const DUPLICATES: &str = r#"
+            <link rel="preload" href="/path/to/example.css" as="style">
+            <link rel="stylesheet" href="/path/to/example.css">
+            <img src="http://example.com/path/to/example.png">
+            <img src="/path/to/example.png">"#;
+        let mut user = test_user();
+        let urls = extract_static_urls(&mut user, DUPLICATES, None, &LoadAssets::default()).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("http://example.com/path/to/example.png".to_string(), "img"),
+                ("/path/to/example.css".to_string(), "css"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cdn_static_elements() {
+        let mut user = test_user();
+        let cdn = LoadAssets::builder()
+            .allow_host("another.com")
+            .allow_host("cdn.example.net")
+            .build();
+        let urls = extract_static_urls(&mut user, CDN, None, &cdn).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("http://another.com/example.png".to_string(), "img"),
+                ("http://CDN.example.net/example.js".to_string(), "js"),
+            ]
+        );
+        assert!(get_src_elements(&mut user, CDN).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn limited_static_elements() {
+        let mut user = test_user();
+        let first = LoadAssets::builder()
+            .allow_host("another.com")
+            .allow_host("cdn.example.net")
+            .max_assets(1)
+            .build();
+        let urls = extract_static_urls(&mut user, CDN, None, &first).await;
+        assert_eq!(
+            urls,
+            vec![("http://another.com/example.png".to_string(), "img")]
+        );
+        let random = LoadAssets::builder()
+            .allow_host("another.com")
+            .allow_host("cdn.example.net")
+            .max_random_assets(1)
+            .build();
+        let urls = extract_static_urls(&mut user, CDN, None, &random).await;
+        assert_eq!(urls.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn typed_static_elements() {
+        let mut user = test_user();
+        let custom = LoadAssets::builder().asset_type(".PDF", "document").build();
+        let urls = extract_static_urls(&mut user, TYPES, None, &custom).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("/path/to/example.json".to_string(), "json"),
+                ("/path/to/example.min.js?v=1".to_string(), "js"),
+                ("/path/to/example.pdf".to_string(), "document"),
+                ("/path/to/font".to_string(), "font"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn drupal_library_static_elements() {
+        const AJAX_PAGE_STATE: &str = r#"
+            <link rel="stylesheet" href="/files/css/css_abc.css?delta=0&amp;theme=olivero&amp;include=eJxLzs8p">
+            <script type="application/json" data-drupal-selector="drupal-settings-json">{"path":{"currentLanguage":"en"},"ajaxPageState":{"theme":"olivero","libraries":"eJxLzs8p"}}</script>
+            <script type="application/vnd.drupal-ajax" data-big-pipe-replacement-for-placeholder-with-id="callback=foo">[{"command":"settings","settings":{"ajaxPageState":{"theme":"olivero","libraries":"eJxLzs8pLc"}},"merge":true}]</script>"#;
+        let mut user = test_user();
+        let drupal = LoadAssets::builder().drupal_libraries("/files/").build();
+        let urls = extract_static_urls(&mut user, AJAX_PAGE_STATE, None, &drupal).await;
+        assert_eq!(
+            urls[1..],
+            [
+                (
+                    "/files/css/css_ajaxpagestate.css?delta=0&language=en&theme=olivero&include=eJxLzs8pLc".to_string(),
+                    "css"
+                ),
+                (
+                    "/files/js/js_ajaxpagestate.js?scope=header&delta=0&language=en&theme=olivero&include=eJxLzs8pLc".to_string(),
+                    "js"
+                ),
+                (
+                    "/files/js/js_ajaxpagestate.js?scope=footer&delta=0&language=en&theme=olivero&include=eJxLzs8pLc".to_string(),
+                    "js"
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn protocol_relative_static_elements() {
+        const URIS: &str = r#"
+            <img src="//example.com/path/to/example.png">
+            <script src="//another.com/example.js"></script>
+            <img src=" data:image/gif;base64,R0lGODlhAQABAAAAACw=">
+            <img src="DATA:image/png;base64,AAAA">"#;
+        let mut user = test_user();
+        let urls = get_src_elements(&mut user, URIS).await;
+        assert_eq!(urls, vec!["http://example.com/path/to/example.png"]);
+        let cdn = LoadAssets::builder().allow_host("another.com").build();
+        let urls = extract_static_urls(&mut user, URIS, None, &cdn).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("http://example.com/path/to/example.png".to_string(), "img"),
+                ("http://another.com/example.js".to_string(), "js"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn filtered_static_elements() {
+        let mut user = test_user();
+        let only_css = LoadAssets::builder().only_css().build();
+        let urls = extract_static_urls(&mut user, TYPES, None, &only_css).await;
+        assert!(urls.is_empty());
+        let no_js = LoadAssets::builder().skip_js().skip_images().build();
+        let urls = extract_static_urls(&mut user, TYPES, None, &no_js).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("/path/to/example.json".to_string(), "json"),
+                ("/path/to/font".to_string(), "font"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn link_header_static_elements() {
+        let mut user = test_user();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            "</path/to/app.css>; rel=preload; as=style, <https://another.com/x.js>; rel=preload"
+                .parse()
+                .unwrap(),
+        );
+        headers.append(
+            "link",
+            r#"</path/to/module.mjs>; rel="modulepreload", </next>; rel=next"#
+                .parse()
+                .unwrap(),
+        );
+        let urls = extract_static_urls(&mut user, "", Some(&headers), &LoadAssets::default()).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("/path/to/app.css".to_string(), "css"),
+                ("/path/to/module.mjs".to_string(), "js"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn noscript_static_elements() {
+        const NOSCRIPT: &str = r#"
+            <img src="/path/to/example.png">
+            <noscript><img src="/path/to/pixel.gif"></noscript>"#;
+        let mut user = test_user();
+        let urls = extract_static_urls(&mut user, NOSCRIPT, None, &LoadAssets::default()).await;
+        assert_eq!(urls.len(), 2);
+        let skip_noscript = LoadAssets::builder().skip_noscript().build();
+        let urls = extract_static_urls(&mut user, NOSCRIPT, None, &skip_noscript).await;
+        assert_eq!(urls, vec![("/path/to/example.png".to_string(), "img")]);
+    }
+
+    #[tokio::test]
+    async fn normalized_static_elements() {
+        const NORMALIZE: &str = r#"
+            <img src=" /path/to/example.png#fragment ">
+            <img src="/path/to/example.png">
+            <img src="../path/../to/example.gif?a=1&amp;b=2">
+            <img src="javascript:void(0)">
+            <embed src="mailto:someone@example.com">
+            <img src="">"#;
+        let mut user = test_user();
+        let urls = get_src_elements(&mut user, NORMALIZE).await;
+        assert_eq!(
+            urls,
+            vec![
+                "/path/to/example.png",
+                "/path/to/example.png",
+                "../path/../to/example.gif?a=1&b=2"
+            ]
+        );
+        let urls = extract_static_urls(&mut user, NORMALIZE, None, &LoadAssets::default()).await;
+        assert_eq!(
+            urls,
+            vec![
+                ("/path/to/example.png".to_string(), "img"),
+                ("../path/../to/example.gif?a=1&b=2".to_string(), "img"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "html-parser")]
+    #[tokio::test]
+    async fn parse_static_elements() {
+        const HTML: &str = r#"
+            <LINK REL=stylesheet HREF='/path/to/example.css'>
+            <IMG SRC=/path/to/example.png>
+            <script
+                type="text/javascript"
+                src='path/to/example.js'></script>
+            <!-- <img src="/path/to/commented.png"> -->
+            <img data-src="/path/to/lazy.png" src="">"#;
+        let configuration = GooseConfiguration::parse_args_default(&EMPTY_ARGS).unwrap();
+        let base_url = get_base_url(Some(HOST.to_string()), None, None).unwrap();
+        let mut user =
+            GooseUser::new(0, "".to_string(), base_url, &configuration, 0, None).unwrap();
+        let urls = get_css_elements(&mut user, HTML).await;
+        assert_eq!(urls, vec!["/path/to/example.css"]);
+        let urls = get_src_elements(&mut user, HTML).await;
+        assert_eq!(urls, vec!["/path/to/example.png", "path/to/example.js"]);
+    }
 }
@@ -0,0 +1,328 @@
+//! Per-validation pass and fail counters, and per-page static asset rollups.
+
+use goose::metrics::{GooseMetric, GooseRequestMetric};
+use goose::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{LoadedAssets, UserKey, ValidationError};
+
+// Validation metrics collected by all Goose users, keyed by name.
+static VALIDATION_METRICS: Mutex<BTreeMap<String, ValidationMetrics>> = Mutex::new(BTreeMap::new());
+
+// Static asset metrics collected by all Goose users, keyed by the name of the page request.
+static ASSET_METRICS: Mutex<BTreeMap<String, AssetMetrics>> = Mutex::new(BTreeMap::new());
+
+// How many responses each Goose user validated and how many failed, keyed by user and
+// validation name.
+static USER_FAILURES: Mutex<BTreeMap<(UserKey, String), (usize, usize)>> =
+    Mutex::new(BTreeMap::new());
+
+/// How often a named [`Validate`](crate::Validate) object was checked, and how often each
+/// of its validations failed.
+///
+/// Validations are named with [`ValidateBuilder::name`](crate::ValidateBuilder::name), or
+/// otherwise by the name of the request they validated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationMetrics {
+    /// The name of the validation.
+    pub name: String,
+    /// How many responses were validated.
+    pub checked: usize,
+    /// How many responses failed at least one validation.
+    pub failed: usize,
+    /// How many times each validation failed, keyed by failure message.
+    pub failures: BTreeMap<String, usize>,
+    /// How long each phase of validation took, keyed by phase. Only recorded for
+    /// validations built with [`ValidateBuilder::timed`](crate::ValidateBuilder::timed).
+    pub timings: BTreeMap<String, ValidationTiming>,
+}
+
+/// How long a phase of validation took across all validated responses.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationTiming {
+    /// How many times the phase was timed.
+    pub count: usize,
+    /// The total time spent in the phase.
+    pub total: Duration,
+    /// The longest time spent in the phase.
+    pub max: Duration,
+}
+
+impl ValidationTiming {
+    /// The average time spent in the phase.
+    pub fn average(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count as u32,
+        }
+    }
+}
+
+/// The weight and latency of the static assets loaded with pages, aggregated by the name of
+/// the page request.
+///
+/// Recorded by [`validate_and_load_static_assets`](crate::validate_and_load_static_assets).
+/// Name page requests differently in each scenario to report asset metrics per scenario.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssetMetrics {
+    /// The name of the page request.
+    pub name: String,
+    /// How many times static assets were loaded for the page.
+    pub pages: usize,
+    /// How many static assets were loaded.
+    pub assets: usize,
+    /// How many static assets failed to load.
+    pub failed: usize,
+    /// How many bytes of static assets were downloaded.
+    pub bytes: usize,
+    /// The total time spent loading the static assets of the page.
+    pub total: Duration,
+    /// The longest time spent loading the static assets of the page.
+    pub max: Duration,
+}
+
+impl AssetMetrics {
+    /// The average number of bytes of static assets downloaded per page, the page weight
+    /// without the html.
+    pub fn average_bytes(&self) -> usize {
+        self.bytes.checked_div(self.pages).unwrap_or(0)
+    }
+
+    /// The average time spent loading the static assets of the page.
+    pub fn average_time(&self) -> Duration {
+        match self.pages {
+            0 => Duration::ZERO,
+            pages => self.total / pages as u32,
+        }
+    }
+}
+
+impl fmt::Display for AssetMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            " {:<40} | {:>8} | {:>8} | {:>12} | {:>10.2?}",
+            self.name,
+            self.pages,
+            self.assets,
+            self.average_bytes(),
+            self.average_time()
+        )
+    }
+}
+
+impl ValidationMetrics {
+    /// The percentage of validated responses that failed at least one validation.
+    pub fn fail_percent(&self) -> f32 {
+        percent(self.failed, self.checked)
+    }
+}
+
+impl fmt::Display for ValidationMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            " {:<40} | {:>8} | {:>8} | {:>7.2}%",
+            self.name,
+            self.checked,
+            self.failed,
+            self.fail_percent()
+        )?;
+        for (failure, count) in &self.failures {
+            writeln!(
+                f,
+                "   {:<38} | {:>8} | {:>8} | {:>7.2}%",
+                failure,
+                "",
+                count,
+                percent(*count, self.checked)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn percent(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f32 / total as f32 * 100.0
+    }
+}
+
+// Returns the metrics of the named validation, adding them if necessary.
+fn metric<'a>(
+    metrics: &'a mut BTreeMap<String, ValidationMetrics>,
+    name: &str,
+) -> &'a mut ValidationMetrics {
+    metrics
+        .entry(name.to_string())
+        .or_insert_with(|| ValidationMetrics {
+            name: name.to_string(),
+            ..Default::default()
+        })
+}
+
+// Record the outcome of validating a response.
+pub(crate) fn record(name: &str, errors: &[ValidationError]) {
+    let mut metrics = VALIDATION_METRICS.lock().unwrap();
+    let metric = metric(&mut metrics, name);
+    metric.checked += 1;
+    if !errors.is_empty() {
+        metric.failed += 1;
+    }
+    for error in errors {
+        *metric.failures.entry(error.to_string()).or_insert(0) += 1;
+    }
+}
+
+// Send the outcome of a named validation to Goose as a request named `validation: {name}`,
+// so it's counted in the success and fail columns of the Goose metrics and reports. It
+// reuses the method and status code of the validated request, with no response time.
+pub(crate) fn record_goose_metric(
+    user: &GooseUser,
+    request: &GooseRequestMetric,
+    name: &str,
+    passed: bool,
+) -> Result<(), Box<TransactionError>> {
+    let mut metric = request.clone();
+    metric.name = format!("validation: {}", name);
+    metric.success = passed;
+    metric.update = false;
+    metric.error = String::new();
+    metric.response_time = 0;
+    metric.coordinated_omission_elapsed = 0;
+    // There's no parent to send metrics to when running outside of a load test.
+    if let Some(metrics_channel) = user.metrics_channel.as_ref() {
+        if let Err(e) = metrics_channel.send(GooseMetric::Request(Box::new(metric))) {
+            return Err(Box::new(e.into()));
+        }
+    }
+    Ok(())
+}
+
+// Record how long a phase of validating a response took.
+pub(crate) fn record_timing(name: &str, phase: &str, elapsed: Duration) {
+    let mut metrics = VALIDATION_METRICS.lock().unwrap();
+    let timing = metric(&mut metrics, name)
+        .timings
+        .entry(phase.to_string())
+        .or_default();
+    timing.count += 1;
+    timing.total += elapsed;
+    timing.max = timing.max.max(elapsed);
+}
+
+// Record the static assets loaded with a page.
+pub(crate) fn record_assets(name: &str, loaded: &LoadedAssets) {
+    let mut metrics = ASSET_METRICS.lock().unwrap();
+    let metric = metrics
+        .entry(name.to_string())
+        .or_insert_with(|| AssetMetrics {
+            name: name.to_string(),
+            ..Default::default()
+        });
+    metric.pages += 1;
+    metric.assets += loaded.assets.len();
+    metric.failed += loaded.failures().len();
+    metric.bytes += loaded.bytes();
+    metric.total += loaded.duration;
+    metric.max = metric.max.max(loaded.duration);
+}
+
+// Record the outcome of validating a response for a Goose user, returning how many
+// responses the user validated and how many failed.
+pub(crate) fn record_user(user: UserKey, name: &str, failed: bool) -> (usize, usize) {
+    let mut tally = USER_FAILURES.lock().unwrap();
+    let (checked, failures) = tally.entry((user, name.to_string())).or_insert((0, 0));
+    *checked += 1;
+    if failed {
+        *failures += 1;
+    }
+    (*checked, *failures)
+}
+
+/// Returns the pass and fail counters of every validation performed so far, ordered by
+/// name. This is typically displayed after the load test completes, alongside the Goose
+/// metrics.
+///
+/// The counters include the failure message of each failed validation. Validations named
+/// with [`ValidateBuilder::name`](crate::ValidateBuilder::name) can also be counted in the
+/// Goose metrics and reports, as requests named `validation: {name}`, with
+/// [`ValidateBuilder::goose_metric`](crate::ValidateBuilder::goose_metric).
+///
+/// The counters are kept for the life of the process, call [`reset_validation_metrics`]
+/// to start counting again, for example before running another load test.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::validation_metrics;
+///
+/// println!(" {:<40} | {:>8} | {:>8} | {:>8}", "Validation", "Checked", "Failed", "Fail %");
+/// for metric in validation_metrics() {
+///     print!("{}", metric);
+/// }
+/// ```
+pub fn validation_metrics() -> Vec<ValidationMetrics> {
+    VALIDATION_METRICS
+        .lock()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// Forget the counters returned by [`validation_metrics`], including the timings recorded
+/// by [`ValidateBuilder::timed`](crate::ValidateBuilder::timed), and the failures tallied
+/// per Goose user by [`ValidateBuilder::tolerate_failures`](crate::ValidateBuilder::tolerate_failures)
+/// and [`ValidateBuilder::tolerate_failure_rate`](crate::ValidateBuilder::tolerate_failure_rate).
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{reset_validation_metrics, validation_metrics};
+///
+/// reset_validation_metrics();
+/// assert!(validation_metrics().is_empty());
+/// ```
+pub fn reset_validation_metrics() {
+    VALIDATION_METRICS.lock().unwrap().clear();
+    USER_FAILURES.lock().unwrap().clear();
+}
+
+/// Returns the weight and latency of the static assets loaded with each page since the
+/// last [`reset_asset_metrics`], ordered by the name of the page request. This is typically
+/// displayed after the load test completes, alongside the Goose metrics.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::asset_metrics;
+///
+/// println!(
+///     " {:<40} | {:>8} | {:>8} | {:>12} | {:>10}",
+///     "Page", "Loaded", "Assets", "Avg bytes", "Avg time"
+/// );
+/// for metric in asset_metrics() {
+///     print!("{}", metric);
+/// }
+/// ```
+pub fn asset_metrics() -> Vec<AssetMetrics> {
+    ASSET_METRICS.lock().unwrap().values().cloned().collect()
+}
+
+/// Forget the static asset metrics returned by [`asset_metrics`], for example before running
+/// another load test in the same process. Which assets each Goose user already loaded and
+/// cached is forgotten separately, with [`reset_loaded_assets`](crate::reset_loaded_assets).
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::{asset_metrics, reset_asset_metrics};
+///
+/// reset_asset_metrics();
+/// assert!(asset_metrics().is_empty());
+/// ```
+pub fn reset_asset_metrics() {
+    ASSET_METRICS.lock().unwrap().clear();
+}
@@ -0,0 +1,1061 @@
+//! Functionality that's specific to WordPress.
+
+pub mod rest;
+
+use std::env;
+
+use goose::goose::GooseResponse;
+use goose::prelude::*;
+use regex::Regex;
+
+use crate::drupal::Form;
+
+/// Parameters that define how to log into a WordPress website and validate that the user
+/// logged in successfully. For complete documentation, refer to [`WpLoginBuilder`].
+#[derive(Clone, Debug)]
+pub struct WpLogin<'a> {
+    // Optionally set a default username.
+    username: &'a str,
+    // Optionally set a default password.
+    password: &'a str,
+    // Optionally set a custom log in path (otherwise defaults to `wp-login.php`).
+    url: &'a str,
+    // Optionally set where to be redirected after logging in.
+    redirect_to: Option<&'a str>,
+    // Whether to check "Remember Me".
+    remember: bool,
+    // Optionally perform validation of the page with the login form.
+    log_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally perform validation once the user logs in.
+    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpLogin<'a> {
+    /// Convenience function to bring [`WpLoginBuilder`] into scope.
+    pub fn builder() -> WpLoginBuilder<'a> {
+        WpLoginBuilder::new()
+    }
+}
+
+/// Used to build a [`WpLogin`] object, necessary to invoke the [`log_in`] function.
+///
+/// The defined `username` and/or `password` can be dynamically overridden by setting
+/// the `GOOSE_USER` and/or `GOOSE_PASS` environment variables when starting the load
+/// test.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::WpLogin;
+///
+/// // Log in as `foo`:`bar`, and land on the front page instead of the dashboard.
+/// let _login = WpLogin::builder()
+///     .username("foo")
+///     .password("bar")
+///     .redirect_to("/")
+///     .build();
+/// ```
+pub struct WpLoginBuilder<'a> {
+    // Optionally set a default username.
+    username: &'a str,
+    // Optionally set a default password.
+    password: &'a str,
+    // Optionally set a custom log in path (otherwise defaults to `wp-login.php`).
+    url: &'a str,
+    // Optionally set where to be redirected after logging in.
+    redirect_to: Option<&'a str>,
+    // Whether to check "Remember Me".
+    remember: bool,
+    // Optionally perform validation of the page with the login form.
+    log_in_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally perform validation once the user logs in.
+    logged_in_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpLoginBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to a username of "username".
+            username: "username",
+            // Defaults to a password of "password".
+            password: "password",
+            // Defaults to "wp-login.php".
+            url: "wp-login.php",
+            // Defaults to the redirect of the log in form, the dashboard.
+            redirect_to: None,
+            // Defaults to a session that ends when the browser closes.
+            remember: false,
+            // Defaults to validating that the log in form exists.
+            log_in_page_validation: None,
+            // Defaults to validating that the admin bar is displayed.
+            logged_in_page_validation: None,
+        }
+    }
+
+    /// Used with [`WpLogin::builder`] to set the username to log in with.
+    ///
+    /// Defaults to `username`. Can be overridden by the `GOOSE_USER` environment
+    /// variable.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// let _login = WpLogin::builder()
+    ///     .username("editor")
+    ///     .build();
+    /// ```
+    pub fn username(mut self, username: impl Into<&'a str>) -> Self {
+        self.username = username.into();
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to set the password to log in with.
+    ///
+    /// Defaults to `password`. Can be overridden by the `GOOSE_PASS` environment
+    /// variable.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// let _login = WpLogin::builder()
+    ///     .password("secret")
+    ///     .build();
+    /// ```
+    pub fn password(mut self, password: impl Into<&'a str>) -> Self {
+        self.password = password.into();
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to set the path of the log in page, for sites that
+    /// move it.
+    ///
+    /// Defaults to `wp-login.php`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// let _login = WpLogin::builder()
+    ///     .url("members/login")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to set where WordPress redirects the user after
+    /// logging in, posted as `redirect_to`.
+    ///
+    /// Defaults to the `redirect_to` of the log in form, which is the dashboard. Refer
+    /// to the [`WpLoginBuilder`] example.
+    pub fn redirect_to(mut self, redirect_to: impl Into<&'a str>) -> Self {
+        self.redirect_to = Some(redirect_to.into());
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to check "Remember Me", so WordPress sets
+    /// persistent cookies that last two weeks.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// let _login = WpLogin::builder()
+    ///     .remember(true)
+    ///     .build();
+    /// ```
+    pub fn remember(mut self, remember: bool) -> Self {
+        self.remember = remember;
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to set a custom validation of the log in page.
+    ///
+    /// Defaults to validating that the page has the `loginform` form.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// let validate = Validate::builder()
+    ///     .title("Log In")
+    ///     .form("loginform")
+    ///     .build();
+    /// let _login = WpLogin::builder()
+    ///     .log_in_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn log_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.log_in_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`WpLogin::builder`] to set a custom validation of the page the user is
+    /// redirected to after logging in.
+    ///
+    /// Defaults to validating that the page displays the admin bar, which WordPress
+    /// displays on the dashboard and, unless disabled in the user's profile, on the
+    /// front end.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// // Validate that the user lands on the dashboard.
+    /// let validate = Validate::builder()
+    ///     .title("Dashboard")
+    ///     .build();
+    /// let _login = WpLogin::builder()
+    ///     .logged_in_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn logged_in_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.logged_in_page_validation = Some(validation);
+        self
+    }
+
+    /// Build the [`WpLogin`] object which is then passed to the [`log_in`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpLogin;
+    ///
+    /// // Log in with the default username and password.
+    /// let _login = WpLogin::builder().build();
+    /// ```
+    pub fn build(self) -> WpLogin<'a> {
+        let Self {
+            username,
+            password,
+            url,
+            redirect_to,
+            remember,
+            log_in_page_validation,
+            logged_in_page_validation,
+        } = self;
+        WpLogin {
+            username,
+            password,
+            url,
+            redirect_to,
+            remember,
+            log_in_page_validation,
+            logged_in_page_validation,
+        }
+    }
+}
+
+/// Extract the error WordPress displayed above the log in form, without markup, such as
+/// `Error: The password you entered for the username admin is incorrect.`
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::get_login_error;
+///
+/// let html = r#"
+/// <div id="login_error" class="notice notice-error">
+///   <strong>Error:</strong> Cookies are blocked or not supported by your browser.<br />
+/// </div>
+/// "#;
+///
+/// assert_eq!(
+///     get_login_error(html).as_deref(),
+///     Some("Error: Cookies are blocked or not supported by your browser.")
+/// );
+/// ```
+pub fn get_login_error(html: &str) -> Option<String> {
+    let re = Regex::new(r#"(?s)<div[^>]*\sid="login_error"[^>]*>(.*?)</div>"#).unwrap();
+    let error = re.captures(html)?;
+    Some(plain_text(&error[1]))
+}
+
+// Strip the markup of an html fragment, decoding entities and collapsing whitespace.
+fn plain_text(html: &str) -> String {
+    let tags = Regex::new(r#"<[^>]*>"#).unwrap();
+    let text = tags.replace_all(html, " ");
+    let text = html_escape::decode_html_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    text
+}
+
+// Get the headers and html of a response to aid in debugging a failure.
+async fn failure_details(goose: GooseResponse) -> (Option<reqwest::header::HeaderMap>, String) {
+    match goose.response {
+        Ok(r) => {
+            let headers = r.headers().clone();
+            let html = r.text().await.unwrap_or_else(|e| e.to_string());
+            (Some(headers), html)
+        }
+        Err(e) => (None, e.to_string()),
+    }
+}
+
+/// Log into a WordPress website.
+///
+/// The log in page is loaded first, as a browser would, which sets the
+/// `wordpress_test_cookie` cookie WordPress requires when the form is posted with
+/// `testcookie`. The form is then posted with the username and password, and the
+/// optional `redirect_to`. If WordPress doesn't redirect, log in failed and the error it
+/// displayed is included in the failure. Otherwise the page the user is redirected to
+/// is validated, by default that it displays the admin bar, and its html returned.
+///
+/// If no default username is set in the [`WpLogin`] object, the function will default to
+/// a username of `username` which can be overridden by the `GOOSE_USER` environment
+/// variable. If no default password is set, the function will default to a password of
+/// `password` which can be overridden by the `GOOSE_PASS` environment variable.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::{log_in, WpLogin};
+///
+/// transaction!(login).set_on_start();
+///
+/// async fn login(user: &mut GooseUser) -> TransactionResult {
+///     let login = WpLogin::builder()
+///         .username("foo")
+///         .password("bar")
+///         .build();
+///     let _html = log_in(user, &login).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn log_in(
+    user: &mut GooseUser,
+    login: &WpLogin<'_>,
+) -> Result<String, Box<TransactionError>> {
+    // Use the `GOOSE_USER` environment variable if it's set, otherwise use the specified
+    // (or default) login username.
+    let username = env::var("GOOSE_USER").unwrap_or_else(|_| login.username.to_string());
+
+    // Use the `GOOSE_PASS` environment variable if it's set, otherwise use the specified
+    // (or default) login password.
+    let password = env::var("GOOSE_PASS").unwrap_or_else(|_| login.password.to_string());
+
+    // By default verify that the log in form exists on the page.
+    let default_validation = crate::Validate::builder().form("loginform").build();
+    let validate = login.log_in_page_validation.unwrap_or(&default_validation);
+
+    // Load the log in page, which also sets the test cookie.
+    let goose = user.get(login.url).await?;
+    let mut login_request = goose.request.clone();
+    let login_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    let form = match Form::parse(&login_page, "loginform") {
+        Some(form) => form,
+        None => {
+            user.set_failure(
+                &format!("{}: no loginform on page", login.url),
+                &mut login_request,
+                None,
+                Some(&login_page),
+            )?;
+            // Return an empty string as log-in failed.
+            return Ok("".to_string());
+        }
+    };
+
+    // Post the log in form, keeping its hidden fields.
+    let mut overrides = vec![
+        ("log", username.as_str()),
+        ("pwd", password.as_str()),
+        ("wp-submit", "Log In"),
+        ("testcookie", "1"),
+    ];
+    if let Some(redirect_to) = login.redirect_to {
+        overrides.push(("redirect_to", redirect_to));
+    }
+    if login.remember {
+        overrides.push(("rememberme", "forever"));
+    }
+    let mut form = form;
+    for (name, value) in overrides {
+        form.set(name, value);
+    }
+    let logged_in_user = user.post_form(login.url, &form.fields).await?;
+
+    // A successful log in is redirected.
+    if !logged_in_user.request.redirected {
+        let mut request = logged_in_user.request.clone();
+        let (headers, html) = failure_details(logged_in_user).await;
+        let mut failure = format!(
+            "{}: login failed (check `GOOSE_USER` and `GOOSE_PASS`)",
+            request.final_url
+        );
+        if let Some(error) = get_login_error(&html) {
+            failure = format!("{}: {}", failure, error);
+        }
+        user.set_failure(&failure, &mut request, headers.as_ref(), Some(&html))?;
+        // Return the html that was loaded, even though log-in failed.
+        return Ok(html);
+    }
+
+    // By default verify that the admin bar is displayed.
+    let default_validation = crate::Validate::builder()
+        .text(r#"id="wpadminbar""#)
+        .build();
+    let validate = login
+        .logged_in_page_validation
+        .unwrap_or(&default_validation);
+    crate::validate_and_load_static_assets(user, logged_in_user, validate).await
+}
+
+/// Parameters that define how to make and validate a search. For complete documentation,
+/// refer to [`WpSearchParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct WpSearchParams<'a> {
+    // The word or words to search for.
+    keys: &'a str,
+    // Optionally set a custom path to search from.
+    url: &'a str,
+    // Optionally limit the search to post types.
+    post_types: &'a [&'a str],
+    // Optionally validate the search results page.
+    results_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpSearchParams<'a> {
+    /// Convenience function to bring [`WpSearchParamsBuilder`] into scope.
+    pub fn builder() -> WpSearchParamsBuilder<'a> {
+        WpSearchParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`WpSearchParams`] object, necessary to invoke the [`search`] function.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress;
+///
+/// transaction!(search);
+///
+/// async fn search(user: &mut GooseUser) -> TransactionResult {
+///     // Search pages for the keys "search terms".
+///     let search_params = wordpress::WpSearchParams::builder()
+///         .keys("search terms")
+///         .post_types(&["page"])
+///         .build();
+///
+///     // Perform the actual search.
+///     let _search_results_html = wordpress::search(user, &search_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct WpSearchParamsBuilder<'a> {
+    // The word or words to search for.
+    keys: &'a str,
+    // Optionally set a custom path to search from.
+    url: &'a str,
+    // Optionally limit the search to post types.
+    post_types: &'a [&'a str],
+    // Optionally validate the search results page.
+    results_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpSearchParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to empty search keys.
+            keys: "",
+            // Defaults to the front page.
+            url: "",
+            // Defaults to searching all post types WordPress searches.
+            post_types: &[],
+            // Defaults to validating that the search results template is displayed.
+            results_page_validation: None,
+        }
+    }
+
+    /// Used with [`WpSearchParams::builder`] to set the keys to search for, passed as `s`.
+    ///
+    /// Defaults to `""`, an empty search string.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpSearchParams;
+    ///
+    /// let _search_params = WpSearchParams::builder()
+    ///     .keys("foo bar")
+    ///     .build();
+    /// ```
+    pub fn keys(mut self, keys: impl Into<&'a str>) -> Self {
+        self.keys = keys.into();
+        self
+    }
+
+    /// Used with [`WpSearchParams::builder`] to set the path the search is made from, for
+    /// sites that install WordPress in a subdirectory.
+    ///
+    /// Defaults to `""`, the front page.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpSearchParams;
+    ///
+    /// let _search_params = WpSearchParams::builder()
+    ///     .url("blog/")
+    ///     .build();
+    /// ```
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`WpSearchParams::builder`] to limit the search to post types, passed as
+    /// `post_type`. WordPress ignores post types that aren't publicly queryable.
+    ///
+    /// Defaults to `&[]`, searching every post type WordPress searches. Refer to the
+    /// [`WpSearchParamsBuilder`] example.
+    pub fn post_types(mut self, post_types: &'a [&'a str]) -> Self {
+        self.post_types = post_types;
+        self
+    }
+
+    /// Used with [`WpSearchParams::builder`] to set a custom validation of the search
+    /// results page.
+    ///
+    /// Defaults to `None`, which validates that the page is displayed with the search
+    /// results template, identified by the `search` class of its `<body>`, whether or not
+    /// anything was found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::WpSearchParams;
+    ///
+    /// // Validate that something was found.
+    /// let validate = Validate::builder()
+    ///     .text("search-results")
+    ///     .build();
+    /// let _search_params = WpSearchParams::builder()
+    ///     .keys("foo")
+    ///     .results_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn results_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.results_page_validation = Some(validation);
+        self
+    }
+
+    /// Build the [`WpSearchParams`] object which is then passed to the [`search`] function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpSearchParams;
+    ///
+    /// // Search for `example keys`.
+    /// let _search_params = WpSearchParams::builder()
+    ///     .keys("example keys")
+    ///     .build();
+    /// ```
+    pub fn build(self) -> WpSearchParams<'a> {
+        let Self {
+            keys,
+            url,
+            post_types,
+            results_page_validation,
+        } = self;
+        WpSearchParams {
+            keys,
+            url,
+            post_types,
+            results_page_validation,
+        }
+    }
+}
+
+/// Returns a [`bool`] indicating whether the page was displayed with WordPress's search
+/// results template, identified by the `search` class WordPress adds to its `<body>`.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::is_search_page;
+///
+/// assert!(is_search_page(r#"<body class="search search-no-results wp-embed-responsive">"#));
+/// assert!(!is_search_page(r#"<body class="home blog search-form-visible">"#));
+/// ```
+pub fn is_search_page(html: &str) -> bool {
+    let re = Regex::new(r#"(?s)<body[^>]*\sclass="([^"]*)""#).unwrap();
+    re.captures(html)
+        .is_some_and(|capture| capture[1].split_whitespace().any(|class| class == "search"))
+}
+
+/// Perform a WordPress search, the way its search widget does with a `?s=` query.
+///
+/// The request is named `wordpress: search`, so searches for different keys are grouped
+/// in the metrics. By default the results page is validated to be displayed with the
+/// search results template. Refer to [`WpSearchParamsBuilder::results_page_validation`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress;
+///
+/// transaction!(search);
+///
+/// async fn search(user: &mut GooseUser) -> TransactionResult {
+///     let search_params = wordpress::WpSearchParams::builder()
+///         .keys("foo")
+///         .build();
+///     let _search_results = wordpress::search(user, &search_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn search<'a>(
+    user: &mut GooseUser,
+    params: &'a WpSearchParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    // Build the query, a single post type is passed as a string and several as an array.
+    let mut query = vec![("s", params.keys)];
+    match params.post_types {
+        [] => {}
+        [post_type] => query.push(("post_type", post_type)),
+        post_types => {
+            for post_type in post_types {
+                query.push(("post_type[]", post_type));
+            }
+        }
+    }
+
+    // Perform the search.
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Get, params.url)?
+        .query(&query);
+    let goose_request = GooseRequest::builder()
+        .path(params.url)
+        .name("wordpress: search")
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let mut request = goose.request.clone();
+
+    // Validate the search results page, by default that it uses the search template.
+    let no_validation = crate::Validate::none();
+    let validate = params.results_page_validation.unwrap_or(&no_validation);
+    let search_results = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    if params.results_page_validation.is_none()
+        && !search_results.is_empty()
+        && !is_search_page(&search_results)
+    {
+        user.set_failure(
+            &format!("{}: not the search results template", request.final_url),
+            &mut request,
+            None,
+            Some(&search_results),
+        )?;
+    }
+
+    // Return the search results.
+    Ok(search_results)
+}
+
+/// Parameters that define how to post a comment and validate that it was posted. For
+/// complete documentation, refer to [`WpCommentParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct WpCommentParams<'a> {
+    // The path of the post to comment on.
+    url: &'a str,
+    // Optionally set the text of the comment.
+    comment: Option<&'a str>,
+    // The name of the comment author.
+    author: &'a str,
+    // The email address of the comment author.
+    email: &'a str,
+    // Optionally reply to a comment.
+    parent: Option<usize>,
+    // Optionally expect the comment to be held for moderation, or approved.
+    moderation: Option<bool>,
+    // Optionally validate the page with the comment form.
+    post_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned once the comment is posted.
+    comment_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpCommentParams<'a> {
+    /// Convenience function to bring [`WpCommentParamsBuilder`] into scope.
+    pub fn builder() -> WpCommentParamsBuilder<'a> {
+        WpCommentParamsBuilder::new()
+    }
+}
+
+/// Used to build a [`WpCommentParams`] object, necessary to invoke the [`post_comment`]
+/// function.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::WpCommentParams;
+///
+/// // Comment on the "Hello world!" post, expecting the comment to be held for moderation.
+/// let _comment_params = WpCommentParams::builder()
+///     .url("2024/01/01/hello-world/")
+///     .author("Goose")
+///     .email("goose@example.com")
+///     .moderation(true)
+///     .build();
+/// ```
+pub struct WpCommentParamsBuilder<'a> {
+    // The path of the post to comment on.
+    url: &'a str,
+    // Optionally set the text of the comment.
+    comment: Option<&'a str>,
+    // The name of the comment author.
+    author: &'a str,
+    // The email address of the comment author.
+    email: &'a str,
+    // Optionally reply to a comment.
+    parent: Option<usize>,
+    // Optionally expect the comment to be held for moderation, or approved.
+    moderation: Option<bool>,
+    // Optionally validate the page with the comment form.
+    post_page_validation: Option<&'a crate::Validate<'a>>,
+    // Optionally validate the page returned once the comment is posted.
+    comment_page_validation: Option<&'a crate::Validate<'a>>,
+}
+impl<'a> WpCommentParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to the first post, "Hello world!".
+            url: "?p=1",
+            // Defaults to random words.
+            comment: None,
+            // Defaults to "Goose".
+            author: "Goose",
+            // Defaults to "goose@example.com".
+            email: "goose@example.com",
+            // Defaults to a top-level comment.
+            parent: None,
+            // Defaults to accepting approved and held comments.
+            moderation: None,
+            // Defaults to validating that the comment form exists.
+            post_page_validation: None,
+            // Defaults to validating that the comment is displayed.
+            comment_page_validation: None,
+        }
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set the path of the post to comment on.
+    ///
+    /// Defaults to `?p=1`, the "Hello world!" post of a new WordPress website. Refer to
+    /// the [`WpCommentParamsBuilder`] example.
+    pub fn url(mut self, url: impl Into<&'a str>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set the text of the comment.
+    ///
+    /// Defaults to 20 random words, as WordPress rejects duplicate comments from the same
+    /// author.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpCommentParams;
+    ///
+    /// let _comment_params = WpCommentParams::builder()
+    ///     .comment("Great post!")
+    ///     .build();
+    /// ```
+    pub fn comment(mut self, comment: impl Into<&'a str>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set the name of the comment author. It's
+    /// ignored by WordPress when the user is logged in.
+    ///
+    /// Defaults to `Goose`. Refer to the [`WpCommentParamsBuilder`] example.
+    pub fn author(mut self, author: impl Into<&'a str>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set the email address of the comment
+    /// author. It's ignored by WordPress when the user is logged in.
+    ///
+    /// Defaults to `goose@example.com`. Refer to the [`WpCommentParamsBuilder`] example.
+    pub fn email(mut self, email: impl Into<&'a str>) -> Self {
+        self.email = email.into();
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to reply to a comment, posted as
+    /// `comment_parent`.
+    ///
+    /// Defaults to posting a top-level comment.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpCommentParams;
+    ///
+    /// // Reply to the comment on the "Hello world!" post of a new WordPress website.
+    /// let _comment_params = WpCommentParams::builder()
+    ///     .parent(1)
+    ///     .build();
+    /// ```
+    pub fn parent(mut self, parent: usize) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to validate whether the comment is held for
+    /// moderation, `true`, or approved, `false`.
+    ///
+    /// Defaults to accepting either. Refer to the [`WpCommentParamsBuilder`] example.
+    pub fn moderation(mut self, moderation: bool) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set a custom validation of the page with
+    /// the comment form.
+    ///
+    /// Defaults to validating that the page has the `commentform` form.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::WpCommentParams;
+    ///
+    /// let validate = Validate::builder()
+    ///     .title("Hello world!")
+    ///     .form("commentform")
+    ///     .build();
+    /// let _comment_params = WpCommentParams::builder()
+    ///     .post_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn post_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.post_page_validation = Some(validation);
+        self
+    }
+
+    /// Used with [`WpCommentParams::builder`] to set a custom validation of the page
+    /// WordPress redirects to once the comment is posted.
+    ///
+    /// Defaults to validating that a held comment is displayed as awaiting moderation,
+    /// and that an approved comment is displayed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::WpCommentParams;
+    ///
+    /// let validate = Validate::builder()
+    ///     .text("Great post!")
+    ///     .build();
+    /// let _comment_params = WpCommentParams::builder()
+    ///     .comment("Great post!")
+    ///     .comment_page_validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn comment_page_validation(mut self, validation: &'a crate::Validate) -> Self {
+        self.comment_page_validation = Some(validation);
+        self
+    }
+
+    /// Build the [`WpCommentParams`] object which is then passed to the [`post_comment`]
+    /// function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::WpCommentParams;
+    ///
+    /// // Post a random comment on the first post.
+    /// let _comment_params = WpCommentParams::builder().build();
+    /// ```
+    pub fn build(self) -> WpCommentParams<'a> {
+        let Self {
+            url,
+            comment,
+            author,
+            email,
+            parent,
+            moderation,
+            post_page_validation,
+            comment_page_validation,
+        } = self;
+        WpCommentParams {
+            url,
+            comment,
+            author,
+            email,
+            parent,
+            moderation,
+            post_page_validation,
+            comment_page_validation,
+        }
+    }
+}
+
+/// Extract the message of a WordPress error page, without markup, such as the
+/// `Error: Duplicate comment detected; it looks as though you’ve already said that!`
+/// displayed when a comment is rejected.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::get_error_message;
+///
+/// let html = r#"
+/// <body id="error-page">
+///   <div class="wp-die-message"><p><strong>Error:</strong> You are posting comments too quickly. Slow down.</p></div>
+/// </body>
+/// "#;
+///
+/// assert_eq!(
+///     get_error_message(html).as_deref(),
+///     Some("Error: You are posting comments too quickly. Slow down.")
+/// );
+/// ```
+pub fn get_error_message(html: &str) -> Option<String> {
+    // WordPress 5.3 and later wrap the message, earlier versions display it in the body.
+    let re = Regex::new(
+        r#"(?s)<div[^>]*\sclass="wp-die-message"[^>]*>(.*?)</div>|<body[^>]*\sid="error-page"[^>]*>(.*?)</body>"#,
+    )
+    .unwrap();
+    let capture = re.captures(html)?;
+    let message = capture.get(1).or_else(|| capture.get(2))?;
+    Some(plain_text(message.as_str()))
+}
+
+/// Post a comment on a WordPress post, the heaviest write anonymous visitors make.
+///
+/// The post is loaded and its `commentform` scraped, keeping its hidden fields such as
+/// `comment_post_ID`, `comment_parent` and any nonces, for example Akismet's
+/// `akismet_comment_nonce` or the `_wp_unfiltered_html_comment` nonce of administrators.
+/// The comment is then posted to the form's action, `wp-comments-post.php`, with a request
+/// named `wordpress: comment`.
+///
+/// WordPress redirects once the comment is saved, to the comment if it was approved or
+/// with `unapproved` in the query if it's held for moderation. If WordPress doesn't
+/// redirect, the comment was rejected and the error it displayed is included in the
+/// failure. Refer to [`WpCommentParamsBuilder::moderation`] and
+/// [`WpCommentParamsBuilder::comment_page_validation`] for how the page redirected to is
+/// validated. Its html is returned.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::{post_comment, WpCommentParams};
+///
+/// transaction!(comment);
+///
+/// async fn comment(user: &mut GooseUser) -> TransactionResult {
+///     let comment_params = WpCommentParams::builder()
+///         .url("2024/01/01/hello-world/")
+///         .build();
+///     let _html = post_comment(user, &comment_params).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn post_comment<'a>(
+    user: &mut GooseUser,
+    params: &'a WpCommentParams<'a>,
+) -> Result<String, Box<TransactionError>> {
+    // By default verify that the comment form exists on the page.
+    let default_validation = crate::Validate::builder().form("commentform").build();
+    let validate = params.post_page_validation.unwrap_or(&default_validation);
+
+    // Load the post.
+    let goose = user.get(params.url).await?;
+    let mut post_request = goose.request.clone();
+    let post_page = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    let mut form = match Form::parse(&post_page, "commentform") {
+        Some(form) => form,
+        None => {
+            user.set_failure(
+                &format!("{}: no commentform on page", params.url),
+                &mut post_request,
+                None,
+                Some(&post_page),
+            )?;
+            // Return an empty string as posting the comment failed.
+            return Ok("".to_string());
+        }
+    };
+
+    // Fill in the comment, keeping the hidden fields.
+    let comment = match params.comment {
+        Some(comment) => comment.to_string(),
+        None => crate::text::random_words(20),
+    };
+    form.set("comment", &comment);
+    form.set("author", params.author);
+    form.set("email", params.email);
+    if let Some(parent) = params.parent {
+        form.set("comment_parent", &parent.to_string());
+    }
+    let action = if form.action.is_empty() {
+        "wp-comments-post.php".to_string()
+    } else {
+        form.action.clone()
+    };
+
+    // Post the comment.
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Post, &action)?
+        .form(&form.fields);
+    let goose_request = GooseRequest::builder()
+        .method(GooseMethod::Post)
+        .path(action.as_str())
+        .name("wordpress: comment")
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+
+    // A saved comment is redirected.
+    if !goose.request.redirected {
+        let mut request = goose.request.clone();
+        let (headers, html) = failure_details(goose).await;
+        let mut failure = format!("{}: comment not posted", request.final_url);
+        if let Some(error) = get_error_message(&html) {
+            failure = format!("{}: {}", failure, error);
+        }
+        user.set_failure(&failure, &mut request, headers.as_ref(), Some(&html))?;
+        // Return the html that was loaded, even though posting the comment failed.
+        return Ok(html);
+    }
+
+    // WordPress adds `unapproved` to the query when the comment is held for moderation.
+    let mut request = goose.request.clone();
+    let held = goose
+        .request
+        .final_url
+        .split(['?', '&', '#'])
+        .any(|part| part.starts_with("unapproved="));
+    let default_validation = match held {
+        true => crate::Validate::builder()
+            .text("Your comment is awaiting moderation")
+            .build(),
+        false => crate::Validate::builder().text(r#"id="comment-"#).build(),
+    };
+    let validate = params
+        .comment_page_validation
+        .unwrap_or(&default_validation);
+    let html = crate::validate_and_load_static_assets(user, goose, validate).await?;
+    if let Some(moderation) = params.moderation {
+        if moderation != held {
+            let description = match held {
+                true => "held for moderation",
+                false => "approved without moderation",
+            };
+            user.set_failure(
+                &format!("{}: comment {}", request.final_url, description),
+                &mut request,
+                None,
+                Some(&html),
+            )?;
+        }
+    }
+
+    Ok(html)
+}
@@ -0,0 +1,546 @@
+//! Functionality that's specific to the WordPress REST API, served under `/wp-json/`.
+//!
+//! These helpers list collections such as posts, reading the `X-WP-Total` and
+//! `X-WP-TotalPages` pagination headers, fetch individual items such as media, and create
+//! and update items authenticated with an application password, validating the structure
+//! of each response.
+
+use goose::metrics::GooseRequestMetric;
+use goose::prelude::*;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::Validate;
+
+/// Parameters that define how to reach the WordPress REST API, authenticate and validate
+/// the responses. For complete documentation, refer to [`WpRestParamsBuilder`].
+#[derive(Clone, Debug)]
+pub struct WpRestParams<'a> {
+    // The path prefix of the REST API.
+    prefix: &'a str,
+    // The namespace of the routes.
+    namespace: &'a str,
+    // Optionally set how many items are returned per page.
+    per_page: Option<usize>,
+    // How many pages of a collection to fetch.
+    max_pages: usize,
+    // Optionally authenticate with a username and application password.
+    application_password: Option<(&'a str, &'a str)>,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> WpRestParams<'a> {
+    /// Convenience function to bring [`WpRestParamsBuilder`] into scope.
+    pub fn builder() -> WpRestParamsBuilder<'a> {
+        WpRestParamsBuilder::new()
+    }
+
+    // The path of a route, such as `posts` or `media/5`.
+    fn path(&self, route: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.prefix.trim_matches('/'),
+            self.namespace.trim_matches('/'),
+            route.trim_start_matches('/')
+        )
+    }
+}
+
+/// Used to build a [`WpRestParams`] object, necessary to invoke the [`get_collection`],
+/// [`list_posts`], [`get_item`], [`get_media`], [`create_item`] and [`update_item`]
+/// functions.
+///
+/// # Example
+/// ```rust
+/// use goose_eggs::wordpress::rest::WpRestParams;
+///
+/// // Fetch the first three pages of ten items, as an author.
+/// let _params = WpRestParams::builder()
+///     .per_page(10)
+///     .max_pages(3)
+///     .application_password("author", "abcd EFGH 1234 ijkl MNOP 6789")
+///     .build();
+/// ```
+pub struct WpRestParamsBuilder<'a> {
+    // The path prefix of the REST API.
+    prefix: &'a str,
+    // The namespace of the routes.
+    namespace: &'a str,
+    // Optionally set how many items are returned per page.
+    per_page: Option<usize>,
+    // How many pages of a collection to fetch.
+    max_pages: usize,
+    // Optionally authenticate with a username and application password.
+    application_password: Option<(&'a str, &'a str)>,
+    // Optionally validate each response.
+    validation: Option<&'a Validate<'a>>,
+}
+impl<'a> WpRestParamsBuilder<'a> {
+    // Internally used when building to set defaults.
+    fn new() -> Self {
+        Self {
+            // Defaults to WordPress's standard path prefix of "wp-json".
+            prefix: "wp-json",
+            // Defaults to the core routes, "wp/v2".
+            namespace: "wp/v2",
+            // Defaults to WordPress's page size of 10.
+            per_page: None,
+            // Defaults to fetching only the first page of a collection.
+            max_pages: 1,
+            // Defaults to making anonymous requests.
+            application_password: None,
+            // Defaults to validating the status code and content type.
+            validation: None,
+        }
+    }
+
+    /// Used with [`WpRestParams::builder`] to set the path prefix of the REST API.
+    ///
+    /// Defaults to `wp-json`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::rest::WpRestParams;
+    ///
+    /// // Use the REST API of WordPress installed in a subdirectory.
+    /// let _params = WpRestParams::builder()
+    ///     .prefix("blog/wp-json")
+    ///     .build();
+    /// ```
+    pub fn prefix(mut self, prefix: impl Into<&'a str>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Used with [`WpRestParams::builder`] to set the namespace of the routes, for
+    /// requesting the routes of plugins.
+    ///
+    /// Defaults to `wp/v2`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::rest::WpRestParams;
+    ///
+    /// // Request WooCommerce's routes.
+    /// let _params = WpRestParams::builder()
+    ///     .namespace("wc/store/v1")
+    ///     .build();
+    /// ```
+    pub fn namespace(mut self, namespace: impl Into<&'a str>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Used with [`WpRestParams::builder`] to set how many items each page of a collection
+    /// returns, passed as `per_page`. WordPress allows up to 100.
+    ///
+    /// Defaults to WordPress's page size of 10. Refer to the [`WpRestParamsBuilder`]
+    /// example.
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Used with [`WpRestParams::builder`] to set how many pages of a collection
+    /// [`get_collection`] fetches, stopping earlier at the last page reported by the
+    /// `X-WP-TotalPages` header.
+    ///
+    /// Defaults to `1`. Refer to the [`WpRestParamsBuilder`] example.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Used with [`WpRestParams::builder`] to authenticate each request with basic auth,
+    /// using a username and one of its application passwords, created on the user's
+    /// profile. Writes must be authenticated.
+    ///
+    /// Defaults to making anonymous requests. Refer to the [`WpRestParamsBuilder`]
+    /// example.
+    pub fn application_password(
+        mut self,
+        username: impl Into<&'a str>,
+        password: impl Into<&'a str>,
+    ) -> Self {
+        self.application_password = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Used with [`WpRestParams::builder`] to set a custom validation of each response.
+    ///
+    /// Defaults to `None`, so it validates that the response has a `200` status code, or
+    /// `201` when creating an item, and a json `Content-Type`. Whatever the validation, a
+    /// collection must be an array and an item must have an `id`.
+    ///
+    /// What validation should be performed is defined by passing a reference to a
+    /// [`Validate`](crate::Validate) object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::Validate;
+    /// use goose_eggs::wordpress::rest::WpRestParams;
+    ///
+    /// // Also validate that the response was cached.
+    /// let validate = Validate::builder()
+    ///     .status(200)
+    ///     .header_value("x-cache", "HIT")
+    ///     .build();
+    /// let _params = WpRestParams::builder()
+    ///     .validation(&validate)
+    ///     .build();
+    /// ```
+    pub fn validation(mut self, validation: &'a Validate) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// Build the [`WpRestParams`] object which is then passed to the [`get_collection`],
+    /// [`list_posts`], [`get_item`], [`get_media`], [`create_item`] and [`update_item`]
+    /// functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose_eggs::wordpress::rest::WpRestParams;
+    ///
+    /// // Make anonymous requests to the core routes.
+    /// let _params = WpRestParams::builder().build();
+    /// ```
+    pub fn build(self) -> WpRestParams<'a> {
+        let Self {
+            prefix,
+            namespace,
+            per_page,
+            max_pages,
+            application_password,
+            validation,
+        } = self;
+        WpRestParams {
+            prefix,
+            namespace,
+            per_page,
+            max_pages,
+            application_password,
+            validation,
+        }
+    }
+}
+
+/// The items fetched by [`get_collection`] and [`list_posts`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Collection {
+    /// The items of each page.
+    pub items: Vec<Value>,
+    /// How many items the collection has, from the `X-WP-Total` header.
+    pub total: Option<usize>,
+    /// How many pages the collection has, from the `X-WP-TotalPages` header.
+    pub total_pages: Option<usize>,
+    /// How many pages were fetched.
+    pub pages: usize,
+}
+
+// Read a numeric header, such as `X-WP-Total`.
+fn header_number(headers: Option<&HeaderMap>, name: &str) -> Option<usize> {
+    headers?.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+// Send a request with an optional json body, and validate the json response with the
+// expected status code, returning it with the response headers and the request.
+async fn send(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    method: GooseMethod,
+    route: &str,
+    query: &[(&str, String)],
+    body: Option<&Value>,
+    status: u16,
+) -> Result<(Value, Option<HeaderMap>, GooseRequestMetric), Box<TransactionError>> {
+    let path = params.path(route);
+    let mut request_builder = user.get_request_builder(&method, &path)?.query(query);
+    if let Some((username, password)) = params.application_password {
+        request_builder = request_builder.basic_auth(username, Some(password));
+    }
+    if let Some(body) = body {
+        request_builder = request_builder
+            .header("content-type", "application/json")
+            .body(body.to_string());
+    }
+    // Name requests by the route without the id, so all items are aggregated.
+    let base = route.split('/').next().unwrap_or(route);
+    let name = format!("wp-json: {} {}", method, base);
+    let goose_request = GooseRequest::builder()
+        .method(method)
+        .path(path.as_str())
+        .name(name.as_str())
+        .set_request_builder(request_builder)
+        .build();
+    let goose = user.request(goose_request).await?;
+    let request = goose.request.clone();
+    let headers = goose.response.as_ref().ok().map(|r| r.headers().clone());
+
+    // By default validate the status code and content type.
+    let default_validation = Validate::builder()
+        .status(status)
+        .header_value("content-type", "application/json")
+        .build();
+    let validate = params.validation.unwrap_or(&default_validation);
+    let json = crate::validate_json(user, goose, validate).await?;
+    Ok((json, headers, request))
+}
+
+// Fetch an item, validating that it has an id.
+async fn send_item(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    method: GooseMethod,
+    route: &str,
+    body: Option<&Value>,
+    status: u16,
+) -> Result<Value, Box<TransactionError>> {
+    let (json, _, mut request) = send(user, params, method, route, &[], body, status).await?;
+    if !json.is_null() && json.get("id").is_none() {
+        user.set_failure(
+            &format!("{}: not a wp-json item", request.final_url),
+            &mut request,
+            None,
+            Some(&json.to_string()),
+        )?;
+    }
+    Ok(json)
+}
+
+/// Fetch a collection, such as `posts`, `pages` or `comments`, following pages up to
+/// [`WpRestParamsBuilder::max_pages`] or the last page reported by the `X-WP-TotalPages`
+/// header. Each page must be an array.
+///
+/// Each request is named `wp-json: GET {route}`, so all pages are aggregated in the Goose
+/// metrics.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{get_collection, WpRestParams};
+///
+/// transaction!(list_pages);
+///
+/// async fn list_pages(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder().per_page(20).max_pages(2).build();
+///     let pages = get_collection(user, &params, "pages").await?;
+///
+///     println!("fetched {} of {:?} pages", pages.items.len(), pages.total);
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_collection(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    route: &str,
+) -> Result<Collection, Box<TransactionError>> {
+    let mut collection = Collection::default();
+    loop {
+        let mut query = vec![("page", (collection.pages + 1).to_string())];
+        if let Some(per_page) = params.per_page {
+            query.push(("per_page", per_page.to_string()));
+        }
+        let (json, headers, mut request) =
+            send(user, params, GooseMethod::Get, route, &query, None, 200).await?;
+        collection.pages += 1;
+        collection.total = header_number(headers.as_ref(), "x-wp-total");
+        collection.total_pages = header_number(headers.as_ref(), "x-wp-totalpages");
+        match json {
+            Value::Array(items) => collection.items.extend(items),
+            Value::Null => break,
+            json => {
+                user.set_failure(
+                    &format!("{}: not a wp-json collection", request.final_url),
+                    &mut request,
+                    None,
+                    Some(&json.to_string()),
+                )?;
+                break;
+            }
+        }
+
+        // Fetch the next page, if any.
+        let last_page = collection.total_pages.unwrap_or(collection.pages);
+        if collection.pages >= params.max_pages || collection.pages >= last_page {
+            break;
+        }
+    }
+
+    Ok(collection)
+}
+
+/// Fetch posts, refer to [`get_collection`].
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{list_posts, WpRestParams};
+///
+/// transaction!(list_recent_posts);
+///
+/// async fn list_recent_posts(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder().build();
+///     let posts = list_posts(user, &params).await?;
+///
+///     for post in &posts.items {
+///         if let Some(title) = post.pointer("/title/rendered") {
+///             println!("{}", title);
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn list_posts(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+) -> Result<Collection, Box<TransactionError>> {
+    get_collection(user, params, "posts").await
+}
+
+/// Fetch an item of a collection by id, such as `posts/1`, validating that it has an `id`.
+///
+/// The request is named `wp-json: GET {collection}`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{get_item, WpRestParams};
+///
+/// transaction!(load_post);
+///
+/// async fn load_post(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder().build();
+///     let _post = get_item(user, &params, "posts", 1).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_item(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    collection: &str,
+    id: usize,
+) -> Result<Value, Box<TransactionError>> {
+    let route = format!("{}/{}", collection, id);
+    send_item(user, params, GooseMethod::Get, &route, None, 200).await
+}
+
+/// Fetch a media item by id, validating that it has an `id` and a `source_url`, the url
+/// of the uploaded file.
+///
+/// The request is named `wp-json: GET media`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{get_media, WpRestParams};
+///
+/// transaction!(load_image);
+///
+/// async fn load_image(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder().build();
+///     let media = get_media(user, &params, 5).await?;
+///
+///     if let Some(url) = media["source_url"].as_str() {
+///         let _goose = user.get(url).await?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn get_media(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    id: usize,
+) -> Result<Value, Box<TransactionError>> {
+    let route = format!("media/{}", id);
+    let (json, _, mut request) =
+        send(user, params, GooseMethod::Get, &route, &[], None, 200).await?;
+    if !json.is_null() && (json.get("id").is_none() || !json["source_url"].is_string()) {
+        user.set_failure(
+            &format!("{}: not a wp-json media item", request.final_url),
+            &mut request,
+            None,
+            Some(&json.to_string()),
+        )?;
+    }
+    Ok(json)
+}
+
+/// Create an item in a collection, such as a post, returning the item that was created.
+/// The request must be authenticated, refer to [`WpRestParamsBuilder::application_password`].
+///
+/// The request is named `wp-json: POST {collection}`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{create_item, WpRestParams};
+/// use serde_json::json;
+///
+/// transaction!(create_post);
+///
+/// async fn create_post(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder()
+///         .application_password("author", "abcd EFGH 1234 ijkl MNOP 6789")
+///         .build();
+///     let post = json!({
+///         "title": "Created with wp-json",
+///         "content": "Load testing the REST API.",
+///         "status": "draft",
+///     });
+///     let post = create_item(user, &params, "posts", &post).await?;
+///
+///     if let Some(id) = post["id"].as_u64() {
+///         println!("created post {}", id);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn create_item(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    collection: &str,
+    item: &Value,
+) -> Result<Value, Box<TransactionError>> {
+    send_item(user, params, GooseMethod::Post, collection, Some(item), 201).await
+}
+
+/// Update an item by id, returning the item that was updated. Only the fields included in
+/// `item` are changed. The request must be authenticated, refer to
+/// [`WpRestParamsBuilder::application_password`].
+///
+/// The request is named `wp-json: POST {collection}`.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+/// use goose_eggs::wordpress::rest::{update_item, WpRestParams};
+/// use serde_json::json;
+///
+/// transaction!(update_post);
+///
+/// async fn update_post(user: &mut GooseUser) -> TransactionResult {
+///     let params = WpRestParams::builder()
+///         .application_password("author", "abcd EFGH 1234 ijkl MNOP 6789")
+///         .build();
+///     let changes = json!({"title": "Updated with wp-json"});
+///     let _post = update_item(user, &params, "posts", 1, &changes).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn update_item(
+    user: &mut GooseUser,
+    params: &WpRestParams<'_>,
+    collection: &str,
+    id: usize,
+    item: &Value,
+) -> Result<Value, Box<TransactionError>> {
+    let route = format!("{}/{}", collection, id);
+    send_item(user, params, GooseMethod::Post, &route, Some(item), 200).await
+}
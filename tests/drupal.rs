@@ -0,0 +1,1178 @@
+use gumdrop::Options;
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
+
+use goose::config::GooseConfiguration;
+use goose::goose::get_base_url;
+use goose::metrics::GooseCoordinatedOmissionMitigation::Disabled;
+use goose::prelude::*;
+use goose_eggs::drupal;
+use std::time::Duration;
+
+// Paths used in the requests made during these tests.
+const LOGIN_PATH: &str = "/user/login";
+const USER_PATH: &str = "/user/1";
+const LOGOUT_CONFIRM_PATH: &str = "/user/logout/confirm";
+const NODE_PATH: &str = "/node/1";
+const NODE_EDIT_PATH: &str = "/node/1/edit";
+const BATCH_PATH: &str = "/batch";
+const NODE_ADD_PATH: &str = "/node/add/article";
+const MEDIA_LIBRARY_PATH: &str = "/media-library";
+const WEBFORM_PATH: &str = "/webform/lead";
+const ADMIN_CONTENT_PATH: &str = "/admin/content";
+const PAGER_PATH: &str = "/node";
+const ARTICLES_PATH: &str = "/jsonapi/node/article";
+const TFA_PATH: &str = "/tfa/1/LHdAFqMc1wYd8sFBXJ3DzsVuzGa7zPFz";
+
+// The log in page of Drupal 10.
+const LOGIN_HTML: &str = r#"
+<form class="user-login-form" data-drupal-selector="user-login-form" action="/user/login" method="post" id="user-login-form" accept-charset="UTF-8">
+  <input autocorrect="none" autocapitalize="none" spellcheck="false" autofocus="autofocus" data-drupal-selector="edit-name" type="text" id="edit-name" name="name" value="" size="60" maxlength="60" class="form-text required" required="required" />
+  <input data-drupal-selector="edit-pass" type="password" id="edit-pass" name="pass" size="60" maxlength="128" class="form-text required" required="required" />
+  <input autocomplete="off" data-drupal-selector="form-bhzme2hetuevnwqr5y4pyp8jcau2dfbherwoscwnajm" type="hidden" name="form_build_id" value="form-bHZME2HeTuevNWQR5Y4pyP8jcAu2dfbHERwoscwnajM" />
+  <input data-drupal-selector="edit-user-login-form" type="hidden" name="form_id" value="user_login_form" />
+  <div data-drupal-selector="edit-actions" class="form-actions js-form-wrapper form-wrapper" id="edit-actions"><input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Log in" class="button js-form-submit form-submit" /></div>
+</form>
+"#;
+
+// The fields Honeypot adds to a form.
+const HONEYPOT_HTML: &str = r#"
+<div class="url-textfield js-form-wrapper form-wrapper" data-drupal-selector="edit-url" style="display: none !important;" id="edit-url"><div class="js-form-item form-item js-form-type-textfield form-type-textfield js-form-item-url form-item-url"><label for="edit-url--2" class="form-item__label">Leave this field blank</label><input autocomplete="off" data-drupal-selector="edit-url" type="text" id="edit-url--2" name="url" value="" size="20" maxlength="128" class="form-text" /></div></div>
+<input autocomplete="off" data-drupal-selector="edit-honeypot-time" type="hidden" name="honeypot_time" value="kdEF3nHyiC8JN5SBVMPTqZ" />
+"#;
+
+// The log in page of Drupal 10, protected by Antibot.
+const ANTIBOT_HTML: &str = r#"
+<form class="user-login-form antibot" data-drupal-selector="user-login-form" data-action="/user/login" action="/antibot" method="post" id="user-login-form" accept-charset="UTF-8">
+  <noscript>You must have JavaScript enabled to use this form.</noscript>
+  <input autocorrect="none" autocapitalize="none" spellcheck="false" autofocus="autofocus" data-drupal-selector="edit-name" type="text" id="edit-name" name="name" value="" size="60" maxlength="60" class="form-text required" required="required" />
+  <input data-drupal-selector="edit-pass" type="password" id="edit-pass" name="pass" size="60" maxlength="128" class="form-text required" required="required" />
+  <input autocomplete="off" data-drupal-selector="form-bhzme2hetuevnwqr5y4pyp8jcau2dfbherwoscwnajm" type="hidden" name="form_build_id" value="form-bHZME2HeTuevNWQR5Y4pyP8jcAu2dfbHERwoscwnajM" />
+  <input data-drupal-selector="edit-user-login-form" type="hidden" name="form_id" value="user_login_form" />
+  <input data-drupal-selector="edit-antibot-key" type="hidden" name="antibot_key" value="" />
+  <div data-drupal-selector="edit-actions" class="form-actions js-form-wrapper form-wrapper" id="edit-actions"><input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Log in" class="button js-form-submit form-submit" /></div>
+</form>
+<script type="application/json" data-drupal-selector="drupal-settings-json">{"path":{"baseUrl":"\/"},"antibot":{"forms":{"user-login-form":{"id":"user-login-form","key":"cdab2f1e"}}}}</script>
+"#;
+
+// The edit form of an article, with a textarea spanning several lines.
+const NODE_EDIT_HTML: &str = r#"
+<form class="node-article-edit-form node-form" data-drupal-selector="node-article-edit-form" enctype="multipart/form-data" action="/node/1/edit" method="post" id="node-article-edit-form" accept-charset="UTF-8">
+  <input data-drupal-selector="edit-title-0-value" type="text" id="edit-title-0-value" name="title[0][value]" value="Fish &amp; chips" size="60" maxlength="255" class="form-text required" />
+  <textarea data-drupal-selector="edit-body-0-value" id="edit-body-0-value" name="body[0][value]" rows="9" cols="60" class="js-text-full text-full form-textarea">&lt;p&gt;Battered cod,
+served with chips.&lt;/p&gt;</textarea>
+  <textarea data-drupal-selector="edit-revision-log-0-value" id="edit-revision-log-0-value" name="revision_log[0][value]" rows="4" cols="60" class="form-textarea"></textarea>
+  <select data-drupal-selector="edit-body-0-format--2" id="edit-body-0-format--2" name="body[0][format]" class="form-select">
+    <option value="basic_html">Basic HTML</option>
+    <option value="full_html" selected="selected">Full HTML</option>
+  </select>
+  <select data-drupal-selector="edit-langcode-0-value" id="edit-langcode-0-value" name="langcode[0][value]" class="form-select">
+    <option value="en">English</option>
+    <option value="es">Spanish</option>
+  </select>
+</form>
+"#;
+
+// The page a user is redirected to once logged in.
+const LOGGED_IN_HTML: &str = r#"
+<!DOCTYPE html>
+<head>
+  <title>editor | Drupal</title>
+</head>
+<body class="user-logged-in path-user">
+  <h1>editor</h1>
+</body>
+"#;
+
+// Build a Goose user that makes requests to the mock server. Each test uses a different
+// user index, as some helpers remember state for each Goose user.
+fn build_user(server: &MockServer, index: usize) -> GooseUser {
+    let config: Vec<&str> = vec![];
+    let mut configuration = GooseConfiguration::parse_args_default(&config).unwrap();
+    configuration.co_mitigation = Some(Disabled);
+    let base_url = get_base_url(Some(server.base_url()), None, None).unwrap();
+    let mut user = GooseUser::new(0, "".to_string(), base_url, &configuration, 0, None).unwrap();
+    user.weighted_users_index = index;
+    user
+}
+
+#[tokio::test]
+// Log into Drupal 7, posting the form_token of the log in form.
+async fn test_log_in_drupal_7() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(
+            r#"
+<form action="/user/login" method="post" id="user-login" accept-charset="UTF-8"><div>
+  <input type="text" id="edit-name" name="name" value="" size="60" maxlength="60" class="form-text required" />
+  <input type="password" id="edit-pass" name="pass" size="60" maxlength="128" class="form-text required" />
+  <input type="hidden" name="form_build_id" value="form-9XYUP1CQGi4nOlVf0F8mrUu4uO9HQ4SlXx8fXLK7Bq4" />
+  <input type="hidden" name="form_token" value="mVrN2b6pU0hBrbPdaSKhiCaWAjbj1p-wP3D5cDnN6oI" />
+  <input type="hidden" name="form_id" value="user_login" />
+  <div class="form-actions form-wrapper" id="edit-actions"><input type="submit" id="edit-submit" name="op" value="Log in" class="form-submit" /></div>
+</div></form>
+"#,
+        );
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor")
+            .x_www_form_urlencoded_tuple("pass", "secret")
+            .x_www_form_urlencoded_tuple("form_id", "user_login")
+            .x_www_form_urlencoded_tuple(
+                "form_token",
+                "mVrN2b6pU0hBrbPdaSKhiCaWAjbj1p-wP3D5cDnN6oI",
+            )
+            .x_www_form_urlencoded_tuple("op", "Log in");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 0);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .password("secret")
+        .drupal_7()
+        .build();
+    let html = drupal::log_in(&mut user, &login_params).await.unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+}
+
+#[tokio::test]
+// Log into Drupal 10, which doesn't add a form_token to the log in form.
+async fn test_log_in_without_form_token() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(LOGIN_HTML);
+    });
+    // Matched first, so a form_token is never posted.
+    let form_token = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_key_exists("form_token");
+        then.status(403);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor")
+            .x_www_form_urlencoded_tuple("pass", "secret")
+            .x_www_form_urlencoded_tuple(
+                "form_build_id",
+                "form-bHZME2HeTuevNWQR5Y4pyP8jcAu2dfbHERwoscwnajM",
+            )
+            .x_www_form_urlencoded_tuple("form_id", "user_login_form")
+            .x_www_form_urlencoded_tuple("op", "Log in");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 9);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .password("secret")
+        .build();
+    let html = drupal::log_in(&mut user, &login_params).await.unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(form_token.hits(), 0);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+}
+
+#[tokio::test]
+// Log out of Drupal 10.1, confirming the log out with the form_token of its form.
+async fn test_log_out_confirm() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let confirm_page = server.mock(|when, then| {
+        when.method(GET).path(LOGOUT_CONFIRM_PATH);
+        then.status(200).body(
+            r#"
+<body class="user-logged-in path-user">
+<form class="user-logout-confirm" data-drupal-selector="user-logout-confirm" action="/user/logout/confirm" method="post" id="user-logout-confirm" accept-charset="UTF-8">
+  Are you sure you want to log out?
+  <input autocomplete="off" data-drupal-selector="form-wqzuhhiu5pqsbxbfojhnlhlizyjsxt2zdrvexvfk5ry" type="hidden" name="form_build_id" value="form-WqZuhHiu5PqsbxBFOjHNlhliZYjsxt2ZdrvEXvFk5rY" />
+  <input data-drupal-selector="edit-user-logout-confirm-form-token" type="hidden" name="form_token" value="8gSbnGdAu9HN-nDg0o1wbAt3kDnEwyfvWmJzvPyNWYY" />
+  <input data-drupal-selector="edit-user-logout-confirm" type="hidden" name="form_id" value="user_logout_confirm" />
+  <div data-drupal-selector="edit-actions" class="form-actions js-form-wrapper form-wrapper" id="edit-actions"><input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Log out" class="button button--primary js-form-submit form-submit" /></div>
+</form>
+</body>
+"#,
+        );
+    });
+    let confirm = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGOUT_CONFIRM_PATH)
+            .x_www_form_urlencoded_tuple(
+                "form_token",
+                "8gSbnGdAu9HN-nDg0o1wbAt3kDnEwyfvWmJzvPyNWYY",
+            )
+            .x_www_form_urlencoded_tuple("form_id", "user_logout_confirm")
+            .x_www_form_urlencoded_tuple("op", "Log out");
+        then.status(302).header("location", "/");
+    });
+    let front_page = server.mock(|when, then| {
+        when.method(GET).path("/");
+        then.status(200)
+            .body(r#"<body class="path-frontpage"><h1>Welcome</h1></body>"#);
+    });
+
+    let mut user = build_user(&server, 1);
+    let html = drupal::log_out(&mut user).await.unwrap();
+    assert!(html.contains("Welcome"));
+    assert_eq!(confirm_page.hits(), 1);
+    assert_eq!(confirm.hits(), 1);
+    assert_eq!(front_page.hits(), 1);
+}
+
+#[tokio::test]
+// Fail to log out of Drupal 10.1 when the confirmation form has no form_token, without
+// posting the form.
+async fn test_log_out_confirm_no_form_token() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let confirm_page = server.mock(|when, then| {
+        when.method(GET).path(LOGOUT_CONFIRM_PATH);
+        then.status(200).body(
+            r#"
+<body class="user-logged-in path-user">
+<form class="user-logout-confirm" data-drupal-selector="user-logout-confirm" action="/user/logout/confirm" method="post" id="user-logout-confirm" accept-charset="UTF-8">
+  Are you sure you want to log out?
+  <input autocomplete="off" data-drupal-selector="form-wqzuhhiu5pqsbxbfojhnlhlizyjsxt2zdrvexvfk5ry" type="hidden" name="form_build_id" value="form-WqZuhHiu5PqsbxBFOjHNlhliZYjsxt2ZdrvEXvFk5rY" />
+  <input data-drupal-selector="edit-user-logout-confirm" type="hidden" name="form_id" value="user_logout_confirm" />
+  <div data-drupal-selector="edit-actions" class="form-actions js-form-wrapper form-wrapper" id="edit-actions"><input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Log out" class="button button--primary js-form-submit form-submit" /></div>
+</form>
+</body>
+"#,
+        );
+    });
+    let confirm = server.mock(|when, then| {
+        when.method(POST).path(LOGOUT_CONFIRM_PATH);
+        then.status(302).header("location", "/");
+    });
+
+    let mut user = build_user(&server, 10);
+    let error = drupal::log_out(&mut user).await.unwrap_err();
+    assert!(format!("{:?}", error).contains("no form_token on page"));
+    assert_eq!(confirm_page.hits(), 1);
+    assert_eq!(confirm.hits(), 0);
+}
+
+#[tokio::test]
+// Edit a node, posting the line breaks of its textareas unchanged.
+async fn test_edit_node_line_breaks() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let edit_page = server.mock(|when, then| {
+        when.method(GET).path(NODE_EDIT_PATH);
+        then.status(200).body(NODE_EDIT_HTML);
+    });
+    let save = server.mock(|when, then| {
+        when.method(POST)
+            .path(NODE_EDIT_PATH)
+            .x_www_form_urlencoded_tuple("title[0][value]", "Fish and chips")
+            .x_www_form_urlencoded_tuple(
+                "body[0][value]",
+                "<p>Battered cod,\nserved with chips.</p>",
+            )
+            .x_www_form_urlencoded_tuple("body[0][format]", "full_html")
+            .x_www_form_urlencoded_tuple("op", "Save");
+        then.status(302).header("location", NODE_PATH);
+    });
+    let saved_node = server.mock(|when, then| {
+        when.method(GET).path(NODE_PATH);
+        then.status(200).body("<h1>Fish and chips</h1>");
+    });
+
+    let mut user = build_user(&server, 11);
+    let edit_params = drupal::EditParams::builder()
+        .nid(1)
+        .fields(&[("title[0][value]", "Fish and chips")])
+        .build();
+    let html = drupal::edit_node(&mut user, &edit_params).await.unwrap();
+    assert!(html.contains("Fish and chips"));
+    assert_eq!(edit_page.hits(), 1);
+    assert_eq!(save.hits(), 1);
+    assert_eq!(saved_node.hits(), 1);
+}
+
+#[tokio::test]
+// Report the error messages of a node that failed to save, taking their text from the
+// elements listed in the version profile of the Goose user.
+async fn test_edit_node_report_errors() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let edit_page = server.mock(|when, then| {
+        when.method(GET).path(NODE_EDIT_PATH);
+        then.status(200).body(NODE_EDIT_HTML);
+    });
+    let save = server.mock(|when, then| {
+        when.method(POST).path(NODE_EDIT_PATH);
+        then.status(200).body(format!(
+            r#"<div class="messages messages--error"><h2>Error message</h2><div class="custom-message">Title field is required.</div><span>Dismiss</span></div>{}"#,
+            NODE_EDIT_HTML
+        ));
+    });
+
+    let mut user = build_user(&server, 12);
+    drupal::set_user_version_profile(
+        &user,
+        drupal::DrupalVersionProfile {
+            message_content: &["custom-message"],
+            ..drupal::DrupalVersionProfile::DRUPAL_10
+        },
+    );
+    let edit_params = drupal::EditParams::builder()
+        .nid(1)
+        .report_errors(true)
+        .build();
+    let error = drupal::edit_node(&mut user, &edit_params)
+        .await
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains("saving node failed: Title field is required.\""));
+    assert_eq!(edit_page.hits(), 1);
+    assert_eq!(save.hits(), 1);
+}
+
+// Mock a batch of the specified id that responds to each poll with the specified progress,
+// and redirects to the front page once finished.
+fn mock_batch<'a>(
+    server: &'a MockServer,
+    id: &str,
+    progress: serde_json::Value,
+) -> (httpmock::Mock<'a>, httpmock::Mock<'a>, httpmock::Mock<'a>) {
+    let poll = server.mock(|when, then| {
+        when.method(POST)
+            .path(BATCH_PATH)
+            .query_param("id", id)
+            .query_param("op", "do");
+        then.status(200).json_body(progress);
+    });
+    let finished = server.mock(|when, then| {
+        when.method(GET)
+            .path(BATCH_PATH)
+            .query_param("id", id)
+            .query_param("op", "finished");
+        then.status(302).header("location", "/");
+    });
+    let front_page = server.mock(|when, then| {
+        when.method(GET).path("/");
+        then.status(200)
+            .body(r#"<body class="path-frontpage"><h1>Welcome</h1></body>"#);
+    });
+    (poll, finished, front_page)
+}
+
+#[tokio::test]
+// Process a batch until it's complete, then finish it.
+async fn test_process_batch_complete() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let (poll, finished, front_page) = mock_batch(
+        &server,
+        "5",
+        serde_json::json!({"status": true, "percentage": 100, "message": "Done"}),
+    );
+
+    let mut user = build_user(&server, 13);
+    let html = drupal::process_batch(&mut user, "/batch?id=5&op=start")
+        .await
+        .unwrap();
+    assert!(html.contains("Welcome"));
+    assert_eq!(poll.hits(), 1);
+    assert_eq!(finished.hits(), 1);
+    assert_eq!(front_page.hits(), 1);
+}
+
+#[tokio::test]
+// Process a batch whose percentage is a string, as returned by most versions of Drupal.
+async fn test_process_batch_string_percentage() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let (poll, finished, front_page) = mock_batch(
+        &server,
+        "6",
+        serde_json::json!({"status": true, "percentage": "100", "message": "Done"}),
+    );
+
+    let mut user = build_user(&server, 14);
+    let html = drupal::process_batch(&mut user, "/batch?id=6&op=start")
+        .await
+        .unwrap();
+    assert!(html.contains("Welcome"));
+    assert_eq!(poll.hits(), 1);
+    assert_eq!(finished.hits(), 1);
+    assert_eq!(front_page.hits(), 1);
+}
+
+#[tokio::test]
+// Fail processing a batch that reports an error, without finishing it.
+async fn test_process_batch_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let (poll, finished, front_page) = mock_batch(
+        &server,
+        "7",
+        serde_json::json!({"status": false, "data": "An error occurred while processing."}),
+    );
+
+    let mut user = build_user(&server, 15);
+    let error = drupal::process_batch(&mut user, "/batch?id=7&op=start")
+        .await
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains("batch 7 failed: An error occurred while processing."));
+    assert_eq!(poll.hits(), 1);
+    assert_eq!(finished.hits(), 0);
+    assert_eq!(front_page.hits(), 0);
+}
+
+#[tokio::test]
+// Upload a file to a managed file field, keeping the fid and form_build_id of the widget
+// Drupal returns.
+async fn test_upload_file() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let upload = server.mock(|when, then| {
+        when.method(POST)
+            .path(NODE_ADD_PATH)
+            .query_param("element_parents", "field_image/widget/0")
+            .query_param("ajax_form", "1")
+            .query_param("_wrapper_format", "drupal_ajax")
+            .header_exists("content-type")
+            .body_contains("name=\"_triggering_element_name\"\r\n\r\nfield_image_0_upload_button")
+            .body_contains("name=\"files[field_image_0]\"; filename=\"logo.png\"")
+            .body_contains("Content-Type: image/png\r\n\r\nPNG");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(
+                r#"[{"command":"update_build_id","old":"form-old","new":"form-new"},{"command":"insert","method":"replaceWith","selector":null,"data":"<input type=\"hidden\" name=\"field_image[0][fids]\" value=\"42\" /><input type=\"text\" name=\"field_image[0][alt]\" value=\"\" />"}]"#,
+            );
+    });
+
+    let html = r#"
+<form data-drupal-selector="node-article-form" enctype="multipart/form-data" action="/node/add/article" method="post" id="node-article-form" accept-charset="UTF-8">
+  <input data-drupal-selector="edit-title-0-value" type="text" id="edit-title-0-value" name="title[0][value]" value="" />
+  <input data-drupal-selector="edit-field-image-0-upload" type="file" id="edit-field-image-0-upload" name="files[field_image_0]" />
+  <input data-drupal-selector="edit-field-image-0-fids" type="hidden" name="field_image[0][fids]" />
+  <input autocomplete="off" data-drupal-selector="form-old" type="hidden" name="form_build_id" value="form-old" />
+  <input data-drupal-selector="edit-node-article-form" type="hidden" name="form_id" value="node_article_form" />
+</form>
+"#;
+    let mut user = build_user(&server, 16);
+    let upload_params = drupal::UploadParams::builder()
+        .url(NODE_ADD_PATH)
+        .form("node-article-form")
+        .field("field_image")
+        .file("logo.png", b"PNG")
+        .content_type("image/png")
+        .build();
+    let uploaded = drupal::upload_file(&mut user, html, &upload_params)
+        .await
+        .unwrap();
+    assert_eq!(upload.hits(), 1);
+    assert_eq!(uploaded.fid, "42");
+    assert_eq!(uploaded.form_build_id, "form-new");
+    let field = |name: &str| {
+        uploaded
+            .fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(field("form_build_id"), Some("form-new"));
+    assert_eq!(field("field_image[0][fids]"), Some("42"));
+    assert_eq!(field("field_image[0][alt]"), Some(""));
+    assert_eq!(field("form_id"), Some("node_article_form"));
+}
+
+#[tokio::test]
+// Upload an image with the Media Library: open the dialog, upload the file, save the media
+// and update the field of the form with the media.
+async fn test_media_library_upload() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let open = server.mock(|when, then| {
+        when.method(POST)
+            .path(NODE_ADD_PATH)
+            .query_param("ajax_form", "1")
+            .x_www_form_urlencoded_tuple(
+                "_triggering_element_name",
+                "field_media_image-media-library-open-button",
+            );
+        then.status(200).json_body(serde_json::json!([
+            {
+                "command": "openDialog",
+                "data": r#"<form data-drupal-selector="media-library-add-form-upload" action="/media-library" method="post" id="media-library-add-form-upload"><input type="file" name="files[upload][]" /><input type="hidden" name="form_build_id" value="form-dialog" /><input type="hidden" name="form_id" value="media_library_add_form_upload" /></form>"#
+            },
+            {
+                "command": "settings",
+                "settings": {"ajax": {"edit-upload": {
+                    "url": "/media-library?ajax_form=1",
+                    "submit": {"_triggering_element_name": "upload_upload_button", "_triggering_element_value": "Upload"}
+                }}}
+            }
+        ]));
+    });
+    let upload = server.mock(|when, then| {
+        when.method(POST)
+            .path(MEDIA_LIBRARY_PATH)
+            .body_contains("upload_upload_button")
+            .body_contains("name=\"files[upload][]\"; filename=\"cat.png\"");
+        then.status(200).json_body(serde_json::json!([
+            {
+                "command": "insert",
+                "data": r#"<form data-drupal-selector="media-library-add-form-upload" action="/media-library" method="post" id="media-library-add-form-upload"><input type="text" name="media[0][fields][field_media_image][0][alt]" value="" /><input type="hidden" name="form_build_id" value="form-dialog" /></form>"#
+            },
+            {
+                "command": "settings",
+                "settings": {"ajax": {"edit-save-insert": {
+                    "url": "/media-library?ajax_form=1",
+                    "submit": {"_triggering_element_name": "op", "_triggering_element_value": "Save and insert"}
+                }}}
+            }
+        ]));
+    });
+    let save = server.mock(|when, then| {
+        when.method(POST)
+            .path(MEDIA_LIBRARY_PATH)
+            .x_www_form_urlencoded_tuple("media[0][fields][field_media_image][0][alt]", "A cat")
+            .x_www_form_urlencoded_tuple("_triggering_element_value", "Save and insert");
+        then.status(200).json_body(serde_json::json!([
+            {
+                "command": "invoke",
+                "selector": "[data-media-library-widget-value=\"field_media_image\"]",
+                "method": "val",
+                "args": ["17"]
+            }
+        ]));
+    });
+    let update = server.mock(|when, then| {
+        when.method(POST)
+            .path(NODE_ADD_PATH)
+            .x_www_form_urlencoded_tuple("field_media_image[media_library_selection]", "17")
+            .x_www_form_urlencoded_tuple(
+                "_triggering_element_name",
+                "field_media_image-media-library-update-widget",
+            );
+        then.status(200).json_body(serde_json::json!([
+            {"command": "update_build_id", "old": "form-old", "new": "form-new"},
+            {
+                "command": "insert",
+                "data": r#"<input type="hidden" name="field_media_image[selection][0][target_id]" value="17" />"#
+            }
+        ]));
+    });
+
+    let html = r#"
+<form data-drupal-selector="node-article-form" enctype="multipart/form-data" action="/node/add/article" method="post" id="node-article-form" accept-charset="UTF-8">
+  <input data-drupal-selector="edit-title-0-value" type="text" id="edit-title-0-value" name="title[0][value]" value="" />
+  <input autocomplete="off" data-drupal-selector="form-old" type="hidden" name="form_build_id" value="form-old" />
+  <input data-drupal-selector="edit-node-article-form" type="hidden" name="form_id" value="node_article_form" />
+</form>
+"#;
+    let mut user = build_user(&server, 17);
+    let media_fields = [("media[0][fields][field_media_image][0][alt]", "A cat")];
+    let media_library_params = drupal::MediaLibraryParams::builder()
+        .url(NODE_ADD_PATH)
+        .file("cat.png", b"PNG")
+        .content_type("image/png")
+        .media_fields(&media_fields)
+        .build();
+    let inserted = drupal::media_library_upload(&mut user, html, &media_library_params)
+        .await
+        .unwrap();
+    assert_eq!(open.hits(), 1);
+    assert_eq!(upload.hits(), 1);
+    assert_eq!(save.hits(), 1);
+    assert_eq!(update.hits(), 1);
+    assert_eq!(inserted.mid, "17");
+    assert_eq!(inserted.form_build_id, "form-new");
+    let field = |name: &str| {
+        inserted
+            .fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(field("form_build_id"), Some("form-new"));
+    assert_eq!(
+        field("field_media_image[media_library_selection]"),
+        Some("17")
+    );
+    assert_eq!(
+        field("field_media_image[selection][0][target_id]"),
+        Some("17")
+    );
+}
+
+#[tokio::test]
+// Submit a two page webform, preserving the wizard state of each page, and validate the
+// confirmation.
+async fn test_webform_submit() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let first_page = server.mock(|when, then| {
+        when.method(GET).path(WEBFORM_PATH);
+        then.status(200).body(
+            r#"
+<form class="webform-submission-form" data-drupal-selector="webform-submission-lead-add-form" action="/webform/lead" method="post" id="webform-submission-lead-add-form" accept-charset="UTF-8">
+  <input data-drupal-selector="edit-name" type="text" id="edit-name" name="name" value="" />
+  <input autocomplete="off" data-drupal-selector="form-page-1" type="hidden" name="form_build_id" value="form-page-1" />
+  <input data-drupal-selector="edit-webform-submission-lead-add-form" type="hidden" name="form_id" value="webform_submission_lead_add_form" />
+  <input data-drupal-selector="edit-wizard-next" type="submit" id="edit-wizard-next" name="op" value="Next &gt;" />
+</form>
+"#,
+        );
+    });
+    let next = server.mock(|when, then| {
+        when.method(POST)
+            .path(WEBFORM_PATH)
+            .x_www_form_urlencoded_tuple("name", "Jane Doe")
+            .x_www_form_urlencoded_tuple("form_build_id", "form-page-1")
+            .x_www_form_urlencoded_tuple("op", "Next >");
+        then.status(200).body(
+            r#"
+<form class="webform-submission-form" data-drupal-selector="webform-submission-lead-add-form" action="/webform/lead" method="post" id="webform-submission-lead-add-form" accept-charset="UTF-8">
+  <input data-drupal-selector="edit-company" type="text" id="edit-company" name="company" value="" />
+  <input data-drupal-selector="edit-current-page" type="hidden" name="current_page" value="page_2" />
+  <input autocomplete="off" data-drupal-selector="form-page-2" type="hidden" name="form_build_id" value="form-page-2" />
+  <input data-drupal-selector="edit-webform-submission-lead-add-form" type="hidden" name="form_id" value="webform_submission_lead_add_form" />
+  <input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Submit" />
+</form>
+"#,
+        );
+    });
+    let submit = server.mock(|when, then| {
+        when.method(POST)
+            .path(WEBFORM_PATH)
+            .x_www_form_urlencoded_tuple("company", "Example")
+            .x_www_form_urlencoded_tuple("current_page", "page_2")
+            .x_www_form_urlencoded_tuple("form_build_id", "form-page-2")
+            .x_www_form_urlencoded_tuple("op", "Submit");
+        then.status(302)
+            .header("location", "/webform/lead/confirmation");
+    });
+    let confirmation = server.mock(|when, then| {
+        when.method(GET).path("/webform/lead/confirmation");
+        then.status(200)
+            .body(r#"<div class="webform-confirmation">Thank you for your submission.</div>"#);
+    });
+
+    let mut user = build_user(&server, 18);
+    let validate = goose_eggs::Validate::builder()
+        .text("Thank you for your submission.")
+        .build();
+    let webform_params = drupal::WebformParams::builder()
+        .webform("lead")
+        .pages(&[&[("name", "Jane Doe")], &[("company", "Example")]])
+        .confirmation_page_validation(&validate)
+        .honeypot_time_restriction(Duration::ZERO)
+        .build();
+    let html = drupal::webform_submit(&mut user, &webform_params)
+        .await
+        .unwrap();
+    assert!(html.contains("Thank you"));
+    assert_eq!(first_page.hits(), 1);
+    assert_eq!(next.hits(), 1);
+    assert_eq!(submit.hits(), 1);
+    assert_eq!(confirmation.hits(), 1);
+}
+
+#[tokio::test]
+// Filter the content overview and perform a bulk action on its first rows.
+async fn test_admin_content_bulk_action() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let overview = server.mock(|when, then| {
+        when.method(GET)
+            .path(ADMIN_CONTENT_PATH)
+            .query_param("title", "Fish")
+            .query_param("type", "article")
+            .query_param("status", "All")
+            .query_param("langcode", "All");
+        then.status(200).body(
+            r#"
+<form class="views-form" data-drupal-selector="views-form-content-page-1" action="/admin/content" method="post" id="views-form-content-page-1" accept-charset="UTF-8">
+  <select data-drupal-selector="edit-action" id="edit-action" name="action" class="form-select">
+    <option value="node_delete_action">Delete content</option>
+    <option value="node_unpublish_action">Unpublish content</option>
+  </select>
+  <table>
+    <tr><td><input data-drupal-selector="edit-node-bulk-form-0" type="checkbox" id="edit-node-bulk-form-0" name="node_bulk_form[0]" value="en-1" class="form-checkbox" /></td><td>Fish and chips</td></tr>
+    <tr><td><input data-drupal-selector="edit-node-bulk-form-1" type="checkbox" id="edit-node-bulk-form-1" name="node_bulk_form[1]" value="en-2" class="form-checkbox" /></td><td>Fish pie</td></tr>
+    <tr><td><input data-drupal-selector="edit-node-bulk-form-2" type="checkbox" id="edit-node-bulk-form-2" name="node_bulk_form[2]" value="en-3" class="form-checkbox" /></td><td>Fish tacos</td></tr>
+  </table>
+  <input autocomplete="off" data-drupal-selector="form-content" type="hidden" name="form_build_id" value="form-content" />
+  <input data-drupal-selector="edit-views-form-content-page-1" type="hidden" name="form_id" value="views_form_content_page_1" />
+  <input data-drupal-selector="edit-submit" type="submit" id="edit-submit" name="op" value="Apply to selected items" />
+</form>
+"#,
+        );
+    });
+    // Matched first, so only the first rows are ever selected.
+    let third_row = server.mock(|when, then| {
+        when.method(POST)
+            .path(ADMIN_CONTENT_PATH)
+            .x_www_form_urlencoded_key_exists("node_bulk_form[2]");
+        then.status(400);
+    });
+    let action = server.mock(|when, then| {
+        when.method(POST)
+            .path(ADMIN_CONTENT_PATH)
+            .x_www_form_urlencoded_tuple("node_bulk_form[0]", "en-1")
+            .x_www_form_urlencoded_tuple("node_bulk_form[1]", "en-2")
+            .x_www_form_urlencoded_tuple("action", "node_unpublish_action")
+            .x_www_form_urlencoded_tuple("form_id", "views_form_content_page_1")
+            .x_www_form_urlencoded_tuple("op", "Apply to selected items");
+        then.status(200)
+            .body("<div>Unpublish content was applied to 2 items.</div>");
+    });
+
+    let mut user = build_user(&server, 19);
+    let validate = goose_eggs::Validate::builder()
+        .text("was applied to 2 items")
+        .build();
+    let admin_content_params = drupal::AdminContentParams::builder()
+        .title("Fish")
+        .content_type("article")
+        .action("node_unpublish_action")
+        .rows(2)
+        .action_page_validation(&validate)
+        .build();
+    let html = drupal::admin_content(&mut user, &admin_content_params)
+        .await
+        .unwrap();
+    assert!(html.contains("applied to 2 items"));
+    assert_eq!(overview.hits(), 1);
+    assert_eq!(third_row.hits(), 0);
+    assert_eq!(action.hits(), 1);
+}
+
+#[tokio::test]
+// Follow the pager until the last page, or until the maximum number of pages is loaded.
+async fn test_follow_pager() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The pages are matched by their query, so the first page is matched last.
+    let third_page = server.mock(|when, then| {
+        when.method(GET).path(PAGER_PATH).query_param("page", "2");
+        then.status(200).body(
+            r#"<h1>Page 3</h1><nav class="pager"><a href="?page=1" rel="prev">Previous</a></nav>"#,
+        );
+    });
+    let second_page = server.mock(|when, then| {
+        when.method(GET).path(PAGER_PATH).query_param("page", "1");
+        then.status(200)
+            .body(r#"<h1>Page 2</h1><nav class="pager"><a href="/node?page=2" title="Go to next page" rel="next">Next</a></nav>"#);
+    });
+    let first_page = server.mock(|when, then| {
+        when.method(GET).path(PAGER_PATH);
+        then.status(200)
+            .body(r#"<h1>Page 1</h1><nav class="pager"><a href="?page=1" title="Go to next page" rel="next">Next</a></nav>"#);
+    });
+
+    let mut user = build_user(&server, 20);
+    let validate = goose_eggs::Validate::none();
+    // Pagers link to the next page with only a query string, or with a path.
+    let pages = drupal::follow_pager(&mut user, PAGER_PATH, 10, &validate)
+        .await
+        .unwrap();
+    assert_eq!(pages.len(), 3);
+    assert!(pages[2].contains("Page 3"));
+    assert_eq!(first_page.hits(), 1);
+    assert_eq!(second_page.hits(), 1);
+    assert_eq!(third_page.hits(), 1);
+
+    // Stop once the maximum number of pages is loaded.
+    let pages = drupal::follow_pager(&mut user, PAGER_PATH, 2, &validate)
+        .await
+        .unwrap();
+    assert_eq!(pages.len(), 2);
+    assert!(pages[1].contains("Page 2"));
+    assert_eq!(first_page.hits(), 2);
+    assert_eq!(second_page.hits(), 2);
+    assert_eq!(third_page.hits(), 1);
+}
+
+#[tokio::test]
+// Log in again and retry the request when the session expired.
+async fn test_validate_logged_in_expired() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Matched first, once the user has logged in again.
+    let content = server.mock(|when, then| {
+        when.method(GET)
+            .path(ADMIN_CONTENT_PATH)
+            .header("cookie", "SESSabc=xyz");
+        then.status(200)
+            .body("<html><head><title>Content | Drupal</title></head><body></body></html>");
+    });
+    let expired = server.mock(|when, then| {
+        when.method(GET).path(ADMIN_CONTENT_PATH);
+        then.status(403).body(LOGIN_HTML);
+    });
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(LOGIN_HTML);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor");
+        then.status(302)
+            .header("location", USER_PATH)
+            .header("set-cookie", "SESSabc=xyz; path=/");
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 21);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .password("secret")
+        .build();
+    let validate = goose_eggs::Validate::builder().title("Content").build();
+    let goose = user.get(ADMIN_CONTENT_PATH).await.unwrap();
+    let html = drupal::validate_logged_in(&mut user, goose, &validate, &login_params)
+        .await
+        .unwrap();
+    assert!(html.contains("Content | Drupal"));
+    assert!(!drupal::session_expired(&user));
+    assert_eq!(expired.hits(), 1);
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+    assert_eq!(content.hits(), 1);
+}
+
+#[tokio::test]
+// Fetch a JSON:API collection, following the next link of each page.
+async fn test_jsonapi_collection_pages() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The second page is matched first, as its query also has the limit of the first.
+    let second_page = server.mock(|when, then| {
+        when.method(GET)
+            .path(ARTICLES_PATH)
+            .query_param("page[offset]", "2");
+        then.status(200)
+            .header("content-type", drupal::jsonapi::MEDIA_TYPE)
+            .json_body(serde_json::json!({
+                "data": [{"type": "node--article", "id": "3"}],
+                "links": {"self": {"href": "/jsonapi/node/article?page%5Boffset%5D=2&page%5Blimit%5D=2"}}
+            }));
+    });
+    let first_page = server.mock(|when, then| {
+        when.method(GET)
+            .path(ARTICLES_PATH)
+            .query_param("page[limit]", "2");
+        then.status(200)
+            .header("content-type", drupal::jsonapi::MEDIA_TYPE)
+            .json_body(serde_json::json!({
+                "data": [
+                    {"type": "node--article", "id": "1"},
+                    {"type": "node--article", "id": "2"}
+                ],
+                "links": {
+                    "next": {"href": server.url("/jsonapi/node/article?page%5Boffset%5D=2&page%5Blimit%5D=2")}
+                }
+            }));
+    });
+
+    let mut user = build_user(&server, 2);
+    let params = drupal::jsonapi::ResourceParams::builder()
+        .resource("node/article")
+        .limit(2)
+        .max_pages(3)
+        .build();
+    let collection = drupal::jsonapi::get_collection(&mut user, &params)
+        .await
+        .unwrap();
+    assert_eq!(collection.pages, 2);
+    let ids: Vec<&str> = collection
+        .data
+        .iter()
+        .map(|resource| resource["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    assert_eq!(first_page.hits(), 1);
+    assert_eq!(second_page.hits(), 1);
+}
+
+#[tokio::test]
+// Check out each account of an exclusive pool to one Goose user at a time.
+async fn test_account_pool_checkout() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(LOGIN_HTML);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor")
+            .x_www_form_urlencoded_tuple("pass", "secret");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let accounts =
+        drupal::AccountPool::new(vec![("editor", "secret"), ("writer", "secret")]).exclusive();
+    let mut first = build_user(&server, 3);
+    let second = build_user(&server, 4);
+    let third = build_user(&server, 5);
+
+    // The first Goose user logs in with the first account, and keeps it.
+    let html = accounts
+        .log_in(&mut first, &drupal::Login::builder().build())
+        .await
+        .unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(accounts.checkout(&first).unwrap().username, "editor");
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+
+    // The other accounts are checked out by other Goose users until none are left.
+    assert_eq!(accounts.checkout(&second).unwrap().username, "writer");
+    assert!(accounts.checkout(&third).is_none());
+
+    // A released account can be checked out by another Goose user.
+    accounts.release(&first);
+    assert_eq!(accounts.checkout(&third).unwrap().username, "editor");
+    assert!(accounts.checkout(&first).is_none());
+}
+
+#[tokio::test]
+// Log in with a form protected by Honeypot, posting its hidden fields unchanged.
+async fn test_log_in_honeypot() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200)
+            .body(LOGIN_HTML.replace("</form>", &format!("{}</form>", HONEYPOT_HTML)));
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor")
+            .x_www_form_urlencoded_tuple("url", "")
+            .x_www_form_urlencoded_tuple("honeypot_time", "kdEF3nHyiC8JN5SBVMPTqZ");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 6);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .honeypot_time_restriction(Duration::ZERO)
+        .build();
+    let html = drupal::log_in(&mut user, &login_params).await.unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+}
+
+#[test]
+// Find the Honeypot fields by their hidden wrapper, whatever the order of its attributes.
+fn test_honeypot_fields() {
+    let form = HONEYPOT_HTML.replace(
+        r#"class="url-textfield js-form-wrapper form-wrapper" data-drupal-selector="edit-url" style="display: none !important;""#,
+        r#"style="display:none !important;" data-drupal-selector="edit-url" class="js-form-wrapper form-wrapper url-textfield""#,
+    );
+    for form in [HONEYPOT_HTML, form.as_str()] {
+        assert_eq!(
+            drupal::get_honeypot_fields(form),
+            vec![
+                ("url".to_string(), "".to_string()),
+                (
+                    "honeypot_time".to_string(),
+                    "kdEF3nHyiC8JN5SBVMPTqZ".to_string()
+                ),
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+// Log in with a form protected by Antibot, posting where its JavaScript would with its key.
+async fn test_log_in_antibot() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(ANTIBOT_HTML);
+    });
+    let antibot = server.mock(|when, then| {
+        when.method(POST).path("/antibot");
+        then.status(403);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor")
+            .x_www_form_urlencoded_tuple("antibot_key", "1e2fabcd");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 7);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .antibot(true)
+        .build();
+    let html = drupal::log_in(&mut user, &login_params).await.unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(antibot.hits(), 0);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+}
+
+#[test]
+// Decode the key of a form protected by Antibot the way its JavaScript does.
+fn test_antibot_form() {
+    let antibot = drupal::get_antibot_form(ANTIBOT_HTML, "user-login-form").unwrap();
+    assert_eq!(antibot.action, "/user/login");
+    assert_eq!(antibot.key, "1e2fabcd");
+
+    // The key is reversed, then each pair of characters is reversed.
+    let html = ANTIBOT_HTML.replace("cdab2f1e", "0123456789abcdef");
+    let antibot = drupal::get_antibot_form(&html, "user-login-form").unwrap();
+    assert_eq!(antibot.key, "efcdab8967452301");
+
+    // Forms Antibot doesn't protect have no data-action.
+    assert!(drupal::get_antibot_form(LOGIN_HTML, "user-login-form").is_none());
+}
+
+#[tokio::test]
+// Log in with two-factor authentication, posting the code to the form redirected to.
+async fn test_log_in_tfa() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(LOGIN_HTML);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("name", "editor");
+        then.status(302).header("location", TFA_PATH);
+    });
+    let tfa_page = server.mock(|when, then| {
+        when.method(GET).path(TFA_PATH);
+        then.status(200).body(
+            r#"
+<form class="tfa-entry-form" data-drupal-selector="tfa-entry-form" action="/tfa/1/LHdAFqMc1wYd8sFBXJ3DzsVuzGa7zPFz" method="post" id="tfa-entry-form" accept-charset="UTF-8">
+  <input autocomplete="off" data-drupal-selector="edit-code" type="text" id="edit-code" name="code" value="" size="60" maxlength="6" class="form-text required" required="required" />
+  <input autocomplete="off" data-drupal-selector="form-ojnwh2ykfsjbq1l2ftnvtqmlcmk4gdgw4ycsvsxkdci" type="hidden" name="form_build_id" value="form-oJNWh2ykFsJbQ1L2ftNvtqMLcMK4gdGw4ycSvsxKDcI" />
+  <input data-drupal-selector="edit-tfa-entry-form" type="hidden" name="form_id" value="tfa_entry_form" />
+  <div data-drupal-selector="edit-actions" class="form-actions js-form-wrapper form-wrapper" id="edit-actions"><input data-drupal-selector="edit-login" type="submit" id="edit-login" name="op" value="Verify" class="button js-form-submit form-submit" /></div>
+</form>
+"#,
+        );
+    });
+    let tfa = server.mock(|when, then| {
+        when.method(POST)
+            .path(TFA_PATH)
+            .x_www_form_urlencoded_tuple("code", "123456")
+            .x_www_form_urlencoded_tuple("form_id", "tfa_entry_form")
+            .x_www_form_urlencoded_tuple("op", "Verify");
+        then.status(302).header("location", USER_PATH);
+    });
+    let logged_in = server.mock(|when, then| {
+        when.method(GET).path(USER_PATH);
+        then.status(200).body(LOGGED_IN_HTML);
+    });
+
+    let mut user = build_user(&server, 8);
+    let login_params = drupal::Login::builder()
+        .username("editor")
+        .tfa(|_user: &GooseUser| "123456".to_string())
+        .build();
+    let html = drupal::log_in(&mut user, &login_params).await.unwrap();
+    assert!(html.contains("user-logged-in"));
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+    assert_eq!(tfa_page.hits(), 1);
+    assert_eq!(tfa.hits(), 1);
+    assert_eq!(logged_in.hits(), 1);
+}
+
+#[test]
+// Get the values a browser would submit for the textareas and selects of a form.
+fn test_form_value_textarea_select() {
+    let form = drupal::get_form(NODE_EDIT_HTML, "node-article-edit-form");
+
+    // Inputs keep their encoded value attribute.
+    assert_eq!(
+        drupal::get_form_value(&form, "title[0][value]"),
+        "Fish &amp; chips"
+    );
+    // Textareas submit their decoded contents, including line breaks, even if empty.
+    assert_eq!(
+        drupal::get_form_value(&form, "body[0][value]"),
+        "<p>Battered cod,\nserved with chips.</p>"
+    );
+    assert_eq!(drupal::get_form_value(&form, "revision_log[0][value]"), "");
+    // Selects submit their selected option, or their first option if none is selected.
+    assert_eq!(
+        drupal::get_form_value(&form, "body[0][format]"),
+        "full_html"
+    );
+    assert_eq!(drupal::get_form_value(&form, "langcode[0][value]"), "en");
+    // Elements not in the form have no value.
+    assert_eq!(drupal::get_form_value(&form, "status[value]"), "none");
+}
@@ -1,15 +1,25 @@
 use gumdrop::Options;
-use httpmock::{Method::GET, MockServer};
+use httpmock::{
+    Method::{GET, HEAD},
+    MockServer,
+};
 
 use goose::config::GooseConfiguration;
 use goose::prelude::*;
 
 // Paths used in load tests performed during these tests.
 const PATH: &str = "/one";
+const JSON_PATH: &str = "/api/one";
+const REDIRECT_PATH: &str = "/redirect";
+const LANGUAGE_PATH: &str = "/language";
+const SPANISH_PATH: &str = "/es";
+const ASSET_PATH: &str = "/logo.png";
+const MODIFIED_ASSET_PATH: &str = "/modified.png";
 
 const HTML: &str = r#"
 <!DOCTYPE html>
 <head>
+  <link rel="canonical" href="https://example.com/one" />
   <title>Title 1234ABCD</title>
 </head>
 <body>
@@ -17,6 +27,8 @@ const HTML: &str = r#"
 </body>
 "#;
 
+const JSON: &str = r#"{"data": [{"id": "1234ABCD", "type": "node--article"}]}"#;
+
 // Test transaction.
 pub async fn get_path_valid(user: &mut GooseUser) -> TransactionResult {
     let goose = user.get(PATH).await?;
@@ -29,6 +41,11 @@ pub async fn get_path_valid(user: &mut GooseUser) -> TransactionResult {
             .text("Test text")
             .text("<!DOCTYPE html>")
             .not_text("<!DocType html>")
+            .any_text(vec!["Not on the page", "Test text"])
+            .canonical("https://example.com/one")
+            .text_in("body", "Test text")
+            .not_text_in("p", "Title")
+            .no_error_markers()
             .header_value("foo", "bar")
             .not_header("bar")
             .build(),
@@ -38,6 +55,288 @@ pub async fn get_path_valid(user: &mut GooseUser) -> TransactionResult {
     Ok(())
 }
 
+// Test transaction that requires one of several texts, none of which are on the page.
+pub async fn get_path_any_text_missing(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .any_text(vec!["Not on the page", "Also not on the page"])
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that only logs failed validations as warnings.
+pub async fn get_path_warn_only(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .title("Not the title")
+            .warn_only()
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that extends a strict validation with a lenient preset.
+pub async fn get_path_extend_lenient(user: &mut GooseUser) -> TransactionResult {
+    let lenient = goose_eggs::Validate::builder()
+        .warn_only()
+        .ignore_body()
+        .build();
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .title("Not the title")
+            .extend(&lenient)
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that retries the request once when validation fails.
+pub async fn get_path_retry(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .title("Not the title")
+            .retries(1)
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that retries a request with a custom header, query and expected status
+// code once when validation fails.
+pub async fn get_path_retry_custom(user: &mut GooseUser) -> TransactionResult {
+    let request_builder = user
+        .get_request_builder(&GooseMethod::Get, PATH)?
+        .query(&[("page", "2")])
+        .header("x-retry", "custom \"value\"");
+    let goose_request = GooseRequest::builder()
+        .set_request_builder(request_builder)
+        .expect_status_code(404)
+        .build();
+    let goose = user.request(goose_request).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .status(404)
+            .title("Not the title")
+            .retries(1)
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that validates a redirect chain.
+pub async fn get_redirect_chain(user: &mut GooseUser) -> TransactionResult {
+    goose_eggs::validate_redirect_chain(
+        user,
+        REDIRECT_PATH,
+        &[goose_eggs::RedirectHop::new(301, PATH)],
+        &goose_eggs::Validate::builder()
+            .status(200)
+            .title("1234ABCD")
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that validates a redirect chain that depends on a cookie.
+pub async fn get_redirect_chain_with_cookie(user: &mut GooseUser) -> TransactionResult {
+    goose_eggs::enable_cookie_jar(user)?;
+    // Choose the language the redirect depends on.
+    let _goose = user.get(LANGUAGE_PATH).await?;
+    goose_eggs::validate_redirect_chain(
+        user,
+        REDIRECT_PATH,
+        &[goose_eggs::RedirectHop::new(302, SPANISH_PATH)],
+        &goose_eggs::Validate::builder()
+            .status(200)
+            .title("1234ABCD")
+            .build(),
+    )
+    .await?;
+    // The cookie set by the redirect is kept.
+    let _goose = user.get(PATH).await?;
+
+    Ok(())
+}
+
+// Test transaction that validates a JSON response.
+pub async fn get_json_path_valid(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(JSON_PATH).await?;
+    let json = goose_eggs::validate_json(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .status(200)
+            .json_pointer("/data/0/id")
+            .json_value("/data/0/type", "node--article")
+            .build(),
+    )
+    .await?;
+    assert_eq!(json["data"][0]["id"], "1234ABCD");
+
+    Ok(())
+}
+
+// Test transaction that validates a JSON response isn't an error or the maintenance page.
+pub async fn get_json_path_not_maintenance(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(JSON_PATH).await?;
+    goose_eggs::validate_json(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .json_pointer("/data/0/id")
+            .error_marker("PDOException")
+            .not_maintenance()
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that loads a page twice, revalidating its cached static assets.
+pub async fn get_path_cached_assets(user: &mut GooseUser) -> TransactionResult {
+    load_path_cached_assets(user, 2).await
+}
+
+// Test transaction that loads a page three times, revalidating its cached static assets.
+pub async fn get_path_cached_assets_three_times(user: &mut GooseUser) -> TransactionResult {
+    load_path_cached_assets(user, 3).await
+}
+
+// Load a page the specified number of times, revalidating its cached static assets.
+async fn load_path_cached_assets(user: &mut GooseUser, times: usize) -> TransactionResult {
+    let validate = goose_eggs::Validate::builder()
+        .assets(
+            goose_eggs::LoadAssets::builder()
+                .conditional_requests()
+                .build(),
+        )
+        .build();
+    for _ in 0..times {
+        let goose = user.get(PATH).await?;
+        goose_eggs::validate_and_load_static_assets(user, goose, &validate).await?;
+    }
+
+    Ok(())
+}
+
+// Test transaction that validates a page was served from the Drupal page cache.
+pub async fn get_path_drupal_cached(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    let validate = goose_eggs::drupal::validate_page_cached().build();
+    goose_eggs::validate_and_load_static_assets(user, goose, &validate).await?;
+
+    Ok(())
+}
+
+// Test transaction that loads a page, validating its static assets are cached.
+pub async fn get_path_validated_assets(user: &mut GooseUser) -> TransactionResult {
+    let validate = goose_eggs::Validate::builder()
+        .assets(
+            goose_eggs::LoadAssets::builder()
+                .validate(
+                    r"\.png$",
+                    goose_eggs::Validate::builder()
+                        .header_value("x-cache", "HIT")
+                        .build(),
+                )
+                .build(),
+        )
+        .build();
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_and_load_static_assets(user, goose, &validate).await?;
+
+    Ok(())
+}
+
+// Test transaction that loads a page, loading its static assets over several connections.
+pub async fn get_path_parallel_assets(user: &mut GooseUser) -> TransactionResult {
+    let validate = goose_eggs::Validate::builder()
+        .assets(goose_eggs::LoadAssets::builder().parallel(3).build())
+        .build();
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_and_load_static_assets(user, goose, &validate).await?;
+
+    Ok(())
+}
+
+// Test transaction that validates the headers of a file download with a HEAD request.
+pub async fn head_download(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.head(ASSET_PATH).await?;
+    goose_eggs::validate_download(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .status(200)
+            .header_value("content-type", "image/png")
+            .magic_bytes(vec![0x89, b'P', b'N', b'G'])
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that names its validation.
+pub async fn get_path_named(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .title("1234ABCD")
+            .name("front page")
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Test transaction that names its validation, and reports it in the Goose metrics.
+pub async fn get_path_named_goose_metric(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(PATH).await?;
+    goose_eggs::validate_page(
+        user,
+        goose,
+        &goose_eggs::Validate::builder()
+            .title("1234ABCD")
+            .name("front page")
+            .goose_metric()
+            .build(),
+    )
+    .await?;
+
+    Ok(())
+}
+
 // Build appropriate configuration for these tests.
 fn build_configuration(server: &MockServer) -> GooseConfiguration {
     // Declare server_url so its lifetime is sufficient when needed.
@@ -63,11 +362,11 @@ fn build_configuration(server: &MockServer) -> GooseConfiguration {
         .expect("failed to parse options and generate a configuration")
 }
 
-async fn run_load_test(server: &MockServer) -> GooseMetrics {
+async fn run_load_test(server: &MockServer, transaction: Transaction) -> GooseMetrics {
     // Run the Goose Attack.
     let goose_metrics = build_load_test(
         build_configuration(server),
-        vec![scenario!("LoadTest").register_transaction(transaction!(get_path_valid))],
+        vec![scenario!("LoadTest").register_transaction(transaction)],
         None,
         None,
     )
@@ -128,7 +427,7 @@ async fn test_valid() {
                 .body(HTML);
         });
 
-    let goose_metrics = run_load_test(&server).await;
+    let goose_metrics = run_load_test(&server, transaction!(get_path_valid)).await;
     assert!(mock_endpoint.hits() == 1);
 
     // Provide debug if this fails.
@@ -153,7 +452,7 @@ async fn test_invalid_status() {
                 .body(HTML);
         });
 
-    let goose_metrics = run_load_test(&server).await;
+    let goose_metrics = run_load_test(&server, transaction!(get_path_valid)).await;
     assert!(mock_endpoint.hits() == 1);
 
     // Provide debug if this fails.
@@ -178,7 +477,7 @@ async fn test_invalid_header() {
                 .body(HTML);
         });
 
-    let goose_metrics = run_load_test(&server).await;
+    let goose_metrics = run_load_test(&server, transaction!(get_path_valid)).await;
     assert!(mock_endpoint.hits() == 1);
 
     // Provide debug if this fails.
@@ -203,7 +502,7 @@ async fn test_invalid_header_value() {
                 .body(HTML);
         });
 
-    let goose_metrics = run_load_test(&server).await;
+    let goose_metrics = run_load_test(&server, transaction!(get_path_valid)).await;
     assert!(mock_endpoint.hits() == 1);
 
     // Provide debug if this fails.
@@ -212,3 +511,590 @@ async fn test_invalid_header_value() {
     }
     assert!(goose_metrics.errors.len() == 1);
 }
+
+#[tokio::test]
+// Make a single request and confirm detection of none of the texts being on the page.
+async fn test_any_text_missing() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_any_text_missing)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+    let error = goose_metrics.errors.values().next().unwrap();
+    assert!(error.error.contains(
+        r#"none of the texts found on page: ["Not on the page", "Also not on the page"]"#
+    ));
+}
+
+#[tokio::test]
+// Make a single request and confirm failed validations are only logged as warnings.
+async fn test_warn_only() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint =
+        // Set up PATH, store in vector at KEY_ONE.
+        server.mock(|when, then| {
+            when.method(GET).path(PATH);
+            then.status(200)
+                .body(HTML);
+        });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_warn_only)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+    assert!(goose_eggs::validation_warnings() >= 1);
+}
+
+#[tokio::test]
+// Make a single request and confirm extending a validation doesn't copy warn_only() or
+// ignore_body() from the extended validation.
+async fn test_extend_keeps_options() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_extend_lenient)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+}
+
+#[tokio::test]
+// Make a single request and validate the JSON response.
+async fn test_validate_json() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(JSON_PATH);
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(JSON);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_json_path_valid)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Make a single request for JSON and confirm detection of the maintenance mode page.
+async fn test_validate_json_maintenance() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        // Served by a cache, so only the maintenance mode validation fails the request.
+        when.method(GET).path(JSON_PATH);
+        then.status(200).body(
+            "<html><head><title>Site under maintenance | Umami</title></head><body><p>Umami is currently under maintenance.</p></body></html>",
+        );
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_json_path_not_maintenance)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Every failed validation is replaced with a single maintenance mode error.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+    let error = goose_metrics.errors.values().next().unwrap();
+    assert!(error.error.ends_with("site in maintenance mode"));
+}
+
+#[tokio::test]
+// Make a single request for JSON and confirm detection of an error marker.
+async fn test_validate_json_error_marker() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(JSON_PATH);
+        then.status(200)
+            .body(r#"{"data": [{"id": "1"}], "message": "PDOException: SQLSTATE[HY000]"}"#);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_json_path_not_maintenance)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+    let error = goose_metrics.errors.values().next().unwrap();
+    assert!(error.error.contains("PDOException"));
+}
+
+#[tokio::test]
+// Make a request that fails validation and confirm it is retried once.
+async fn test_retries() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_retry)).await;
+    assert!(mock_endpoint.hits() == 2);
+
+    // Only the final attempt is reported as an error.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+}
+
+#[tokio::test]
+// Make a single request with a custom header, query and expected status code, and confirm
+// the retry sends the same request and expects the same status code.
+async fn test_retries_custom_request() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH)
+            .query_param("page", "2")
+            .header("x-retry", "custom \"value\"");
+        then.status(404).body(HTML);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_retry_custom)).await;
+    assert!(mock_endpoint.hits() == 2);
+
+    // Only the failed title is reported, not the expected status code.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+    let error = goose_metrics.errors.values().next().unwrap();
+    assert!(error.error.contains("Not the title"));
+}
+
+#[tokio::test]
+// Make a request that redirects and validate each hop of the redirect chain.
+async fn test_redirect_chain() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let redirect_endpoint = server.mock(|when, then| {
+        when.method(GET).path(REDIRECT_PATH);
+        then.status(301).header("location", PATH);
+    });
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_redirect_chain))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(redirect_endpoint.hits() == 1);
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Follow a redirect chain that depends on the cookies of the user.
+async fn test_redirect_chain_with_cookie() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let language_endpoint = server.mock(|when, then| {
+        when.method(GET).path(LANGUAGE_PATH);
+        then.status(200).header("set-cookie", "lang=es; Path=/");
+    });
+    let redirect_endpoint = server.mock(|when, then| {
+        when.method(GET).path(REDIRECT_PATH).cookie("lang", "es");
+        then.status(302)
+            .header("location", SPANISH_PATH)
+            .header("set-cookie", "redirected=1; Path=/");
+    });
+    let spanish_endpoint = server.mock(|when, then| {
+        when.method(GET)
+            .path(SPANISH_PATH)
+            .cookie("lang", "es")
+            .cookie("redirected", "1");
+        then.status(200).body(HTML);
+    });
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH).cookie("redirected", "1");
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_redirect_chain_with_cookie))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(language_endpoint.hits() == 1);
+    assert!(redirect_endpoint.hits() == 1);
+    assert!(spanish_endpoint.hits() == 1);
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Load a page twice and confirm its static assets are revalidated the second time.
+async fn test_conditional_requests() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200)
+            .body(format!(r#"{}<img src="{}">"#, HTML, ASSET_PATH));
+    });
+    let cached_endpoint = server.mock(|when, then| {
+        when.method(GET)
+            .path(ASSET_PATH)
+            .header("if-none-match", "\"1234ABCD\"");
+        then.status(304);
+    });
+    let asset_endpoint = server.mock(|when, then| {
+        when.method(GET).path(ASSET_PATH);
+        then.status(200).header("etag", "\"1234ABCD\"").body("PNG");
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_path_cached_assets))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(mock_endpoint.hits() == 2);
+    assert!(asset_endpoint.hits() == 1);
+    assert!(cached_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Load a page three times, and confirm a static asset that was modified since it was cached
+// is accepted and revalidated with its new ETag.
+async fn test_conditional_requests_modified() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200)
+            .body(format!(r#"{}<img src="{}">"#, HTML, MODIFIED_ASSET_PATH));
+    });
+    let cached_endpoint = server.mock(|when, then| {
+        when.method(GET)
+            .path(MODIFIED_ASSET_PATH)
+            .header("if-none-match", "\"5678EFGH\"");
+        then.status(304);
+    });
+    let modified_endpoint = server.mock(|when, then| {
+        when.method(GET)
+            .path(MODIFIED_ASSET_PATH)
+            .header("if-none-match", "\"1234ABCD\"");
+        then.status(200)
+            .header("etag", "\"5678EFGH\"")
+            .body("New PNG");
+    });
+    let asset_endpoint = server.mock(|when, then| {
+        when.method(GET).path(MODIFIED_ASSET_PATH);
+        then.status(200).header("etag", "\"1234ABCD\"").body("PNG");
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_path_cached_assets_three_times))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(mock_endpoint.hits() == 3);
+    assert!(asset_endpoint.hits() == 1);
+    assert!(modified_endpoint.hits() == 1);
+    assert!(cached_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+    let assets = goose_metrics
+        .requests
+        .get("GET static asset: img")
+        .expect("static assets not in the Goose metrics");
+    assert_eq!(assets.success_count, 3);
+    assert_eq!(assets.fail_count, 0);
+}
+
+#[tokio::test]
+// Load a page and confirm detection of a static asset that fails validation.
+async fn test_asset_validation() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200)
+            .body(format!(r#"{}<img src="{}">"#, HTML, ASSET_PATH));
+    });
+    let asset_endpoint = server.mock(|when, then| {
+        when.method(GET).path(ASSET_PATH);
+        then.status(200).header("x-cache", "MISS").body("PNG");
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_path_validated_assets))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(mock_endpoint.hits() == 1);
+    assert!(asset_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+}
+
+#[tokio::test]
+// Make a single request and confirm detection of a page missing the Drupal page cache.
+async fn test_drupal_page_cache() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200)
+            .header("x-generator", "Drupal 10 (https://www.drupal.org)")
+            .header("x-drupal-cache", "MISS")
+            .body(HTML);
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(get_path_drupal_cached)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if goose_metrics.errors.len() != 1 {
+        println!("EXPECTED ONE ERROR: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.len() == 1);
+}
+
+#[tokio::test]
+// Make a single HEAD request for a file, and confirm only its headers are validated.
+async fn test_validate_download_head() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(HEAD).path(ASSET_PATH);
+        then.status(200)
+            .header("content-type", "image/png")
+            .header("content-length", "1234");
+    });
+
+    let goose_metrics = run_load_test(&server, transaction!(head_download)).await;
+    assert!(mock_endpoint.hits() == 1);
+
+    // Provide debug if this fails.
+    if !goose_metrics.errors.is_empty() {
+        println!("UNEXPECTED ERRORS: {:#?}", goose_metrics.errors);
+    }
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Make a single request and confirm its named validation isn't counted in the Goose metrics
+// unless enabled.
+async fn test_named_validation_not_in_goose_metrics() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    // Only the validated request is counted.
+    let goose_metrics = run_load_test(&server, transaction!(get_path_named)).await;
+    assert!(mock_endpoint.hits() == 1);
+    assert!(!goose_metrics
+        .requests
+        .contains_key("GET validation: front page"));
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Make a single request and confirm its named validation is counted in the Goose metrics.
+async fn test_named_validation_metrics() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(HTML);
+    });
+
+    let goose_metrics = build_load_test(
+        build_configuration(&server),
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_path_named_goose_metric))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(mock_endpoint.hits() == 1);
+
+    // The validation is reported alongside the request it validated.
+    let validation = goose_metrics
+        .requests
+        .get("GET validation: front page")
+        .expect("validation not in the Goose metrics");
+    assert_eq!(validation.success_count, 1);
+    assert_eq!(validation.fail_count, 0);
+    assert!(goose_metrics.errors.is_empty());
+}
+
+#[tokio::test]
+// Load static assets in parallel and confirm their requests are attributed to the scenario
+// and transaction that loaded the page.
+async fn test_parallel_asset_attribution() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let images: String = (1..=5)
+        .map(|i| format!(r#"<img src="/logo{}.png">"#, i))
+        .collect();
+    let mock_endpoint = server.mock(|when, then| {
+        when.method(GET).path(PATH);
+        then.status(200).body(format!("{}{}", HTML, images));
+    });
+    let asset_endpoint = server.mock(|when, then| {
+        when.method(GET).path_contains("/logo");
+        then.status(200).body("PNG");
+    });
+
+    // Log the requests, which include the scenario and transaction they're attributed to.
+    let request_log = std::env::temp_dir().join(format!(
+        "goose-eggs-parallel-assets-{}.json",
+        std::process::id()
+    ));
+    let mut configuration = build_configuration(&server);
+    configuration.request_log = request_log.to_string_lossy().to_string();
+    configuration.request_format = Some(goose::logger::GooseLogFormat::Json);
+
+    let goose_metrics = build_load_test(
+        configuration,
+        vec![scenario!("LoadAssets")
+            .register_transaction(transaction!(get_path_parallel_assets).set_name("load page"))],
+        None,
+        None,
+    )
+    .execute()
+    .await
+    .unwrap();
+    assert!(mock_endpoint.hits() == 1);
+    assert!(asset_endpoint.hits() == 5);
+
+    let assets = goose_metrics
+        .requests
+        .get("GET static asset: img")
+        .expect("static assets not in the Goose metrics");
+    assert_eq!(assets.success_count, 5);
+
+    let log = std::fs::read_to_string(&request_log).unwrap();
+    let _ = std::fs::remove_file(&request_log);
+    let requests: Vec<serde_json::Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let page = requests
+        .iter()
+        .find(|request| request["final_url"].as_str().unwrap().ends_with(PATH))
+        .expect("page request not logged");
+    assert_eq!(page["transaction_name"], "load page");
+    let assets: Vec<&serde_json::Value> = requests
+        .iter()
+        .filter(|request| request["name"] == "static asset: img")
+        .collect();
+    assert_eq!(assets.len(), 5);
+    for asset in assets {
+        for field in [
+            "scenario_index",
+            "scenario_name",
+            "transaction_index",
+            "transaction_name",
+        ] {
+            assert_eq!(asset[field], page[field]);
+        }
+    }
+}
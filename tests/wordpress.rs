@@ -0,0 +1,205 @@
+use gumdrop::Options;
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
+
+use goose::config::GooseConfiguration;
+use goose::goose::get_base_url;
+use goose::metrics::GooseCoordinatedOmissionMitigation::Disabled;
+use goose::prelude::*;
+use goose_eggs::wordpress;
+
+// Paths used in the requests made during these tests.
+const LOGIN_PATH: &str = "/wp-login.php";
+const POST_PATH: &str = "/2024/01/01/hello-world/";
+const POSTS_PATH: &str = "/wp-json/wp/v2/posts";
+
+// The log in page of WordPress.
+const LOGIN_HTML: &str = r#"
+<form name="loginform" id="loginform" action="/wp-login.php" method="post">
+  <input type="text" name="log" id="user_login" class="input" value="" size="20" autocapitalize="off" autocomplete="username" required="required" />
+  <input type="password" name="pwd" id="user_pass" class="input password-input" value="" size="20" autocomplete="current-password" spellcheck="false" required="required" />
+  <input name="rememberme" type="checkbox" id="rememberme" value="forever" />
+  <input type="submit" name="wp-submit" id="wp-submit" class="button button-primary button-large" value="Log In" />
+  <input type="hidden" name="redirect_to" value="/wp-admin/" />
+  <input type="hidden" name="testcookie" value="1" />
+</form>
+"#;
+
+// The comment form of a WordPress post.
+const COMMENT_FORM_HTML: &str = r#"
+<form action="/wp-comments-post.php" method="post" id="commentform" class="comment-form" novalidate>
+  <textarea id="comment" name="comment" cols="45" rows="8" maxlength="65525" required></textarea>
+  <input id="author" name="author" type="text" value="" size="30" maxlength="245" autocomplete="name" required />
+  <input id="email" name="email" type="email" value="" size="30" maxlength="100" aria-describedby="email-notes" autocomplete="email" required />
+  <input name="submit" type="submit" id="submit" class="submit" value="Post Comment" />
+  <input type='hidden' name='comment_post_ID' value='1' id='comment_post_ID' />
+  <input type='hidden' name='comment_parent' id='comment_parent' value='0' />
+</form>
+"#;
+
+// Build a Goose user that makes requests to the mock server.
+fn build_user(server: &MockServer) -> GooseUser {
+    let config: Vec<&str> = vec![];
+    let mut configuration = GooseConfiguration::parse_args_default(&config).unwrap();
+    configuration.co_mitigation = Some(Disabled);
+    let base_url = get_base_url(Some(server.base_url()), None, None).unwrap();
+    GooseUser::new(0, "".to_string(), base_url, &configuration, 0, None).unwrap()
+}
+
+#[tokio::test]
+// Fail to log in, and confirm the error WordPress displayed is included in the failure.
+async fn test_log_in_error() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let login_page = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).body(LOGIN_HTML);
+    });
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path(LOGIN_PATH)
+            .x_www_form_urlencoded_tuple("log", "editor")
+            .x_www_form_urlencoded_tuple("pwd", "wrong")
+            .x_www_form_urlencoded_tuple("testcookie", "1");
+        then.status(200).body(format!(
+            r#"<div id="login_error" class="notice notice-error"><p><strong>Error:</strong> The password you entered for the username <strong>editor</strong> is incorrect. <a href="/wp-login.php?action=lostpassword">Lost your password?</a></p></div>{}"#,
+            LOGIN_HTML
+        ));
+    });
+
+    let mut user = build_user(&server);
+    let login_params = wordpress::WpLogin::builder()
+        .username("editor")
+        .password("wrong")
+        .build();
+    let error = wordpress::log_in(&mut user, &login_params)
+        .await
+        .unwrap_err();
+    match *error {
+        TransactionError::RequestFailed { raw_request } => {
+            assert!(raw_request.error.contains("login failed"));
+            assert!(raw_request.error.ends_with(
+                "Error: The password you entered for the username editor is incorrect. Lost your password?"
+            ));
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+    assert_eq!(login_page.hits(), 1);
+    assert_eq!(login.hits(), 1);
+}
+
+#[tokio::test]
+// Post a comment that's held for moderation, and validate whether it's expected to be.
+async fn test_post_comment_moderation() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // The redirect to the comment held for moderation is matched first, as it's for the
+    // same path as the post.
+    let held_page = server.mock(|when, then| {
+        when.method(GET)
+            .path(POST_PATH)
+            .query_param("unapproved", "12");
+        then.status(200).body(format!(
+            r#"<article id="div-comment-12" class="comment"><em class="comment-awaiting-moderation">Your comment is awaiting moderation. This is a preview; your comment will be visible after it has been approved.</em></article>{}"#,
+            COMMENT_FORM_HTML
+        ));
+    });
+    let post_page = server.mock(|when, then| {
+        when.method(GET).path(POST_PATH);
+        then.status(200).body(COMMENT_FORM_HTML);
+    });
+    let comment = server.mock(|when, then| {
+        when.method(POST)
+            .path("/wp-comments-post.php")
+            .x_www_form_urlencoded_tuple("comment", "Thanks for the post.")
+            .x_www_form_urlencoded_tuple("comment_post_ID", "1")
+            .x_www_form_urlencoded_tuple("author", "Goose");
+        then.status(302).header(
+            "location",
+            format!(
+                "{}?unapproved=12&moderation-hash=2b0c4a8ee8cbab35f6f0cb1de1dc3ed3#comment-12",
+                POST_PATH
+            ),
+        );
+    });
+
+    // The comment is expected to be held for moderation.
+    let mut user = build_user(&server);
+    let comment_params = wordpress::WpCommentParams::builder()
+        .url(POST_PATH)
+        .comment("Thanks for the post.")
+        .moderation(true)
+        .build();
+    let html = wordpress::post_comment(&mut user, &comment_params)
+        .await
+        .unwrap();
+    assert!(html.contains("Your comment is awaiting moderation"));
+
+    // The comment is expected to be approved without moderation.
+    let comment_params = wordpress::WpCommentParams::builder()
+        .url(POST_PATH)
+        .comment("Thanks for the post.")
+        .moderation(false)
+        .build();
+    let error = wordpress::post_comment(&mut user, &comment_params)
+        .await
+        .unwrap_err();
+    match *error {
+        TransactionError::RequestFailed { raw_request } => {
+            assert!(raw_request.error.ends_with("comment held for moderation"));
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    assert_eq!(post_page.hits(), 2);
+    assert_eq!(comment.hits(), 2);
+    assert_eq!(held_page.hits(), 2);
+}
+
+#[tokio::test]
+// Fetch a collection of posts, following pages up to the X-WP-TotalPages header.
+async fn test_rest_collection_pages() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let first_page = server.mock(|when, then| {
+        when.method(GET).path(POSTS_PATH).query_param("page", "1");
+        then.status(200)
+            .header("content-type", "application/json; charset=UTF-8")
+            .header("x-wp-total", "3")
+            .header("x-wp-totalpages", "2")
+            .json_body(serde_json::json!([{"id": 3}, {"id": 2}]));
+    });
+    let second_page = server.mock(|when, then| {
+        when.method(GET).path(POSTS_PATH).query_param("page", "2");
+        then.status(200)
+            .header("content-type", "application/json; charset=UTF-8")
+            .header("x-wp-total", "3")
+            .header("x-wp-totalpages", "2")
+            .json_body(serde_json::json!([{"id": 1}]));
+    });
+
+    // More pages are allowed than the collection has, so only two are fetched.
+    let mut user = build_user(&server);
+    let params = wordpress::rest::WpRestParams::builder()
+        .max_pages(5)
+        .build();
+    let collection = wordpress::rest::get_collection(&mut user, &params, "posts")
+        .await
+        .unwrap();
+    assert_eq!(collection.pages, 2);
+    assert_eq!(collection.total, Some(3));
+    assert_eq!(collection.total_pages, Some(2));
+    let ids: Vec<u64> = collection
+        .items
+        .iter()
+        .map(|post| post["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+    assert_eq!(first_page.hits(), 1);
+    assert_eq!(second_page.hits(), 1);
+}